@@ -0,0 +1,259 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{AIClient, Completion, RateLimitHeaders, RateLimitInfo, TokenUsage, ai_error};
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1";
+const RATE_LIMIT_HEADERS: RateLimitHeaders = [
+    "anthropic-ratelimit-requests-limit",
+    "anthropic-ratelimit-requests-remaining",
+    "anthropic-ratelimit-requests-reset",
+    "anthropic-ratelimit-tokens-limit",
+    "anthropic-ratelimit-tokens-remaining",
+];
+
+/// Anthropic only caches prefixes of at least this many tokens, twice as many for Haiku models
+const MIN_CACHED_TOKENS: usize = 1024;
+
+/// Rough characters per token of English prose, to tell whether a prefix clears the minimum
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Anthropic API client
+pub struct AnthropicClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Serialize)]
+struct AnthropicRequest {
+    model: String,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    system: Vec<SystemBlock>,
+    messages: Vec<Message>,
+}
+
+/// Instructions sent as a system block, marked for Anthropic's prompt cache when long enough
+#[derive(Serialize)]
+struct SystemBlock {
+    #[serde(rename = "type")]
+    block_type: &'static str,
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
+}
+
+#[derive(Serialize)]
+struct CacheControl {
+    #[serde(rename = "type")]
+    cache_type: &'static str,
+}
+
+#[derive(Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct AnthropicResponse {
+    content: Option<Vec<ContentBlock>>,
+    usage: Option<AnthropicUsage>,
+    error: Option<AnthropicError>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicUsage {
+    /// Prompt tokens after the last cache breakpoint
+    input_tokens: u64,
+    output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    content_type: String,
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct ModelList {
+    data: Vec<Model>,
+}
+
+#[derive(Deserialize)]
+struct Model {
+    id: String,
+}
+
+impl AnthropicClient {
+    /// Creates a new Anthropic client from the ANTHROPIC_API_KEY secret
+    pub fn new(model: &str) -> Result<Self> {
+        let api_key = secrets::require("ANTHROPIC_API_KEY")?;
+
+        Ok(Self::with_api_key(model, &api_key))
+    }
+
+    /// Creates a new Anthropic client with an explicit API key
+    pub fn with_api_key(model: &str, api_key: &str) -> Self {
+        Self {
+            client: tls::client(),
+            base_url: ANTHROPIC_API_URL.to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+        }
+    }
+
+    /// Sends requests to `base_url` instead of the public API, e.g. a proxy or a mock server
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Whether `prefix` is long enough for Anthropic to cache it; shorter breakpoints are
+    /// ignored by the API, so they aren't sent
+    fn cacheable(&self, prefix: &str) -> bool {
+        let minimum = if self.model.contains("haiku") {
+            2 * MIN_CACHED_TOKENS
+        } else {
+            MIN_CACHED_TOKENS
+        };
+        prefix.len() / CHARS_PER_TOKEN >= minimum
+    }
+
+    /// Sends the prompt, with `instructions` as a system block cached when long enough
+    async fn send(&self, instructions: Option<&str>, prompt: &str) -> Result<Completion> {
+        let request = AnthropicRequest {
+            model: self.model.clone(),
+            max_tokens: 4096,
+            system: instructions
+                .map(|text| SystemBlock {
+                    block_type: "text",
+                    text: text.to_string(),
+                    cache_control: self.cacheable(text).then_some(CacheControl {
+                        cache_type: "ephemeral",
+                    }),
+                })
+                .into_iter()
+                .collect(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: prompt.to_string(),
+            }],
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/messages", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send request to Anthropic API")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("Anthropic", response)
+                .await
+                .into());
+        }
+
+        let rate_limit = RateLimitInfo::from_headers(response.headers(), &RATE_LIMIT_HEADERS);
+        let anthropic_response: AnthropicResponse = response
+            .json()
+            .await
+            .context("Failed to parse Anthropic response")?;
+
+        if let Some(error) = anthropic_response.error {
+            return Err(ai_error("Anthropic", error.message));
+        }
+
+        let text = anthropic_response
+            .content
+            .map(|blocks| {
+                blocks
+                    .into_iter()
+                    .filter(|b| b.content_type == "text")
+                    .filter_map(|b| b.text)
+                    .collect::<Vec<_>>()
+                    .concat()
+            })
+            .unwrap_or_default();
+
+        let usage = anthropic_response.usage.map(|u| TokenUsage {
+            prompt_tokens: u.input_tokens
+                + u.cache_creation_input_tokens
+                + u.cache_read_input_tokens,
+            completion_tokens: u.output_tokens,
+            cached_tokens: u.cache_read_input_tokens,
+            cache_write_tokens: u.cache_creation_input_tokens,
+        });
+
+        Ok(Completion {
+            text,
+            usage,
+            rate_limit,
+        })
+    }
+}
+
+#[async_trait]
+impl AIClient for AnthropicClient {
+    async fn generate(&self, prompt: &str) -> Result<Completion> {
+        self.send(None, prompt).await
+    }
+
+    async fn list_models(&self) -> Result<Option<Vec<String>>> {
+        let response = self
+            .client
+            .get(format!("{}/models?limit=1000", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .send()
+            .await
+            .context("Failed to send request to Anthropic API")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("Anthropic", response)
+                .await
+                .into());
+        }
+
+        // Newest first, as Anthropic lists them
+        let list: ModelList = response
+            .json()
+            .await
+            .context("Failed to parse Anthropic models response")?;
+        Ok(Some(list.data.into_iter().map(|model| model.id).collect()))
+    }
+
+    async fn generate_cached(&self, instructions: &str, content: &str) -> Result<Completion> {
+        self.send(Some(instructions), content).await
+    }
+
+    async fn generate_json_cached(
+        &self,
+        instructions: &str,
+        content: &str,
+        _schema: &Value,
+    ) -> Result<Completion> {
+        self.send(Some(instructions), content).await
+    }
+}
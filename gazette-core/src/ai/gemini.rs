@@ -0,0 +1,264 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{AIClient, Completion, TokenUsage};
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+
+const GEMINI_API_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+
+/// Gemini API client
+pub struct GeminiClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    contents: Vec<Content>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GenerationConfig>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    response_mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_schema: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct Content {
+    parts: Vec<Part>,
+}
+
+#[derive(Serialize)]
+struct Part {
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    candidates: Option<Vec<Candidate>>,
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+    /// Tokens of the prompt prefix Gemini answered from its implicit cache
+    #[serde(default)]
+    cached_content_token_count: u64,
+}
+
+#[derive(Deserialize)]
+struct Candidate {
+    content: CandidateContent,
+}
+
+#[derive(Deserialize)]
+struct CandidateContent {
+    parts: Vec<CandidatePart>,
+}
+
+#[derive(Deserialize)]
+struct CandidatePart {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct ModelList {
+    #[serde(default)]
+    models: Vec<Model>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Model {
+    /// e.g. "models/gemini-2.0-flash"
+    name: String,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+}
+
+impl GeminiClient {
+    /// Creates a new Gemini client from the GEMINI_API_KEY secret
+    pub fn new(model: &str) -> Result<Self> {
+        let api_key = secrets::require("GEMINI_API_KEY")?;
+
+        Ok(Self::with_api_key(model, &api_key))
+    }
+
+    /// Creates a new Gemini client with an explicit API key
+    pub fn with_api_key(model: &str, api_key: &str) -> Self {
+        Self {
+            client: tls::client(),
+            base_url: GEMINI_API_URL.to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+        }
+    }
+
+    /// Sends requests to `base_url` instead of the public API, e.g. a proxy or a mock server
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sends a generateContent request with an optional generation config
+    async fn complete(
+        &self,
+        prompt: &str,
+        generation_config: Option<GenerationConfig>,
+    ) -> Result<Completion> {
+        let url = format!(
+            "{}/{}:generateContent?key={}",
+            self.base_url, self.model, self.api_key
+        );
+
+        let request = GeminiRequest {
+            contents: vec![Content {
+                parts: vec![Part {
+                    text: prompt.to_string(),
+                }],
+            }],
+            generation_config,
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send request to Gemini API")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("Gemini", response)
+                .await
+                .into());
+        }
+
+        let gemini_response: GeminiResponse = response
+            .json()
+            .await
+            .context("Failed to parse Gemini response")?;
+
+        let usage = gemini_response.usage_metadata.map(|u| TokenUsage {
+            prompt_tokens: u.prompt_token_count,
+            completion_tokens: u.candidates_token_count,
+            cached_tokens: u.cached_content_token_count,
+            cache_write_tokens: 0,
+        });
+
+        let text = gemini_response
+            .candidates
+            .and_then(|c| c.into_iter().next())
+            .map(|c| {
+                c.content
+                    .parts
+                    .into_iter()
+                    .map(|p| p.text)
+                    .collect::<Vec<_>>()
+                    .join("")
+            })
+            .unwrap_or_default();
+
+        Ok(Completion {
+            text,
+            usage,
+            rate_limit: None,
+        })
+    }
+}
+
+#[async_trait]
+impl AIClient for GeminiClient {
+    async fn generate(&self, prompt: &str) -> Result<Completion> {
+        self.complete(prompt, None).await
+    }
+
+    async fn list_models(&self) -> Result<Option<Vec<String>>> {
+        let url = format!("{}?pageSize=1000&key={}", self.base_url, self.api_key);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to send request to Gemini API")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("Gemini", response)
+                .await
+                .into());
+        }
+
+        let list: ModelList = response
+            .json()
+            .await
+            .context("Failed to parse Gemini models response")?;
+        Ok(Some(
+            list.models
+                .into_iter()
+                .filter(|model| {
+                    model
+                        .supported_generation_methods
+                        .iter()
+                        .any(|method| method == "generateContent")
+                })
+                .map(|model| model.name.trim_start_matches("models/").to_string())
+                .collect(),
+        ))
+    }
+
+    async fn generate_json(&self, prompt: &str, schema: &Value) -> Result<Completion> {
+        let config = GenerationConfig {
+            response_mime_type: "application/json".to_string(),
+            response_schema: Some(response_schema(schema)),
+        };
+
+        self.complete(prompt, Some(config)).await
+    }
+}
+
+/// Converts a JSON schema to the OpenAPI subset Gemini's responseSchema takes: upper-case
+/// types, and no additionalProperties, which Gemini rejects
+fn response_schema(schema: &Value) -> Value {
+    match schema {
+        Value::Object(fields) => {
+            let converted: Map<String, Value> = fields
+                .iter()
+                .filter(|(key, _)| key.as_str() != "additionalProperties")
+                .map(|(key, value)| match (key.as_str(), value) {
+                    ("type", Value::String(kind)) => (key.clone(), kind.to_uppercase().into()),
+                    // Property names are data, only their schemas are converted
+                    ("properties", Value::Object(properties)) => (
+                        key.clone(),
+                        properties
+                            .iter()
+                            .map(|(name, schema)| (name.clone(), response_schema(schema)))
+                            .collect::<Map<_, _>>()
+                            .into(),
+                    ),
+                    _ => (key.clone(), response_schema(value)),
+                })
+                .collect();
+            Value::Object(converted)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(response_schema).collect()),
+        other => other.clone(),
+    }
+}
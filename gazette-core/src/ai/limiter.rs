@@ -0,0 +1,70 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::AIProvider;
+
+/// Spaces requests to a provider evenly so they stay under a requests-per-minute limit
+#[derive(Debug)]
+pub struct RequestLimiter {
+    interval: Duration,
+    /// When the next request may be sent
+    next: Mutex<Instant>,
+}
+
+/// One limiter per provider, shared by every service in the process so parallel repos and
+/// overlapping daemon runs count against the same limit
+static LIMITERS: Mutex<Vec<(AIProvider, u32, Arc<RequestLimiter>)>> = Mutex::new(Vec::new());
+
+impl RequestLimiter {
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(60) / requests_per_minute.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Claims the next free slot, returning how long to wait before sending the request
+    pub fn reserve(&self) -> Duration {
+        let now = Instant::now();
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = (*next).max(now);
+        *next = slot + self.interval;
+        slot - now
+    }
+}
+
+/// The process-wide limiter of `provider`, replaced when its limit changed
+pub fn limiter(provider: AIProvider, requests_per_minute: u32) -> Arc<RequestLimiter> {
+    let mut limiters = LIMITERS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, _, limiter)) = limiters
+        .iter()
+        .find(|(p, rpm, _)| *p == provider && *rpm == requests_per_minute)
+    {
+        return limiter.clone();
+    }
+
+    let limiter = Arc::new(RequestLimiter::new(requests_per_minute));
+    limiters.retain(|(p, _, _)| *p != provider);
+    limiters.push((provider, requests_per_minute, limiter.clone()));
+    limiter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_are_spaced_over_the_minute() {
+        let spacing = RequestLimiter::new(60);
+
+        assert_eq!(spacing.reserve(), Duration::ZERO);
+        let second = spacing.reserve();
+        let third = spacing.reserve();
+        assert!(second > Duration::from_millis(900) && second <= Duration::from_secs(1));
+        assert!(third > Duration::from_millis(1900) && third <= Duration::from_secs(2));
+
+        let shared = limiter(AIProvider::Ollama, 600);
+        assert!(Arc::ptr_eq(&shared, &limiter(AIProvider::Ollama, 600)));
+        assert!(!Arc::ptr_eq(&shared, &limiter(AIProvider::Ollama, 300)));
+    }
+}
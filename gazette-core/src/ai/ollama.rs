@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{AIClient, Completion, TokenUsage, ai_error};
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+
+const DEFAULT_HOST: &str = "http://localhost:11434";
+
+/// Ollama API client for local models
+pub struct OllamaClient {
+    client: reqwest::Client,
+    host: String,
+    model: String,
+}
+
+#[derive(Serialize)]
+struct OllamaRequest {
+    model: String,
+    prompt: String,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponse {
+    response: Option<String>,
+    prompt_eval_count: Option<u64>,
+    eval_count: Option<u64>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTags {
+    #[serde(default)]
+    models: Vec<OllamaModel>,
+}
+
+#[derive(Deserialize)]
+struct OllamaModel {
+    /// e.g. "llama3.2:latest"
+    name: String,
+}
+
+impl OllamaClient {
+    /// Creates a new Ollama client
+    /// Uses the OLLAMA_HOST secret or defaults to localhost:11434
+    pub fn new(model: &str) -> Result<Self> {
+        let host = secrets::get("OLLAMA_HOST").unwrap_or_else(|| DEFAULT_HOST.to_string());
+
+        Ok(Self::with_host(model, &host))
+    }
+
+    /// Creates a new Ollama client for an explicit host
+    pub fn with_host(model: &str, host: &str) -> Self {
+        Self {
+            client: tls::client(),
+            host: host.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+        }
+    }
+
+    /// Sends a non-streaming generate request, optionally forcing a response format
+    async fn complete(&self, prompt: &str, format: Option<String>) -> Result<Completion> {
+        let url = format!("{}/api/generate", self.host);
+
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            prompt: prompt.to_string(),
+            stream: false,
+            format,
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send request to Ollama. Is Ollama running?")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("Ollama", response)
+                .await
+                .into());
+        }
+
+        let ollama_response: OllamaResponse = response
+            .json()
+            .await
+            .context("Failed to parse Ollama response")?;
+
+        if let Some(error) = ollama_response.error {
+            return Err(ai_error("Ollama", error));
+        }
+
+        let usage = match (
+            ollama_response.prompt_eval_count,
+            ollama_response.eval_count,
+        ) {
+            (None, None) => None,
+            (prompt, completion) => Some(TokenUsage {
+                prompt_tokens: prompt.unwrap_or(0),
+                completion_tokens: completion.unwrap_or(0),
+                cached_tokens: 0,
+                cache_write_tokens: 0,
+            }),
+        };
+
+        let text = ollama_response.response.unwrap_or_default();
+
+        Ok(Completion {
+            text,
+            usage,
+            rate_limit: None,
+        })
+    }
+}
+
+#[async_trait]
+impl AIClient for OllamaClient {
+    async fn generate(&self, prompt: &str) -> Result<Completion> {
+        self.complete(prompt, None).await
+    }
+
+    /// The models pulled to the Ollama host
+    async fn list_models(&self) -> Result<Option<Vec<String>>> {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.host))
+            .send()
+            .await
+            .context("Failed to send request to Ollama. Is Ollama running?")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("Ollama", response)
+                .await
+                .into());
+        }
+
+        let tags: OllamaTags = response
+            .json()
+            .await
+            .context("Failed to parse Ollama tags response")?;
+        let mut models: Vec<String> = tags.models.into_iter().map(|model| model.name).collect();
+        models.sort();
+        Ok(Some(models))
+    }
+
+    async fn generate_json(&self, prompt: &str, _schema: &Value) -> Result<Completion> {
+        self.complete(prompt, Some("json".to_string())).await
+    }
+}
@@ -0,0 +1,263 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::{AIClient, Completion, RateLimitHeaders, RateLimitInfo, TokenUsage, ai_error};
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+
+const OPENAI_API_URL: &str = "https://api.openai.com/v1";
+const RATE_LIMIT_HEADERS: RateLimitHeaders = [
+    "x-ratelimit-limit-requests",
+    "x-ratelimit-remaining-requests",
+    "x-ratelimit-reset-requests",
+    "x-ratelimit-limit-tokens",
+    "x-ratelimit-remaining-tokens",
+];
+
+/// OpenAI API client
+pub struct OpenAIClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Serialize)]
+struct OpenAIRequest {
+    model: String,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct OpenAIResponse {
+    choices: Option<Vec<Choice>>,
+    usage: Option<OpenAIUsage>,
+    error: Option<OpenAIError>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+/// OpenAI caches long prompt prefixes on its own and reports the hits here
+#[derive(Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: u64,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ResponseMessage {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct ModelList {
+    data: Vec<Model>,
+}
+
+#[derive(Deserialize)]
+struct Model {
+    id: String,
+}
+
+impl OpenAIClient {
+    /// Creates a new OpenAI client from the OPENAI_API_KEY secret
+    pub fn new(model: &str) -> Result<Self> {
+        let api_key = secrets::require("OPENAI_API_KEY")?;
+
+        Ok(Self::with_api_key(model, &api_key))
+    }
+
+    /// Creates a new OpenAI client with an explicit API key
+    pub fn with_api_key(model: &str, api_key: &str) -> Self {
+        Self {
+            client: tls::client(),
+            base_url: OPENAI_API_URL.to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+        }
+    }
+
+    /// Sends requests to `base_url` instead of the public API, e.g. a proxy or a mock server
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// True for chat models, leaving out the audio, image, embedding and moderation ones the
+    /// models endpoint lists as well
+    fn is_chat_model(id: &str) -> bool {
+        let family = id.starts_with("gpt-") || id.starts_with("chatgpt-") || is_o_series(id);
+        let special = [
+            "audio",
+            "realtime",
+            "transcribe",
+            "tts",
+            "image",
+            "instruct",
+            "search",
+        ];
+        family && !special.iter().any(|kind| id.contains(kind))
+    }
+
+    /// Sends a chat completion request, optionally constraining the response format
+    async fn complete(&self, prompt: &str, response_format: Option<Value>) -> Result<Completion> {
+        let request = OpenAIRequest {
+            model: self.model.clone(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: prompt.to_string(),
+            }],
+            // Reasoning models reject any temperature but their default
+            temperature: (!self.is_reasoning_model()).then_some(0.7),
+            response_format,
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send request to OpenAI API")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("OpenAI", response)
+                .await
+                .into());
+        }
+
+        let rate_limit = RateLimitInfo::from_headers(response.headers(), &RATE_LIMIT_HEADERS);
+        let openai_response: OpenAIResponse = response
+            .json()
+            .await
+            .context("Failed to parse OpenAI response")?;
+
+        if let Some(error) = openai_response.error {
+            return Err(ai_error("OpenAI", error.message));
+        }
+
+        let usage = openai_response.usage.map(|u| TokenUsage {
+            prompt_tokens: u.prompt_tokens,
+            completion_tokens: u.completion_tokens,
+            cached_tokens: u.prompt_tokens_details.map_or(0, |d| d.cached_tokens),
+            cache_write_tokens: 0,
+        });
+
+        let text = openai_response
+            .choices
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.message.content)
+            .unwrap_or_default();
+
+        Ok(Completion {
+            text,
+            usage,
+            rate_limit,
+        })
+    }
+
+    /// The o-series and GPT-5 reasoning models; GPT-5's chat variant takes a temperature
+    fn is_reasoning_model(&self) -> bool {
+        is_o_series(&self.model)
+            || (self.model.starts_with("gpt-5") && !self.model.starts_with("gpt-5-chat"))
+    }
+
+    /// Older models only support JSON mode, not schema-constrained structured outputs,
+    /// including the first gpt-4o snapshot
+    fn supports_json_schema(&self) -> bool {
+        ["gpt-4o", "gpt-4.1", "gpt-5", "o1", "o3", "o4"]
+            .iter()
+            .any(|family| self.model.starts_with(family))
+            && !["o1-preview", "o1-mini", "gpt-4o-2024-05-13"]
+                .iter()
+                .any(|early| self.model.starts_with(early))
+    }
+}
+
+/// o1, o3-mini, o4-mini and the rest of the o-series
+fn is_o_series(id: &str) -> bool {
+    id.starts_with('o') && id[1..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[async_trait]
+impl AIClient for OpenAIClient {
+    async fn generate(&self, prompt: &str) -> Result<Completion> {
+        self.complete(prompt, None).await
+    }
+
+    async fn list_models(&self) -> Result<Option<Vec<String>>> {
+        let response = self
+            .client
+            .get(format!("{}/models", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send()
+            .await
+            .context("Failed to send request to OpenAI API")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_ai_response("OpenAI", response)
+                .await
+                .into());
+        }
+
+        let list: ModelList = response
+            .json()
+            .await
+            .context("Failed to parse OpenAI models response")?;
+        let mut models: Vec<String> = list
+            .data
+            .into_iter()
+            .map(|model| model.id)
+            .filter(|id| Self::is_chat_model(id))
+            .collect();
+        models.sort();
+        Ok(Some(models))
+    }
+
+    async fn generate_json(&self, prompt: &str, schema: &Value) -> Result<Completion> {
+        let response_format = if self.supports_json_schema() {
+            json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "changelog",
+                    "strict": true,
+                    "schema": schema,
+                }
+            })
+        } else {
+            json!({ "type": "json_object" })
+        };
+
+        self.complete(prompt, Some(response_format)).await
+    }
+}
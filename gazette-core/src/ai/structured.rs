@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Changelog returned by the AI as structured JSON
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StructuredChangelog {
+    #[serde(default)]
+    pub categories: Vec<ChangelogCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogCategory {
+    pub name: String,
+    #[serde(default)]
+    pub entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub summary: String,
+    #[serde(default)]
+    pub pr_numbers: Vec<u64>,
+    #[serde(default)]
+    pub jira_keys: Vec<String>,
+}
+
+impl StructuredChangelog {
+    /// JSON schema describing the expected response, usable with strict structured outputs
+    pub fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "categories": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "entries": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "summary": { "type": "string" },
+                                        "pr_numbers": {
+                                            "type": "array",
+                                            "items": { "type": "integer" }
+                                        },
+                                        "jira_keys": {
+                                            "type": "array",
+                                            "items": { "type": "string" }
+                                        }
+                                    },
+                                    "required": ["summary", "pr_numbers", "jira_keys"],
+                                    "additionalProperties": false
+                                }
+                            }
+                        },
+                        "required": ["name", "entries"],
+                        "additionalProperties": false
+                    }
+                }
+            },
+            "required": ["categories"],
+            "additionalProperties": false
+        })
+    }
+
+    /// Parses a model response, tolerating markdown code fences and surrounding prose
+    pub fn parse(text: &str) -> Result<Self> {
+        let start = text.find('{');
+        let end = text.rfind('}');
+
+        let json = match (start, end) {
+            (Some(start), Some(end)) if start < end => &text[start..=end],
+            _ => anyhow::bail!("AI response does not contain a JSON object"),
+        };
+
+        serde_json::from_str(json).context("Failed to parse structured changelog from AI response")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.iter().all(|c| c.entries.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fenced_json() {
+        let text = "Here you go:\n```json\n{\"categories\":[{\"name\":\"Features\",\"entries\":[{\"summary\":\"Add login\",\"pr_numbers\":[12],\"jira_keys\":[\"AUTH-1\"]}]}]}\n```";
+        let changelog = StructuredChangelog::parse(text).unwrap();
+        assert_eq!(changelog.categories.len(), 1);
+        assert_eq!(changelog.categories[0].entries[0].pr_numbers, vec![12]);
+        assert!(!changelog.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_non_json() {
+        assert!(StructuredChangelog::parse("no json here").is_err());
+    }
+}
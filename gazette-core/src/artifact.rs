@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::ai::{ChangelogCategory, StructuredChangelog};
+use crate::changelog::PrContext;
+use crate::config::{Repo, TimePeriod};
+use crate::leadtime::{self, LeadTimeStats};
+
+/// Machine-readable companion of a generated changelog, for dashboards and release bots
+#[derive(Serialize, Debug)]
+pub struct ChangelogArtifact {
+    pub repo: String,
+    pub generated_at: DateTime<Utc>,
+    pub period: String,
+    /// Categorized entries; only available with the structured changelog format
+    pub categories: Option<Vec<ChangelogCategory>>,
+    pub pull_requests: Vec<ArtifactPullRequest>,
+    /// Lead-time percentiles, when enabled and any PR has an opening time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lead_time: Option<LeadTimeStats>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ArtifactPullRequest {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub merged_at: Option<DateTime<Utc>>,
+    /// Hours from opening the PR to merging it
+    pub lead_time_hours: Option<f64>,
+    pub jira_keys: Vec<String>,
+}
+
+impl ChangelogArtifact {
+    pub fn new(
+        repo: &Repo,
+        period: TimePeriod,
+        contexts: &[PrContext],
+        categories: Option<StructuredChangelog>,
+    ) -> Self {
+        let pull_requests = contexts
+            .iter()
+            .map(|ctx| ArtifactPullRequest {
+                number: ctx.pr.number,
+                title: ctx.pr.title.clone(),
+                url: ctx.pr.html_url.clone(),
+                merged_at: ctx.pr.merged_at,
+                lead_time_hours: leadtime::lead_time(&ctx.pr).map(leadtime::hours),
+                jira_keys: ctx.jira_issues.iter().map(|i| i.key.clone()).collect(),
+            })
+            .collect();
+
+        Self {
+            repo: repo.full_name(),
+            generated_at: Utc::now(),
+            period: period.description(),
+            categories: categories.map(|c| c.categories),
+            pull_requests,
+            lead_time: None,
+        }
+    }
+
+    pub fn with_lead_time(mut self, stats: Option<LeadTimeStats>) -> Self {
+        self.lead_time = stats;
+        self
+    }
+
+    /// Writes the artifact next to the markdown changelog, with a .json extension
+    pub fn save_next_to(&self, markdown_path: &Path) -> Result<PathBuf> {
+        let path = markdown_path.with_extension("json");
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize changelog artifact")?;
+        fs::write(&path, content).context("Failed to write changelog artifact")?;
+        Ok(path)
+    }
+}
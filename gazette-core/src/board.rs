@@ -0,0 +1,76 @@
+use crate::changelog::PrContext;
+
+/// Board statuses of work that is simply out, which entries aren't annotated with
+const FINISHED_STATUSES: [&str; 6] = [
+    "done",
+    "shipped",
+    "released",
+    "closed",
+    "complete",
+    "completed",
+];
+
+/// True for statuses such as "Done" that readers assume for every entry
+pub fn is_finished(status: &str) -> bool {
+    FINISHED_STATUSES
+        .iter()
+        .any(|finished| status.trim().eq_ignore_ascii_case(finished))
+}
+
+/// The "Board status:" line of a PR in the AI context
+pub fn describe(ctx: &PrContext) -> Option<String> {
+    ctx.project_status
+        .as_ref()
+        .map(|status| format!("Board status: {}", status))
+}
+
+/// The status an entry is annotated with: the first unfinished one among its PRs'
+pub fn entry_status<'a>(pr_numbers: &[u64], contexts: &'a [PrContext]) -> Option<&'a str> {
+    contexts
+        .iter()
+        .filter(|ctx| pr_numbers.contains(&ctx.pr.number))
+        .filter_map(|ctx| ctx.project_status.as_deref())
+        .find(|status| !is_finished(status))
+}
+
+/// Prompt note asking a freeform changelog to annotate entries with their board status
+pub fn context_block(contexts: &[PrContext]) -> Option<String> {
+    if !contexts
+        .iter()
+        .filter_map(|ctx| ctx.project_status.as_deref())
+        .any(|status| !is_finished(status))
+    {
+        return None;
+    }
+
+    Some(
+        "## Board status\nSome PRs list the project-board status of their linked issue, such as \"In beta\" or \"Behind flag\". End the entries covering them with that status in italics and parentheses, e.g. \"_(In beta)_\"; statuses such as Done or Shipped need no note.\n\n"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(number: u64, status: Option<&str>) -> PrContext {
+        let mut ctx = PrContext::fixture(number, &format!("PR {}", number));
+        ctx.project_status = status.map(str::to_string);
+        ctx
+    }
+
+    #[test]
+    fn test_entries_take_the_first_unfinished_status() {
+        let contexts = vec![
+            context(1, Some("Shipped")),
+            context(2, Some("In beta")),
+            context(3, None),
+        ];
+
+        assert_eq!(entry_status(&[1, 2], &contexts), Some("In beta"));
+        assert_eq!(entry_status(&[1, 3], &contexts), None);
+        assert!(is_finished(" done "));
+        assert!(context_block(&contexts[..1]).is_none());
+        assert!(context_block(&contexts).is_some());
+    }
+}
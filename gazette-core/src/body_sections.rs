@@ -0,0 +1,180 @@
+use anyhow::{Result, bail};
+use regex::Regex;
+
+use crate::config::BodySection;
+
+/// Parses rules typed as "Release Notes; <!-- notes --> <!-- /notes -->": heading names, or an
+/// opening and a closing HTML comment
+pub fn parse_rules(input: &str) -> Result<Vec<BodySection>> {
+    let comment = Regex::new(r"<!--\s*(.*?)\s*-->").unwrap();
+    let mut rules = Vec::new();
+
+    for part in input.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        if part.contains("<!--") {
+            let markers: Vec<&str> = comment
+                .captures_iter(part)
+                .map(|captures| captures.get(1).map_or("", |m| m.as_str()))
+                .collect();
+            let [start, end] = markers.as_slice() else {
+                bail!("Expected an opening and a closing comment in '{}'", part);
+            };
+            if start.is_empty() || start == end {
+                bail!("The comments in '{}' must differ and not be empty", part);
+            }
+            rules.push(BodySection::Markers {
+                start: start.to_string(),
+                end: end.to_string(),
+            });
+        } else {
+            let heading = part.trim_start_matches('#').trim();
+            if !heading.is_empty() {
+                rules.push(BodySection::Heading {
+                    heading: heading.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Formats rules the way `parse_rules` reads them
+pub fn format_rules(rules: &[BodySection]) -> String {
+    rules
+        .iter()
+        .map(|rule| match rule {
+            BodySection::Heading { heading } => heading.clone(),
+            BodySection::Markers { start, end } => format!("<!-- {} --> <!-- {} -->", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// The parts of a PR description the rules pick out, in the rules' order and without the
+/// template's HTML comments; None when the description has none of them, e.g. because it
+/// wasn't written from the template, so it is used whole
+pub fn extract(body: &str, rules: &[BodySection]) -> Option<String> {
+    let mut found = false;
+    let mut parts = Vec::new();
+    for rule in rules {
+        let sections = match rule {
+            BodySection::Heading { heading } => under_heading(body, heading),
+            BodySection::Markers { start, end } => between_markers(body, start, end),
+        };
+        for section in sections {
+            found = true;
+            let section = strip_comments(&section);
+            if !section.is_empty() {
+                parts.push(section);
+            }
+        }
+    }
+    found.then(|| parts.join("\n\n"))
+}
+
+/// The text under each heading named `name`, up to the next heading of the same or a higher
+/// level; lines in code blocks aren't headings
+fn under_heading(body: &str, name: &str) -> Vec<String> {
+    let wanted = normalized(name);
+    let mut sections = Vec::new();
+    // Level of the heading being collected, and its lines so far
+    let mut current: Option<(usize, Vec<&str>)> = None;
+    let mut in_code = false;
+
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        let heading = if in_code { None } else { heading(line) };
+
+        if let Some((level, text)) = heading {
+            if let Some((open, _)) = &current
+                && level <= *open
+            {
+                sections.extend(current.take().map(|(_, lines)| lines.join("\n")));
+            }
+            if current.is_none() && normalized(text) == wanted {
+                current = Some((level, Vec::new()));
+                continue;
+            }
+        }
+        if let Some((_, lines)) = &mut current {
+            lines.push(line);
+        }
+    }
+
+    sections.extend(current.map(|(_, lines)| lines.join("\n")));
+    sections
+}
+
+/// The text between each `<!-- start -->` and the next `<!-- end -->`, or the end of the body
+fn between_markers(body: &str, start: &str, end: &str) -> Vec<String> {
+    let marker =
+        |text: &str| Regex::new(&format!(r"<!--\s*{}\s*-->", regex::escape(text))).unwrap();
+    let (start, end) = (marker(start), marker(end));
+
+    let mut sections = Vec::new();
+    let mut rest = body;
+    while let Some(opening) = start.find(rest) {
+        rest = &rest[opening.end()..];
+        match end.find(rest) {
+            Some(closing) => {
+                sections.push(rest[..closing.start()].to_string());
+                rest = &rest[closing.end()..];
+            }
+            None => {
+                sections.push(rest.to_string());
+                break;
+            }
+        }
+    }
+    sections
+}
+
+/// A markdown heading's level and text, e.g. (2, "Release Notes") for "## Release Notes ##"
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_start();
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix([' ', '\t'])?;
+    (1..=6)
+        .contains(&level)
+        .then(|| (level, text.trim().trim_end_matches('#').trim()))
+}
+
+/// Heading text compared without case or surrounding emoji and punctuation
+fn normalized(text: &str) -> String {
+    text.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn strip_comments(text: &str) -> String {
+    let comments = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    comments.replace_all(text, "").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_the_release_notes_are_kept() {
+        let rules = parse_rules("## Release Notes; <!-- notes --> <!-- /notes -->").unwrap();
+        assert_eq!(
+            format_rules(&rules),
+            "Release Notes; <!-- notes --> <!-- /notes -->"
+        );
+        assert!(parse_rules("<!-- notes -->").is_err());
+
+        let body = "## Checklist\n- [x] Tests\n\n## 📝 Release notes\n<!-- What users will notice -->\nExports are 3x faster.\n\n### Details\nStreamed rows.\n```sh\n# not a heading\n```\n## Screenshots\nnone\n\n<!-- notes -->Works offline.<!-- /notes -->";
+        assert_eq!(
+            extract(body, &rules).unwrap(),
+            "Exports are 3x faster.\n\n### Details\nStreamed rows.\n```sh\n# not a heading\n```\n\nWorks offline."
+        );
+
+        let unfilled =
+            "## Release Notes\n<!-- What users will notice -->\n\n## Checklist\n- [ ] Tests";
+        assert_eq!(extract(unfilled, &rules).unwrap(), "");
+        assert_eq!(extract("Fixes a typo", &rules), None);
+        assert_eq!(extract(body, &[]), None);
+    }
+}
@@ -0,0 +1,121 @@
+use regex::Regex;
+
+use crate::changelog::PrContext;
+use crate::github::PullRequest;
+
+/// Labels that mark a PR as breaking, matched case-insensitively
+const BREAKING_LABELS: [&str; 4] = [
+    "breaking",
+    "breaking change",
+    "breaking-change",
+    "semver-major",
+];
+
+/// Why a PR counts as a breaking change
+#[derive(Debug, PartialEq)]
+pub struct BreakingChange {
+    pub number: u64,
+    pub title: String,
+    /// The text of a `BREAKING CHANGE:` footer, when the PR has one
+    pub note: Option<String>,
+}
+
+/// Detects a breaking change from a `type!:` title, a `BREAKING CHANGE:` footer or a label
+pub fn detect(pr: &PullRequest) -> Option<BreakingChange> {
+    let bang_title = Regex::new(r"^\s*\w+(\([^)]*\))?!:").unwrap();
+    let footer = Regex::new(r"(?m)^\s*BREAKING[ -]CHANGE:\s*(.*)$").unwrap();
+
+    let note = pr
+        .body
+        .as_deref()
+        .and_then(|body| footer.captures(body))
+        .map(|captures| captures[1].trim().to_string());
+    let labelled = pr.labels.iter().any(|label| {
+        BREAKING_LABELS
+            .iter()
+            .any(|breaking| label.name.eq_ignore_ascii_case(breaking))
+    });
+
+    (note.is_some() || labelled || bang_title.is_match(&pr.title)).then(|| BreakingChange {
+        number: pr.number,
+        title: pr.title.clone(),
+        note: note.filter(|note| !note.is_empty()),
+    })
+}
+
+/// Builds the block listing the breaking PRs for the prompt, None when there are none
+pub fn context_block(contexts: &[PrContext]) -> Option<String> {
+    let changes: Vec<BreakingChange> = contexts.iter().filter_map(|ctx| detect(&ctx.pr)).collect();
+    if changes.is_empty() {
+        return None;
+    }
+
+    let mut block = String::from(
+        "## Breaking changes\nThese PRs are breaking changes. List every one of them first, under a \"Breaking Changes\" heading, and say what users must change:\n",
+    );
+    for change in &changes {
+        match &change.note {
+            Some(note) => block.push_str(&format!(
+                "- PR #{}: {} ({})\n",
+                change.number, change.title, note
+            )),
+            None => block.push_str(&format!("- PR #{}: {}\n", change.number, change.title)),
+        }
+    }
+    block.push('\n');
+    Some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::GitHubLabel;
+
+    fn pr(title: &str, body: &str, labels: &[&str]) -> PullRequest {
+        PullRequest {
+            number: 7,
+            title: title.to_string(),
+            body: Some(body.to_string()),
+            merged_at: None,
+            user: None,
+            html_url: "https://github.com/acme/backend/pull/7".to_string(),
+            created_at: None,
+            merge_commit_sha: None,
+            author_association: None,
+            labels: labels
+                .iter()
+                .map(|name| GitHubLabel {
+                    name: name.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_breaking_changes_are_detected() {
+        assert!(detect(&pr("feat(api)!: drop v1 routes", "", &[])).is_some());
+        assert!(detect(&pr("refactor!: rename config keys", "", &[])).is_some());
+        assert!(detect(&pr("Bump ratatui", "", &["Breaking-Change"])).is_some());
+        assert_eq!(
+            detect(&pr(
+                "feat: new auth",
+                "Adds OAuth.\n\nBREAKING CHANGE: API keys are no longer accepted",
+                &[]
+            ))
+            .unwrap()
+            .note
+            .as_deref(),
+            Some("API keys are no longer accepted")
+        );
+
+        assert!(
+            detect(&pr(
+                "feat: add export",
+                "Mentions breaking changes in prose",
+                &["enhancement"]
+            ))
+            .is_none()
+        );
+        assert!(detect(&pr("Fix the ! in titles: escape it", "", &[])).is_none());
+    }
+}
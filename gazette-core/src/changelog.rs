@@ -0,0 +1,2740 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::ai::{
+    self, AIClient, ChangelogEntry, Completion, RequestLimiter, StructuredChangelog, TokenUsage,
+};
+use crate::artifact::ChangelogArtifact;
+use crate::board;
+use crate::body_sections;
+use crate::breaking;
+use crate::chart;
+use crate::ci::{self, CiStatus};
+use crate::clock;
+use crate::comments;
+use crate::compare;
+use crate::components::{self, ComponentMatcher, OTHER_COMPONENT};
+use crate::config::{
+    AIProvider, ActivityChart, BodySection, Category, ChangelogFormat, CommentOrder, Component,
+    Config, DeliveryChannel, DuplicatePrs, ExistingChangelog, GenerationMode, LabelMapping,
+    MarkdownFlavor, Repo, ReviewerCredits, TimePeriod,
+};
+use crate::contributors::{self, Contributors};
+use crate::conventional;
+use crate::delivery::{self, Delivery, DeliveryOutcome, OutputSink};
+use crate::dependencies;
+use crate::deployments::{self, Deployment};
+use crate::digest;
+use crate::discussions;
+use crate::error::GazetteError;
+use crate::excerpts;
+use crate::feed;
+use crate::github::{
+    GitHubClient, GitHubDiscussion, GitHubRelease, PrComment, PrStats, PullRequest,
+};
+use crate::history;
+use crate::hooks::{self, PostGenerateHook};
+use crate::impact;
+use crate::jira::{self, Epic, JiraClient, JiraIssue, OpenIssue};
+use crate::keep_a_changelog;
+use crate::labels;
+use crate::leadtime;
+use crate::links::{self, KnownRefs, LinkReport};
+use crate::markdown::{self, YamlValue};
+use crate::paths::PathFilter;
+use crate::progress::{ProgressFn, Stage};
+use crate::reviewers;
+use crate::runlog::{self, RunLogEntry, RunStep};
+use crate::secrets;
+use crate::security::{self, SecurityFix};
+use crate::source::PrSource;
+use crate::statistics::Statistics;
+use crate::store::{PrReport, Store};
+use crate::summary_cache::{self, SummaryCache};
+use crate::taxonomy;
+use crate::template::{self, RunInfo, TemplateContext};
+use crate::titles::TitleFilter;
+use crate::tracker::IssueTracker;
+use crate::usage;
+use crate::versions;
+
+/// Number of per-PR summaries requested concurrently in two-pass mode
+const SUMMARY_CONCURRENCY: usize = 4;
+
+/// Number of PRs whose changed files or sizes are fetched concurrently
+const CHANGED_FILES_CONCURRENCY: usize = 8;
+
+/// Aggregated data for a single PR
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct PrContext {
+    pub pr: PullRequest,
+    pub jira_issues: Vec<JiraIssue>,
+    /// Already included in an earlier changelog and kept because duplicates are marked
+    #[serde(default)]
+    pub previously_reported: bool,
+    /// Components of the repo the PR changed files in, in configuration order
+    #[serde(default)]
+    pub components: Vec<String>,
+    /// Size of the PR, fetched when impact scoring is enabled
+    #[serde(default)]
+    pub stats: Option<PrStats>,
+    /// Whether the PR reached the tracked deployment environment
+    #[serde(default)]
+    pub deployment: Option<Deployment>,
+    /// CI results of the PR's merge commit, when CI status checks are enabled
+    #[serde(default)]
+    pub ci: Option<CiStatus>,
+    /// Project-board status of the PR's linked issue, e.g. "In beta"
+    #[serde(default)]
+    pub project_status: Option<String>,
+    /// Linked issues not in a terminal status yet, collected when they're warned about
+    #[serde(default)]
+    pub open_issues: Vec<OpenIssue>,
+    /// Excerpted comments, when PR comments are included
+    #[serde(default)]
+    pub comments: Vec<PrComment>,
+    /// `@login` of the people who approved the PR, when reviewers are credited
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+    /// Co-authors from the `Co-authored-by:` trailers of the PR's commits, when contributors
+    /// are credited or counted
+    #[serde(default)]
+    pub co_authors: Vec<String>,
+}
+
+#[cfg(test)]
+impl PrContext {
+    /// A PR of acme/backend with nothing but its number and title, for tests to fill in
+    pub(crate) fn fixture(number: u64, title: &str) -> Self {
+        Self {
+            pr: PullRequest {
+                number,
+                title: title.to_string(),
+                html_url: format!("https://github.com/acme/backend/pull/{}", number),
+                ..PullRequest::default()
+            },
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn labeled(mut self, labels: &[&str]) -> Self {
+        self.pr.labels = labels
+            .iter()
+            .map(|name| crate::github::GitHubLabel {
+                name: name.to_string(),
+            })
+            .collect();
+        self
+    }
+}
+
+/// A changelog generated by the AI but not yet saved or delivered
+pub struct ChangelogDraft {
+    pub repo: Repo,
+    pub period: TimePeriod,
+    /// The final markdown document
+    pub content: String,
+    /// Categorized entries, when the structured format is used
+    pub structured: Option<StructuredChangelog>,
+    pub contexts: Vec<PrContext>,
+    pub usage: Option<TokenUsage>,
+    pub cost: Option<f64>,
+    pub links: LinkReport,
+    /// Things to check before publishing, such as PRs linking unfinished issues
+    pub warnings: Vec<String>,
+    /// The release the changelog is named after, with version naming
+    pub release: Option<GitHubRelease>,
+}
+
+/// Result of a successful changelog generation
+pub struct GeneratedChangelog {
+    pub path: PathBuf,
+    /// The markdown generated in this run
+    pub content: String,
+    pub usage: Option<TokenUsage>,
+    pub cost: Option<f64>,
+    pub links: LinkReport,
+    pub warnings: Vec<String>,
+    /// Path of the JSON artifact, when enabled
+    pub artifact: Option<PathBuf>,
+    /// Path of the repo's Atom feed, when enabled
+    pub feed: Option<PathBuf>,
+    pub deliveries: Vec<DeliveryOutcome>,
+}
+
+/// Executive summary written by a final AI pass over a digest's changelogs
+pub struct DigestSummary {
+    pub text: String,
+    pub usage: Option<TokenUsage>,
+    pub cost: Option<f64>,
+}
+
+/// Token usage and cost accumulated over the AI calls of a single generation
+#[derive(Default)]
+struct UsageTally {
+    usage: Option<TokenUsage>,
+    cost: Option<f64>,
+}
+
+/// What happened in a repo within the period besides its merged PRs
+#[derive(Default)]
+struct Activity {
+    security: Vec<SecurityFix>,
+    discussions: Vec<GitHubDiscussion>,
+}
+
+/// Service responsible for generating changelogs
+pub struct ChangelogService {
+    source: Box<dyn PrSource>,
+    trackers: Vec<Box<dyn IssueTracker>>,
+    /// None when changelogs are formatted from PR titles and labels alone
+    ai_client: Option<Box<dyn AIClient>>,
+    /// Keeps AI calls under the provider's configured requests-per-minute limit
+    ai_limiter: Option<Arc<RequestLimiter>>,
+    ai_provider: AIProvider,
+    ai_model: String,
+    format: ChangelogFormat,
+    /// Custom sections replacing the built-in categories; empty keeps them
+    categories: Vec<Category>,
+    /// Labels whose PRs go in a given category, shown with an emoji
+    label_map: BTreeMap<String, LabelMapping>,
+    markdown_flavor: MarkdownFlavor,
+    frontmatter: bool,
+    frontmatter_tags: Vec<String>,
+    frontmatter_fields: BTreeMap<String, String>,
+    version_naming: bool,
+    version_file_pattern: String,
+    version_title_pattern: String,
+    mode: GenerationMode,
+    /// Mode chosen for the whole run, overriding the per-repo modes
+    mode_override: Option<GenerationMode>,
+    two_pass: bool,
+    json_artifact: bool,
+    atom_feed: bool,
+    contributor_shoutouts: bool,
+    reviewer_credits: ReviewerCredits,
+    lead_time_stats: bool,
+    stats_appendix: bool,
+    activity_chart: ActivityChart,
+    group_dependency_updates: bool,
+    impact_scoring: bool,
+    /// Fetch each PR's size, for impact scoring and the stats section's line counts
+    pr_sizes: bool,
+    /// Read the co-authors of each PR's commits, for the contributors section and stats
+    commit_co_authors: bool,
+    /// Environment whose deployments tell shipped PRs from merged-only ones
+    deployment_environment: Option<String>,
+    ci_status: bool,
+    security_advisories: bool,
+    /// Comments excerpted per PR, 0 for none
+    pr_comments: usize,
+    pr_comment_order: CommentOrder,
+    /// Discussion categories listed alongside the PRs, None when discussions aren't
+    discussion_categories: Option<Vec<String>>,
+    /// Leave out linked issues that aren't in a terminal status
+    jira_done_only: bool,
+    /// Note the PRs whose linked issues aren't done yet in the draft's warnings
+    warn_open_issues: bool,
+    /// Comment each Jira issue's changelog entries on it after delivering
+    jira_comments: bool,
+    /// Cluster the entries of Jira epics with several of them under the epic's name
+    group_by_epic: bool,
+    /// Sections of PR descriptions given to the AI in place of the whole description
+    pr_body_sections: Vec<BodySection>,
+    /// Single-select project field, e.g. "Status", read for each PR's linked issue
+    project_status_field: Option<String>,
+    delivery_channels: Vec<DeliveryChannel>,
+    /// Sinks added by the embedding code, delivered to after the configured channels
+    sinks: Vec<Box<dyn OutputSink>>,
+    keep_a_changelog: bool,
+    pull_request_dir: String,
+    document_template: Option<String>,
+    /// Overrides the default changelog_<repo>_<date>.md location
+    output_path: Option<PathBuf>,
+    /// Name of the window in a run of several, e.g. "7d", ending the saved files' names
+    window: Option<String>,
+    existing_changelog: ExistingChangelog,
+    /// Overwrite changelog files that already exist
+    force: bool,
+    /// Asked what to do with an existing file when existing_changelog is Prompt
+    collision_resolver: Option<CollisionResolver>,
+    duplicate_prs: DuplicatePrs,
+    cross_check_changelog: bool,
+    /// Title patterns of PRs left out of every repo's changelogs
+    exclude_titles: Vec<String>,
+    /// Issue keys looked up at the same time while gathering a repo's context
+    issue_concurrency: usize,
+    /// Command that filters or edits the PR context before drafting
+    pre_generate_hook: Option<String>,
+    instructions: Option<String>,
+    /// Jira site the issue links point to, read once for the run
+    jira_base_url: Option<String>,
+    /// Release each repo and period's changelog is named after, looked up once for the run
+    releases: Mutex<Vec<(String, TimePeriod, Option<GitHubRelease>)>>,
+    progress: Option<ProgressFn>,
+    /// Shared by the run log entries of everything this service generates
+    run_started: DateTime<Utc>,
+}
+
+impl ChangelogService {
+    /// Creates a new changelog service
+    /// Jira client is optional - if credentials are missing, Jira context will be skipped
+    pub fn new() -> Result<Self> {
+        Self::from_config(&Config::load()?)
+    }
+
+    /// Creates a changelog service from an explicit config, e.g. one overridden by CLI flags
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let source = Box::new(GitHubClient::new()?);
+
+        // Load AI provider and model from config
+        let model = config.get_ai_model();
+        let ai_client = if config.no_ai {
+            None
+        } else {
+            Some(ai::create_ai_client(config.ai_provider, &model)?)
+        };
+
+        // Jira is optional, and can be skipped for a run
+        let skip_jira = config.no_jira || config.no_enrichment;
+        let jira = JiraClient::new().ok().filter(|_| !skip_jira);
+        let trackers = jira
+            .into_iter()
+            .map(|jira| Box::new(jira) as Box<dyn IssueTracker>)
+            .collect();
+
+        let sinks = config
+            .post_generate_hook
+            .as_deref()
+            .map(|command| Box::new(PostGenerateHook::new(command)) as Box<dyn OutputSink>)
+            .into_iter()
+            .collect();
+
+        Ok(Self {
+            source,
+            trackers,
+            ai_client,
+            ai_limiter: config
+                .ai_rate_limit()
+                .map(|rpm| ai::limiter(config.ai_provider, rpm)),
+            ai_provider: config.ai_provider,
+            ai_model: model,
+            format: config.changelog_format,
+            categories: config.categories.clone(),
+            label_map: config.label_map.clone(),
+            markdown_flavor: config.markdown_flavor,
+            frontmatter: config.frontmatter,
+            frontmatter_tags: config.frontmatter_tags.clone(),
+            frontmatter_fields: config.frontmatter_fields.clone(),
+            version_naming: config.version_naming,
+            version_file_pattern: config.get_version_file_pattern(),
+            version_title_pattern: config.get_version_title_pattern(),
+            mode: config.generation_mode,
+            mode_override: None,
+            two_pass: config.two_pass_generation,
+            json_artifact: config.json_artifact,
+            atom_feed: config.atom_feed,
+            contributor_shoutouts: config.contributor_shoutouts,
+            reviewer_credits: if config.no_enrichment {
+                ReviewerCredits::Off
+            } else {
+                config.reviewer_credits
+            },
+            lead_time_stats: config.lead_time_stats,
+            stats_appendix: config.stats_appendix,
+            activity_chart: config.activity_chart,
+            group_dependency_updates: config.group_dependency_updates,
+            impact_scoring: config.impact_scoring && !config.no_enrichment,
+            pr_sizes: (config.impact_scoring || config.stats_appendix) && !config.no_enrichment,
+            commit_co_authors: (config.contributor_shoutouts || config.stats_appendix)
+                && !config.no_enrichment,
+            deployment_environment: config
+                .deployment_environment
+                .clone()
+                .filter(|_| !config.no_enrichment),
+            ci_status: config.ci_status && !config.no_enrichment,
+            security_advisories: config.security_advisories && !config.no_enrichment,
+            pr_comments: if config.no_enrichment {
+                0
+            } else {
+                config.pr_comments
+            },
+            pr_comment_order: config.pr_comment_order,
+            discussion_categories: config
+                .discussions
+                .then(|| config.discussion_categories.clone())
+                .filter(|_| !config.no_enrichment),
+            jira_done_only: config.jira_done_only,
+            warn_open_issues: config.warn_open_issues,
+            jira_comments: config.jira_comments,
+            group_by_epic: config.group_by_epic,
+            pr_body_sections: config.pr_body_sections.clone(),
+            project_status_field: config
+                .project_status_field
+                .clone()
+                .filter(|_| !config.no_enrichment),
+            // The Jira version publisher is added by the caller as a sink, see `with_sink`
+            delivery_channels: config
+                .delivery_channels
+                .iter()
+                .copied()
+                .filter(|channel| *channel != DeliveryChannel::JiraVersion)
+                .collect(),
+            sinks,
+            keep_a_changelog: config.keep_a_changelog,
+            pull_request_dir: config.get_pull_request_dir(),
+            document_template: config.document_template.clone(),
+            output_path: None,
+            window: None,
+            existing_changelog: config.existing_changelog,
+            force: false,
+            collision_resolver: None,
+            duplicate_prs: config.duplicate_prs,
+            cross_check_changelog: config.cross_check_changelog,
+            exclude_titles: config.exclude_titles.clone(),
+            issue_concurrency: config.get_issue_concurrency(),
+            pre_generate_hook: config.pre_generate_hook.clone(),
+            instructions: None,
+            jira_base_url: secrets::get("JIRA_URL"),
+            releases: Mutex::default(),
+            progress: None,
+            run_started: Utc::now(),
+        })
+    }
+
+    /// Saves changelogs to `path` instead of the working directory
+    pub fn with_output(mut self, path: PathBuf) -> Self {
+        self.output_path = Some(path);
+        self
+    }
+
+    /// Fetches PRs from `source` instead of the GitHub API
+    pub fn with_source(mut self, source: Box<dyn PrSource>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Resolves issue keys with `tracker` as well, after the trackers already added
+    pub fn with_tracker(mut self, tracker: Box<dyn IssueTracker>) -> Self {
+        self.trackers.push(tracker);
+        self
+    }
+
+    /// Delivers every saved changelog to `sink` as well as the configured channels
+    pub fn with_sink(mut self, sink: Box<dyn OutputSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Names saved changelogs after `window`, e.g. changelog_<repo>_<date>_7d.md, so the windows
+    /// of one run don't overwrite each other; PRs count as reported by the same window only
+    pub fn with_window(mut self, window: &str) -> Self {
+        self.window = Some(window.to_string());
+        self
+    }
+
+    /// Overwrites changelog files that already exist, e.g. for automation re-running a day
+    pub fn with_force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// Generates every repo in `mode`, ignoring the modes set per repo
+    pub fn with_mode(mut self, mode: GenerationMode) -> Self {
+        self.mode_override = Some(mode);
+        self
+    }
+
+    /// The mode `repo` is generated in: the run's, then the repo's, then the configured one
+    pub fn mode_for(&self, repo: &Repo) -> GenerationMode {
+        self.mode_override.or(repo.mode).unwrap_or(self.mode)
+    }
+
+    /// Release notes are saved as their own files rather than merged into CHANGELOG.md
+    fn uses_keep_a_changelog(&self, repo: &Repo) -> bool {
+        self.keep_a_changelog && self.mode_for(repo) == GenerationMode::Changelog
+    }
+
+    /// Lets `resolver` decide what to do with existing changelog files when existing_changelog
+    /// is Prompt; without one, a numbered copy is saved
+    pub fn with_collision_resolver(mut self, resolver: CollisionResolver) -> Self {
+        self.collision_resolver = Some(resolver);
+        self
+    }
+
+    /// Reports each stage of a generation to `progress`
+    pub fn with_progress(mut self, progress: ProgressFn) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Appends extra instructions to the AI prompt, e.g. to tweak the tone of a regeneration
+    pub fn with_instructions(mut self, instructions: String) -> Self {
+        self.instructions = Some(instructions);
+        self
+    }
+
+    /// Generates a changelog for a single repository, then saves and delivers it
+    pub async fn generate_for_repo(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+    ) -> Result<GeneratedChangelog> {
+        let draft = self.draft_for_repo(repo, period).await?;
+        self.publish(draft).await
+    }
+
+    /// Generates a changelog for a single repository without saving or delivering it
+    pub async fn draft_for_repo(&self, repo: &Repo, period: TimePeriod) -> Result<ChangelogDraft> {
+        let pr_contexts = self.gather_for_repo(repo, period.clone()).await?;
+        self.draft_gathered(repo, period, pr_contexts).await
+    }
+
+    /// Steps 1 and 2 of drafting: fetches the merged PRs and their Jira context
+    pub async fn gather_for_repo(&self, repo: &Repo, period: TimePeriod) -> Result<Vec<PrContext>> {
+        let started = Instant::now();
+        let result = self.fetch_contexts(repo, period).await;
+        let prs = result.as_ref().ok().map(Vec::len);
+        self.log_step(repo, RunStep::Gather, started, &result, |entry| {
+            entry.prs = prs
+        });
+        result
+    }
+
+    async fn fetch_contexts(&self, repo: &Repo, period: TimePeriod) -> Result<Vec<PrContext>> {
+        // 1. Fetch merged PRs within the configured period
+        let on_page = |page| self.report(repo, Stage::FetchingPrs { page });
+        let mut prs = self
+            .source
+            .merged_prs(repo, period.clone(), &on_page)
+            .await?;
+
+        // A milestone lists its PRs whatever the dated changelogs already covered
+        let mut reported = if matches!(period, TimePeriod::Milestone { .. }) {
+            HashSet::new()
+        } else {
+            let release = self.release_for(repo, &period).await?;
+            self.previously_reported(repo, release.as_ref())?
+        };
+        // Notes the maintainers wrote themselves count as reported whatever the period
+        if self.cross_check_changelog && self.duplicate_prs != DuplicatePrs::Include {
+            reported.extend(self.documented_prs(repo).await?);
+        }
+        if self.duplicate_prs == DuplicatePrs::Skip {
+            prs.retain(|pr| !reported.contains(&pr.number));
+        }
+
+        let titles = TitleFilter::new(self.exclude_titles.iter().chain(&repo.exclude_titles))?;
+        prs.retain(|pr| !titles.excludes(&pr.title));
+
+        // Path scopes and components both go by the files each PR changed
+        let filter = PathFilter::new(&repo.paths)?;
+        let matcher = ComponentMatcher::new(&repo.components)?;
+        let changed = if repo.paths.is_empty() && repo.components.is_empty() {
+            HashMap::new()
+        } else {
+            self.changed_files(repo, &prs).await?
+        };
+        if !repo.paths.is_empty() {
+            prs.retain(|pr| filter.matches_any(&changed[&pr.number]));
+        }
+
+        if prs.is_empty() {
+            return Err(GazetteError::EmptyPeriod { period }.into());
+        }
+
+        // 2. Fetch Jira context for each PR
+        let mut pr_contexts = self.enrich_with_issues(repo, &prs).await;
+        for ctx in &mut pr_contexts {
+            ctx.previously_reported = reported.contains(&ctx.pr.number);
+            if let Some(files) = changed.get(&ctx.pr.number) {
+                ctx.components = matcher.components_of(files);
+            }
+            if self.warn_open_issues {
+                ctx.open_issues = ctx
+                    .jira_issues
+                    .iter()
+                    .filter(|issue| issue.is_done() == Some(false))
+                    .map(|issue| OpenIssue {
+                        key: issue.key.clone(),
+                        status: issue
+                            .fields
+                            .status
+                            .as_ref()
+                            .map(|s| s.name.clone())
+                            .unwrap_or_default(),
+                    })
+                    .collect();
+            }
+            if self.jira_done_only {
+                ctx.jira_issues
+                    .retain(|issue| issue.is_done() != Some(false));
+            }
+        }
+        if self.pr_sizes {
+            self.measure_prs(repo, &mut pr_contexts).await?;
+        }
+        if self.pr_comments > 0 {
+            self.read_comments(repo, &mut pr_contexts).await?;
+        }
+        if self.reviewer_credits != ReviewerCredits::Off {
+            self.read_reviews(repo, &mut pr_contexts).await?;
+        }
+        if self.commit_co_authors {
+            self.read_commits(repo, &mut pr_contexts).await?;
+        }
+        if let Some(environment) = &self.deployment_environment {
+            self.track_deployments(repo, &period, environment, &mut pr_contexts)
+                .await?;
+        }
+        if self.ci_status {
+            self.check_ci(repo, &mut pr_contexts).await?;
+        }
+        if let Some(field) = &self.project_status_field {
+            self.read_board(repo, field, &mut pr_contexts).await?;
+        }
+        Store::open()?.record_prs(repo, &pr_contexts)?;
+
+        Ok(pr_contexts)
+    }
+
+    /// Runs `fetch` for each of a repo's PRs, CHANGED_FILES_CONCURRENCY at a time and in order,
+    /// reporting the stage `stage` builds from the PRs done and the total as they complete
+    async fn fetch_per_pr<I, T, Fut>(
+        &self,
+        repo: &Repo,
+        items: Vec<I>,
+        stage: impl Fn(usize, usize) -> Stage,
+        fetch: impl FnMut(I) -> Fut,
+    ) -> Vec<T>
+    where
+        Fut: Future<Output = T>,
+    {
+        let total = items.len();
+        stream::iter(items)
+            .map(fetch)
+            .buffered(CHANGED_FILES_CONCURRENCY)
+            .enumerate()
+            .map(|(done, result)| {
+                self.report(repo, stage(done + 1, total));
+                result
+            })
+            .collect()
+            .await
+    }
+
+    /// Fetches the size of each PR for impact scoring and the stats section
+    async fn measure_prs(&self, repo: &Repo, contexts: &mut [PrContext]) -> Result<()> {
+        let results = self
+            .fetch_per_pr(
+                repo,
+                pr_numbers(contexts),
+                |done, total| Stage::MeasuringPrs { done, total },
+                |number| async move { self.source.pr_stats(repo, number).await },
+            )
+            .await;
+
+        for (ctx, stats) in contexts.iter_mut().zip(results) {
+            let stats = stats
+                .with_context(|| format!("Failed to fetch the size of PR #{}", ctx.pr.number))?;
+            ctx.stats = Some(stats);
+        }
+        Ok(())
+    }
+
+    /// Fetches the comments of each PR and keeps the ones to excerpt
+    async fn read_comments(&self, repo: &Repo, contexts: &mut [PrContext]) -> Result<()> {
+        let results = self
+            .fetch_per_pr(
+                repo,
+                pr_numbers(contexts),
+                |done, total| Stage::ReadingComments { done, total },
+                |number| async move { self.source.pr_comments(repo, number).await },
+            )
+            .await;
+
+        for (ctx, comments) in contexts.iter_mut().zip(results) {
+            let comments = comments.with_context(|| {
+                format!("Failed to fetch the comments of PR #{}", ctx.pr.number)
+            })?;
+            ctx.comments = comments::select(comments, self.pr_comments, self.pr_comment_order);
+        }
+        Ok(())
+    }
+
+    /// Fetches who approved each PR
+    async fn read_reviews(&self, repo: &Repo, contexts: &mut [PrContext]) -> Result<()> {
+        let results = self
+            .fetch_per_pr(
+                repo,
+                pr_numbers(contexts),
+                |done, total| Stage::ReadingReviews { done, total },
+                |number| async move { self.source.pr_reviews(repo, number).await },
+            )
+            .await;
+
+        for (ctx, reviews) in contexts.iter_mut().zip(results) {
+            let reviews = reviews
+                .with_context(|| format!("Failed to fetch the reviews of PR #{}", ctx.pr.number))?;
+            ctx.reviewers = reviewers::approvers(&reviews);
+        }
+        Ok(())
+    }
+
+    /// Fetches the commits of each PR for the co-authors credited in their messages
+    async fn read_commits(&self, repo: &Repo, contexts: &mut [PrContext]) -> Result<()> {
+        let results = self
+            .fetch_per_pr(
+                repo,
+                pr_numbers(contexts),
+                |done, total| Stage::ReadingCommits { done, total },
+                |number| async move { self.source.commit_messages(repo, number).await },
+            )
+            .await;
+
+        for (ctx, messages) in contexts.iter_mut().zip(results) {
+            let messages = messages
+                .with_context(|| format!("Failed to fetch the commits of PR #{}", ctx.pr.number))?;
+            let mut co_authors: Vec<String> = Vec::new();
+            for name in messages
+                .iter()
+                .flat_map(|message| contributors::co_authors(message))
+            {
+                if !co_authors
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(&name))
+                {
+                    co_authors.push(name);
+                }
+            }
+            ctx.co_authors = co_authors;
+        }
+        Ok(())
+    }
+
+    /// Marks each PR as deployed to the environment or merged only
+    async fn track_deployments(
+        &self,
+        repo: &Repo,
+        period: &TimePeriod,
+        environment: &str,
+        contexts: &mut [PrContext],
+    ) -> Result<()> {
+        let since = period.bounds_at(Utc::now()).0;
+        let deployments = self.source.deployments(repo, environment, since).await?;
+        // Most likely a repo that doesn't deploy through GitHub, rather than nothing shipped
+        if deployments.is_empty() {
+            return Ok(());
+        }
+        let merges: Vec<(u64, Option<String>, Option<DateTime<Utc>>)> = contexts
+            .iter()
+            .map(|ctx| {
+                (
+                    ctx.pr.number,
+                    ctx.pr.merge_commit_sha.clone(),
+                    ctx.pr.merged_at,
+                )
+            })
+            .collect();
+
+        let deployments = &deployments;
+        let fetch = |(number, sha, merged_at): (u64, Option<String>, Option<DateTime<Utc>>)| async move {
+            let (Some(sha), Some(merged_at)) = (sha, merged_at) else {
+                return Ok(None);
+            };
+            let shipped = deployments::first_shipping(merged_at, deployments, |head| {
+                self.source.commit_reaches(repo, &sha, head)
+            })
+            .await
+            .with_context(|| format!("Failed to check the deployment of PR #{}", number))?;
+            Ok::<_, anyhow::Error>(Some(match shipped {
+                Some(deployment) => Deployment::Deployed {
+                    environment: environment.to_string(),
+                    at: deployment.created_at,
+                },
+                None => Deployment::Pending {
+                    environment: environment.to_string(),
+                },
+            }))
+        };
+        let results = self
+            .fetch_per_pr(
+                repo,
+                merges,
+                |done, total| Stage::CheckingDeployments { done, total },
+                fetch,
+            )
+            .await;
+
+        for (ctx, deployment) in contexts.iter_mut().zip(results) {
+            ctx.deployment = deployment?;
+        }
+        Ok(())
+    }
+
+    /// Fetches the CI results of each PR's merge commit
+    async fn check_ci(&self, repo: &Repo, contexts: &mut [PrContext]) -> Result<()> {
+        let merges: Vec<(u64, Option<String>)> = contexts
+            .iter()
+            .map(|ctx| (ctx.pr.number, ctx.pr.merge_commit_sha.clone()))
+            .collect();
+
+        let fetch = |(number, sha): (u64, Option<String>)| async move {
+            let Some(sha) = sha else {
+                return Ok(None);
+            };
+            let checks = self
+                .source
+                .commit_checks(repo, &sha)
+                .await
+                .with_context(|| format!("Failed to fetch the CI status of PR #{}", number))?;
+            Ok::<_, anyhow::Error>(CiStatus::from_checks(&checks))
+        };
+        let results = self
+            .fetch_per_pr(
+                repo,
+                merges,
+                |done, total| Stage::CheckingCi { done, total },
+                fetch,
+            )
+            .await;
+
+        for (ctx, ci) in contexts.iter_mut().zip(results) {
+            ctx.ci = ci?;
+        }
+        Ok(())
+    }
+
+    /// Reads the project-board status of each PR's linked issue
+    async fn read_board(&self, repo: &Repo, field: &str, contexts: &mut [PrContext]) -> Result<()> {
+        let results = self
+            .fetch_per_pr(
+                repo,
+                pr_numbers(contexts),
+                |done, total| Stage::ReadingBoard { done, total },
+                |number| async move {
+                    self.source
+                        .project_status(repo, number, field)
+                        .await
+                        .with_context(|| {
+                            format!("Failed to read the board status of PR #{}", number)
+                        })
+                },
+            )
+            .await;
+
+        for (ctx, status) in contexts.iter_mut().zip(results) {
+            ctx.project_status = status?;
+        }
+        Ok(())
+    }
+
+    /// Lists the files each PR changed, keyed by PR number
+    async fn changed_files(
+        &self,
+        repo: &Repo,
+        prs: &[PullRequest],
+    ) -> Result<HashMap<u64, Vec<String>>> {
+        // Iterating over numbers keeps the closure free of higher-ranked lifetimes
+        let numbers: Vec<u64> = prs.iter().map(|pr| pr.number).collect();
+        let results = self
+            .fetch_per_pr(
+                repo,
+                numbers,
+                |done, total| Stage::CheckingPaths { done, total },
+                |number| async move {
+                    let files = self.source.changed_files(repo, number).await;
+                    (number, files)
+                },
+            )
+            .await;
+
+        results
+            .into_iter()
+            .map(|(number, files)| {
+                let files =
+                    files.with_context(|| format!("Failed to list the files of PR #{}", number))?;
+                Ok((number, files))
+            })
+            .collect()
+    }
+
+    /// Drafts the changelog from gathered PRs, e.g. after the user dropped some of them
+    /// The PRs are stored as the run's context so a regeneration reuses the same set
+    pub async fn draft_gathered(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        pr_contexts: Vec<PrContext>,
+    ) -> Result<ChangelogDraft> {
+        let started = Instant::now();
+        let prepared = self
+            .prepare_contexts(repo, period.clone(), pr_contexts)
+            .await;
+        if prepared.is_err() {
+            self.log_step(repo, RunStep::Draft, started, &prepared, |_| {});
+        }
+
+        self.draft_from_contexts(repo, period, prepared?, self.instructions.as_deref())
+            .await
+    }
+
+    /// Runs the pre-generation hook and stores what is left as the run's context
+    async fn prepare_contexts(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        mut pr_contexts: Vec<PrContext>,
+    ) -> Result<Vec<PrContext>> {
+        if let Some(command) = self.pre_generate_hook.clone() {
+            let hook_repo = repo.clone();
+            pr_contexts = tokio::task::spawn_blocking(move || {
+                hooks::run_pre_generate(&command, &hook_repo, pr_contexts)
+            })
+            .await
+            .context("Pre-generation hook panicked")??;
+        }
+        if pr_contexts.is_empty() {
+            return Err(GazetteError::EmptyPeriod { period }.into());
+        }
+        Store::open()?.save_run_context(repo, period, &pr_contexts)?;
+
+        Ok(pr_contexts)
+    }
+
+    /// Drafts the changelog again from the context stored by the repo's last run,
+    /// re-invoking only the AI so output can be iterated on without hitting GitHub or Jira
+    pub async fn regenerate_for_repo(&self, repo: &Repo) -> Result<ChangelogDraft> {
+        let run = Store::open()?.run_context(repo)?.with_context(|| {
+            format!(
+                "No stored context for {}; generate a changelog first",
+                repo.full_name()
+            )
+        })?;
+
+        self.draft_from_contexts(repo, run.period, run.contexts, self.instructions.as_deref())
+            .await
+    }
+
+    /// Generates a changelog again after a failed attempt, then saves and delivers it
+    /// The context the attempt stored is reused when it was captured since `since` for the
+    /// same period, so a retry after an AI failure doesn't hit GitHub or Jira again
+    pub async fn retry_for_repo(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        since: DateTime<Utc>,
+    ) -> Result<GeneratedChangelog> {
+        let stored = Store::open()?
+            .run_context(repo)?
+            .filter(|run| run.period == period && run.captured_at >= since);
+
+        let draft = match stored {
+            Some(run) => {
+                self.draft_from_contexts(repo, period, run.contexts, self.instructions.as_deref())
+                    .await?
+            }
+            None => self.draft_for_repo(repo, period).await?,
+        };
+        self.publish(draft).await
+    }
+
+    /// Drafts the changelog again from a draft's context with a hint for the AI, e.g. "be more concise"
+    pub async fn redraft(&self, draft: &ChangelogDraft, hint: &str) -> Result<ChangelogDraft> {
+        let instructions = match &self.instructions {
+            Some(instructions) => format!("{}\n{}", instructions, hint),
+            None => hint.to_string(),
+        };
+
+        self.draft_from_contexts(
+            &draft.repo,
+            draft.period.clone(),
+            draft.contexts.clone(),
+            Some(&instructions),
+        )
+        .await
+    }
+
+    async fn draft_from_contexts(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        pr_contexts: Vec<PrContext>,
+        instructions: Option<&str>,
+    ) -> Result<ChangelogDraft> {
+        let started = Instant::now();
+        let prs = pr_contexts.len();
+        let result = self
+            .prompt_ai(repo, period, pr_contexts, instructions)
+            .await;
+        self.log_step(repo, RunStep::Draft, started, &result, |entry| {
+            entry.prs = Some(prs);
+            if self.ai_client.is_some() {
+                entry.ai_provider = Some(self.ai_provider.short_name().to_string());
+                entry.ai_model = Some(self.ai_model.clone());
+            }
+        });
+        result
+    }
+
+    /// Steps 3 and on of drafting: prompts the AI (or formats the PRs directly without one) and renders the result
+    async fn prompt_ai(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        pr_contexts: Vec<PrContext>,
+        instructions: Option<&str>,
+    ) -> Result<ChangelogDraft> {
+        let mut tally = UsageTally::default();
+        let mode = self.mode_for(repo);
+        let release = self.release_for(repo, &period).await?;
+
+        // Dependency bumps skip drafting and are listed after the drafted categories
+        let (updates, drafted): (Vec<PrContext>, Vec<PrContext>) = if self.group_dependency_updates
+        {
+            pr_contexts
+                .iter()
+                .cloned()
+                .partition(|ctx| dependencies::is_update(&ctx.pr))
+        } else {
+            (Vec::new(), pr_contexts.clone())
+        };
+
+        // Without an AI client the entries come straight from the PR titles and labels
+        let (mut changelog, mut structured) = match &self.ai_client {
+            _ if drafted.is_empty() => {
+                let activity = self.activity(repo, &period).await?;
+                let structured = self.arrange(StructuredChangelog::default(), &[], &activity);
+                let markdown = render_structured(
+                    repo,
+                    &structured,
+                    &pr_contexts,
+                    mode,
+                    false,
+                    false,
+                    self.jira_base_url.as_deref(),
+                );
+                (markdown, Some(structured))
+            }
+            Some(ai_client) => {
+                self.generate_with_ai(
+                    ai_client.as_ref(),
+                    repo,
+                    period.clone(),
+                    &drafted,
+                    instructions,
+                    &mut tally,
+                )
+                .await?
+            }
+            None => {
+                let activity = self.activity(repo, &period).await?;
+                let structured =
+                    self.arrange(conventional::changelog(&drafted, mode), &drafted, &activity);
+                let by_component = !self.uses_keep_a_changelog(repo);
+                let by_epic = by_component && self.group_by_epic;
+                let markdown = render_structured(
+                    repo,
+                    &structured,
+                    &drafted,
+                    mode,
+                    by_component,
+                    by_epic,
+                    self.jira_base_url.as_deref(),
+                );
+                (markdown, Some(structured))
+            }
+        };
+        if let Some(category) = dependencies::category(&updates) {
+            let entries: Vec<String> = category
+                .entries
+                .iter()
+                .map(|entry| render_entry(entry, &pr_contexts, self.jira_base_url.as_deref()))
+                .collect();
+            changelog = format!(
+                "{}\n\n## {}\n{}\n",
+                changelog.trim_end(),
+                category.name,
+                entries.join("\n")
+            );
+            if let Some(structured) = &mut structured {
+                structured.categories.push(category);
+            }
+        }
+
+        // Verify PR and Jira links against the fetched data before saving
+        let (mut changelog, link_report) = links::repair_links(
+            &changelog,
+            &known_refs(repo, &pr_contexts, self.jira_base_url.as_deref()),
+        );
+
+        // CHANGELOG.md entries are merged per category, so thanks only go in standalone documents
+        if self.contributor_shoutouts
+            && !self.uses_keep_a_changelog(repo)
+            && let Some(section) = Contributors::collect(&pr_contexts).render()
+        {
+            changelog = format!("{}\n\n{}", changelog.trim_end(), section);
+        }
+        if self.reviewer_credits == ReviewerCredits::Appendix
+            && !self.uses_keep_a_changelog(repo)
+            && let Some(section) = reviewers::appendix(&pr_contexts)
+        {
+            changelog = format!("{}\n\n{}", changelog.trim_end(), section);
+        }
+        if self.lead_time_stats
+            && !self.uses_keep_a_changelog(repo)
+            && let Some(stats) = leadtime::stats(&pr_contexts)
+        {
+            changelog = format!("{}\n\n{}", changelog.trim_end(), leadtime::render(&stats));
+        }
+        if self.stats_appendix
+            && !self.uses_keep_a_changelog(repo)
+            && let Some(section) = Statistics::collect(&pr_contexts).render()
+        {
+            changelog = format!("{}\n\n{}", changelog.trim_end(), section);
+        }
+        if !self.uses_keep_a_changelog(repo)
+            && let Some(section) = chart::render(
+                self.activity_chart,
+                &chart::merges_per_day(&period, &pr_contexts),
+            )
+        {
+            changelog = format!("{}\n\n{}", changelog.trim_end(), section);
+        }
+
+        if let Some(release) = &release {
+            let title = versions::expand(
+                &self.version_title_pattern,
+                release,
+                mode.file_prefix(),
+                &repo.file_stem(),
+            );
+            changelog = versions::retitle(&changelog, &title);
+        }
+
+        // Assemble the final document from the template; CHANGELOG.md mode keeps its own layout
+        let changelog = match &self.document_template {
+            Some(path) if !self.uses_keep_a_changelog(repo) => {
+                let (ai_provider, ai_model) = match &self.ai_client {
+                    Some(_) => (self.ai_provider.short_name(), self.ai_model.as_str()),
+                    None => ("none", ""),
+                };
+                let run = RunInfo {
+                    repo,
+                    period: period.clone(),
+                    ai_provider,
+                    ai_model,
+                    jira_base_url: self.jira_base_url.as_deref(),
+                };
+                let context =
+                    TemplateContext::new(&run, &changelog, structured.as_ref(), &pr_contexts);
+                template::render_file(path, &context)?
+            }
+            _ => changelog,
+        };
+        // CHANGELOG.md keeps its own layout and has no frontmatter
+        let changelog = if self.uses_keep_a_changelog(repo) {
+            changelog
+        } else {
+            let changelog = markdown::normalize(&changelog, self.markdown_flavor);
+            match self.frontmatter_for(repo, &changelog) {
+                Some(fields) => markdown::with_frontmatter(&changelog, &fields),
+                None => changelog,
+            }
+        };
+
+        let warnings = jira::open_issue_warnings(&pr_contexts);
+        Ok(ChangelogDraft {
+            repo: repo.clone(),
+            period,
+            content: changelog,
+            structured,
+            contexts: pr_contexts,
+            usage: tally.usage,
+            cost: tally.cost,
+            links: link_report,
+            warnings,
+            release,
+        })
+    }
+
+    /// Steps 3 and 4 of drafting: builds the prompt and turns the AI's answer into markdown
+    async fn generate_with_ai(
+        &self,
+        ai_client: &dyn AIClient,
+        repo: &Repo,
+        period: TimePeriod,
+        pr_contexts: &[PrContext],
+        instructions: Option<&str>,
+        tally: &mut UsageTally,
+    ) -> Result<(String, Option<StructuredChangelog>)> {
+        let mode = self.mode_for(repo);
+        let activity = self.activity(repo, &period).await?;
+
+        // 3. Aggregate data into text format for AI, summarizing each PR first in two-pass mode
+        let mut context_text = if self.two_pass {
+            self.summarize_prs(ai_client, repo, pr_contexts, tally)
+                .await?
+        } else {
+            self.format_pr_context(pr_contexts)
+        };
+        if self.impact_scoring
+            && let Some(block) = impact::context_block(pr_contexts)
+        {
+            context_text.insert_str(0, &block);
+        }
+        if let Some(block) = breaking::context_block(pr_contexts) {
+            context_text.insert_str(0, &block);
+        }
+        // Structured entries are marked when rendered
+        if self.format == ChangelogFormat::Freeform
+            && let Some(block) = deployments::context_block(pr_contexts)
+        {
+            context_text.push_str(&block);
+        }
+        if self.format == ChangelogFormat::Freeform
+            && let Some(block) = ci::context_block(pr_contexts)
+        {
+            context_text.push_str(&block);
+        }
+        if self.format == ChangelogFormat::Freeform
+            && let Some(block) = board::context_block(pr_contexts)
+        {
+            context_text.push_str(&block);
+        }
+        if self.format == ChangelogFormat::Freeform
+            && let Some(block) = security::context_block(&activity.security)
+        {
+            context_text.push_str(&block);
+        }
+        if let Some(block) = discussions::context_block(
+            &activity.discussions,
+            self.format == ChangelogFormat::Freeform,
+        ) {
+            context_text.push_str(&block);
+        }
+        // Configured guidance is the same on every run, so it goes in the cached prefix
+        let mut guidance = String::new();
+        if self.format == ChangelogFormat::Freeform
+            && let Some(hint) = markdown::prompt_hint(self.markdown_flavor)
+        {
+            guidance.push_str(hint);
+        }
+        // Structured entries are grouped locally; CHANGELOG.md sections can't nest components
+        if self.format == ChangelogFormat::Freeform
+            && !self.uses_keep_a_changelog(repo)
+            && let Some(block) = components::context_block(&repo.components, pr_contexts)
+        {
+            context_text.push_str(&block);
+        }
+        if self.format == ChangelogFormat::Freeform
+            && self.reviewer_credits == ReviewerCredits::PerEntry
+            && let Some(block) = reviewers::context_block(pr_contexts)
+        {
+            context_text.push_str(&block);
+        }
+        if let Some(block) = labels::context_block(&self.label_map, pr_contexts) {
+            context_text.push_str(&block);
+        }
+        if let Some(block) = taxonomy::context_block(&self.categories) {
+            guidance.push_str(&block);
+        }
+        if let Some(instructions) = instructions {
+            context_text.push_str(&format!("\n## Additional instructions\n{}\n", instructions));
+        }
+
+        // 4. Generate changelog with AI
+        self.wait_for_ai(Some(repo)).await;
+        self.report(repo, Stage::WaitingOnAi);
+        let completion = match self.format {
+            ChangelogFormat::Structured => {
+                ai_client
+                    .generate_structured_changelog(
+                        &repo.full_name(),
+                        &context_text,
+                        &period.description(),
+                        &guidance,
+                        mode,
+                    )
+                    .await?
+            }
+            ChangelogFormat::Freeform => {
+                ai_client
+                    .generate_changelog(
+                        &repo.full_name(),
+                        &context_text,
+                        &period.description(),
+                        &guidance,
+                        mode,
+                    )
+                    .await?
+            }
+        };
+
+        self.record_usage(tally, completion.usage)?;
+        self.record_rate_limit(&completion);
+
+        // Validate AI output to avoid silently writing empty changelog files
+        if completion.text.trim().is_empty() {
+            return Err(self.ai_error(
+                "returned an empty changelog; please try again or check the AI provider configuration",
+            ));
+        }
+
+        Ok(match self.format {
+            ChangelogFormat::Structured => {
+                let structured = self.arrange(
+                    StructuredChangelog::parse(&completion.text)?,
+                    pr_contexts,
+                    &activity,
+                );
+                if structured.is_empty() {
+                    return Err(
+                        self.ai_error("returned a changelog with no entries; please try again")
+                    );
+                }
+                let by_component = !self.uses_keep_a_changelog(repo);
+                let by_epic = by_component && self.group_by_epic;
+                let markdown = render_structured(
+                    repo,
+                    &structured,
+                    pr_contexts,
+                    mode,
+                    by_component,
+                    by_epic,
+                    self.jira_base_url.as_deref(),
+                );
+                (markdown, Some(structured))
+            }
+            ChangelogFormat::Freeform => (completion.text.clone(), None),
+        })
+    }
+
+    /// Sorts structured entries into the label-mapped and configured categories, lists the
+    /// security fixes and discussions, then adds the label mappings' emoji to the category names
+    fn arrange(
+        &self,
+        changelog: StructuredChangelog,
+        contexts: &[PrContext],
+        activity: &Activity,
+    ) -> StructuredChangelog {
+        let changelog = labels::apply(&self.label_map, changelog, contexts);
+        let changelog = taxonomy::apply(&self.categories, changelog, contexts);
+        let changelog = security::apply(changelog, &activity.security);
+        let mut changelog = discussions::apply(changelog, &activity.discussions);
+        if self.reviewer_credits == ReviewerCredits::PerEntry {
+            reviewers::credit_entries(&mut changelog, contexts);
+        }
+        for category in &mut changelog.categories {
+            category.name = labels::heading(&self.label_map, category.name.trim());
+        }
+        changelog
+    }
+
+    /// Asks the AI for a leadership-facing summary of the changelogs combined into a digest
+    /// Returns None when changelogs are formatted without an AI provider
+    pub async fn summarize_digest(
+        &self,
+        title: &str,
+        period: TimePeriod,
+        changelogs: &[(Repo, String)],
+    ) -> Result<Option<DigestSummary>> {
+        let Some(ai_client) = &self.ai_client else {
+            return Ok(None);
+        };
+
+        self.wait_for_ai(None).await;
+        let completion = ai_client
+            .generate_digest_summary(
+                title,
+                &digest::summary_context(changelogs),
+                &period.description(),
+            )
+            .await?;
+        let mut tally = UsageTally::default();
+        self.record_usage(&mut tally, completion.usage)?;
+        self.record_rate_limit(&completion);
+
+        if completion.text.trim().is_empty() {
+            return Err(self.ai_error("returned an empty digest summary; please try again"));
+        }
+
+        Ok(Some(DigestSummary {
+            text: completion.text.trim().to_string(),
+            usage: tally.usage,
+            cost: tally.cost,
+        }))
+    }
+
+    /// Saves a drafted changelog and fans it out to the artifact, feed and delivery channels
+    pub async fn publish(&self, draft: ChangelogDraft) -> Result<GeneratedChangelog> {
+        let started = Instant::now();
+        let repo = draft.repo.clone();
+        let prs = draft.contexts.len();
+        let result = self.save_and_deliver(draft).await;
+        self.log_step(&repo, RunStep::Publish, started, &result, |entry| {
+            entry.prs = Some(prs);
+            if let Ok(generated) = &result {
+                entry.path = Some(generated.path.clone());
+                entry.failed_deliveries = generated
+                    .deliveries
+                    .iter()
+                    .filter_map(|outcome| {
+                        let error = outcome.result.as_ref().err()?;
+                        Some(format!("{}: {:#}", outcome.sink, error))
+                    })
+                    .collect();
+            }
+        });
+        result
+    }
+
+    async fn save_and_deliver(&self, draft: ChangelogDraft) -> Result<GeneratedChangelog> {
+        let ChangelogDraft {
+            repo,
+            period,
+            content,
+            structured,
+            contexts,
+            usage,
+            cost,
+            links,
+            warnings,
+            release,
+        } = draft;
+
+        // 5. Save to file
+        self.report(&repo, Stage::Saving);
+        let path = if self.uses_keep_a_changelog(&repo) {
+            self.update_keep_a_changelog(&repo, &content).await?
+        } else {
+            self.save_changelog(&repo, &content, release.as_ref())?
+        };
+
+        let excerpts = if self.jira_comments {
+            excerpts::excerpts(&content, structured.as_ref(), &contexts)
+        } else {
+            Vec::new()
+        };
+
+        let run_prs = compare::run_prs(&contexts, structured.as_ref());
+        Store::open()?.record_run(&repo, period.clone(), &path, &run_prs)?;
+
+        let artifact = if self.json_artifact {
+            let mut artifact = ChangelogArtifact::new(&repo, period.clone(), &contexts, structured);
+            if self.lead_time_stats {
+                artifact = artifact.with_lead_time(leadtime::stats(&contexts));
+            }
+            Some(artifact.save_next_to(&path)?)
+        } else {
+            None
+        };
+
+        let feed = if self.atom_feed {
+            Some(feed::publish(
+                &repo,
+                period.clone(),
+                markdown::strip_frontmatter(&content),
+            )?)
+        } else {
+            None
+        };
+
+        if !matches!(period, TimePeriod::Milestone { .. }) {
+            let numbers: Vec<u64> = contexts.iter().map(|ctx| ctx.pr.number).collect();
+            Store::open()?.record_reported(&repo, &numbers, &path)?;
+        }
+
+        let repo_path = self.repo_path_for(&repo, &path);
+        history::record(&repo, &path, &repo_path, period.clone())?;
+
+        // Delivery failures are reported per sink without failing the generation
+        let delivery = Delivery {
+            repo: &repo,
+            content: &content,
+            period: period.description(),
+            path: &path,
+            repo_path,
+            jira_keys: jira::linked_keys(&contexts),
+            version: release.map(|release| release.tag_name),
+        };
+        if !self.delivery_channels.is_empty() || !self.sinks.is_empty() || !excerpts.is_empty() {
+            self.report(&repo, Stage::Delivering);
+        }
+        let mut deliveries =
+            delivery::deliver_all(&self.delivery_channels, &self.sinks, &delivery).await;
+        if !excerpts.is_empty() {
+            let changelog_url = deliveries
+                .iter()
+                .filter_map(|outcome| outcome.result.as_ref().ok().cloned().flatten())
+                .find(|link| link.starts_with("http"));
+            let period = period.description();
+            deliveries.extend(
+                excerpts::post_comments(&repo, &period, &path, &excerpts, changelog_url.as_deref())
+                    .await,
+            );
+        }
+
+        Ok(GeneratedChangelog {
+            path,
+            content,
+            usage,
+            cost,
+            links,
+            warnings,
+            artifact,
+            feed,
+            deliveries,
+        })
+    }
+
+    /// Appends a step's outcome to the run log
+    fn log_step<T>(
+        &self,
+        repo: &Repo,
+        step: RunStep,
+        started: Instant,
+        result: &Result<T>,
+        fill: impl FnOnce(&mut RunLogEntry),
+    ) {
+        let mut entry = RunLogEntry::new(
+            self.run_started,
+            repo,
+            step,
+            started.elapsed(),
+            result.as_ref().err(),
+        );
+        fill(&mut entry);
+        // Losing a log line must not fail the generation it describes
+        let _ = runlog::append(&entry);
+    }
+
+    /// A completion from the configured provider that can't be used as a changelog
+    fn ai_error(&self, message: &str) -> anyhow::Error {
+        GazetteError::AiProvider {
+            provider: self.ai_provider.short_name().to_string(),
+            status: None,
+            message: message.to_string(),
+        }
+        .into()
+    }
+
+    /// Records a completion's token usage in the cost ledger and the running tally
+    fn record_usage(&self, tally: &mut UsageTally, usage: Option<TokenUsage>) -> Result<()> {
+        let Some(usage) = usage else {
+            return Ok(());
+        };
+
+        let cost = usage::record_usage(self.ai_provider, &self.ai_model, &usage)?;
+
+        tally
+            .usage
+            .get_or_insert_with(TokenUsage::default)
+            .add(&usage);
+        if let Some(cost) = cost {
+            *tally.cost.get_or_insert(0.0) += cost;
+        }
+
+        Ok(())
+    }
+
+    /// Keeps the quota the provider reported, shown by `gazette limits`
+    fn record_rate_limit(&self, completion: &Completion) {
+        if let Some(info) = &completion.rate_limit {
+            // A lost quota snapshot must not fail the generation
+            let _ = Store::open().and_then(|store| {
+                store.record_ai_rate_limit(self.ai_provider, &self.ai_model, info)
+            });
+        }
+    }
+
+    /// First pass of two-pass generation: summarizes each PR on its own, reusing cached summaries
+    /// Returns the condensed context used for the aggregate pass
+    async fn summarize_prs(
+        &self,
+        ai_client: &dyn AIClient,
+        repo: &Repo,
+        contexts: &[PrContext],
+        tally: &mut UsageTally,
+    ) -> Result<String> {
+        let repo_name = repo.full_name();
+        let cache = SummaryCache::load()?;
+
+        // Iterating by index keeps the closure free of higher-ranked lifetimes, which would
+        // otherwise make the future unusable from spawned tasks
+        let results: Vec<_> = stream::iter(0..contexts.len())
+            .map(|index| {
+                let ctx = &contexts[index];
+                let pr_text = self.format_pr_context(std::slice::from_ref(ctx));
+                let fingerprint = summary_cache::fingerprint(&self.ai_model, &pr_text);
+                let cached = cache
+                    .get(repo, ctx.pr.number, fingerprint)
+                    .map(str::to_string);
+                let repo_name = &repo_name;
+
+                async move {
+                    let result = match cached {
+                        Some(summary) => Ok((summary, None, false)),
+                        None => {
+                            self.wait_for_ai(Some(repo)).await;
+                            ai_client
+                                .generate_pr_summary(repo_name, &pr_text)
+                                .await
+                                .map(|c| (c.text.trim().to_string(), c.usage, true))
+                        }
+                    };
+                    (ctx, fingerprint, result)
+                }
+            })
+            .buffered(SUMMARY_CONCURRENCY)
+            .enumerate()
+            .map(|(done, result)| {
+                let total = contexts.len();
+                self.report(
+                    repo,
+                    Stage::SummarizingPrs {
+                        done: done + 1,
+                        total,
+                    },
+                );
+                result
+            })
+            .collect()
+            .await;
+
+        // Reload before writing so summaries cached by concurrent runs are kept
+        let mut cache = SummaryCache::load()?;
+        let mut output = String::new();
+
+        for (ctx, fingerprint, result) in results {
+            let (summary, usage, fresh) = result?;
+            self.record_usage(tally, usage)?;
+
+            if fresh && !summary.is_empty() {
+                cache.insert(repo, ctx.pr.number, fingerprint, summary.clone());
+            }
+
+            output.push_str(&self.format_pr_summary(ctx, &summary));
+        }
+
+        cache.save()?;
+
+        Ok(output)
+    }
+
+    /// Formats a PR with its first-pass summary for the aggregate pass
+    fn format_pr_summary(&self, ctx: &PrContext, summary: &str) -> String {
+        let mut output = format!("## PR #{}: {}\n", ctx.pr.number, ctx.pr.title);
+        output.push_str(&format!("URL: {}\n", ctx.pr.html_url));
+        if !ctx.components.is_empty() {
+            output.push_str(&format!("Components: {}\n", ctx.components.join(", ")));
+        }
+        if self.impact_scoring
+            && let Some(impact) = impact::describe(ctx)
+        {
+            output.push_str(&format!("{}\n", impact));
+        }
+        if let Some(deployment) = deployments::describe(ctx) {
+            output.push_str(&format!("{}\n", deployment));
+        }
+        if let Some(ci) = ci::describe(ctx) {
+            output.push_str(&format!("{}\n", ci));
+        }
+        if let Some(status) = board::describe(ctx) {
+            output.push_str(&format!("{}\n", status));
+        }
+        if let Some(category) = labels::describe(&self.label_map, ctx) {
+            output.push_str(&format!("{}\n", category));
+        }
+        // Structured entries are credited when arranged
+        if self.format == ChangelogFormat::Freeform
+            && self.reviewer_credits == ReviewerCredits::PerEntry
+            && let Some(approved) = reviewers::describe(ctx)
+        {
+            output.push_str(&format!("{}\n", approved));
+        }
+        output.push_str(&format!("Summary: {}\n", summary));
+
+        if !ctx.jira_issues.is_empty() {
+            output.push_str("Jira Context:\n");
+            for issue in &ctx.jira_issues {
+                match &self.jira_base_url {
+                    Some(base) => output.push_str(&format!(
+                        "- {} ({}/browse/{}): {}\n",
+                        issue.key,
+                        base.trim_end_matches('/'),
+                        issue.key,
+                        issue.fields.summary
+                    )),
+                    None => {
+                        output.push_str(&format!("- {}: {}\n", issue.key, issue.fields.summary))
+                    }
+                }
+            }
+        }
+
+        output.push_str("\n---\n\n");
+        output
+    }
+
+    /// Enriches PRs with the issues their title and body reference
+    async fn enrich_with_issues(&self, repo: &Repo, prs: &[PullRequest]) -> Vec<PrContext> {
+        // Extract the keys of each PR up front, with the trackers recognizing each one,
+        // so progress can be reported against the total
+        let keys: Vec<BTreeMap<String, Vec<usize>>> = prs
+            .iter()
+            .map(|pr| {
+                let text = match &pr.body {
+                    Some(body) => format!("{}\n{}", pr.title, body),
+                    None => pr.title.clone(),
+                };
+                let mut keys: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+                for (index, tracker) in self.trackers.iter().enumerate() {
+                    for key in tracker.extract_keys(&text) {
+                        let owners = keys.entry(key).or_default();
+                        if !owners.contains(&index) {
+                            owners.push(index);
+                        }
+                    }
+                }
+                keys
+            })
+            .collect();
+
+        // Look each distinct key up once, a few at a time, even when several PRs mention it
+        let mut unique: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for pr_keys in &keys {
+            for (key, owners) in pr_keys {
+                unique.entry(key.clone()).or_insert_with(|| owners.clone());
+            }
+        }
+        let total = unique.len();
+
+        let lookups: Vec<(String, Option<JiraIssue>)> = stream::iter(unique)
+            .map(|(key, owners)| async move {
+                let issue = self.lookup_issue(&key, &owners).await;
+                (key, issue)
+            })
+            .buffer_unordered(self.issue_concurrency)
+            .enumerate()
+            .map(|(done, lookup)| {
+                self.report(
+                    repo,
+                    Stage::EnrichingIssues {
+                        done: done + 1,
+                        total,
+                    },
+                );
+                lookup
+            })
+            .collect()
+            .await;
+        let resolved: HashMap<String, JiraIssue> = lookups
+            .into_iter()
+            .filter_map(|(key, issue)| Some((key, issue?)))
+            .collect();
+
+        // Issues keep the order their keys were listed in for each PR
+        prs.iter()
+            .zip(&keys)
+            .map(|(pr, pr_keys)| PrContext {
+                pr: pr.clone(),
+                jira_issues: pr_keys
+                    .keys()
+                    .filter_map(|key| resolved.get(key).cloned())
+                    .collect(),
+                ..PrContext::default()
+            })
+            .collect()
+    }
+
+    /// Resolves an issue from the cache, falling back to the trackers and caching what they find
+    async fn lookup_issue(&self, key: &str, owners: &[usize]) -> Option<JiraIssue> {
+        if let Ok(Some(issue)) = Store::open().and_then(|store| store.jira_issue(key)) {
+            return Some(issue);
+        }
+
+        let issue = self.resolve_issue(key, owners).await?;
+        if let Ok(store) = Store::open() {
+            let _ = store.cache_jira_issue(&issue);
+        }
+        Some(issue)
+    }
+
+    /// Asks each tracker recognizing the key in turn until one finds the issue
+    /// Lookup errors are skipped like missing issues, so one tracker's outage doesn't fail the run
+    async fn resolve_issue(&self, key: &str, owners: &[usize]) -> Option<JiraIssue> {
+        for &index in owners {
+            if let Ok(Some(issue)) = self.trackers[index].resolve(key).await {
+                return Some(issue);
+            }
+        }
+        None
+    }
+
+    /// Waits for a free slot under the AI provider's requests-per-minute limit, reporting the
+    /// wait when the call is queued
+    async fn wait_for_ai(&self, repo: Option<&Repo>) {
+        let Some(limiter) = &self.ai_limiter else {
+            return;
+        };
+        let wait = limiter.reserve();
+        if wait.is_zero() {
+            return;
+        }
+        if let Some(repo) = repo {
+            self.report(
+                repo,
+                Stage::QueuedForAi {
+                    wait_secs: wait.as_secs_f64().ceil() as u64,
+                },
+            );
+        }
+        tokio::time::sleep(wait).await;
+    }
+
+    fn report(&self, repo: &Repo, stage: Stage) {
+        if let Some(progress) = &self.progress {
+            progress(repo, stage);
+        }
+    }
+
+    /// Formats PR contexts as text for AI
+    fn format_pr_context(&self, contexts: &[PrContext]) -> String {
+        let mut output = String::new();
+
+        for ctx in contexts {
+            output.push_str(&format!("## PR #{}: {}\n", ctx.pr.number, ctx.pr.title));
+            output.push_str(&format!("URL: {}\n", ctx.pr.html_url));
+            if !ctx.components.is_empty() {
+                output.push_str(&format!("Components: {}\n", ctx.components.join(", ")));
+            }
+            if self.impact_scoring
+                && let Some(impact) = impact::describe(ctx)
+            {
+                output.push_str(&format!("{}\n", impact));
+            }
+            if let Some(deployment) = deployments::describe(ctx) {
+                output.push_str(&format!("{}\n", deployment));
+            }
+            if let Some(ci) = ci::describe(ctx) {
+                output.push_str(&format!("{}\n", ci));
+            }
+            if let Some(status) = board::describe(ctx) {
+                output.push_str(&format!("{}\n", status));
+            }
+            if let Some(category) = labels::describe(&self.label_map, ctx) {
+                output.push_str(&format!("{}\n", category));
+            }
+            // Structured entries are credited when arranged
+            if self.format == ChangelogFormat::Freeform
+                && self.reviewer_credits == ReviewerCredits::PerEntry
+                && let Some(approved) = reviewers::describe(ctx)
+            {
+                output.push_str(&format!("{}\n", approved));
+            }
+
+            if let Some(merged) = ctx.pr.merged_at {
+                output.push_str(&format!(
+                    "Merged at: {}\n",
+                    clock::format(merged, "%Y-%m-%d %H:%M %Z")
+                ));
+            }
+
+            if ctx.previously_reported {
+                output.push_str(
+                    "Previously reported: yes, this PR was already in an earlier changelog; label its entry as previously reported\n",
+                );
+            }
+
+            if let Some(body) = &ctx.pr.body {
+                let body = body_sections::extract(body, &self.pr_body_sections)
+                    .unwrap_or_else(|| body.clone());
+                if !body.trim().is_empty() {
+                    output.push_str(&format!("Description:\n{}\n", body));
+                }
+            }
+            if let Some(comments) = comments::describe(ctx) {
+                output.push_str(&comments);
+            }
+
+            if !ctx.jira_issues.is_empty() {
+                output.push_str("\nJira Context:\n");
+                for issue in &ctx.jira_issues {
+                    let jira_url = self
+                        .jira_base_url
+                        .as_ref()
+                        .map(|base| format!("{}/browse/{}", base.trim_end_matches('/'), issue.key));
+                    if let Some(url) = jira_url {
+                        output.push_str(&format!(
+                            "- {} ({}): {}\n",
+                            issue.key, url, issue.fields.summary
+                        ));
+                    } else {
+                        output.push_str(&format!("- {}: {}\n", issue.key, issue.fields.summary));
+                    }
+                    if let Some(status) = &issue.fields.status {
+                        output.push_str(&format!("  Status: {}\n", status.name));
+                    }
+                    if let Some(desc) = issue.description_text()
+                        && !desc.trim().is_empty()
+                    {
+                        let truncated: String = desc.chars().take(500).collect();
+                        output.push_str(&format!("  Details: {}\n", truncated));
+                    }
+                }
+            }
+
+            output.push_str("\n---\n\n");
+        }
+
+        output
+    }
+
+    /// Merges the changelog entries into the repo's CHANGELOG.md and returns its path
+    /// Uses the repo's local changelog_path if set, otherwise starts from the file on GitHub
+    /// and writes the updated copy to the current directory
+    async fn update_keep_a_changelog(&self, repo: &Repo, content: &str) -> Result<PathBuf> {
+        let local = self
+            .output_path
+            .clone()
+            .or_else(|| repo.changelog_path.as_ref().map(PathBuf::from));
+        let (path, existing) = match local {
+            Some(path) => {
+                let existing = if path.exists() {
+                    Some(fs::read_to_string(&path).context("Failed to read CHANGELOG.md")?)
+                } else {
+                    None
+                };
+                (path, existing)
+            }
+            None => {
+                let path = PathBuf::from(format!("CHANGELOG_{}.md", repo.file_stem()));
+                let existing = self.source.file_contents(repo, "CHANGELOG.md").await?;
+                (path, existing)
+            }
+        };
+
+        let entries = keep_a_changelog::extract_entries(content);
+        if entries.is_empty() {
+            anyhow::bail!("Generated changelog has no entries to add to CHANGELOG.md");
+        }
+
+        let merged = keep_a_changelog::merge_unreleased(existing.as_deref(), &entries);
+        fs::write(&path, merged).context("Failed to write CHANGELOG.md")?;
+
+        Ok(path)
+    }
+
+    /// Path the saved changelog should have inside the target repo
+    fn repo_path_for(&self, repo: &Repo, path: &std::path::Path) -> String {
+        if self.uses_keep_a_changelog(repo) {
+            return keep_a_changelog::REPO_PATH.to_string();
+        }
+
+        let filename = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        if self.pull_request_dir.is_empty() {
+            filename
+        } else {
+            format!("{}/{}", self.pull_request_dir, filename)
+        }
+    }
+
+    /// Where save_changelog writes the dated changelog file
+    fn changelog_file_path(&self, repo: &Repo, release: Option<&GitHubRelease>) -> PathBuf {
+        if let Some(path) = &self.output_path {
+            return path.clone();
+        }
+
+        let prefix = self.mode_for(repo).file_prefix();
+        let path = match release {
+            Some(release) => PathBuf::from(versions::file_name(
+                &self.version_file_pattern,
+                release,
+                prefix,
+                &repo.file_stem(),
+            )),
+            None => {
+                let date = clock::today().format("%Y-%m-%d");
+                PathBuf::from(format!("{}_{}_{}.md", prefix, repo.file_stem(), date))
+            }
+        };
+        match &self.window {
+            Some(window) => {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                path.with_file_name(format!("{}_{}.md", stem, window))
+            }
+            None => path,
+        }
+    }
+
+    /// The release a changelog of the period is named after; None without version naming
+    /// Gathering and drafting both need it, so the releases are listed once per repo and period
+    async fn release_for(&self, repo: &Repo, period: &TimePeriod) -> Result<Option<GitHubRelease>> {
+        if !self.version_naming || self.uses_keep_a_changelog(repo) {
+            return Ok(None);
+        }
+
+        let name = repo.full_name();
+        let known = |releases: &[(String, TimePeriod, Option<GitHubRelease>)]| {
+            releases
+                .iter()
+                .find(|(known, known_period, _)| *known == name && known_period == period)
+                .map(|(_, _, release)| release.clone())
+        };
+        if let Some(release) = known(&self.releases.lock().unwrap()) {
+            return Ok(release);
+        }
+
+        let releases = self.source.releases(repo).await?;
+        let release = versions::release_for(period, &releases).cloned();
+        self.releases
+            .lock()
+            .unwrap()
+            .push((name, period.clone(), release.clone()));
+        Ok(release)
+    }
+
+    /// The security fixes and discussions of the period, when listing them is enabled
+    /// Milestones aren't bound in time, so they list none
+    async fn activity(&self, repo: &Repo, period: &TimePeriod) -> Result<Activity> {
+        let mut activity = Activity::default();
+        if matches!(period, TimePeriod::Milestone { .. }) {
+            return Ok(activity);
+        }
+
+        if self.security_advisories {
+            let advisories = self.source.security_advisories(repo).await?;
+            let alerts = self.source.fixed_dependabot_alerts(repo).await?;
+            activity.security = security::resolved_in(period, &advisories, &alerts);
+        }
+        if let Some(categories) = &self.discussion_categories {
+            let listed = self.source.discussions(repo).await?;
+            activity.discussions = discussions::in_period(period, listed, categories);
+        }
+        Ok(activity)
+    }
+
+    /// Numbers of the PRs the repo's CHANGELOG.md mentions, read from `changelog_path` when
+    /// it exists locally and from the default branch otherwise
+    async fn documented_prs(&self, repo: &Repo) -> Result<HashSet<u64>> {
+        let existing = match &repo.changelog_path {
+            Some(path) if Path::new(path).exists() => {
+                Some(fs::read_to_string(path).context("Failed to read CHANGELOG.md")?)
+            }
+            _ => self
+                .source
+                .file_contents(repo, "CHANGELOG.md")
+                .await
+                .with_context(|| format!("Failed to fetch the CHANGELOG.md of {}", repo))?,
+        };
+
+        Ok(existing
+            .map(|markdown| keep_a_changelog::documented_prs(&markdown, &repo.full_name()))
+            .unwrap_or_default())
+    }
+
+    /// Numbers of the PRs of `repo` included in earlier changelogs, empty when duplicates are included
+    fn previously_reported(
+        &self,
+        repo: &Repo,
+        release: Option<&GitHubRelease>,
+    ) -> Result<HashSet<u64>> {
+        if self.duplicate_prs == DuplicatePrs::Include {
+            return Ok(HashSet::new());
+        }
+
+        // Merged files accumulate like CHANGELOG.md, so their PRs aren't added twice
+        let merges = self.existing_changelog == ExistingChangelog::Merge && !self.force;
+        let target = (!self.uses_keep_a_changelog(repo) && !merges)
+            .then(|| self.changelog_file_path(repo, release));
+
+        Ok(reported_numbers(
+            &Store::open()?.reported_prs(repo)?,
+            self.mode_for(repo),
+            self.window.as_deref(),
+            target.as_deref(),
+            clock::today(),
+        ))
+    }
+
+    /// Frontmatter fields of a saved changelog; None unless enabled or the docs flavor needs a title
+    fn frontmatter_for(&self, repo: &Repo, content: &str) -> Option<Vec<(String, YamlValue)>> {
+        if !self.frontmatter && self.markdown_flavor != MarkdownFlavor::Docs {
+            return None;
+        }
+
+        let title = content
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .map(str::trim)
+            .map(str::to_string)
+            .unwrap_or_else(|| repo.full_name());
+        let mut fields = vec![("title".to_string(), YamlValue::Text(title))];
+        if !self.frontmatter {
+            return Some(fields);
+        }
+
+        let mut tags = self.frontmatter_tags.clone();
+        for group in &repo.groups {
+            if !tags.contains(group) {
+                tags.push(group.clone());
+            }
+        }
+        fields.extend([
+            (
+                "date".to_string(),
+                YamlValue::Text(clock::today().format("%Y-%m-%d").to_string()),
+            ),
+            ("repo".to_string(), YamlValue::Text(repo.full_name())),
+            ("tags".to_string(), YamlValue::List(tags)),
+        ]);
+        fields.extend(
+            self.frontmatter_fields
+                .iter()
+                .filter(|(key, _)| !["title", "date", "repo", "tags"].contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), YamlValue::Text(value.clone()))),
+        );
+        Some(fields)
+    }
+
+    fn save_changelog(
+        &self,
+        repo: &Repo,
+        content: &str,
+        release: Option<&GitHubRelease>,
+    ) -> Result<PathBuf> {
+        let path = self.changelog_file_path(repo, release);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).context("Failed to create output directory")?;
+        }
+
+        let (path, content) = match self.collision(&path) {
+            Collision::Overwrite => (path, content.to_string()),
+            Collision::Copy => (numbered_copy(&path), content.to_string()),
+            Collision::Merge => {
+                let existing =
+                    fs::read_to_string(&path).context("Failed to read the existing changelog")?;
+                let merged = markdown::merge_sections(&existing, content);
+                (path, merged)
+            }
+        };
+        fs::write(&path, content).context("Failed to write changelog file")?;
+
+        Ok(path)
+    }
+
+    /// How to save to `path`, which is only overwritten when forced or when it doesn't exist
+    fn collision(&self, path: &Path) -> Collision {
+        if self.force || !path.exists() {
+            return Collision::Overwrite;
+        }
+
+        match self.existing_changelog {
+            ExistingChangelog::Suffix => Collision::Copy,
+            ExistingChangelog::Merge => Collision::Merge,
+            ExistingChangelog::Prompt => self
+                .collision_resolver
+                .as_ref()
+                .map_or(Collision::Copy, |resolve| resolve(path)),
+        }
+    }
+}
+
+/// What a save does with a changelog file that already exists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Collision {
+    Copy,
+    Merge,
+    Overwrite,
+}
+
+/// Decides what happens to an existing changelog file, e.g. by asking the user
+pub type CollisionResolver = Arc<dyn Fn(&Path) -> Collision + Send + Sync>;
+
+impl Collision {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Copy, Self::Merge, Self::Overwrite]
+    }
+}
+
+impl std::fmt::Display for Collision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Copy => write!(f, "Save a numbered copy"),
+            Self::Merge => write!(f, "Merge into it"),
+            Self::Overwrite => write!(f, "Overwrite it"),
+        }
+    }
+}
+
+/// The first free `<stem>_<n>.md` next to `path`, counting from 2
+fn numbered_copy(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()));
+    (2..)
+        .map(|n| {
+            path.with_file_name(format!(
+                "{}_{}{}",
+                stem,
+                n,
+                extension.as_deref().unwrap_or("")
+            ))
+        })
+        .find(|copy| !copy.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// The file a numbered copy was saved next to, or `path` itself
+fn original_of(path: &Path) -> PathBuf {
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return path.to_path_buf();
+    };
+    match stem.rsplit_once('_') {
+        Some((original, n)) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => {
+            let name = match path.extension() {
+                Some(ext) => format!("{}.{}", original, ext.to_string_lossy()),
+                None => original.to_string(),
+            };
+            path.with_file_name(name)
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+/// The mode a saved file was generated in, told apart by its name
+fn mode_of_file(path: &Path) -> GenerationMode {
+    let prefix = format!("{}_", GenerationMode::ReleaseNotes.file_prefix());
+    let release_notes = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with(&prefix));
+
+    if release_notes {
+        GenerationMode::ReleaseNotes
+    } else {
+        GenerationMode::Changelog
+    }
+}
+
+/// The window ending a saved file's name, e.g. "7d", None for runs of a single window
+fn window_of_file(path: &Path) -> Option<String> {
+    let original = original_of(path);
+    let stem = original.file_stem()?.to_str()?;
+    let (_, window) = stem.rsplit_once('_')?;
+    TimePeriod::from_name(window).map(|_| window.to_string())
+}
+
+/// The PRs an earlier file of the same mode and window reported
+/// Release notes and changelogs each cover every PR once, as does each window of a run
+fn reported_numbers(
+    reports: &[PrReport],
+    mode: GenerationMode,
+    window: Option<&str>,
+    target: Option<&Path>,
+    today: NaiveDate,
+) -> HashSet<u64> {
+    reports
+        .iter()
+        .filter(|report| counts_as_reported(report, target, today))
+        .filter(|report| mode_of_file(&report.path) == mode)
+        .filter(|report| window_of_file(&report.path).as_deref() == window)
+        .map(|report| report.number)
+        .collect()
+}
+
+/// A report counts unless it is today's version of the file being written (or a numbered copy
+/// of it), which a re-run regenerates; `target` is None when changelogs accumulate in a file
+fn counts_as_reported(report: &PrReport, target: Option<&Path>, today: NaiveDate) -> bool {
+    target != Some(original_of(&report.path).as_path())
+        || clock::zone().date_of(report.reported_at) < today
+}
+
+/// Collects the PR URLs and Jira keys present in the context, used to validate generated links
+fn known_refs(repo: &Repo, contexts: &[PrContext], jira_base_url: Option<&str>) -> KnownRefs {
+    KnownRefs {
+        repo: repo.full_name(),
+        prs: contexts
+            .iter()
+            .map(|ctx| (ctx.pr.number, ctx.pr.html_url.clone()))
+            .collect(),
+        jira_keys: contexts
+            .iter()
+            .flat_map(|ctx| ctx.jira_issues.iter().map(|issue| issue.key.clone()))
+            .collect(),
+        jira_base_url: jira_base_url.map(str::to_string),
+    }
+}
+
+/// Numbers of the PRs, in order
+fn pr_numbers(contexts: &[PrContext]) -> Vec<u64> {
+    contexts.iter().map(|ctx| ctx.pr.number).collect()
+}
+
+/// Renders a structured changelog as markdown, building links from the fetched PR and Jira data
+/// References the AI invented (unknown PR numbers or Jira keys) are dropped
+/// With `by_component`, each category's entries are grouped under the repo's components, and
+/// with `by_epic` the entries sharing a Jira epic are clustered under it. Jira keys link to
+/// issues on `jira_base_url` when it's known
+pub fn render_structured(
+    repo: &Repo,
+    changelog: &StructuredChangelog,
+    contexts: &[PrContext],
+    mode: GenerationMode,
+    by_component: bool,
+    by_epic: bool,
+    jira_base_url: Option<&str>,
+) -> String {
+    let date = clock::today().format("%Y-%m-%d");
+    let mut output = format!("# {} for {} - {}\n", mode.title(), repo.full_name(), date);
+
+    for category in &changelog.categories {
+        if category.entries.is_empty() {
+            continue;
+        }
+
+        output.push_str(&format!("\n## {}\n", category.name.trim()));
+
+        let groups = if by_component {
+            component_groups(&repo.components, &category.entries, contexts)
+        } else {
+            Vec::new()
+        };
+        if groups.iter().all(|(name, _)| name.is_none()) {
+            let entries: Vec<&ChangelogEntry> = category.entries.iter().collect();
+            output.push_str(&render_entries(
+                &entries,
+                contexts,
+                by_epic,
+                "###",
+                jira_base_url,
+            ));
+            continue;
+        }
+
+        for (name, entries) in groups {
+            output.push_str(&format!("\n### {}\n", name.unwrap_or(OTHER_COMPONENT)));
+            output.push_str(&render_entries(
+                &entries,
+                contexts,
+                by_epic,
+                "####",
+                jira_base_url,
+            ));
+        }
+    }
+
+    output
+}
+
+/// Renders entries one per line; with `by_epic`, those of an epic with several entries follow
+/// the others under a "Checkout revamp: 4 changes" heading at `level`
+fn render_entries(
+    entries: &[&ChangelogEntry],
+    contexts: &[PrContext],
+    by_epic: bool,
+    level: &str,
+    jira_base_url: Option<&str>,
+) -> String {
+    let (loose, clusters) = if by_epic {
+        epic_groups(entries, contexts)
+    } else {
+        (entries.to_vec(), Vec::new())
+    };
+
+    let mut output = String::new();
+    for entry in loose {
+        output.push_str(&render_entry(entry, contexts, jira_base_url));
+        output.push('\n');
+    }
+    for (epic, entries) in clusters {
+        output.push_str(&format!(
+            "\n{} {}: {} changes\n",
+            level,
+            epic.summary.trim(),
+            entries.len()
+        ));
+        for entry in entries {
+            output.push_str(&render_entry(entry, contexts, jira_base_url));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Splits entries into those of epics with several entries, in order of first appearance, and
+/// the rest; an entry goes to the epic of the first of its PRs' issues that has one
+fn epic_groups<'a, 'c>(
+    entries: &[&'a ChangelogEntry],
+    contexts: &'c [PrContext],
+) -> (
+    Vec<&'a ChangelogEntry>,
+    Vec<(Epic<'c>, Vec<&'a ChangelogEntry>)>,
+) {
+    let mut clusters: Vec<(Epic<'c>, Vec<&ChangelogEntry>)> = Vec::new();
+    for &entry in entries {
+        let epic = contexts
+            .iter()
+            .filter(|ctx| entry.pr_numbers.contains(&ctx.pr.number))
+            .flat_map(|ctx| &ctx.jira_issues)
+            .find_map(JiraIssue::epic);
+        if let Some(epic) = epic {
+            match clusters.iter_mut().find(|(e, _)| e.key == epic.key) {
+                Some((_, cluster)) => cluster.push(entry),
+                None => clusters.push((epic, vec![entry])),
+            }
+        }
+    }
+
+    clusters.retain(|(_, cluster)| cluster.len() > 1);
+    let loose = entries
+        .iter()
+        .copied()
+        .filter(|&entry| {
+            !clusters
+                .iter()
+                .any(|(_, cluster)| cluster.iter().any(|&e| std::ptr::eq(e, entry)))
+        })
+        .collect();
+    (loose, clusters)
+}
+
+/// Splits a category's entries by component, in configuration order with the rest last
+fn component_groups<'a>(
+    components: &'a [Component],
+    entries: &'a [ChangelogEntry],
+    contexts: &[PrContext],
+) -> Vec<(Option<&'a str>, Vec<&'a ChangelogEntry>)> {
+    let mut groups: Vec<(Option<&str>, Vec<&ChangelogEntry>)> = components
+        .iter()
+        .map(|component| Some(component.name.as_str()))
+        .chain([None])
+        .map(|name| (name, Vec::new()))
+        .collect();
+
+    for entry in entries {
+        let name = components::entry_component(components, &entry.pr_numbers, contexts);
+        if let Some((_, group)) = groups.iter_mut().find(|(n, _)| *n == name) {
+            group.push(entry);
+        }
+    }
+
+    groups.retain(|(_, entries)| !entries.is_empty());
+    groups
+}
+
+/// Renders a single structured entry as a markdown list item with its PR and Jira links
+pub fn render_entry(
+    entry: &ChangelogEntry,
+    contexts: &[PrContext],
+    jira_base_url: Option<&str>,
+) -> String {
+    let mut line = format!("- {}", entry.summary.trim());
+
+    let refs = entry_refs(entry, contexts, jira_base_url);
+    if !refs.is_empty() {
+        line.push_str(&format!(" ({})", refs.join(", ")));
+    }
+
+    let mut prs = contexts
+        .iter()
+        .filter(|ctx| entry.pr_numbers.contains(&ctx.pr.number))
+        .peekable();
+    if prs.peek().is_some() && prs.all(|ctx| ctx.previously_reported) {
+        line.push_str(" _(previously reported)_");
+    }
+    if deployments::all_pending(&entry.pr_numbers, contexts) {
+        line.push_str(" _(not yet deployed)_");
+    }
+    if ci::any_failing(&entry.pr_numbers, contexts) {
+        line.push_str(" _(merged but currently failing CI)_");
+    }
+    if let Some(status) = board::entry_status(&entry.pr_numbers, contexts) {
+        line.push_str(&format!(" _({})_", status));
+    }
+
+    line
+}
+
+/// Markdown links for the PRs and Jira issues an entry references, skipping unknown ones
+pub fn entry_refs(
+    entry: &ChangelogEntry,
+    contexts: &[PrContext],
+    jira_base_url: Option<&str>,
+) -> Vec<String> {
+    let pr_links = entry
+        .pr_numbers
+        .iter()
+        .filter_map(|number| contexts.iter().find(|ctx| ctx.pr.number == *number))
+        .map(|ctx| format!("[#{}]({})", ctx.pr.number, ctx.pr.html_url));
+
+    let jira_links = entry
+        .jira_keys
+        .iter()
+        .filter(|key| {
+            contexts
+                .iter()
+                .any(|ctx| ctx.jira_issues.iter().any(|issue| &issue.key == *key))
+        })
+        .map(|key| match jira_base_url {
+            Some(base) => format!("[{}]({}/browse/{})", key, base.trim_end_matches('/'), key),
+            None => key.clone(),
+        });
+
+    pr_links.chain(jira_links).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn pr_context(number: u64) -> PrContext {
+        PrContext::fixture(number, &format!("PR {}", number))
+    }
+
+    #[test]
+    fn test_render_structured_drops_unknown_refs() {
+        let structured = StructuredChangelog::parse(
+            r#"{"categories":[{"name":"Features","entries":[{"summary":"Add OAuth2","pr_numbers":[142,999],"jira_keys":["AUTH-1"]}]},{"name":"Empty","entries":[]}]}"#,
+        )
+        .unwrap();
+        let repo = Repo::new("acme", "backend");
+
+        let markdown = render_structured(
+            &repo,
+            &structured,
+            &[pr_context(142)],
+            GenerationMode::Changelog,
+            true,
+            false,
+            None,
+        );
+
+        assert!(markdown.starts_with("# Changelog for acme/backend - "));
+        assert!(markdown.contains(
+            "## Features\n- Add OAuth2 ([#142](https://github.com/acme/backend/pull/142))\n"
+        ));
+        assert!(!markdown.contains("999"));
+        assert!(!markdown.contains("AUTH-1"));
+        assert!(!markdown.contains("## Empty"));
+    }
+
+    #[test]
+    fn test_render_structured_groups_entries_by_component() {
+        let structured = StructuredChangelog::parse(
+            r#"{"categories":[{"name":"Features","entries":[{"summary":"Add dark mode","pr_numbers":[2],"jira_keys":[]},{"summary":"Bump CI","pr_numbers":[3],"jira_keys":[]},{"summary":"Add rate limits","pr_numbers":[1],"jira_keys":[]}]}]}"#,
+        )
+        .unwrap();
+        let mut repo = Repo::new("acme", "backend");
+        repo.components = components::parse_mapping("API = crates/api; Frontend = web").unwrap();
+        let mut contexts = vec![pr_context(1), pr_context(2), pr_context(3)];
+        contexts[0].components = vec!["API".to_string(), "Frontend".to_string()];
+        contexts[1].components = vec!["Frontend".to_string()];
+
+        let markdown = render_structured(
+            &repo,
+            &structured,
+            &contexts,
+            GenerationMode::Changelog,
+            true,
+            false,
+            None,
+        );
+
+        let api = markdown.find("### API\n- Add rate limits").unwrap();
+        let frontend = markdown.find("### Frontend\n- Add dark mode").unwrap();
+        let other = markdown.find("### Other\n- Bump CI").unwrap();
+        assert!(api < frontend && frontend < other);
+
+        let flat = render_structured(
+            &repo,
+            &structured,
+            &contexts,
+            GenerationMode::Changelog,
+            false,
+            false,
+            None,
+        );
+        assert!(!flat.contains("###"));
+    }
+
+    #[test]
+    fn test_render_structured_clusters_entries_by_epic() {
+        let structured = StructuredChangelog::parse(
+            r#"{"categories":[{"name":"Features","entries":[{"summary":"Add Apple Pay","pr_numbers":[1],"jira_keys":[]},{"summary":"Add dark mode","pr_numbers":[2],"jira_keys":[]},{"summary":"Save carts","pr_numbers":[3],"jira_keys":[]},{"summary":"Add SSO","pr_numbers":[4],"jira_keys":[]}]}]}"#,
+        )
+        .unwrap();
+        let issue = |key: &str, fields: serde_json::Value| {
+            serde_json::from_value::<JiraIssue>(json!({ "key": key, "fields": fields })).unwrap()
+        };
+        let epic = json!({
+            "key": "SHOP-1",
+            "fields": { "summary": "Checkout revamp", "issuetype": { "name": "Epic" } },
+        });
+        let mut contexts = vec![pr_context(1), pr_context(2), pr_context(3), pr_context(4)];
+        contexts[0].jira_issues = vec![issue(
+            "SHOP-2",
+            json!({ "summary": "Apple Pay", "parent": epic }),
+        )];
+        contexts[2].jira_issues = vec![issue(
+            "SHOP-1",
+            json!({ "summary": "Checkout revamp", "issuetype": { "name": "Epic" } }),
+        )];
+        contexts[3].jira_issues = vec![issue(
+            "AUTH-2",
+            json!({ "summary": "SSO", "parent": { "key": "AUTH-1", "fields": { "summary": "Login", "issuetype": { "name": "Epic" } } } }),
+        )];
+
+        let markdown = render_structured(
+            &Repo::new("acme", "backend"),
+            &structured,
+            &contexts,
+            GenerationMode::Changelog,
+            true,
+            true,
+            None,
+        );
+
+        assert!(markdown.contains("## Features\n- Add dark mode"));
+        assert!(markdown.contains("- Add SSO"));
+        assert!(!markdown.contains("### Login"));
+        let cluster = markdown
+            .find("\n### Checkout revamp: 2 changes\n- Add Apple Pay")
+            .unwrap();
+        assert!(markdown.find("- Save carts").unwrap() > cluster);
+        assert!(markdown.find("- Add SSO").unwrap() < cluster);
+    }
+
+    #[test]
+    fn test_rerun_of_todays_file_is_not_a_duplicate() {
+        let today = clock::today();
+        let report = PrReport {
+            number: 142,
+            path: PathBuf::from("changelog_backend_today.md"),
+            reported_at: Utc::now(),
+        };
+
+        assert!(!counts_as_reported(&report, Some(&report.path), today));
+        assert!(counts_as_reported(
+            &report,
+            Some(Path::new("changelog_backend_tomorrow.md")),
+            today
+        ));
+        assert!(counts_as_reported(&report, None, today));
+        let copy = PrReport {
+            path: PathBuf::from("changelog_backend_today_2.md"),
+            ..report.clone()
+        };
+        assert!(!counts_as_reported(&copy, Some(&report.path), today));
+        assert!(counts_as_reported(
+            &report,
+            Some(&report.path),
+            today + chrono::Duration::days(1)
+        ));
+
+        assert_eq!(mode_of_file(&report.path), GenerationMode::Changelog);
+        assert_eq!(
+            mode_of_file(Path::new("out/release-notes_backend_2026-10-14.md")),
+            GenerationMode::ReleaseNotes
+        );
+    }
+
+    #[test]
+    fn test_release_notes_leave_the_changelog_reports_in_place() {
+        let mut store = Store::open_in_memory().unwrap();
+        let repo = Repo::new("acme", "backend");
+        let today = clock::today();
+        let last_week = Path::new("changelog_backend_2026-10-07.md");
+        let target = Path::new("changelog_backend_2026-10-14.md");
+        let reported = |store: &Store| {
+            let reports = store.reported_prs(&repo).unwrap();
+            reported_numbers(
+                &reports,
+                GenerationMode::Changelog,
+                None,
+                Some(target),
+                today,
+            )
+        };
+
+        store.record_reported(&repo, &[1, 2], last_week).unwrap();
+        store
+            .record_reported(
+                &repo,
+                &[2],
+                Path::new("release-notes_backend_2026-10-10.md"),
+            )
+            .unwrap();
+        assert_eq!(reported(&store), HashSet::from([1, 2]));
+
+        store.record_reported(&repo, &[3], target).unwrap();
+        assert_eq!(reported(&store), HashSet::from([1, 2]));
+    }
+
+    #[test]
+    fn test_a_daily_run_doesnt_reopen_the_weekly_rollup() {
+        let mut store = Store::open_in_memory().unwrap();
+        let repo = Repo::new("acme", "backend");
+        let today = clock::today();
+        let weekly = |store: &Store| {
+            let reports = store.reported_prs(&repo).unwrap();
+            let target = Path::new("changelog_backend_2026-10-14_7d.md");
+            reported_numbers(
+                &reports,
+                GenerationMode::Changelog,
+                Some("7d"),
+                Some(target),
+                today,
+            )
+        };
+
+        store
+            .record_reported(&repo, &[1], Path::new("changelog_backend_2026-10-07_7d.md"))
+            .unwrap();
+        store
+            .record_reported(
+                &repo,
+                &[1, 2],
+                Path::new("changelog_backend_2026-10-13_24h.md"),
+            )
+            .unwrap();
+        assert_eq!(weekly(&store), HashSet::from([1]));
+    }
+
+    #[test]
+    fn test_windows_are_read_from_file_names() {
+        assert_eq!(
+            window_of_file(Path::new("changelog_backend_2026-10-14_7d.md")).as_deref(),
+            Some("7d")
+        );
+        assert_eq!(
+            window_of_file(Path::new("changelog_backend_2026-10-14_this-week.md")).as_deref(),
+            Some("this-week")
+        );
+        assert_eq!(
+            window_of_file(Path::new("changelog_backend_2026-10-14.md")),
+            None
+        );
+        assert_eq!(
+            window_of_file(Path::new("changelog_backend_2026-10-14_7d_3.md")).as_deref(),
+            Some("7d")
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::changelog::PrContext;
+use crate::clock;
+use crate::config::{ActivityChart, TimePeriod};
+
+/// Width in characters of the busiest day's bar in an ASCII chart
+const ASCII_WIDTH: usize = 30;
+
+/// Merges per day in the configured timezone, with every day of the period listed, quiet
+/// ones included. Periods without a start, such as milestones, begin at the first merge
+pub fn merges_per_day(period: &TimePeriod, contexts: &[PrContext]) -> Vec<(NaiveDate, usize)> {
+    let zone = clock::zone();
+    let mut merges: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for merged_at in contexts.iter().filter_map(|ctx| ctx.pr.merged_at) {
+        *merges.entry(zone.date_of(merged_at)).or_default() += 1;
+    }
+    let (Some(first), Some(last)) = (merges.keys().next(), merges.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    let (start, end) = period.bounds();
+    let start = if start == DateTime::<Utc>::MIN_UTC {
+        *first
+    } else {
+        zone.date_of(start).min(*first)
+    };
+    let end = zone.date_of(end).max(*last);
+    start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .map(|day| (day, merges.get(&day).copied().unwrap_or_default()))
+        .collect()
+}
+
+/// The merges per day as a markdown section, None without merges or with the chart off
+pub fn render(kind: ActivityChart, days: &[(NaiveDate, usize)]) -> Option<String> {
+    let busiest = days.iter().map(|(_, count)| *count).max()?;
+    let chart = match kind {
+        ActivityChart::Off => return None,
+        ActivityChart::Mermaid => mermaid(days, busiest),
+        ActivityChart::Ascii => ascii(days, busiest),
+    };
+    Some(format!("## Activity\n\n{}", chart))
+}
+
+/// A mermaid bar chart, which GitHub and most docs sites draw from a code block
+fn mermaid(days: &[(NaiveDate, usize)], busiest: usize) -> String {
+    let labels: Vec<String> = days
+        .iter()
+        .map(|(day, _)| format!("\"{}\"", day.format("%b %-d")))
+        .collect();
+    let counts: Vec<String> = days.iter().map(|(_, count)| count.to_string()).collect();
+    format!(
+        "```mermaid\nxychart-beta\n    title \"Merges per day\"\n    x-axis [{}]\n    y-axis \"Merges\" 0 --> {}\n    bar [{}]\n```\n",
+        labels.join(", "),
+        busiest,
+        counts.join(", ")
+    )
+}
+
+/// A bar per day in a plain code block, for renderers without mermaid
+fn ascii(days: &[(NaiveDate, usize)], busiest: usize) -> String {
+    let mut chart = String::from("```text\n");
+    for (day, count) in days {
+        chart.push_str(&format!("{} │", day.format("%a %b %e")));
+        if *count > 0 {
+            let width = (count * ASCII_WIDTH).div_ceil(busiest);
+            chart.push_str(&format!(" {} {}", "█".repeat(width), count));
+        }
+        chart.push('\n');
+    }
+    chart.push_str("```\n");
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_charts_show_every_day() {
+        let days = vec![(day(4), 2), (day(5), 0), (day(6), 1)];
+
+        assert_eq!(
+            render(ActivityChart::Mermaid, &days).unwrap(),
+            "## Activity\n\n```mermaid\nxychart-beta\n    title \"Merges per day\"\n    x-axis [\"Mar 4\", \"Mar 5\", \"Mar 6\"]\n    y-axis \"Merges\" 0 --> 2\n    bar [2, 0, 1]\n```\n"
+        );
+        let ascii = render(ActivityChart::Ascii, &days).unwrap();
+        assert!(ascii.contains(&format!("Mon Mar  4 │ {} 2\n", "█".repeat(30))));
+        assert!(ascii.contains("Tue Mar  5 │\n"));
+        assert!(ascii.contains(&format!("Wed Mar  6 │ {} 1\n", "█".repeat(15))));
+        assert!(render(ActivityChart::Off, &days).is_none());
+        assert!(render(ActivityChart::Ascii, &[]).is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use crate::changelog::PrContext;
+use crate::github::CommitChecks;
+
+/// Check-run conclusions that count as a failed CI run
+const FAILED_CONCLUSIONS: [&str; 3] = ["failure", "timed_out", "startup_failure"];
+
+/// How CI went on a PR's merge commit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum CiStatus {
+    Passing,
+    Pending,
+    /// Names of the failed checks
+    Failing {
+        checks: Vec<String>,
+    },
+}
+
+impl CiStatus {
+    /// Sums up a commit's check runs and statuses; None when no CI reported on it
+    pub fn from_checks(checks: &CommitChecks) -> Option<Self> {
+        if checks.check_runs.is_empty() && checks.statuses.is_empty() {
+            return None;
+        }
+
+        let failed: Vec<String> = checks
+            .check_runs
+            .iter()
+            .filter(|run| {
+                run.conclusion
+                    .as_deref()
+                    .is_some_and(|c| FAILED_CONCLUSIONS.contains(&c))
+            })
+            .map(|run| run.name.clone())
+            .chain(
+                checks
+                    .statuses
+                    .iter()
+                    .filter(|status| matches!(status.state.as_str(), "failure" | "error"))
+                    .map(|status| status.context.clone()),
+            )
+            .collect();
+        if !failed.is_empty() {
+            return Some(Self::Failing { checks: failed });
+        }
+
+        let pending = checks
+            .check_runs
+            .iter()
+            .any(|run| run.status != "completed")
+            || checks
+                .statuses
+                .iter()
+                .any(|status| status.state == "pending");
+        Some(if pending {
+            Self::Pending
+        } else {
+            Self::Passing
+        })
+    }
+}
+
+/// The "CI:" line of a PR in the AI context
+pub fn describe(ctx: &PrContext) -> Option<String> {
+    match ctx.ci.as_ref()? {
+        CiStatus::Passing => Some("CI: passing after merge".to_string()),
+        CiStatus::Pending => Some("CI: still running after merge".to_string()),
+        CiStatus::Failing { checks } => {
+            Some(format!("CI: failing after merge ({})", checks.join(", ")))
+        }
+    }
+}
+
+/// True when any PR of an entry is failing CI on its merge commit
+pub fn any_failing(pr_numbers: &[u64], contexts: &[PrContext]) -> bool {
+    contexts
+        .iter()
+        .filter(|ctx| pr_numbers.contains(&ctx.pr.number))
+        .any(|ctx| matches!(ctx.ci, Some(CiStatus::Failing { .. })))
+}
+
+/// Prompt note asking a freeform changelog to flag changes whose CI failed after merging
+pub fn context_block(contexts: &[PrContext]) -> Option<String> {
+    if !contexts
+        .iter()
+        .any(|ctx| matches!(ctx.ci, Some(CiStatus::Failing { .. })))
+    {
+        return None;
+    }
+
+    Some(
+        "## CI\nSome PRs are failing CI on their merge commit. Mark the entries covering them with \"_(merged but currently failing CI)_\".\n\n"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{CheckRun, CommitStatus};
+
+    fn run(name: &str, status: &str, conclusion: Option<&str>) -> CheckRun {
+        CheckRun {
+            name: name.to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_checks_and_statuses_are_combined() {
+        assert_eq!(CiStatus::from_checks(&CommitChecks::default()), None);
+
+        let mut checks = CommitChecks {
+            check_runs: vec![
+                run("build", "completed", Some("success")),
+                run("lint", "completed", Some("skipped")),
+            ],
+            statuses: Vec::new(),
+        };
+        assert_eq!(CiStatus::from_checks(&checks), Some(CiStatus::Passing));
+
+        checks.check_runs.push(run("e2e", "in_progress", None));
+        assert_eq!(CiStatus::from_checks(&checks), Some(CiStatus::Pending));
+
+        checks.statuses.push(CommitStatus {
+            context: "ci/jenkins".to_string(),
+            state: "error".to_string(),
+        });
+        checks
+            .check_runs
+            .push(run("test", "completed", Some("failure")));
+        assert_eq!(
+            CiStatus::from_checks(&checks),
+            Some(CiStatus::Failing {
+                checks: vec!["test".to_string(), "ci/jenkins".to_string()]
+            })
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+use std::sync::RwLock;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::error::GazetteError;
+
+/// The timezone dates are shown in and calendar periods start in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Zone {
+    /// The timezone of the machine gazette runs on
+    #[default]
+    System,
+    /// An IANA timezone, e.g. Europe/Berlin
+    Named(Tz),
+}
+
+static ZONE: RwLock<Zone> = RwLock::new(Zone::System);
+
+impl Zone {
+    /// Parses an IANA timezone name; "UTC" works as well
+    pub fn parse(name: &str) -> Result<Self, GazetteError> {
+        name.trim().parse::<Tz>().map(Self::Named).map_err(|_| {
+            GazetteError::Config(format!(
+                "Unknown timezone '{}'. Use an IANA name such as Europe/Berlin or UTC",
+                name.trim()
+            ))
+        })
+    }
+
+    /// The calendar date of `at` in this timezone
+    pub fn date_of(&self, at: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Self::System => at.with_timezone(&Local).date_naive(),
+            Self::Named(tz) => at.with_timezone(tz).date_naive(),
+        }
+    }
+
+    /// Midnight at the start of `date` in this timezone
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+        match self {
+            Self::System => start_of_day_in(&Local, midnight),
+            Self::Named(tz) => start_of_day_in(tz, midnight),
+        }
+    }
+
+    /// Formats `at` in this timezone with a strftime pattern
+    pub fn format(&self, at: DateTime<Utc>, pattern: &str) -> String {
+        match self {
+            Self::System => at.with_timezone(&Local).format(pattern).to_string(),
+            Self::Named(tz) => at.with_timezone(tz).format(pattern).to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::System => write!(f, "System timezone"),
+            Self::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+/// Days that start inside a DST gap begin an hour later, when the clocks have moved on
+fn start_of_day_in<T: TimeZone>(tz: &T, midnight: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(midnight + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Makes `zone` the timezone of every date gazette shows or computes from now on
+pub fn set_zone(zone: Zone) {
+    *ZONE.write().unwrap_or_else(|e| e.into_inner()) = zone;
+}
+
+/// The configured timezone
+pub fn zone() -> Zone {
+    *ZONE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Today's date in the configured timezone
+pub fn today() -> NaiveDate {
+    zone().date_of(Utc::now())
+}
+
+/// Formats `at` in the configured timezone
+pub fn format(at: DateTime<Utc>, pattern: &str) -> String {
+    zone().format(at, pattern)
+}
@@ -0,0 +1,106 @@
+use crate::changelog::PrContext;
+use crate::config::CommentOrder;
+use crate::github::PrComment;
+
+/// Characters of a comment kept in the AI context
+const EXCERPT_CHARS: usize = 300;
+
+/// Up to `count` comments worth excerpting, leaving out bots and empty ones
+/// The chosen comments keep the conversation's order
+pub fn select(comments: Vec<PrComment>, count: usize, order: CommentOrder) -> Vec<PrComment> {
+    let mut comments: Vec<(usize, PrComment)> = comments
+        .into_iter()
+        .filter(|comment| {
+            !comment.body.trim().is_empty()
+                && !comment.author.ends_with("[bot]")
+                && !comment.author.ends_with("-bot")
+        })
+        .enumerate()
+        .collect();
+
+    match order {
+        CommentOrder::MostReacted => comments.sort_by(|(a_index, a), (b_index, b)| {
+            b.reactions.cmp(&a.reactions).then(b_index.cmp(a_index))
+        }),
+        CommentOrder::Latest => comments.reverse(),
+    }
+    comments.truncate(count);
+    comments.sort_by_key(|(index, _)| *index);
+    comments.into_iter().map(|(_, comment)| comment).collect()
+}
+
+/// The "Comments:" lines of a PR in the AI context, each comment shortened to an excerpt
+pub fn describe(ctx: &PrContext) -> Option<String> {
+    if ctx.comments.is_empty() {
+        return None;
+    }
+
+    let mut output = String::from("Comments:\n");
+    for comment in &ctx.comments {
+        let reactions = match comment.reactions {
+            0 => String::new(),
+            1 => " (1 reaction)".to_string(),
+            n => format!(" ({} reactions)", n),
+        };
+        output.push_str(&format!(
+            "- @{}{}: {}\n",
+            comment.author,
+            reactions,
+            excerpt(&comment.body)
+        ));
+    }
+    Some(output)
+}
+
+/// The comment on one line, cut at a word boundary when too long
+fn excerpt(body: &str) -> String {
+    let text = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= EXCERPT_CHARS {
+        return text;
+    }
+
+    let cut: String = text.chars().take(EXCERPT_CHARS).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) => &cut[..space],
+        None => &cut,
+    };
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn comment(author: &str, body: &str, reactions: u64) -> PrComment {
+        PrComment {
+            author: author.to_string(),
+            body: body.to_string(),
+            reactions,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_most_reacted_comments_are_kept_in_order() {
+        let comments = vec![
+            comment("alice", "Why not cache this?", 1),
+            comment("codecov[bot]", "Coverage went up", 9),
+            comment("bob", "Caching broke tenants last time, see INC-42", 5),
+            comment("carol", "LGTM", 0),
+            comment("dave", &"word ".repeat(100), 3),
+        ];
+
+        let selected = select(comments.clone(), 2, CommentOrder::MostReacted);
+        let authors: Vec<&str> = selected.iter().map(|c| c.author.as_str()).collect();
+        assert_eq!(authors, ["bob", "dave"]);
+
+        let latest = select(comments, 2, CommentOrder::Latest);
+        let authors: Vec<&str> = latest.iter().map(|c| c.author.as_str()).collect();
+        assert_eq!(authors, ["carol", "dave"]);
+
+        let long = excerpt(&"word ".repeat(100));
+        assert!(long.ends_with("word…"));
+        assert!(long.chars().count() <= EXCERPT_CHARS + 1);
+    }
+}
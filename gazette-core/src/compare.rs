@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ai::StructuredChangelog;
+use crate::changelog::PrContext;
+
+/// A PR as it appeared in a saved run
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunPr {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    /// The changelog entry written for the PR; only known for structured changelogs
+    #[serde(default)]
+    pub summary: Option<String>,
+}
+
+/// What changed between two runs of the same repo
+#[derive(Debug, Default)]
+pub struct RunDelta {
+    pub added: Vec<RunPr>,
+    pub removed: Vec<RunPr>,
+    /// (before, after) pairs whose summary or title changed
+    pub changed: Vec<(RunPr, RunPr)>,
+}
+
+impl RunDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Captures the PRs of a run, with the structured entry summaries that reference them
+pub fn run_prs(contexts: &[PrContext], structured: Option<&StructuredChangelog>) -> Vec<RunPr> {
+    contexts
+        .iter()
+        .map(|ctx| {
+            let summaries: Vec<&str> = structured
+                .into_iter()
+                .flat_map(|s| &s.categories)
+                .flat_map(|c| &c.entries)
+                .filter(|e| e.pr_numbers.contains(&ctx.pr.number))
+                .map(|e| e.summary.trim())
+                .collect();
+
+            RunPr {
+                number: ctx.pr.number,
+                title: ctx.pr.title.clone(),
+                url: ctx.pr.html_url.clone(),
+                summary: (!summaries.is_empty()).then(|| summaries.join("; ")),
+            }
+        })
+        .collect()
+}
+
+/// Diffs the PR sets of an older and a newer run
+pub fn diff(before: &[RunPr], after: &[RunPr]) -> RunDelta {
+    let mut delta = RunDelta::default();
+
+    for pr in after {
+        match before.iter().find(|old| old.number == pr.number) {
+            None => delta.added.push(pr.clone()),
+            Some(old) if old.summary != pr.summary || old.title != pr.title => {
+                delta.changed.push((old.clone(), pr.clone()));
+            }
+            Some(_) => {}
+        }
+    }
+
+    delta.removed = before
+        .iter()
+        .filter(|old| !after.iter().any(|pr| pr.number == old.number))
+        .cloned()
+        .collect();
+
+    delta
+}
+
+/// Renders the delta as a markdown section to append to or post alongside a changelog
+pub fn render(delta: &RunDelta) -> String {
+    let mut output = String::from("## What changed since the last gazette\n\n");
+
+    if delta.is_empty() {
+        output.push_str("Nothing changed.\n");
+        return output;
+    }
+
+    let line = |pr: &RunPr| {
+        let text = pr.summary.as_deref().unwrap_or(&pr.title);
+        format!("- {} ([#{}]({}))\n", text, pr.number, pr.url)
+    };
+
+    if !delta.added.is_empty() {
+        output.push_str("### New\n");
+        for pr in &delta.added {
+            output.push_str(&line(pr));
+        }
+        output.push('\n');
+    }
+
+    if !delta.changed.is_empty() {
+        output.push_str("### Updated\n");
+        for (before, after) in &delta.changed {
+            output.push_str(&line(after));
+            let previous = before.summary.as_deref().unwrap_or(&before.title);
+            output.push_str(&format!("  - _previously: {}_\n", previous));
+        }
+        output.push('\n');
+    }
+
+    if !delta.removed.is_empty() {
+        output.push_str("### Removed\n");
+        for pr in &delta.removed {
+            output.push_str(&line(pr));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64, summary: &str) -> RunPr {
+        RunPr {
+            number,
+            title: format!("PR {}", number),
+            url: format!("https://github.com/acme/backend/pull/{}", number),
+            summary: Some(summary.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_diff_finds_new_removed_and_changed_prs() {
+        let before = vec![pr(1, "Add OAuth2"), pr(2, "Fix login"), pr(3, "Drop IE11")];
+        let after = vec![
+            pr(1, "Add OAuth2"),
+            pr(2, "Fix login redirect"),
+            pr(4, "Add SSO"),
+        ];
+
+        let delta = diff(&before, &after);
+
+        assert_eq!(delta.added, vec![pr(4, "Add SSO")]);
+        assert_eq!(delta.removed, vec![pr(3, "Drop IE11")]);
+        assert_eq!(delta.changed.len(), 1);
+        assert_eq!(delta.changed[0].1.number, 2);
+
+        let rendered = render(&delta);
+        assert!(rendered.contains(
+            "### Updated\n- Fix login redirect ([#2](https://github.com/acme/backend/pull/2))\n  - _previously: Fix login_\n"
+        ));
+        assert!(rendered.contains("### New\n- Add SSO"));
+        assert!(rendered.contains("### Removed\n- Drop IE11"));
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::{Context, Result};
+
+use crate::changelog::PrContext;
+use crate::config::Component;
+use crate::paths::PathFilter;
+
+/// Heading for entries whose PRs touched none of the configured components
+pub const OTHER_COMPONENT: &str = "Other";
+
+/// Assigns a PR's changed files to the repo's components, in configuration order
+pub struct ComponentMatcher {
+    filters: Vec<(String, PathFilter)>,
+}
+
+impl ComponentMatcher {
+    pub fn new(components: &[Component]) -> Result<Self> {
+        let filters = components
+            .iter()
+            .map(|component| {
+                let filter = PathFilter::new(&component.paths)
+                    .with_context(|| format!("Invalid paths for component '{}'", component.name))?;
+                Ok((component.name.clone(), filter))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { filters })
+    }
+
+    /// Names of the components any of the files belong to
+    pub fn components_of(&self, files: &[String]) -> Vec<String> {
+        self.filters
+            .iter()
+            .filter(|(_, filter)| filter.matches_any(files))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+/// Parses a component mapping typed as "API = crates/api/**, libs/api; Frontend = web/**"
+pub fn parse_mapping(input: &str) -> Result<Vec<Component>> {
+    let mut components: Vec<Component> = Vec::new();
+
+    for part in input.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, paths) = part
+            .split_once('=')
+            .with_context(|| format!("Expected 'Name = paths' in '{}'", part))?;
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("Missing component name in '{}'", part);
+        }
+        let paths: Vec<String> = paths
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect();
+        if paths.is_empty() {
+            anyhow::bail!("Component '{}' has no paths", name);
+        }
+
+        match components.iter_mut().find(|c| c.name == name) {
+            Some(existing) => existing.paths.extend(paths),
+            None => components.push(Component {
+                name: name.to_string(),
+                paths,
+            }),
+        }
+    }
+
+    ComponentMatcher::new(&components)?;
+    Ok(components)
+}
+
+/// Formats a component mapping the way `parse_mapping` reads it
+pub fn format_mapping(components: &[Component]) -> String {
+    components
+        .iter()
+        .map(|c| format!("{} = {}", c.name, c.paths.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// The component an entry is grouped under: the first configured one among its PRs'
+pub fn entry_component<'a>(
+    components: &'a [Component],
+    pr_numbers: &[u64],
+    contexts: &[PrContext],
+) -> Option<&'a str> {
+    components
+        .iter()
+        .find(|component| {
+            contexts
+                .iter()
+                .filter(|ctx| pr_numbers.contains(&ctx.pr.number))
+                .any(|ctx| ctx.components.contains(&component.name))
+        })
+        .map(|component| component.name.as_str())
+}
+
+/// Prompt note asking a freeform changelog to group each category by component
+pub fn context_block(components: &[Component], contexts: &[PrContext]) -> Option<String> {
+    if contexts.iter().all(|ctx| ctx.components.is_empty()) {
+        return None;
+    }
+
+    let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+    Some(format!(
+        "## Components\nWithin each category, group the entries under a \"### <component>\" heading per component ({}), using the components listed for each PR, and put changes without one under \"### {}\".\n\n",
+        names.join(", "),
+        OTHER_COMPONENT
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapping_round_trips_and_assigns_files() {
+        let components =
+            parse_mapping("API = crates/api/**, proto; Frontend = web/**; API = libs/api").unwrap();
+        assert_eq!(components.len(), 2);
+        assert_eq!(
+            components[0].paths,
+            vec!["crates/api/**", "proto", "libs/api"]
+        );
+        assert_eq!(
+            parse_mapping(&format_mapping(&components)).unwrap(),
+            components
+        );
+
+        let matcher = ComponentMatcher::new(&components).unwrap();
+        let files = vec![
+            "web/src/app.tsx".to_string(),
+            "proto/user.proto".to_string(),
+        ];
+        assert_eq!(matcher.components_of(&files), vec!["API", "Frontend"]);
+        assert!(matcher.components_of(&["README.md".to_string()]).is_empty());
+
+        assert!(parse_mapping("API crates/api/**").is_err());
+        assert!(parse_mapping("API = ").is_err());
+    }
+}
@@ -0,0 +1,1235 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{self, Zone};
+use crate::error::GazetteError;
+use crate::layers::{self, CONFIG_FILE, Layers};
+use crate::tls::{self, TlsOptions};
+use crate::versions;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum AIProvider {
+    #[default]
+    Gemini,
+    OpenAI,
+    Anthropic,
+    Ollama,
+}
+
+impl AIProvider {
+    /// Returns all available AI providers
+    pub fn all() -> Vec<Self> {
+        vec![Self::Gemini, Self::OpenAI, Self::Anthropic, Self::Ollama]
+    }
+
+    /// Parses a provider name as given on the command line (e.g., "openai")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "gemini" => Some(Self::Gemini),
+            "openai" => Some(Self::OpenAI),
+            "anthropic" | "claude" => Some(Self::Anthropic),
+            "ollama" => Some(Self::Ollama),
+            _ => None,
+        }
+    }
+
+    /// Returns the environment variable name for the API key
+    pub fn api_key_env_var(&self) -> &'static str {
+        match self {
+            Self::Gemini => "GEMINI_API_KEY",
+            Self::OpenAI => "OPENAI_API_KEY",
+            Self::Anthropic => "ANTHROPIC_API_KEY",
+            Self::Ollama => "OLLAMA_HOST",
+        }
+    }
+
+    /// Returns a user-friendly prompt for the API key
+    pub fn api_key_prompt(&self) -> &'static str {
+        match self {
+            Self::Gemini => "Enter your Gemini API key:",
+            Self::OpenAI => "Enter your OpenAI API key:",
+            Self::Anthropic => "Enter your Anthropic API key:",
+            Self::Ollama => "Enter your Ollama host (default: http://localhost:11434):",
+        }
+    }
+
+    /// Returns default value for the credential (if any)
+    pub fn default_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Ollama => Some("http://localhost:11434"),
+            _ => None,
+        }
+    }
+
+    /// Returns available models for this provider
+    pub fn available_models(&self) -> Vec<&'static str> {
+        match self {
+            Self::Gemini => vec![
+                "gemini-2.0-flash",
+                "gemini-2.0-flash-lite",
+                "gemini-1.5-pro",
+                "gemini-1.5-flash",
+            ],
+            Self::OpenAI => vec![
+                "gpt-4o",
+                "gpt-4o-mini",
+                "gpt-4-turbo",
+                "gpt-4",
+                "gpt-3.5-turbo",
+            ],
+            Self::Anthropic => vec![
+                "claude-sonnet-4-20250514",
+                "claude-3-5-sonnet-20241022",
+                "claude-3-5-haiku-20241022",
+                "claude-3-opus-20240229",
+            ],
+            Self::Ollama => vec![
+                "llama3.2",
+                "llama3.1",
+                "mistral",
+                "codellama",
+                "deepseek-coder",
+            ],
+        }
+    }
+
+    /// Returns the default model for this provider
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            Self::Gemini => "gemini-2.0-flash",
+            Self::OpenAI => "gpt-4o",
+            Self::Anthropic => "claude-sonnet-4-20250514",
+            Self::Ollama => "llama3.2",
+        }
+    }
+
+    /// Returns a short name for display
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            Self::Gemini => "Gemini",
+            Self::OpenAI => "OpenAI",
+            Self::Anthropic => "Claude",
+            Self::Ollama => "Ollama",
+        }
+    }
+}
+
+impl fmt::Display for AIProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gemini => write!(f, "Gemini (Google)"),
+            Self::OpenAI => write!(f, "OpenAI (GPT)"),
+            Self::Anthropic => write!(f, "Anthropic (Claude)"),
+            Self::Ollama => write!(f, "Ollama (Local)"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(tag = "type", content = "value")]
+pub enum TimePeriod {
+    LastHour,
+    Last6Hours,
+    Last12Hours,
+    #[default]
+    Last24Hours,
+    Last7Days,
+    Last30Days,
+    /// From Monday 00:00 UTC of the current week until now
+    ThisWeek,
+    /// The previous Monday-to-Sunday week
+    LastWeek,
+    /// From the start of a day until now
+    SinceDate {
+        date: NaiveDate,
+    },
+    Custom {
+        seconds: i64,
+    },
+    /// An explicit window, used by the daemon to continue exactly where the last run ended
+    Window {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    /// Every merged PR attached to a GitHub milestone, whenever it was merged
+    Milestone {
+        title: String,
+    },
+    /// A Linear team's cycle, as resolved when the run started; one in progress ends now
+    Cycle {
+        team: String,
+        number: u32,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+}
+
+impl TimePeriod {
+    /// Presets offered by the menu, in order
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self::LastHour,
+            Self::Last6Hours,
+            Self::Last12Hours,
+            Self::Last24Hours,
+            Self::Last7Days,
+            Self::Last30Days,
+            Self::ThisWeek,
+            Self::LastWeek,
+        ]
+    }
+
+    /// Every preset name accepted on the command line, with the preset it stands for
+    pub const NAMES: &'static [(&'static str, TimePeriod)] = &[
+        ("1h", Self::LastHour),
+        ("last-hour", Self::LastHour),
+        ("6h", Self::Last6Hours),
+        ("12h", Self::Last12Hours),
+        ("24h", Self::Last24Hours),
+        ("1d", Self::Last24Hours),
+        ("day", Self::Last24Hours),
+        ("7d", Self::Last7Days),
+        ("week", Self::Last7Days),
+        ("30d", Self::Last30Days),
+        ("month", Self::Last30Days),
+        ("this-week", Self::ThisWeek),
+        ("last-week", Self::LastWeek),
+    ];
+
+    /// Parses a preset name as given on the command line (e.g., "7d" or "last-week")
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase().replace('_', "-");
+        Self::NAMES
+            .iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, period)| period.clone())
+    }
+
+    /// Returns the duration for this time period
+    pub fn to_duration(&self) -> Duration {
+        match self {
+            Self::LastHour => Duration::hours(1),
+            Self::Last6Hours => Duration::hours(6),
+            Self::Last12Hours => Duration::hours(12),
+            Self::Last24Hours => Duration::hours(24),
+            Self::Last7Days => Duration::days(7),
+            Self::Last30Days => Duration::days(30),
+            Self::Custom { seconds } => Duration::seconds(*seconds),
+            Self::Window { start, end } => *end - *start,
+            Self::ThisWeek
+            | Self::LastWeek
+            | Self::SinceDate { .. }
+            | Self::Milestone { .. }
+            | Self::Cycle { .. } => {
+                let (start, end) = self.bounds();
+                end - start
+            }
+        }
+    }
+
+    /// Returns the (exclusive) start and (inclusive) end of the period, relative to now
+    pub fn bounds(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        self.bounds_at(Utc::now())
+    }
+
+    /// Returns the bounds of the period as seen at `now`
+    pub fn bounds_at(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        self.bounds_in(now, clock::zone())
+    }
+
+    /// Returns the bounds as seen at `now`, with calendar periods starting at midnight in `zone`
+    pub fn bounds_in(&self, now: DateTime<Utc>, zone: Zone) -> (DateTime<Utc>, DateTime<Utc>) {
+        let this_monday = || {
+            let today = zone.date_of(now);
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            zone.start_of_day(monday)
+        };
+
+        match self {
+            Self::Window { start, end } => (*start, *end),
+            Self::ThisWeek => (this_monday(), now),
+            Self::LastWeek => {
+                let end = this_monday();
+                let monday = zone.date_of(end) - Duration::weeks(1);
+                (zone.start_of_day(monday), end)
+            }
+            Self::SinceDate { date } => (zone.start_of_day(*date), now),
+            // Milestones aren't bound in time; this covers anything merged into one
+            Self::Milestone { .. } => (DateTime::<Utc>::MIN_UTC, now),
+            Self::Cycle { start, end, .. } => (*start, (*end).min(now)),
+            _ => (now - self.to_duration(), now),
+        }
+    }
+
+    /// Human-readable description
+    pub fn description(&self) -> String {
+        match self {
+            Self::LastHour => "last hour".to_string(),
+            Self::Last6Hours => "last 6 hours".to_string(),
+            Self::Last12Hours => "last 12 hours".to_string(),
+            Self::Last24Hours => "last 24 hours".to_string(),
+            Self::Last7Days => "last 7 days".to_string(),
+            Self::Last30Days => "last 30 days".to_string(),
+            Self::ThisWeek => "current week".to_string(),
+            Self::LastWeek => {
+                let (start, end) = self.bounds();
+                let zone = clock::zone();
+                format!(
+                    "previous week ({} to {})",
+                    zone.format(start, "%Y-%m-%d"),
+                    zone.date_of(end).pred_opt().unwrap_or_default()
+                )
+            }
+            Self::SinceDate { date } => format!("period since {}", date.format("%Y-%m-%d")),
+            Self::Custom { seconds } => {
+                let hours = seconds / 3600;
+                let mins = (seconds % 3600) / 60;
+                let secs = seconds % 60;
+                format!("last {:02}:{:02}:{:02}", hours, mins, secs)
+            }
+            Self::Window { start, .. } => {
+                format!(
+                    "period since {}",
+                    clock::format(*start, "%Y-%m-%d %H:%M %Z")
+                )
+            }
+            Self::Milestone { title } => format!("milestone {}", title),
+            Self::Cycle { team, number, .. } => format!("{} cycle {}", team, number),
+        }
+    }
+}
+
+impl fmt::Display for TimePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LastHour => write!(f, "Last hour"),
+            Self::Last6Hours => write!(f, "Last 6 hours"),
+            Self::Last12Hours => write!(f, "Last 12 hours"),
+            Self::Last24Hours => write!(f, "Last 24 hours"),
+            Self::Last7Days => write!(f, "Last 7 days"),
+            Self::Last30Days => write!(f, "Last 30 days"),
+            Self::ThisWeek => write!(f, "This week (since Monday)"),
+            Self::LastWeek => write!(f, "Last week (Monday to Sunday)"),
+            Self::SinceDate { date } => write!(f, "Since {}", date.format("%Y-%m-%d")),
+            Self::Custom { seconds } => {
+                let hours = seconds / 3600;
+                let mins = (seconds % 3600) / 60;
+                let secs = seconds % 60;
+                write!(f, "Custom ({:02}:{:02}:{:02})", hours, mins, secs)
+            }
+            Self::Window { start, end } => write!(
+                f,
+                "{} – {}",
+                clock::format(*start, "%Y-%m-%d %H:%M"),
+                clock::format(*end, "%Y-%m-%d %H:%M")
+            ),
+            Self::Milestone { title } => write!(f, "Milestone {}", title),
+            Self::Cycle { team, number, .. } => write!(f, "{} cycle {}", team, number),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChangelogFormat {
+    /// The AI returns categorized entries as JSON and the markdown is rendered locally
+    #[default]
+    Structured,
+    /// The AI writes the markdown directly
+    Freeform,
+}
+
+impl ChangelogFormat {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Structured, Self::Freeform]
+    }
+}
+
+impl fmt::Display for ChangelogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Structured => write!(f, "Structured (rendered locally)"),
+            Self::Freeform => write!(f, "Freeform (AI-written markdown)"),
+        }
+    }
+}
+
+/// Markdown dialect saved changelogs are normalized to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum MarkdownFlavor {
+    /// GitHub-flavored markdown, as written
+    #[default]
+    Github,
+    /// Strict CommonMark, without GitHub's extensions
+    CommonMark,
+    /// CommonMark that MkDocs and Docusaurus accept, with a title frontmatter
+    Docs,
+}
+
+impl MarkdownFlavor {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Github, Self::CommonMark, Self::Docs]
+    }
+}
+
+impl fmt::Display for MarkdownFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Github => write!(f, "GitHub-flavored"),
+            Self::CommonMark => write!(f, "CommonMark (strict)"),
+            Self::Docs => write!(f, "MkDocs / Docusaurus (with frontmatter)"),
+        }
+    }
+}
+
+/// Who the generated document is written for
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum GenerationMode {
+    /// Every notable change, for the team
+    #[default]
+    Changelog,
+    /// User-facing changes, breaking changes and upgrade steps, for publishing
+    ReleaseNotes,
+}
+
+impl GenerationMode {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Changelog, Self::ReleaseNotes]
+    }
+
+    /// Parses a mode name as given on the command line (e.g., "release-notes")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('_', "-").as_str() {
+            "changelog" => Some(Self::Changelog),
+            "release-notes" | "release" => Some(Self::ReleaseNotes),
+            _ => None,
+        }
+    }
+
+    /// Start of the saved file's name, e.g. release-notes_<repo>_<date>.md
+    pub fn file_prefix(&self) -> &'static str {
+        match self {
+            Self::Changelog => "changelog",
+            Self::ReleaseNotes => "release-notes",
+        }
+    }
+
+    /// Document title used by the structured format
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Changelog => "Changelog",
+            Self::ReleaseNotes => "Release notes",
+        }
+    }
+}
+
+impl fmt::Display for GenerationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Changelog => write!(f, "Changelog (every notable change)"),
+            Self::ReleaseNotes => write!(f, "Release notes (user-facing, upgrade steps first)"),
+        }
+    }
+}
+
+/// What to do with PRs that were already included in an earlier changelog
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum DuplicatePrs {
+    #[default]
+    Skip,
+    /// Keep them, flagged as previously reported
+    Mark,
+    Include,
+}
+
+impl DuplicatePrs {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Skip, Self::Mark, Self::Include]
+    }
+}
+
+impl fmt::Display for DuplicatePrs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Skip => write!(f, "Skip them"),
+            Self::Mark => write!(f, "Mark them as previously reported"),
+            Self::Include => write!(f, "Include them again"),
+        }
+    }
+}
+
+/// What to do when the changelog file a run would write already exists, e.g. on a re-run
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum ExistingChangelog {
+    /// Save next to it with a sequence number, e.g. changelog_<repo>_<date>_2.md
+    #[default]
+    Suffix,
+    /// Add the new entries to its sections
+    Merge,
+    /// Ask each time, saving a numbered copy when there's no one to ask
+    Prompt,
+}
+
+impl ExistingChangelog {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Suffix, Self::Merge, Self::Prompt]
+    }
+}
+
+impl fmt::Display for ExistingChangelog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Suffix => write!(f, "Save a numbered copy"),
+            Self::Merge => write!(f, "Merge into the existing file"),
+            Self::Prompt => write!(f, "Ask each time"),
+        }
+    }
+}
+
+/// Which of a PR's comments go into the AI context
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum CommentOrder {
+    #[default]
+    MostReacted,
+    Latest,
+}
+
+impl CommentOrder {
+    pub fn all() -> Vec<Self> {
+        vec![Self::MostReacted, Self::Latest]
+    }
+}
+
+impl fmt::Display for CommentOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MostReacted => write!(f, "The most-reacted comments"),
+            Self::Latest => write!(f, "The latest comments"),
+        }
+    }
+}
+
+/// How the people who approved each PR are credited
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReviewerCredits {
+    #[default]
+    Off,
+    /// "reviewed by @alice" at the end of each entry
+    PerEntry,
+    /// A section thanking the reviewers at the end of the changelog
+    Appendix,
+}
+
+impl ReviewerCredits {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Off, Self::PerEntry, Self::Appendix]
+    }
+}
+
+impl fmt::Display for ReviewerCredits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "Don't credit reviewers"),
+            Self::PerEntry => write!(f, "Credit the reviewers of each entry"),
+            Self::Appendix => write!(f, "Thank the reviewers in a closing section"),
+        }
+    }
+}
+
+/// How the merges per day are charted in the changelog
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum ActivityChart {
+    #[default]
+    Off,
+    /// A mermaid bar chart, drawn by GitHub and most docs sites
+    Mermaid,
+    /// Bars of block characters in a code block, readable anywhere
+    Ascii,
+}
+
+impl ActivityChart {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Off, Self::Mermaid, Self::Ascii]
+    }
+}
+
+impl fmt::Display for ActivityChart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "No chart"),
+            Self::Mermaid => write!(f, "Mermaid chart"),
+            Self::Ascii => write!(f, "ASCII chart"),
+        }
+    }
+}
+
+/// Where tokens and API keys are persisted
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum SecretBackend {
+    #[default]
+    Keyring,
+    /// Plaintext .env in the working directory
+    EnvFile,
+    /// Passphrase-encrypted secrets.age in the working directory, for headless servers
+    EncryptedFile,
+}
+
+impl SecretBackend {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Keyring, Self::EncryptedFile, Self::EnvFile]
+    }
+}
+
+impl fmt::Display for SecretBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keyring => write!(f, "OS keyring"),
+            Self::EnvFile => write!(f, ".env file (plaintext)"),
+            Self::EncryptedFile => write!(f, "Encrypted file (secrets.age)"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DeliveryChannel {
+    Discord,
+    Telegram,
+    Slack,
+    PullRequest,
+    Discussion,
+    Gist,
+    Directory,
+    JiraVersion,
+}
+
+impl DeliveryChannel {
+    /// Returns all available delivery channels
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Discord,
+            Self::Telegram,
+            Self::Slack,
+            Self::PullRequest,
+            Self::Discussion,
+            Self::Gist,
+            Self::Directory,
+            Self::JiraVersion,
+        ]
+    }
+
+    /// Returns the environment variables holding this channel's credentials, with their prompts
+    pub fn credentials(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Discord => &[("DISCORD_WEBHOOK_URL", "Enter your Discord webhook URL:")],
+            Self::Telegram => &[
+                ("TELEGRAM_BOT_TOKEN", "Enter your Telegram bot token:"),
+                ("TELEGRAM_CHAT_ID", "Enter the Telegram chat ID:"),
+            ],
+            Self::Slack => &[(
+                "SLACK_WEBHOOK_URL",
+                "Enter your Slack incoming webhook URL:",
+            )],
+            // Use the GitHub token loaded at startup
+            Self::PullRequest | Self::Discussion | Self::Gist => &[],
+            Self::Directory => &[],
+            Self::JiraVersion => &[
+                (
+                    "JIRA_URL",
+                    "Jira URL (e.g., https://company.atlassian.net):",
+                ),
+                ("JIRA_EMAIL", "Jira email:"),
+                ("JIRA_API_TOKEN", "Jira API token:"),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for DeliveryChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Discord => write!(f, "Discord (webhook)"),
+            Self::Telegram => write!(f, "Telegram (bot)"),
+            Self::Slack => write!(f, "Slack (webhook)"),
+            Self::PullRequest => write!(f, "GitHub pull request"),
+            Self::Discussion => write!(f, "GitHub Discussion"),
+            Self::Gist => write!(f, "GitHub Gist"),
+            Self::Directory => write!(f, "Copy to a directory"),
+            Self::JiraVersion => write!(f, "Jira version"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Repo {
+    pub owner: String,
+    pub name: String,
+    /// Local CHANGELOG.md to update in Keep a Changelog mode; fetched from GitHub when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog_path: Option<String>,
+    /// Tags like "payments" or "infra" for group-scoped generation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    /// Cron expression or cadence ("hourly", "weekly") for daemon runs, overriding group
+    /// schedules and the global interval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    /// Generation mode for this repo, overriding the configured one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<GenerationMode>,
+    /// Name telling apart several subscriptions to one repo, e.g. "payments" in a monorepo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Path prefixes or globs (e.g., "services/payments/**"); only PRs touching them are included
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Named parts of the repo, used to group changelog entries by the files they touched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<Component>,
+    /// Title patterns of PRs left out of this repo's changelogs, on top of the global ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_titles: Vec<String>,
+}
+
+/// A part of a repository, e.g. "API" for `crates/api/**`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Component {
+    pub name: String,
+    /// Path prefixes or globs, matched like a subscription's paths
+    pub paths: Vec<String>,
+}
+
+/// The category and emoji a GitHub label puts its PRs under, e.g. "🔒 Security"
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LabelMapping {
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<String>,
+}
+
+/// Where a PR template puts the release notes, so only that part of a description is given to
+/// the AI
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum BodySection {
+    /// A heading of any level, e.g. "Release Notes", matched case-insensitively; the section
+    /// runs until the next heading of the same or a higher level
+    Heading { heading: String },
+    /// The text between two HTML comments, e.g. `<!-- release-notes -->` and
+    /// `<!-- /release-notes -->`, given by what's inside them
+    Markers { start: String, end: String },
+}
+
+/// A changelog section of a custom taxonomy, e.g. "Performance"
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Category {
+    pub name: String,
+    /// What belongs in it, told to the AI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Conventional-commit types that put a PR in it, e.g. "perf"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    /// Labels that put a PR in it, matched case-insensitively
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+impl Repo {
+    pub fn new(owner: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            name: name.into(),
+            changelog_path: None,
+            groups: Vec::new(),
+            schedule: None,
+            mode: None,
+            scope: None,
+            paths: Vec::new(),
+            components: Vec::new(),
+            exclude_titles: Vec::new(),
+        }
+    }
+
+    /// Parses "owner/name" format into a Repo, with an optional "@scope" suffix
+    pub fn from_full_name(full_name: &str) -> Option<Self> {
+        let (full_name, scope) = match full_name.split_once('@') {
+            Some((full_name, scope)) => (full_name, Some(scope.trim())),
+            None => (full_name, None),
+        };
+        let parts: Vec<&str> = full_name.split('/').collect();
+        if parts.len() == 2 && scope.is_none_or(|scope| !scope.is_empty()) {
+            let mut repo = Self::new(parts[0], parts[1]);
+            repo.scope = scope.map(str::to_string);
+            Some(repo)
+        } else {
+            None
+        }
+    }
+
+    /// Identifies the subscription: "owner/name", or "owner/name@scope" for a scoped one
+    /// Unlike `full_name`, which names the repo on GitHub, this keys per-subscription state
+    pub fn key(&self) -> String {
+        match &self.scope {
+            Some(scope) => format!("{}/{}@{}", self.owner, self.name, scope),
+            None => self.full_name(),
+        }
+    }
+
+    /// The repo part of saved file names: "name", or "name-scope" for a scoped subscription
+    pub fn file_stem(&self) -> String {
+        match &self.scope {
+            Some(scope) => format!("{}-{}", self.name, scope),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.name)
+    }
+}
+
+impl fmt::Display for Repo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub repos: Vec<Repo>,
+    #[serde(default)]
+    pub time_period: TimePeriod,
+    /// Key of the Linear team whose cycles `--cycle` refers to, e.g. "ENG"
+    #[serde(default)]
+    pub linear_team: Option<String>,
+    /// IANA timezone (e.g., Europe/Berlin) for dates, merge times, calendar periods and schedules
+    /// None uses the system timezone
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub ai_provider: AIProvider,
+    #[serde(default)]
+    pub ai_model: Option<String>,
+    /// Requests per minute allowed to each AI provider, e.g. {"OpenAI": 500}; unlisted ones
+    /// aren't limited
+    #[serde(default)]
+    pub ai_requests_per_minute: BTreeMap<AIProvider, u32>,
+    #[serde(default)]
+    pub changelog_format: ChangelogFormat,
+    /// Sections changelogs use instead of the built-in ones, in document order
+    #[serde(default)]
+    pub categories: Vec<Category>,
+    /// GitHub labels mapped to the category and emoji of their PRs, e.g. "security"
+    #[serde(default)]
+    pub label_map: BTreeMap<String, LabelMapping>,
+    /// Markdown dialect saved changelogs are normalized to
+    #[serde(default)]
+    pub markdown_flavor: MarkdownFlavor,
+    /// YAML frontmatter (title, date, repo, tags) on saved changelogs, for static site generators
+    #[serde(default)]
+    pub frontmatter: bool,
+    /// Tags listed in the frontmatter besides the repo's groups, e.g. "changelog"
+    #[serde(default)]
+    pub frontmatter_tags: Vec<String>,
+    /// Extra frontmatter fields written as-is, e.g. layout = "post" for Jekyll
+    #[serde(default)]
+    pub frontmatter_fields: BTreeMap<String, String>,
+    /// Name and title changelogs after the release published in their period, when there is one
+    #[serde(default)]
+    pub version_naming: bool,
+    /// File name of version-named changelogs, with {prefix}, {repo}, {version} and {date}
+    #[serde(default)]
+    pub version_file_pattern: Option<String>,
+    /// Title of version-named changelogs, with the same placeholders
+    #[serde(default)]
+    pub version_title_pattern: Option<String>,
+    /// Changelogs or public release notes, unless a repo says otherwise
+    #[serde(default)]
+    pub generation_mode: GenerationMode,
+    /// Format changelogs from PR titles and labels instead of asking the AI provider
+    #[serde(default)]
+    pub no_ai: bool,
+    /// Skip Jira lookups even when Jira credentials are configured, e.g. while Jira is down
+    #[serde(default)]
+    pub no_jira: bool,
+    /// Skip every lookup beyond the PRs themselves: Jira, PR sizes, deployments, CI results and
+    /// board statuses
+    #[serde(default)]
+    pub no_enrichment: bool,
+    /// Only give the AI Jira issues in a terminal status category, such as Done or Resolved
+    #[serde(default)]
+    pub jira_done_only: bool,
+    /// Warn about merged PRs whose linked Jira issues aren't done yet
+    #[serde(default)]
+    pub warn_open_issues: bool,
+    /// Comment on each Jira issue behind the changelog with its entries, once delivered
+    #[serde(default)]
+    pub jira_comments: bool,
+    /// Group the changelog entries of a Jira epic with several of them under its name
+    #[serde(default)]
+    pub group_by_epic: bool,
+    /// Summarize each PR individually before composing the changelog
+    #[serde(default)]
+    pub two_pass_generation: bool,
+    /// Also write a .json artifact next to each markdown changelog
+    #[serde(default)]
+    pub json_artifact: bool,
+    /// Append each changelog to per-repo and combined Atom feeds in feeds/
+    #[serde(default)]
+    pub atom_feed: bool,
+    /// Append a section thanking the PR authors and co-authors
+    #[serde(default)]
+    pub contributor_shoutouts: bool,
+    /// Credit the people who approved each PR, per entry or in a closing section
+    #[serde(default)]
+    pub reviewer_credits: ReviewerCredits,
+    /// Append lead-time percentiles (PR opened to merged) to changelogs and JSON artifacts
+    #[serde(default)]
+    pub lead_time_stats: bool,
+    /// Append a section counting the PRs, contributors and changed lines, and the busiest day
+    #[serde(default)]
+    pub stats_appendix: bool,
+    /// Chart the merges per day of the period at the end of each changelog
+    #[serde(default)]
+    pub activity_chart: ActivityChart,
+    /// List Dependabot and Renovate PRs as package bumps in one section instead of drafting them
+    #[serde(default)]
+    pub group_dependency_updates: bool,
+    /// Score each PR's impact from its size and labels and have the AI order entries by it
+    #[serde(default)]
+    pub impact_scoring: bool,
+    /// Environment, e.g. "production", whose GitHub deployments mark which PRs have shipped
+    #[serde(default)]
+    pub deployment_environment: Option<String>,
+    /// Check the CI results of each PR's merge commit and flag the failing ones
+    #[serde(default)]
+    pub ci_status: bool,
+    /// List the security advisories published and Dependabot alerts fixed in the period
+    #[serde(default)]
+    pub security_advisories: bool,
+    /// List the GitHub Discussions started or answered in the period
+    #[serde(default)]
+    pub discussions: bool,
+    /// Discussion categories to list, e.g. "Announcements"; all of them when empty
+    #[serde(default)]
+    pub discussion_categories: Vec<String>,
+    /// Comments of each PR excerpted in the AI context; none when 0
+    #[serde(default)]
+    pub pr_comments: usize,
+    /// Whether the most-reacted or the latest comments are excerpted
+    #[serde(default)]
+    pub pr_comment_order: CommentOrder,
+    /// Single-select Projects field, e.g. "Status", whose value for each PR's linked issue
+    /// annotates its entry, such as "In beta" or "Behind flag"
+    #[serde(default)]
+    pub project_status_field: Option<String>,
+    /// Open group digests with an AI-written summary for leadership
+    #[serde(default)]
+    pub executive_summary: bool,
+    /// Channels every generated changelog is delivered to
+    #[serde(default)]
+    pub delivery_channels: Vec<DeliveryChannel>,
+    /// Merge entries into the repo's CHANGELOG.md instead of writing dated standalone files
+    #[serde(default)]
+    pub keep_a_changelog: bool,
+    /// Directory changelogs are committed to when delivering as a pull request
+    #[serde(default)]
+    pub pull_request_dir: Option<String>,
+    /// Discussion category changelogs are posted to
+    #[serde(default)]
+    pub discussion_category: Option<String>,
+    /// Central "owner/name" repo for discussions; defaults to each changelog's repo
+    #[serde(default)]
+    pub discussion_repo: Option<String>,
+    /// Create public gists instead of secret ones
+    #[serde(default)]
+    pub gist_public: bool,
+    /// Directory saved changelogs are copied to by the directory delivery channel
+    #[serde(default)]
+    pub delivery_dir: Option<String>,
+    /// Project key the Jira version channel releases in; the linked issues' projects when unset
+    #[serde(default)]
+    pub jira_version_project: Option<String>,
+    /// Tera template used to assemble the saved document
+    #[serde(default)]
+    pub document_template: Option<String>,
+    /// Shell command given the PR context JSON on stdin, printing the context to draft from
+    #[serde(default)]
+    pub pre_generate_hook: Option<String>,
+    /// Shell command run with the saved changelog's path after each generation
+    #[serde(default)]
+    pub post_generate_hook: Option<String>,
+    /// Where credentials entered in gazette are saved
+    #[serde(default)]
+    pub secret_backend: SecretBackend,
+    /// PEM files of extra root certificates to trust, e.g. a corporate TLS-intercepting proxy's CA
+    #[serde(default)]
+    pub ca_certificates: Vec<String>,
+    /// Trust only `ca_certificates`, not the system's certificate store
+    #[serde(default)]
+    pub no_system_roots: bool,
+    /// Minutes between daemon runs
+    #[serde(default)]
+    pub daemon_interval_minutes: Option<u64>,
+    /// Repos generated at the same time when running several at once
+    #[serde(default)]
+    pub max_parallel_repos: Option<usize>,
+    /// Issue keys looked up at the same time for each repo
+    #[serde(default)]
+    pub issue_concurrency: Option<usize>,
+    /// Cron expressions for daemon runs, keyed by repo group
+    #[serde(default)]
+    pub group_schedules: BTreeMap<String, String>,
+    /// Handling of PRs already reported by an earlier run, e.g. with overlapping windows
+    #[serde(default)]
+    pub duplicate_prs: DuplicatePrs,
+    /// Also treat PRs the repo's own CHANGELOG.md mentions as previously reported
+    #[serde(default)]
+    pub cross_check_changelog: bool,
+    /// Handling of a changelog file that already exists; `--force` overwrites it instead
+    #[serde(default)]
+    pub existing_changelog: ExistingChangelog,
+    /// Title patterns of PRs left out of every changelog: regexes, or globs prefixed "glob:"
+    #[serde(default)]
+    pub exclude_titles: Vec<String>,
+    /// Pick which fetched PRs to include before drafting interactively
+    #[serde(default)]
+    pub select_prs: bool,
+    /// Sections of PR descriptions given to the AI instead of the whole description, for
+    /// descriptions that have one
+    #[serde(default)]
+    pub pr_body_sections: Vec<BodySection>,
+}
+
+impl Config {
+    /// Returns the AI model, falling back to provider default
+    pub fn get_ai_model(&self) -> String {
+        self.ai_model
+            .clone()
+            .unwrap_or_else(|| self.ai_provider.default_model().to_string())
+    }
+
+    /// Returns the directory changelogs are committed to, defaulting to docs/changelogs
+    pub fn get_pull_request_dir(&self) -> String {
+        self.pull_request_dir
+            .clone()
+            .unwrap_or_else(|| "docs/changelogs".to_string())
+    }
+
+    /// Returns the file name pattern of version-named changelogs
+    pub fn get_version_file_pattern(&self) -> String {
+        self.version_file_pattern
+            .clone()
+            .unwrap_or_else(|| versions::DEFAULT_FILE_PATTERN.to_string())
+    }
+
+    /// Returns the title pattern of version-named changelogs
+    pub fn get_version_title_pattern(&self) -> String {
+        self.version_title_pattern
+            .clone()
+            .unwrap_or_else(|| versions::DEFAULT_TITLE_PATTERN.to_string())
+    }
+
+    /// Returns the configured timezone, defaulting to the system's
+    pub fn zone(&self) -> Result<Zone> {
+        match &self.timezone {
+            Some(name) => Ok(Zone::parse(name)?),
+            None => Ok(Zone::System),
+        }
+    }
+
+    /// The certificate authorities the HTTP clients trust
+    pub fn tls_options(&self) -> Result<TlsOptions> {
+        Ok(TlsOptions::load(
+            &self.ca_certificates,
+            !self.no_system_roots,
+        )?)
+    }
+
+    /// Makes the timezone and certificate authorities the process-wide ones
+    /// Loading a config or creating a service leaves them alone, so the caller sets them once
+    /// at startup
+    pub fn apply_process_settings(&self) -> Result<()> {
+        clock::set_zone(self.zone()?);
+        tls::set_options(self.tls_options()?);
+        Ok(())
+    }
+
+    /// The requests-per-minute limit of the configured AI provider, if it has one
+    pub fn ai_rate_limit(&self) -> Option<u32> {
+        self.ai_requests_per_minute
+            .get(&self.ai_provider)
+            .copied()
+            .filter(|rpm| *rpm > 0)
+    }
+
+    /// Returns how many repos are generated at the same time, defaulting to 4
+    pub fn get_max_parallel_repos(&self) -> usize {
+        self.max_parallel_repos.unwrap_or(4).max(1)
+    }
+
+    /// Returns how many issue keys are looked up at the same time, defaulting to 8
+    pub fn get_issue_concurrency(&self) -> usize {
+        self.issue_concurrency.unwrap_or(8).max(1)
+    }
+
+    /// Returns the minutes between daemon runs, defaulting to 60
+    pub fn get_daemon_interval_minutes(&self) -> u64 {
+        self.daemon_interval_minutes.unwrap_or(60)
+    }
+
+    /// Returns the cron expression the daemon uses for a repo: its own, else its first scheduled group
+    /// None means the repo runs on the global interval
+    pub fn schedule_for(&self, repo: &Repo) -> Option<String> {
+        repo.schedule.clone().or_else(|| {
+            repo.groups
+                .iter()
+                .find_map(|group| self.group_schedules.get(group).cloned())
+        })
+    }
+
+    /// Returns every group used by a subscription, sorted
+    pub fn groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = self
+            .repos
+            .iter()
+            .flat_map(|r| r.groups.iter().cloned())
+            .collect();
+        groups.sort();
+        groups.dedup();
+        groups
+    }
+
+    /// Returns the subscriptions tagged with the given group
+    pub fn repos_in_group(&self, group: &str) -> Vec<Repo> {
+        self.repos
+            .iter()
+            .filter(|r| r.groups.iter().any(|g| g.eq_ignore_ascii_case(group)))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the discussion category, defaulting to Announcements
+    pub fn get_discussion_category(&self) -> String {
+        self.discussion_category
+            .clone()
+            .unwrap_or_else(|| "Announcements".to_string())
+    }
+}
+
+impl Config {
+    /// Turns an old repos.json into config.json when there's no config.json yet
+    /// Returns whether it did, for the caller to tell the user
+    pub fn migrate_repos_json() -> Result<bool> {
+        let old_repos_path = Path::new("repos.json");
+        if Path::new(CONFIG_FILE).exists() || !old_repos_path.exists() {
+            return Ok(false);
+        }
+
+        let content = fs::read_to_string(old_repos_path).context("Failed to read repos.json")?;
+        let repos: Vec<Repo> = serde_json::from_str(&content)
+            .map_err(|e| GazetteError::Config(format!("Failed to parse repos.json: {}", e)))?;
+
+        let config = Config {
+            repos,
+            ..Config::default()
+        };
+        config.save()?;
+
+        // Remove old file after migration
+        fs::remove_file(old_repos_path).ok();
+        Ok(true)
+    }
+
+    /// Loads config from the global config, config.json and .gazette.toml
+    pub fn load() -> Result<Self> {
+        let layers = Layers::discover()?;
+        let config: Config = serde_json::from_value(layers.merged()).map_err(|e| {
+            GazetteError::Config(format!("Failed to parse {}: {}", layers.files(), e))
+        })?;
+
+        Ok(config)
+    }
+
+    /// Saves config to config.json, or to the global config when there's no config.json
+    /// With several config files, only the settings changed since loading are written
+    pub fn save(&self) -> Result<()> {
+        let layers = Layers::discover()?;
+        let after = serde_json::to_value(self).context("Failed to serialize config")?;
+        let saved = if layers.is_single() {
+            after
+        } else {
+            let before: Config = serde_json::from_value(layers.merged()).unwrap_or_default();
+            let before = serde_json::to_value(before).context("Failed to serialize config")?;
+            layers.saved(&before, &after)
+        };
+
+        let path = layers.target();
+        let content = match path.extension() {
+            Some(ext) if ext == "toml" => {
+                toml::to_string_pretty(&toml::Value::try_from(layers::without_nulls(saved))?)
+                    .context("Failed to serialize config")?
+            }
+            _ => serde_json::to_string_pretty(&saved).context("Failed to serialize config")?,
+        };
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+}
+
+pub fn load_repos() -> Result<Vec<Repo>> {
+    Ok(Config::load()?.repos)
+}
+
+pub fn load_time_period() -> Result<TimePeriod> {
+    Ok(Config::load()?.time_period)
+}
+
+pub fn load_ai_provider() -> Result<AIProvider> {
+    Ok(Config::load()?.ai_provider)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_calendar_periods_align_to_monday() {
+        let utc = Zone::parse("UTC").unwrap();
+        // A Wednesday afternoon
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 15, 30, 0).unwrap();
+        let monday = Utc.with_ymd_and_hms(2026, 10, 12, 0, 0, 0).unwrap();
+
+        assert_eq!(TimePeriod::ThisWeek.bounds_in(now, utc), (monday, now));
+        assert_eq!(
+            TimePeriod::LastWeek.bounds_in(now, utc),
+            (monday - Duration::weeks(1), monday)
+        );
+
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        assert_eq!(
+            TimePeriod::SinceDate { date }.bounds_in(now, utc).0,
+            Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap()
+        );
+
+        // Monday midnight in Tokyo is still Sunday in UTC
+        let tokyo = Zone::parse("Asia/Tokyo").unwrap();
+        let tokyo_monday = Utc.with_ymd_and_hms(2026, 10, 11, 15, 0, 0).unwrap();
+        assert_eq!(TimePeriod::ThisWeek.bounds_in(now, tokyo).0, tokyo_monday);
+
+        // The week before the end of DST in Berlin is an hour longer
+        let berlin = Zone::parse("Europe/Berlin").unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 10, 28, 12, 0, 0).unwrap();
+        let (start, end) = TimePeriod::LastWeek.bounds_in(now, berlin);
+        assert_eq!(end - start, Duration::hours(7 * 24 + 1));
+        assert!(Zone::parse("Mars/Olympus").is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use crate::changelog::PrContext;
+
+/// GitHub marks PRs opened by someone who hadn't contributed to the repo before this way
+const FIRST_TIME_ASSOCIATIONS: [&str; 2] = ["FIRST_TIME_CONTRIBUTOR", "FIRST_TIMER"];
+
+/// People credited for the PRs of a changelog
+#[derive(Debug, Default, PartialEq)]
+pub struct Contributors {
+    /// `@login` for GitHub users, the plain name for co-authors without a known login
+    pub names: Vec<String>,
+    /// Authors whose first contribution to the repo is in this changelog
+    pub first_time: Vec<String>,
+}
+
+impl Contributors {
+    /// Collects PR authors and the `Co-authored-by:` trailers of PR descriptions and commits,
+    /// leaving out bots
+    pub fn collect(contexts: &[PrContext]) -> Self {
+        // Keyed case-insensitively so the same person isn't thanked twice
+        let mut names: BTreeMap<String, String> = BTreeMap::new();
+        let mut first_time: BTreeMap<String, String> = BTreeMap::new();
+
+        for ctx in contexts {
+            if let Some(user) = &ctx.pr.user
+                && !is_bot(&user.login)
+            {
+                let name = format!("@{}", user.login);
+                if ctx
+                    .pr
+                    .author_association
+                    .as_deref()
+                    .is_some_and(|association| FIRST_TIME_ASSOCIATIONS.contains(&association))
+                {
+                    first_time.insert(name.to_lowercase(), name.clone());
+                }
+                names.insert(name.to_lowercase(), name);
+            }
+
+            for name in co_authors(ctx.pr.body.as_deref().unwrap_or_default())
+                .into_iter()
+                .chain(ctx.co_authors.iter().cloned())
+            {
+                names.entry(name.to_lowercase()).or_insert(name);
+            }
+        }
+
+        Self {
+            names: names.into_values().collect(),
+            first_time: first_time.into_values().collect(),
+        }
+    }
+
+    /// Renders the thanks as a markdown section, None when nobody is credited
+    pub fn render(&self) -> Option<String> {
+        if self.names.is_empty() {
+            return None;
+        }
+
+        let mut section = format!("## Contributors\n\nThanks to {}!\n", join(&self.names));
+        if !self.first_time.is_empty() {
+            section.push_str(&format!(
+                "\nWelcome to our first-time contributors: {} 🎉\n",
+                join(&self.first_time)
+            ));
+        }
+        Some(section)
+    }
+}
+
+/// Names from `Co-authored-by:` trailers, using the login of GitHub noreply addresses
+pub fn co_authors(text: &str) -> Vec<String> {
+    let trailer = Regex::new(r"(?mi)^\s*co-authored-by:\s*(.+?)\s*<([^>]*)>").unwrap();
+    let noreply = Regex::new(r"^(?:\d+\+)?([A-Za-z0-9-]+)@users\.noreply\.github\.com$").unwrap();
+
+    trailer
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let name = captures[1].trim();
+            let email = captures[2].trim();
+            let name = match noreply.captures(email) {
+                Some(login) => format!("@{}", &login[1]),
+                None => name.to_string(),
+            };
+            (!name.is_empty() && !is_bot(&name)).then_some(name)
+        })
+        .collect()
+}
+
+fn is_bot(login: &str) -> bool {
+    login.ends_with("[bot]") || login.ends_with("-bot")
+}
+
+/// Joins names as "a, b and c"
+pub fn join(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::GitHubUser;
+
+    fn context(login: &str, association: &str, body: &str) -> PrContext {
+        let mut ctx = PrContext::fixture(1, "Change");
+        ctx.pr.body = Some(body.to_string());
+        ctx.pr.user = Some(GitHubUser {
+            login: login.to_string(),
+        });
+        ctx.pr.author_association = Some(association.to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_contributors_are_deduplicated_and_bots_skipped() {
+        let mut paired = context("erin", "MEMBER", "");
+        paired.co_authors = vec!["@frank".to_string(), "Dana".to_string()];
+        let contexts = vec![
+            paired,
+            context(
+                "alice",
+                "MEMBER",
+                "Pairing session\n\nCo-authored-by: Bob Builder <123+bob@users.noreply.github.com>\nCo-authored-by: Dana <dana@acme.io>",
+            ),
+            context("carol", "FIRST_TIME_CONTRIBUTOR", ""),
+            context("Bob", "CONTRIBUTOR", ""),
+            context("dependabot[bot]", "NONE", ""),
+        ];
+
+        let contributors = Contributors::collect(&contexts);
+        assert_eq!(
+            contributors.names,
+            vec!["@alice", "@Bob", "@carol", "@erin", "@frank", "Dana"]
+        );
+        assert_eq!(contributors.first_time, vec!["@carol"]);
+        assert_eq!(
+            contributors.render().unwrap(),
+            "## Contributors\n\nThanks to @alice, @Bob, @carol, @erin, @frank and Dana!\n\nWelcome to our first-time contributors: @carol 🎉\n"
+        );
+        assert_eq!(Contributors::default().render(), None);
+    }
+}
@@ -0,0 +1,160 @@
+use regex::Regex;
+
+use crate::ai::{ChangelogCategory, ChangelogEntry, StructuredChangelog};
+use crate::breaking;
+use crate::changelog::PrContext;
+use crate::config::GenerationMode;
+
+/// Categories in the order they appear in the document
+const CATEGORIES: [&str; 6] = [
+    "Breaking Changes",
+    "Features",
+    "Fixes",
+    "Documentation",
+    "Chores",
+    "Other Changes",
+];
+
+/// Builds a changelog from PR titles and labels alone, without asking an AI
+/// Release notes leave out chores, which users don't notice
+pub fn changelog(contexts: &[PrContext], mode: GenerationMode) -> StructuredChangelog {
+    let title = Regex::new(r"^\s*(\w+)(?:\(([^)]*)\))?!?:\s*(.+)$").unwrap();
+    let mut categories: Vec<ChangelogCategory> = CATEGORIES
+        .iter()
+        .map(|name| ChangelogCategory {
+            name: name.to_string(),
+            entries: Vec::new(),
+        })
+        .collect();
+
+    for ctx in contexts {
+        let parsed = title.captures(&ctx.pr.title);
+        let kind = parsed.as_ref().map(|captures| captures[1].to_lowercase());
+        let category = if breaking::detect(&ctx.pr).is_some() {
+            "Breaking Changes"
+        } else {
+            kind.as_deref()
+                .and_then(category_for_type)
+                .or_else(|| {
+                    ctx.pr
+                        .labels
+                        .iter()
+                        .find_map(|label| category_for_label(&label.name))
+                })
+                .unwrap_or("Other Changes")
+        };
+        if category == "Chores" && mode == GenerationMode::ReleaseNotes {
+            continue;
+        }
+
+        let summary = match &parsed {
+            Some(captures) => {
+                let description = capitalize(captures[3].trim());
+                match captures.get(2).map(|scope| scope.as_str().trim()) {
+                    Some(scope) if !scope.is_empty() => format!("**{}:** {}", scope, description),
+                    _ => description,
+                }
+            }
+            None => capitalize(ctx.pr.title.trim()),
+        };
+
+        let entry = ChangelogEntry {
+            summary,
+            pr_numbers: vec![ctx.pr.number],
+            jira_keys: ctx
+                .jira_issues
+                .iter()
+                .map(|issue| issue.key.clone())
+                .collect(),
+        };
+        if let Some(target) = categories.iter_mut().find(|c| c.name == category) {
+            target.entries.push(entry);
+        }
+    }
+
+    categories.retain(|category| !category.entries.is_empty());
+    StructuredChangelog { categories }
+}
+
+/// The lowercased type of a conventional-commit title, e.g. "feat" for "feat(api): add search"
+pub fn commit_type(title: &str) -> Option<String> {
+    let pattern = Regex::new(r"^\s*(\w+)(?:\([^)]*\))?!?:\s*\S").unwrap();
+    pattern
+        .captures(title)
+        .map(|captures| captures[1].to_lowercase())
+}
+
+fn category_for_type(kind: &str) -> Option<&'static str> {
+    match kind {
+        "feat" | "feature" => Some("Features"),
+        "fix" | "bugfix" | "hotfix" => Some("Fixes"),
+        "docs" | "doc" => Some("Documentation"),
+        "chore" | "ci" | "build" | "deps" | "refactor" | "test" | "tests" | "style" | "perf"
+        | "revert" => Some("Chores"),
+        _ => None,
+    }
+}
+
+fn category_for_label(label: &str) -> Option<&'static str> {
+    match label.to_lowercase().as_str() {
+        "feature" | "enhancement" | "feat" => Some("Features"),
+        "bug" | "fix" | "bugfix" => Some("Fixes"),
+        "documentation" | "docs" => Some("Documentation"),
+        "chore" | "dependencies" | "ci" | "refactor" | "tests" => Some("Chores"),
+        _ => None,
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(number: u64, title: &str, labels: &[&str]) -> PrContext {
+        PrContext::fixture(number, title).labeled(labels)
+    }
+
+    #[test]
+    fn test_prs_are_grouped_by_title_type_and_labels() {
+        let contexts = vec![
+            context(1, "fix(auth): refresh expired tokens", &[]),
+            context(2, "feat: export to CSV", &[]),
+            context(3, "chore(deps): bump serde", &[]),
+            context(4, "Handle empty carts", &["bug"]),
+            context(5, "feat(api)!: drop v1 routes", &[]),
+            context(6, "Tidy up the landing page", &[]),
+        ];
+
+        let changelog = changelog(&contexts, GenerationMode::Changelog);
+        let names: Vec<&str> = changelog
+            .categories
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Breaking Changes",
+                "Features",
+                "Fixes",
+                "Chores",
+                "Other Changes"
+            ]
+        );
+        assert_eq!(
+            changelog.categories[2].entries[0].summary,
+            "**auth:** Refresh expired tokens"
+        );
+        assert_eq!(changelog.categories[2].entries[1].pr_numbers, vec![4]);
+
+        let notes = super::changelog(&contexts, GenerationMode::ReleaseNotes);
+        assert!(notes.categories.iter().all(|c| c.name != "Chores"));
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use super::Delivery;
+use crate::config::Config;
+
+/// Copies saved changelogs into another directory, e.g. a docs site or a shared drive
+pub struct DirectorySink {
+    dir: PathBuf,
+}
+
+impl DirectorySink {
+    pub fn new() -> Result<Self> {
+        let dir = Config::load()?
+            .delivery_dir
+            .context("No delivery directory configured")?;
+        Ok(Self {
+            dir: PathBuf::from(dir),
+        })
+    }
+
+    /// Writes the changelog under its saved file name, returning the copy's path
+    pub fn copy(&self, delivery: &Delivery<'_>) -> Result<String> {
+        let name = delivery
+            .path
+            .file_name()
+            .context("Saved changelog has no file name")?;
+        let target = self.dir.join(name);
+
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        fs::write(&target, delivery.content)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+
+        Ok(target.display().to_string())
+    }
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use reqwest::multipart::{Form, Part};
+use serde::Serialize;
+
+use super::split_message;
+use crate::config::Repo;
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+
+/// Discord's maximum message length
+const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+/// Changelogs needing more messages than this are attached as a file instead
+const MAX_DISCORD_CHUNKS: usize = 5;
+
+/// Discord webhook client
+pub struct DiscordClient {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+#[derive(Serialize)]
+struct WebhookMessage {
+    content: String,
+}
+
+impl DiscordClient {
+    /// Creates a new Discord client from the DISCORD_WEBHOOK_URL secret
+    pub fn new() -> Result<Self> {
+        let webhook_url = secrets::require("DISCORD_WEBHOOK_URL")?;
+
+        Ok(Self {
+            client: tls::client(),
+            webhook_url,
+        })
+    }
+
+    /// Posts the changelog, chunked across messages or attached as a markdown file if too long
+    pub async fn send(&self, repo: &Repo, content: &str, path: &Path) -> Result<()> {
+        let chunks = split_message(content, DISCORD_MESSAGE_LIMIT);
+
+        if chunks.len() > MAX_DISCORD_CHUNKS {
+            return self.send_file(repo, content, path).await;
+        }
+
+        for chunk in chunks {
+            let response = self
+                .client
+                .post(&self.webhook_url)
+                .json(&WebhookMessage { content: chunk })
+                .send()
+                .await
+                .context("Failed to send message to Discord")?;
+
+            check_response(response).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn send_file(&self, repo: &Repo, content: &str, path: &Path) -> Result<()> {
+        let filename = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "changelog.md".to_string());
+
+        let payload = serde_json::to_string(&WebhookMessage {
+            content: format!("📋 Changelog for **{}**", repo.full_name()),
+        })?;
+
+        let file = Part::bytes(content.as_bytes().to_vec())
+            .file_name(filename)
+            .mime_str("text/markdown")?;
+
+        let form = Form::new()
+            .text("payload_json", payload)
+            .part("files[0]", file);
+
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .multipart(form)
+            .send()
+            .await
+            .context("Failed to upload changelog to Discord")?;
+
+        check_response(response).await
+    }
+}
+
+async fn check_response(response: reqwest::Response) -> Result<()> {
+    if !response.status().is_success() {
+        return Err(GazetteError::from_response("Discord", response)
+            .await
+            .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+
+use super::Delivery;
+use crate::clock;
+use crate::config::{Config, Repo};
+use crate::github::GitHubClient;
+
+/// Publishes the changelog as a new GitHub Discussion
+pub struct DiscussionPublisher {
+    github: GitHubClient,
+    category: String,
+    /// Central repo to post to; defaults to the changelog's own repo
+    target_repo: Option<Repo>,
+}
+
+impl DiscussionPublisher {
+    pub fn new() -> Result<Self> {
+        let config = Config::load()?;
+
+        let target_repo = match &config.discussion_repo {
+            Some(full_name) => Some(
+                Repo::from_full_name(full_name)
+                    .context("Invalid discussion repo. Use 'owner/name'")?,
+            ),
+            None => None,
+        };
+
+        Ok(Self::with_client(
+            GitHubClient::new()?,
+            &config.get_discussion_category(),
+            target_repo,
+        ))
+    }
+
+    /// Posts discussions through `github`, e.g. one pointed at another base URL
+    pub fn with_client(github: GitHubClient, category: &str, target_repo: Option<Repo>) -> Self {
+        Self {
+            github,
+            category: category.to_string(),
+            target_repo,
+        }
+    }
+
+    /// Returns the URL of the created discussion
+    pub async fn publish(&self, delivery: &Delivery<'_>) -> Result<String> {
+        let target = self.target_repo.as_ref().unwrap_or(delivery.repo);
+        let title = format!(
+            "Changelog for {} — {}",
+            delivery.repo.full_name(),
+            clock::today().format("%Y-%m-%d")
+        );
+
+        self.github
+            .create_discussion(target, &self.category, &title, delivery.content)
+            .await
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+
+use super::Delivery;
+use crate::github::{GitHubClient, GitHubRelease};
+use crate::markdown;
+
+/// Replaces the body of a draft GitHub release with the changelog, e.g. one release-drafter
+/// opened, so it can be published as is
+pub struct DraftReleasePublisher {
+    github: GitHubClient,
+    release: GitHubRelease,
+}
+
+impl DraftReleasePublisher {
+    pub fn new(release: GitHubRelease) -> Result<Self> {
+        Ok(Self {
+            github: GitHubClient::new()?,
+            release,
+        })
+    }
+
+    /// Tag of the draft, e.g. "v2.4.0"
+    pub fn tag(&self) -> &str {
+        &self.release.tag_name
+    }
+
+    /// Returns the URL of the updated draft
+    pub async fn publish(&self, delivery: &Delivery<'_>) -> Result<String> {
+        self.github
+            .update_release_body(delivery.repo, self.release.id, &body(delivery.content))
+            .await
+    }
+}
+
+/// The changelog without its frontmatter and `#` title, which the release has its own of
+fn body(content: &str) -> String {
+    let content = markdown::strip_frontmatter(content);
+    let rest = match content.split_once('\n') {
+        Some((first, rest)) if first.starts_with("# ") => rest,
+        None if content.starts_with("# ") => "",
+        _ => content,
+    };
+    rest.trim_start_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_drops_the_frontmatter_and_title() {
+        assert_eq!(
+            body(
+                "---\nrepo: acme/web\n---\n# Release notes for acme/web - 2026-10-14\n\n## Features\n- SSO\n"
+            ),
+            "## Features\n- SSO\n"
+        );
+        assert_eq!(body("## Fixes\n- Retries\n"), "## Fixes\n- Retries\n");
+    }
+}
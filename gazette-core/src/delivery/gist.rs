@@ -0,0 +1,43 @@
+use anyhow::Result;
+
+use super::Delivery;
+use crate::clock;
+use crate::config::Config;
+use crate::github::GitHubClient;
+
+/// Uploads the changelog as a GitHub Gist, for sharing one-off digests
+pub struct GistPublisher {
+    github: GitHubClient,
+    public: bool,
+}
+
+impl GistPublisher {
+    pub fn new() -> Result<Self> {
+        let config = Config::load()?;
+
+        Ok(Self::with_client(GitHubClient::new()?, config.gist_public))
+    }
+
+    /// Uploads gists through `github`, e.g. one pointed at another base URL
+    pub fn with_client(github: GitHubClient, public: bool) -> Self {
+        Self { github, public }
+    }
+
+    /// Returns the URL of the created gist
+    pub async fn publish(&self, delivery: &Delivery<'_>) -> Result<String> {
+        let filename = delivery
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| format!("{}_changelog.md", delivery.repo.name));
+        let description = format!(
+            "Changelog for {} — {}",
+            delivery.repo.full_name(),
+            clock::today().format("%Y-%m-%d")
+        );
+
+        self.github
+            .create_gist(&description, self.public, &filename, delivery.content)
+            .await
+    }
+}
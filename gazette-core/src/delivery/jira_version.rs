@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use super::Delivery;
+use crate::clock;
+use crate::error::GazetteError;
+use crate::jira::{JiraClient, VersionDraft};
+use crate::markdown;
+
+/// Characters Jira keeps of a version's description
+const DESCRIPTION_LIMIT: usize = 255;
+
+/// Creates or updates a Jira version for the changelog and adds it to the linked issues'
+/// fix versions, with the notes as its description
+pub struct JiraVersionPublisher {
+    jira: JiraClient,
+    /// Project the version is created in; the linked issues' projects when unset
+    project: Option<String>,
+}
+
+impl JiraVersionPublisher {
+    pub fn new(jira: JiraClient, project: Option<String>) -> Self {
+        Self { jira, project }
+    }
+
+    /// Returns the URL of the version, the first project's when the issues span several
+    pub async fn publish(&self, delivery: &Delivery<'_>) -> Result<String> {
+        let projects = issues_by_project(&delivery.jira_keys, self.project.as_deref());
+        if projects.is_empty() {
+            return Err(GazetteError::Config(
+                "The changelog links no Jira issues; set jira_version_project to create a version anyway"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let today = clock::today().format("%Y-%m-%d").to_string();
+        let draft = VersionDraft {
+            name: delivery
+                .version
+                .clone()
+                .unwrap_or_else(|| format!("{} {}", delivery.repo.name, today)),
+            description: description(delivery.content),
+            released: delivery.version.is_some(),
+            release_date: delivery.version.is_some().then_some(today),
+        };
+
+        let mut urls = Vec::new();
+        for (project, keys) in projects {
+            let version = self.jira.upsert_version(project, &draft).await?;
+            for key in keys {
+                self.jira.add_fix_version(key, &version).await?;
+            }
+            urls.push(self.jira.version_url(project, &version));
+        }
+        Ok(urls.remove(0))
+    }
+}
+
+/// The issue keys grouped by their project, e.g. "AUTH" for AUTH-7
+/// With a configured project, only its issues are kept, and it is released in even without any
+fn issues_by_project<'a>(
+    keys: &'a [String],
+    project: Option<&'a str>,
+) -> BTreeMap<&'a str, Vec<&'a str>> {
+    let mut projects: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    if let Some(project) = project {
+        projects.insert(project, Vec::new());
+    }
+    for key in keys {
+        let Some((prefix, _)) = key.rsplit_once('-') else {
+            continue;
+        };
+        if project.is_none_or(|project| project == prefix) {
+            projects.entry(prefix).or_default().push(key);
+        }
+    }
+    projects
+}
+
+/// The notes' entries as plain text, one per line, cut after the last whole entry that fits
+fn description(content: &str) -> String {
+    let entries: Vec<String> = markdown::list_items(content)
+        .map(markdown::without_links)
+        .collect();
+    let full = entries.join("\n");
+    if full.chars().count() <= DESCRIPTION_LIMIT {
+        return full;
+    }
+
+    let mut cut: String = full.chars().take(DESCRIPTION_LIMIT - 1).collect();
+    if let Some(end) = cut.rfind('\n') {
+        cut.truncate(end + 1);
+    }
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issues_are_released_in_their_projects() {
+        let keys = vec![
+            "AUTH-7".to_string(),
+            "WEB-12".to_string(),
+            "AUTH-9".to_string(),
+        ];
+
+        let projects = issues_by_project(&keys, None);
+        assert_eq!(projects["AUTH"], ["AUTH-7", "AUTH-9"]);
+        assert_eq!(projects["WEB"], ["WEB-12"]);
+        let configured = issues_by_project(&keys, Some("OPS"));
+        assert_eq!(configured.len(), 1);
+        assert!(configured["OPS"].is_empty());
+
+        let content = format!(
+            "# Changelog\n\n## Features\n\n- SSO logins ([#12](https://github.com/acme/web/pull/12))\n- {}\n",
+            "x".repeat(300)
+        );
+        assert_eq!(description(&content), "SSO logins (#12)\n…");
+    }
+}
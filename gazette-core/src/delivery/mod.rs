@@ -0,0 +1,275 @@
+mod directory;
+mod discord;
+mod discussion;
+mod draft_release;
+mod gist;
+mod jira_version;
+mod pull_request;
+mod slack;
+mod telegram;
+
+use std::path::Path;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::config::{DeliveryChannel, Repo};
+use crate::error::GazetteError;
+use crate::markdown;
+
+pub use directory::DirectorySink;
+pub use discord::DiscordClient;
+pub use discussion::DiscussionPublisher;
+pub use draft_release::DraftReleasePublisher;
+pub use gist::GistPublisher;
+pub use jira_version::JiraVersionPublisher;
+pub use pull_request::PullRequestPublisher;
+pub use slack::SlackClient;
+pub use telegram::TelegramClient;
+
+/// A generated changelog ready to be delivered
+pub struct Delivery<'a> {
+    pub repo: &'a Repo,
+    /// The changelog generated in this run
+    pub content: &'a str,
+    /// Description of the period covered, e.g. "last 7 days"
+    pub period: String,
+    /// Local file the changelog was saved to
+    pub path: &'a Path,
+    /// Path the saved file should have when committed to the repo
+    pub repo_path: String,
+    /// Jira issues the changelog's PRs link to
+    pub jira_keys: Vec<String>,
+    /// Tag of the release the changelog is named after, when version naming found one
+    pub version: Option<String>,
+}
+
+/// Somewhere a saved changelog is sent, e.g. a chat webhook or a GitHub gist
+/// Each run fans out to every configured sink, plus those added with `ChangelogService::with_sink`
+#[async_trait]
+pub trait OutputSink: Send + Sync {
+    /// Name shown with the sink's outcome, e.g. "Discord (webhook)"
+    fn name(&self) -> String;
+
+    /// Sends the changelog, returning a link to it when the sink provides one
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>>;
+}
+
+/// Outcome of delivering a changelog to a single sink
+pub struct DeliveryOutcome {
+    pub sink: String,
+    /// Link to the delivered changelog, when the sink provides one
+    pub result: Result<Option<String>>,
+}
+
+/// Creates the sink for a configured delivery channel
+/// The Jira version publisher needs the configured project, so callers build it themselves
+pub fn sink_for(channel: DeliveryChannel) -> Result<Box<dyn OutputSink>> {
+    Ok(match channel {
+        DeliveryChannel::Discord => Box::new(DiscordClient::new()?),
+        DeliveryChannel::Telegram => Box::new(TelegramClient::new()?),
+        DeliveryChannel::Slack => Box::new(SlackClient::new()?),
+        DeliveryChannel::PullRequest => Box::new(PullRequestPublisher::new()?),
+        DeliveryChannel::Discussion => Box::new(DiscussionPublisher::new()?),
+        DeliveryChannel::Gist => Box::new(GistPublisher::new()?),
+        DeliveryChannel::Directory => Box::new(DirectorySink::new()?),
+        DeliveryChannel::JiraVersion => {
+            return Err(GazetteError::Config(
+                "The Jira version publisher is built with JiraVersionPublisher::new".to_string(),
+            )
+            .into());
+        }
+    })
+}
+
+/// Delivers a changelog to the given channel
+pub async fn deliver(channel: DeliveryChannel, delivery: &Delivery<'_>) -> Result<Option<String>> {
+    sink_for(channel)?.deliver(delivery).await
+}
+
+/// Delivers a changelog to every channel and extra sink, reporting each one's result separately
+/// A channel whose sink can't be created, e.g. for missing credentials, fails on its own
+pub async fn deliver_all(
+    channels: &[DeliveryChannel],
+    sinks: &[Box<dyn OutputSink>],
+    delivery: &Delivery<'_>,
+) -> Vec<DeliveryOutcome> {
+    let mut outcomes = Vec::new();
+
+    for channel in channels {
+        let result = deliver(*channel, delivery).await;
+        outcomes.push(DeliveryOutcome {
+            sink: channel.to_string(),
+            result,
+        });
+    }
+
+    for sink in sinks {
+        let result = sink.deliver(delivery).await;
+        outcomes.push(DeliveryOutcome {
+            sink: sink.name(),
+            result,
+        });
+    }
+
+    outcomes
+}
+
+#[async_trait]
+impl OutputSink for DiscordClient {
+    fn name(&self) -> String {
+        DeliveryChannel::Discord.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        let content = markdown::strip_frontmatter(delivery.content);
+        self.send(delivery.repo, content, delivery.path).await?;
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl OutputSink for TelegramClient {
+    fn name(&self) -> String {
+        DeliveryChannel::Telegram.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        self.send(markdown::strip_frontmatter(delivery.content))
+            .await?;
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl OutputSink for SlackClient {
+    fn name(&self) -> String {
+        DeliveryChannel::Slack.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        self.send(delivery).await?;
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl OutputSink for PullRequestPublisher {
+    fn name(&self) -> String {
+        DeliveryChannel::PullRequest.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        Ok(Some(self.publish(delivery).await?))
+    }
+}
+
+#[async_trait]
+impl OutputSink for DiscussionPublisher {
+    fn name(&self) -> String {
+        DeliveryChannel::Discussion.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        Ok(Some(self.publish(delivery).await?))
+    }
+}
+
+#[async_trait]
+impl OutputSink for GistPublisher {
+    fn name(&self) -> String {
+        DeliveryChannel::Gist.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        Ok(Some(self.publish(delivery).await?))
+    }
+}
+
+#[async_trait]
+impl OutputSink for DirectorySink {
+    fn name(&self) -> String {
+        DeliveryChannel::Directory.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        Ok(Some(self.copy(delivery)?))
+    }
+}
+
+#[async_trait]
+impl OutputSink for DraftReleasePublisher {
+    fn name(&self) -> String {
+        format!("Draft release {}", self.tag())
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        Ok(Some(self.publish(delivery).await?))
+    }
+}
+
+#[async_trait]
+impl OutputSink for JiraVersionPublisher {
+    fn name(&self) -> String {
+        DeliveryChannel::JiraVersion.to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        Ok(Some(self.publish(delivery).await?))
+    }
+}
+
+/// Splits text into chunks of at most `limit` characters, preferring line boundaries
+pub fn split_message(text: &str, limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        let line_len = line.chars().count();
+
+        // Hard-split lines that can never fit in a single chunk
+        if line_len > limit {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            let chars: Vec<char> = line.chars().collect();
+            for piece in chars.chunks(limit) {
+                chunks.push(piece.iter().collect());
+            }
+            continue;
+        }
+
+        let separator = if current.is_empty() { 0 } else { 1 };
+        if current.chars().count() + separator + line_len > limit {
+            chunks.push(std::mem::take(&mut current));
+        }
+
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_message_on_lines() {
+        let text = "aaaa\nbbbb\ncccc";
+        assert_eq!(split_message(text, 9), vec!["aaaa\nbbbb", "cccc"]);
+    }
+
+    #[test]
+    fn test_split_message_long_line() {
+        assert_eq!(split_message("abcdefg", 3), vec!["abc", "def", "g"]);
+        assert!(split_message("", 3).is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use super::Delivery;
+use crate::github::GitHubClient;
+use crate::{clock, keep_a_changelog};
+
+/// GitHub's maximum pull request body length
+const PR_BODY_LIMIT: usize = 65536;
+
+/// Commits the changelog to a new branch of the target repo and opens a pull request
+pub struct PullRequestPublisher {
+    github: GitHubClient,
+}
+
+impl PullRequestPublisher {
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_client(GitHubClient::new()?))
+    }
+
+    /// Opens pull requests through `github`, e.g. one pointed at another base URL
+    pub fn with_client(github: GitHubClient) -> Self {
+        Self { github }
+    }
+
+    /// Returns the URL of the opened pull request
+    pub async fn publish(&self, delivery: &Delivery<'_>) -> Result<String> {
+        let repo = delivery.repo;
+        let now = Utc::now();
+        let branch = format!("gazette/changelog-{}", clock::format(now, "%Y%m%d-%H%M%S"));
+        let title = format!(
+            "docs: changelog for {} ({})",
+            repo.name,
+            clock::format(now, "%Y-%m-%d")
+        );
+
+        // Commit the saved file, which may hold more than this run (e.g., a whole CHANGELOG.md)
+        let mut file_content =
+            fs::read_to_string(delivery.path).context("Failed to read saved changelog")?;
+
+        let base = self.github.get_default_branch(repo).await?;
+
+        // The local CHANGELOG.md may be behind the base branch, so merge the unreleased entries
+        // into the base branch's copy rather than replacing it
+        if delivery.repo_path == keep_a_changelog::REPO_PATH {
+            let upstream = self
+                .github
+                .get_file_contents(repo, keep_a_changelog::REPO_PATH)
+                .await?;
+            let mut entries = keep_a_changelog::unreleased_entries(&file_content);
+            if entries.is_empty() {
+                entries = keep_a_changelog::extract_entries(delivery.content);
+            }
+            file_content = keep_a_changelog::merge_unreleased(upstream.as_deref(), &entries);
+        }
+
+        self.github.create_branch(repo, &branch, &base).await?;
+        self.github
+            .put_file(repo, &branch, &delivery.repo_path, &file_content, &title)
+            .await?;
+
+        let body: String = delivery.content.chars().take(PR_BODY_LIMIT).collect();
+        let pr = self
+            .github
+            .create_pull_request(repo, &branch, &base, &title, &body)
+            .await?;
+
+        Ok(pr.html_url)
+    }
+}
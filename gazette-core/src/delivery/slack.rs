@@ -0,0 +1,283 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+
+use super::{Delivery, split_message};
+use crate::error::GazetteError;
+use crate::markdown;
+use crate::secrets;
+use crate::tls;
+
+/// Slack rejects section texts longer than this, so long categories span several sections
+const SECTION_TEXT_LIMIT: usize = 3000;
+
+/// Slack's maximum header text length
+const HEADER_TEXT_LIMIT: usize = 150;
+
+/// Slack's maximum number of blocks in one message; longer changelogs are split
+const MAX_BLOCKS_PER_MESSAGE: usize = 50;
+
+/// Slack incoming webhook client
+pub struct SlackClient {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+#[derive(Serialize)]
+struct WebhookMessage {
+    /// Fallback shown in notifications
+    text: String,
+    blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Block {
+    Header { text: Text },
+    Section { text: Text },
+    Context { elements: Vec<Text> },
+    Divider,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Text {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    text: String,
+}
+
+impl Text {
+    fn plain(text: String) -> Self {
+        Self {
+            kind: "plain_text",
+            text,
+        }
+    }
+
+    fn mrkdwn(text: String) -> Self {
+        Self {
+            kind: "mrkdwn",
+            text,
+        }
+    }
+}
+
+impl SlackClient {
+    /// Creates a new Slack client from the SLACK_WEBHOOK_URL secret
+    pub fn new() -> Result<Self> {
+        let webhook_url = secrets::require("SLACK_WEBHOOK_URL")?;
+
+        Ok(Self {
+            client: tls::client(),
+            webhook_url,
+        })
+    }
+
+    /// Posts the changelog as Block Kit messages, split across messages if needed
+    pub async fn send(&self, delivery: &Delivery<'_>) -> Result<()> {
+        for message in messages(delivery) {
+            let response = self
+                .client
+                .post(&self.webhook_url)
+                .json(&message)
+                .send()
+                .await
+                .context("Failed to send message to Slack")?;
+
+            if !response.status().is_success() {
+                return Err(GazetteError::from_response("Slack", response).await.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The changelog as a header, a repo/period context and a section per category,
+/// spread over as many messages as Slack's block limit requires
+fn messages(delivery: &Delivery<'_>) -> Vec<WebhookMessage> {
+    let content = markdown::strip_frontmatter(delivery.content);
+    let (title, body) = match content.trim_start().split_once('\n') {
+        Some((first, rest)) if first.starts_with("# ") => (first[2..].trim().to_string(), rest),
+        _ => (
+            format!("Changelog for {}", delivery.repo.full_name()),
+            content,
+        ),
+    };
+    let title: String = title.chars().take(HEADER_TEXT_LIMIT).collect();
+    let about = format!("*{}* · {}", delivery.repo.full_name(), delivery.period);
+
+    let body_blocks = body_blocks(body);
+    let per_message = MAX_BLOCKS_PER_MESSAGE - 2;
+    let parts: Vec<&[Block]> = if body_blocks.is_empty() {
+        vec![&[]]
+    } else {
+        body_blocks.chunks(per_message).collect()
+    };
+    let total = parts.len();
+
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(index, part)| {
+            let mut blocks = if index == 0 {
+                vec![
+                    Block::Header {
+                        text: Text::plain(title.clone()),
+                    },
+                    Block::Context {
+                        elements: vec![Text::mrkdwn(about.clone())],
+                    },
+                ]
+            } else {
+                vec![Block::Context {
+                    elements: vec![Text::mrkdwn(format!(
+                        "{} · part {} of {}",
+                        about,
+                        index + 1,
+                        total
+                    ))],
+                }]
+            };
+            blocks.extend(part.iter().cloned());
+
+            WebhookMessage {
+                text: if index == 0 {
+                    title.clone()
+                } else {
+                    format!("{} (continued)", title)
+                },
+                blocks,
+            }
+        })
+        .collect()
+}
+
+/// A section per `##` category, separated by dividers
+fn body_blocks(body: &str) -> Vec<Block> {
+    let mut groups: Vec<Vec<String>> = vec![Vec::new()];
+    let mut in_fence = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && (line.starts_with("## ") || line.trim() == "---") {
+            groups.push(Vec::new());
+            if line.trim() == "---" {
+                continue;
+            }
+        }
+        groups.last_mut().unwrap().push(line.to_string());
+    }
+
+    let mut blocks = Vec::new();
+    for group in groups {
+        let text = to_mrkdwn(&group.join("\n"));
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if !blocks.is_empty() {
+            blocks.push(Block::Divider);
+        }
+        blocks.extend(
+            split_message(text, SECTION_TEXT_LIMIT)
+                .into_iter()
+                .map(|chunk| Block::Section {
+                    text: Text::mrkdwn(chunk),
+                }),
+        );
+    }
+    blocks
+}
+
+/// Converts markdown to Slack's mrkdwn, leaving fenced code blocks alone
+fn to_mrkdwn(markdown: &str) -> String {
+    let heading = Regex::new(r"^#{1,6}\s+(.+)$").unwrap();
+    let bullet = Regex::new(r"^(\s*)[-*+]\s+").unwrap();
+    let bold = Regex::new(r"\*\*(.+?)\*\*|__(.+?)__").unwrap();
+    let strike = Regex::new(r"~~(.+?)~~").unwrap();
+    let link = Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").unwrap();
+    let autolink = Regex::new(r"&lt;(https?://[^\s&]+)&gt;").unwrap();
+
+    let mut in_fence = false;
+    markdown
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                return line.trim().to_string();
+            }
+            if in_fence {
+                return line.to_string();
+            }
+
+            let line = line
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            let line = heading.replace(&line, "*$1*");
+            let line = bullet.replace(&line, "$1• ");
+            let line = bold.replace_all(&line, "*$1$2*");
+            let line = strike.replace_all(&line, "~$1~");
+            let line = link.replace_all(&line, "<$2|$1>");
+            autolink.replace_all(&line, "<$1>").into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Repo;
+    use std::path::Path;
+
+    fn delivery<'a>(repo: &'a Repo, content: &'a str) -> Delivery<'a> {
+        Delivery {
+            repo,
+            content,
+            period: "last 7 days".to_string(),
+            path: Path::new("changelog.md"),
+            repo_path: "changelog.md".to_string(),
+            jira_keys: Vec::new(),
+            version: None,
+        }
+    }
+
+    #[test]
+    fn test_changelog_becomes_blocks_split_across_messages() {
+        let repo = Repo::new("acme", "backend");
+        let content = "# Weekly changes\n\n## Features\n- **OAuth2** login, see [docs](https://acme.dev/docs)\n- Faster <search> & ~~old~~ filters\n\n## Fixes\n- Crash on start\n";
+
+        let sent = messages(&delivery(&repo, content));
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].text, "Weekly changes");
+        assert_eq!(
+            sent[0].blocks[1],
+            Block::Context {
+                elements: vec![Text::mrkdwn("*acme/backend* · last 7 days".to_string())]
+            }
+        );
+        assert_eq!(
+            sent[0].blocks[2],
+            Block::Section {
+                text: Text::mrkdwn("*Features*\n• *OAuth2* login, see <https://acme.dev/docs|docs>\n• Faster &lt;search&gt; &amp; ~old~ filters".to_string())
+            }
+        );
+        assert_eq!(sent[0].blocks[3], Block::Divider);
+        assert_eq!(sent[0].blocks.len(), 5);
+
+        let long: String = (0..60)
+            .map(|i| format!("## Area {}\n- Change\n", i))
+            .collect();
+        let sent = messages(&delivery(&repo, &long));
+        assert_eq!(sent.len(), 3);
+        assert!(
+            sent.iter()
+                .all(|m| m.blocks.len() <= MAX_BLOCKS_PER_MESSAGE)
+        );
+        assert_eq!(sent[0].text, "Changelog for acme/backend");
+        assert_eq!(sent[2].text, "Changelog for acme/backend (continued)");
+    }
+}
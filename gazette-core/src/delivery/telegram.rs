@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::split_message;
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// Telegram's maximum message length
+const TELEGRAM_MESSAGE_LIMIT: usize = 4096;
+
+/// Telegram bot client
+pub struct TelegramClient {
+    client: reqwest::Client,
+    bot_token: String,
+    chat_id: String,
+}
+
+#[derive(Serialize)]
+struct SendMessageRequest<'a> {
+    chat_id: &'a str,
+    text: String,
+    disable_web_page_preview: bool,
+}
+
+#[derive(Deserialize)]
+struct TelegramResponse {
+    ok: bool,
+    description: Option<String>,
+}
+
+impl TelegramClient {
+    /// Creates a new Telegram client from TELEGRAM_BOT_TOKEN and TELEGRAM_CHAT_ID
+    pub fn new() -> Result<Self> {
+        let bot_token = secrets::require("TELEGRAM_BOT_TOKEN")?;
+        let chat_id = secrets::require("TELEGRAM_CHAT_ID")?;
+
+        Ok(Self {
+            client: tls::client(),
+            bot_token,
+            chat_id,
+        })
+    }
+
+    /// Sends the changelog as plain text, split across messages if needed
+    pub async fn send(&self, content: &str) -> Result<()> {
+        let url = format!("{}/bot{}/sendMessage", TELEGRAM_API_URL, self.bot_token);
+
+        for chunk in split_message(content, TELEGRAM_MESSAGE_LIMIT) {
+            let request = SendMessageRequest {
+                chat_id: &self.chat_id,
+                text: chunk,
+                disable_web_page_preview: true,
+            };
+
+            let response = self
+                .client
+                .post(&url)
+                .json(&request)
+                .send()
+                .await
+                .context("Failed to send message to Telegram")?;
+
+            let status = response.status();
+            let telegram_response: TelegramResponse = response
+                .json()
+                .await
+                .context("Failed to parse Telegram response")?;
+
+            if !status.is_success() || !telegram_response.ok {
+                let description = telegram_response.description.unwrap_or_default();
+                return Err(
+                    GazetteError::from_status("Telegram", status, description, None).into(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use regex::Regex;
+
+use crate::ai::{ChangelogCategory, ChangelogEntry};
+use crate::changelog::PrContext;
+use crate::github::PullRequest;
+
+/// Name of the section dependency updates are collapsed into
+pub const SECTION_TITLE: &str = "Dependency updates";
+
+/// Logins of the bots opening dependency update PRs
+const UPDATE_BOTS: [&str; 4] = [
+    "dependabot[bot]",
+    "dependabot-preview[bot]",
+    "renovate[bot]",
+    "renovate-bot",
+];
+
+/// A package bump parsed from an update PR's title
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyUpdate {
+    pub package: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// True for PRs opened by Dependabot or Renovate, or labelled as dependency bumps with a
+/// title naming the package
+pub fn is_update(pr: &PullRequest) -> bool {
+    let by_bot = pr
+        .user
+        .as_ref()
+        .is_some_and(|user| UPDATE_BOTS.contains(&user.login.as_str()));
+    let labelled = pr
+        .labels
+        .iter()
+        .any(|label| label.name.eq_ignore_ascii_case("dependencies"));
+    by_bot || (labelled && parse_title(&pr.title).is_some())
+}
+
+/// Reads the package and versions from Dependabot titles ("Bump serde from 1.0.1 to 1.0.2")
+/// and Renovate ones ("Update dependency serde to v1.0.2"), with or without a
+/// conventional-commit prefix
+pub fn parse_title(title: &str) -> Option<DependencyUpdate> {
+    let prefix = r"(?i)^(?:\w+(?:\([^)]*\))?!?:\s*)?";
+    let bump = Regex::new(&format!(
+        r"{}bump (?P<package>\S+) from (?P<from>\S+) to (?P<to>\S+)",
+        prefix
+    ))
+    .unwrap();
+    let update = Regex::new(&format!(
+        r"{}update (?:(?:dependency|module|package|crate|rust crate|npm package|docker image|image) )?(?P<package>\S+)(?: (?:action|docker tag|digest))? to (?P<to>\S+)",
+        prefix
+    ))
+    .unwrap();
+
+    let captures = bump.captures(title).or_else(|| update.captures(title))?;
+    let version = |name| {
+        captures
+            .name(name)
+            .map(|m| m.as_str().trim_end_matches([',', '.']).to_string())
+    };
+    Some(DependencyUpdate {
+        package: captures["package"].to_string(),
+        from: version("from"),
+        to: version("to"),
+    })
+}
+
+/// The update PRs as one category, a line per package with repeat bumps folded into one
+/// from the first version to the last
+pub fn category(updates: &[PrContext]) -> Option<ChangelogCategory> {
+    let mut updates: Vec<&PrContext> = updates.iter().collect();
+    updates.sort_by_key(|ctx| ctx.pr.merged_at);
+
+    let mut bumps: Vec<(String, DependencyUpdate, Vec<u64>)> = Vec::new();
+    for ctx in updates {
+        // Grouped updates such as "Bump the npm group with 3 updates" keep their title
+        let update = parse_title(&ctx.pr.title).unwrap_or_else(|| DependencyUpdate {
+            package: ctx.pr.title.trim().to_string(),
+            from: None,
+            to: None,
+        });
+        let key = update.package.to_lowercase();
+        match bumps.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, bump, numbers)) => {
+                bump.from = bump.from.take().or(update.from);
+                bump.to = update.to.or(bump.to.take());
+                numbers.push(ctx.pr.number);
+            }
+            None => bumps.push((key, update, vec![ctx.pr.number])),
+        }
+    }
+    if bumps.is_empty() {
+        return None;
+    }
+
+    bumps.sort_by(|a, b| a.0.cmp(&b.0));
+    Some(ChangelogCategory {
+        name: SECTION_TITLE.to_string(),
+        entries: bumps
+            .into_iter()
+            .map(|(_, bump, pr_numbers)| ChangelogEntry {
+                summary: describe(&bump),
+                pr_numbers,
+                jira_keys: Vec::new(),
+            })
+            .collect(),
+    })
+}
+
+/// "serde 1.0.1 → 1.0.2", or as much of it as the title gave
+fn describe(update: &DependencyUpdate) -> String {
+    match (&update.from, &update.to) {
+        (Some(from), Some(to)) => format!("`{}` {} → {}", update.package, from, to),
+        (None, Some(to)) => format!("`{}` → {}", update.package, to),
+        _ => update.package.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    use crate::github::GitHubUser;
+
+    fn context(number: u64, title: &str) -> PrContext {
+        let mut ctx = PrContext::fixture(number, title);
+        ctx.pr.merged_at = Some(Utc::now() - Duration::hours(10 - number as i64));
+        ctx.pr.user = Some(GitHubUser {
+            login: "dependabot[bot]".to_string(),
+        });
+        ctx
+    }
+
+    #[test]
+    fn test_bumps_are_parsed_and_folded_per_package() {
+        assert_eq!(
+            parse_title("chore(deps): update rust crate tokio to v1.38.0"),
+            Some(DependencyUpdate {
+                package: "tokio".to_string(),
+                from: None,
+                to: Some("v1.38.0".to_string()),
+            })
+        );
+        assert!(parse_title("Add a bump allocator").is_none());
+
+        let updates = vec![
+            context(1, "Bump serde from 1.0.1 to 1.0.2"),
+            context(2, "build(deps): bump axum from 0.6.0 to 0.7.0 in /api"),
+            context(3, "Bump serde from 1.0.2 to 1.0.3"),
+            context(4, "Bump the npm group with 3 updates"),
+        ];
+        assert!(is_update(&updates[3].pr));
+
+        let category = category(&updates).unwrap();
+        let lines: Vec<(&str, &[u64])> = category
+            .entries
+            .iter()
+            .map(|e| (e.summary.as_str(), e.pr_numbers.as_slice()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("`axum` 0.6.0 → 0.7.0", &[2][..]),
+                ("Bump the npm group with 3 updates", &[4][..]),
+                ("`serde` 1.0.1 → 1.0.3", &[1, 3][..]),
+            ]
+        );
+    }
+}
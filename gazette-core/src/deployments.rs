@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::changelog::PrContext;
+use crate::clock;
+use crate::github::GitHubDeployment;
+
+/// Whether a merged PR has reached the tracked environment yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum Deployment {
+    Deployed {
+        environment: String,
+        at: DateTime<Utc>,
+    },
+    Pending {
+        environment: String,
+    },
+}
+
+/// The first of the deployments (oldest first) that shipped the merge; None when none did
+///
+/// `reaches(head)` tells whether the merge commit is part of a deployed commit's history.
+/// Deployments only ever move forward, so the newest one is checked first and the rest
+/// are only searched when it shipped the merge
+pub async fn first_shipping<'a, F, Fut>(
+    merged_at: DateTime<Utc>,
+    deployments: &'a [GitHubDeployment],
+    mut reaches: F,
+) -> anyhow::Result<Option<&'a GitHubDeployment>>
+where
+    F: FnMut(&'a str) -> Fut,
+    Fut: Future<Output = anyhow::Result<bool>>,
+{
+    let candidates: Vec<&GitHubDeployment> = deployments
+        .iter()
+        .filter(|d| d.created_at >= merged_at)
+        .collect();
+    let Some(latest) = candidates.last() else {
+        return Ok(None);
+    };
+    if !reaches(&latest.sha).await? {
+        return Ok(None);
+    }
+
+    for deployment in &candidates[..candidates.len() - 1] {
+        if reaches(&deployment.sha).await? {
+            return Ok(Some(deployment));
+        }
+    }
+    Ok(Some(latest))
+}
+
+/// The "Deployed:" line of a PR in the AI context
+pub fn describe(ctx: &PrContext) -> Option<String> {
+    match ctx.deployment.as_ref()? {
+        Deployment::Deployed { environment, at } => Some(format!(
+            "Deployed: to {} at {}",
+            environment,
+            clock::format(*at, "%Y-%m-%d %H:%M %Z")
+        )),
+        Deployment::Pending { environment } => Some(format!(
+            "Deployed: not yet, merged but not in {}",
+            environment
+        )),
+    }
+}
+
+/// True when every PR of an entry was merged but hasn't been deployed
+pub fn all_pending(pr_numbers: &[u64], contexts: &[PrContext]) -> bool {
+    let mut prs = contexts
+        .iter()
+        .filter(|ctx| pr_numbers.contains(&ctx.pr.number))
+        .peekable();
+    prs.peek().is_some()
+        && prs.all(|ctx| matches!(ctx.deployment, Some(Deployment::Pending { .. })))
+}
+
+/// Prompt note asking a freeform changelog to tell shipped changes from merged-only ones
+pub fn context_block(contexts: &[PrContext]) -> Option<String> {
+    if contexts.iter().all(|ctx| ctx.deployment.is_none()) {
+        return None;
+    }
+
+    Some(
+        "## Deployments\nEach PR says whether it has been deployed yet. Mark entries whose changes are all merged but not yet deployed with \"_(not yet deployed)_\" so readers know what has actually shipped.\n\n"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn deployment(id: u64, sha: &str, day: u32) -> GitHubDeployment {
+        GitHubDeployment {
+            id,
+            sha: sha.to_string(),
+            environment: "production".to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_first_shipping_deployment_is_found() {
+        let deployments = vec![
+            deployment(1, "old", 1),
+            deployment(2, "before", 3),
+            deployment(3, "with", 4),
+            deployment(4, "after", 6),
+        ];
+        let merged_at = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let shipped = ["with", "after"];
+
+        let found = first_shipping(merged_at, &deployments, |sha| async move {
+            Ok(shipped.contains(&sha))
+        })
+        .await
+        .unwrap();
+        assert_eq!(found.map(|d| d.id), Some(3));
+
+        let none = first_shipping(merged_at, &deployments, |_| async { Ok(false) })
+            .await
+            .unwrap();
+        assert!(none.is_none());
+    }
+}
@@ -0,0 +1,256 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::clock;
+use crate::config::Repo;
+use crate::markdown;
+
+/// A group's digest, as listed in the index of a per-group run
+pub struct GroupDigest {
+    pub group: String,
+    pub path: PathBuf,
+    pub repos: Vec<Repo>,
+}
+
+/// Combines per-repo changelogs into one document under a single title
+/// Each repo's headings are demoted one level so they nest below the digest title; an
+/// executive summary goes first, followed by links to each repo's section
+pub fn combine(title: &str, summary: Option<&str>, changelogs: &[(Repo, String)]) -> String {
+    let mut output = format!("# {}\n", title);
+
+    if let Some(summary) = summary {
+        output.push_str(&format!("\n## Summary\n\n{}\n\n", summary.trim()));
+        for ((repo, _), anchor) in changelogs.iter().zip(section_anchors(changelogs)) {
+            match anchor {
+                Some(anchor) => output.push_str(&format!("- [{}](#{})\n", repo, anchor)),
+                None => output.push_str(&format!("- {}\n", repo)),
+            }
+        }
+    }
+
+    for (_, content) in changelogs {
+        output.push('\n');
+        output.push_str(demote_headings(markdown::strip_frontmatter(content)).trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Saves a digest as digest_<name>_<date>.md in the working directory
+pub fn save(name: &str, content: &str) -> Result<PathBuf> {
+    let date = clock::today().format("%Y-%m-%d");
+    let path = PathBuf::from(format!("digest_{}_{}.md", name, date));
+
+    fs::write(&path, content).context("Failed to write digest file")?;
+
+    Ok(path)
+}
+
+/// Splits a run's changelogs by repo group, in group order; repos in several groups are in each,
+/// and repos without a group in none
+pub fn by_group(changelogs: &[(Repo, String)]) -> Vec<(String, Vec<(Repo, String)>)> {
+    let mut groups: Vec<String> = changelogs
+        .iter()
+        .flat_map(|(repo, _)| repo.groups.iter().cloned())
+        .collect();
+    groups.sort();
+    groups.dedup();
+
+    groups
+        .into_iter()
+        .map(|group| {
+            let members = changelogs
+                .iter()
+                .filter(|(repo, _)| repo.groups.contains(&group))
+                .cloned()
+                .collect();
+            (group, members)
+        })
+        .collect()
+}
+
+/// Links each group's digest, listing the repos it covers
+pub fn index(title: &str, digests: &[GroupDigest]) -> String {
+    let mut output = format!(
+        "# {}
+
+",
+        title
+    );
+
+    for digest in digests {
+        let file = digest
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| digest.path.display().to_string());
+        let repos: Vec<String> = digest.repos.iter().map(Repo::full_name).collect();
+        output.push_str(&format!(
+            "- [{}]({}) — {}\n",
+            digest.group,
+            file,
+            repos.join(", ")
+        ));
+    }
+
+    output
+}
+
+/// Saves the index of a per-group run as digest_index_<date>.md next to the digests
+pub fn save_index(content: &str) -> Result<PathBuf> {
+    let date = clock::today().format("%Y-%m-%d");
+    let path = PathBuf::from(format!("digest_index_{}.md", date));
+
+    fs::write(&path, content).context("Failed to write digest index")?;
+
+    Ok(path)
+}
+
+/// Text given to the AI for the executive summary: each changelog with its section's anchor
+pub fn summary_context(changelogs: &[(Repo, String)]) -> String {
+    changelogs
+        .iter()
+        .zip(section_anchors(changelogs))
+        .map(|((repo, content), anchor)| {
+            let anchor = anchor
+                .map(|anchor| format!(" (anchor: #{})", anchor))
+                .unwrap_or_default();
+            format!("## {}{}\n{}\n\n---\n\n", repo, anchor, content.trim())
+        })
+        .collect()
+}
+
+/// GitHub-style anchors of each changelog's title once combined, de-duplicated like GitHub does
+fn section_anchors(changelogs: &[(Repo, String)]) -> Vec<Option<String>> {
+    let mut seen: Vec<String> = vec![slug("Summary")];
+
+    changelogs
+        .iter()
+        .map(|(_, content)| {
+            let heading = content.lines().find_map(|line| line.strip_prefix("# "))?;
+            let base = slug(heading);
+            let mut anchor = base.clone();
+            let mut n = 1;
+            while seen.contains(&anchor) {
+                anchor = format!("{}-{}", base, n);
+                n += 1;
+            }
+            seen.push(anchor.clone());
+            Some(anchor)
+        })
+        .collect()
+}
+
+/// Lowercases a heading, drops punctuation and turns spaces into dashes
+fn slug(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+fn demote_headings(markdown: &str) -> String {
+    let mut in_code_block = false;
+
+    markdown
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            if !in_code_block && line.starts_with('#') && !line.starts_with("######") {
+                format!("#{}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_nests_repo_changelogs() {
+        let changelogs = vec![
+            (
+                Repo::new("acme", "backend"),
+                "# Changelog for acme/backend\n\n## Features\n- Add OAuth2\n".to_string(),
+            ),
+            (
+                Repo::new("acme", "web"),
+                "# Changelog for acme/web\n\n```sh\n# not a heading\n```\n".to_string(),
+            ),
+        ];
+
+        assert_eq!(
+            combine("payments digest", None, &changelogs),
+            "# payments digest\n\n## Changelog for acme/backend\n\n### Features\n- Add OAuth2\n\n## Changelog for acme/web\n\n```sh\n# not a heading\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_summary_links_to_each_repo_section() {
+        let changelogs = vec![
+            (
+                Repo::new("acme", "backend"),
+                "# Changelog for acme/backend - 2026-10-14\n- Add OAuth2\n".to_string(),
+            ),
+            (
+                Repo::new("acme", "web"),
+                "# Changelog for acme/backend - 2026-10-14\n- Dark mode\n".to_string(),
+            ),
+        ];
+
+        let digest = combine("Team digest", Some("We shipped OAuth2."), &changelogs);
+
+        assert!(digest.starts_with("# Team digest\n\n## Summary\n\nWe shipped OAuth2.\n\n"));
+        assert!(digest.contains("- [acme/backend](#changelog-for-acmebackend---2026-10-14)\n"));
+        assert!(digest.contains("- [acme/web](#changelog-for-acmebackend---2026-10-14-1)\n"));
+        assert!(
+            summary_context(&changelogs)
+                .contains("## acme/web (anchor: #changelog-for-acmebackend---2026-10-14-1)")
+        );
+    }
+
+    #[test]
+    fn test_changelogs_are_split_by_group_and_indexed() {
+        let tagged = |name: &str, groups: &[&str]| Repo {
+            groups: groups.iter().map(|g| g.to_string()).collect(),
+            ..Repo::new("acme", name)
+        };
+        let changelogs = vec![
+            (
+                tagged("api", &["payments", "platform"]),
+                "# api".to_string(),
+            ),
+            (tagged("web", &["frontend"]), "# web".to_string()),
+            (tagged("docs", &[]), "# docs".to_string()),
+        ];
+
+        let groups = by_group(&changelogs);
+        let names: Vec<(&str, usize)> = groups
+            .iter()
+            .map(|(group, members)| (group.as_str(), members.len()))
+            .collect();
+        assert_eq!(names, [("frontend", 1), ("payments", 1), ("platform", 1)]);
+
+        let digests = vec![GroupDigest {
+            group: "payments".to_string(),
+            path: PathBuf::from("out/digest_payments_2026-10-14.md"),
+            repos: vec![Repo::new("acme", "api"), Repo::new("acme", "billing")],
+        }];
+        assert_eq!(
+            index("Team digests", &digests),
+            "# Team digests\n\n- [payments](digest_payments_2026-10-14.md) — acme/api, acme/billing\n"
+        );
+    }
+}
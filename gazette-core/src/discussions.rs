@@ -0,0 +1,149 @@
+use crate::ai::{ChangelogCategory, ChangelogEntry, StructuredChangelog};
+use crate::config::TimePeriod;
+use crate::github::GitHubDiscussion;
+
+/// Name of the section discussions are listed in
+pub const SECTION_TITLE: &str = "Discussions";
+
+/// The discussions started or answered within the period, in the given categories (any
+/// category when none are given), oldest first
+pub fn in_period(
+    period: &TimePeriod,
+    discussions: Vec<GitHubDiscussion>,
+    categories: &[String],
+) -> Vec<GitHubDiscussion> {
+    let (start, end) = period.bounds();
+    let mut found: Vec<GitHubDiscussion> = discussions
+        .into_iter()
+        .filter(|discussion| {
+            categories.is_empty()
+                || categories
+                    .iter()
+                    .any(|category| category.eq_ignore_ascii_case(&discussion.category))
+        })
+        .filter(|discussion| {
+            [Some(discussion.created_at), discussion.answer_chosen_at]
+                .into_iter()
+                .flatten()
+                .any(|at| at > start && at <= end)
+        })
+        .collect();
+    found.sort_by_key(|discussion| discussion.created_at);
+    found
+}
+
+/// The discussion as one markdown line, e.g. "[RFC: Drop Node 16](…) (Ideas, answered)"
+fn describe(discussion: &GitHubDiscussion) -> String {
+    let state = if discussion.answer.is_some() {
+        format!("{}, answered", discussion.category)
+    } else {
+        discussion.category.clone()
+    };
+    format!(
+        "[{}]({}) ({})",
+        discussion.title.trim(),
+        discussion.url,
+        state
+    )
+}
+
+/// Adds a section listing the discussions after the changelog's categories
+pub fn apply(
+    mut changelog: StructuredChangelog,
+    discussions: &[GitHubDiscussion],
+) -> StructuredChangelog {
+    if discussions.is_empty() {
+        return changelog;
+    }
+
+    changelog.categories.push(ChangelogCategory {
+        name: SECTION_TITLE.to_string(),
+        entries: discussions
+            .iter()
+            .map(|discussion| ChangelogEntry {
+                summary: describe(discussion),
+                pr_numbers: Vec::new(),
+                jira_keys: Vec::new(),
+            })
+            .collect(),
+    });
+    changelog
+}
+
+/// Prompt context with each discussion and its chosen answer
+///
+/// A freeform changelog is asked for a section on their outcomes; structured changelogs
+/// list them in their own section, so entries only refer to them where a PR implements one
+pub fn context_block(discussions: &[GitHubDiscussion], freeform: bool) -> Option<String> {
+    if discussions.is_empty() {
+        return None;
+    }
+
+    let mut block = String::from("## Discussions\n");
+    if freeform {
+        block.push_str("These discussions were started or answered in the period. Add a \"Discussions\" section with a line per discussion linking it and stating the decision or RFC outcome, or that it is still open.\n\n");
+    } else {
+        block.push_str("These discussions were started or answered in the period and are listed separately. Don't add entries for them; mention one only where a PR implements its outcome.\n\n");
+    }
+    for discussion in discussions {
+        block.push_str(&format!(
+            "### {} ({})\nURL: {}\n",
+            discussion.title.trim(),
+            discussion.category,
+            discussion.url
+        ));
+        if !discussion.body.trim().is_empty() {
+            block.push_str(&format!("Description:\n{}\n", discussion.body.trim()));
+        }
+        if let Some(answer) = &discussion.answer {
+            block.push_str(&format!("Answer:\n{}\n", answer.trim()));
+        }
+        block.push('\n');
+    }
+    Some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn discussion(
+        number: u64,
+        category: &str,
+        days_ago: i64,
+        answered_days_ago: Option<i64>,
+    ) -> GitHubDiscussion {
+        GitHubDiscussion {
+            number,
+            title: format!("RFC {}", number),
+            url: format!("https://github.com/acme/backend/discussions/{}", number),
+            body: String::new(),
+            category: category.to_string(),
+            created_at: Utc::now() - Duration::days(days_ago),
+            answer: answered_days_ago.map(|_| "Accepted".to_string()),
+            answer_chosen_at: answered_days_ago.map(|days| Utc::now() - Duration::days(days)),
+        }
+    }
+
+    #[test]
+    fn test_discussions_started_or_answered_in_the_period_are_listed() {
+        let discussions = vec![
+            discussion(1, "Ideas", 40, Some(2)),
+            discussion(2, "Announcements", 3, None),
+            discussion(3, "Ideas", 40, None),
+            discussion(4, "Q&A", 1, None),
+        ];
+        let categories = vec!["ideas".to_string(), "Announcements".to_string()];
+
+        let found = in_period(&TimePeriod::Last7Days, discussions, &categories);
+        let numbers: Vec<u64> = found.iter().map(|d| d.number).collect();
+        assert_eq!(numbers, [1, 2]);
+
+        let changelog = apply(StructuredChangelog::default(), &found);
+        assert_eq!(
+            changelog.categories[0].entries[0].summary,
+            "[RFC 1](https://github.com/acme/backend/discussions/1) (Ideas, answered)"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Response, StatusCode};
+use thiserror::Error;
+
+use crate::config::TimePeriod;
+
+/// Failures callers react to differently: retrying, asking for credentials or skipping a repo
+/// Local I/O and subprocess failures stay plain `anyhow` errors
+#[derive(Debug, Error)]
+pub enum GazetteError {
+    /// A credential isn't set in the session, the environment or the secret store
+    #[error("{0} not configured")]
+    MissingCredential(String),
+    /// The service rejected the configured credentials
+    #[error("{service} API error ({status}): {body}")]
+    Auth {
+        service: String,
+        status: StatusCode,
+        body: String,
+    },
+    /// The service is throttling requests, with how long it asked us to wait when it said
+    #[error("{service} API error ({status}): {body}")]
+    RateLimited {
+        service: String,
+        status: StatusCode,
+        body: String,
+        retry_after: Option<Duration>,
+    },
+    /// The repo, file or issue doesn't exist or isn't visible with the configured credentials
+    #[error("{service} API error ({status}): {body}")]
+    NotFound {
+        service: String,
+        status: StatusCode,
+        body: String,
+    },
+    /// Any other unsuccessful response
+    #[error("{service} API error ({status}): {body}")]
+    Api {
+        service: String,
+        status: StatusCode,
+        body: String,
+    },
+    /// The AI provider failed or returned nothing usable
+    #[error(
+        "{provider} API error{}: {message}",
+        .status.map(|status| format!(" ({})", status)).unwrap_or_default()
+    )]
+    AiProvider {
+        provider: String,
+        status: Option<StatusCode>,
+        message: String,
+    },
+    /// No PRs were merged in the period, so callers can tell a quiet repo from a failure
+    #[error("No PRs merged in the {}", .period.description())]
+    EmptyPeriod { period: TimePeriod },
+    /// The configuration or the options given can't be used
+    #[error("{0}")]
+    Config(String),
+}
+
+impl GazetteError {
+    /// Finds a `GazetteError` anywhere in an error's chain, e.g. under added context
+    pub fn find(error: &anyhow::Error) -> Option<&GazetteError> {
+        error.chain().find_map(|cause| cause.downcast_ref())
+    }
+
+    /// Classifies an unsuccessful response from `service`, reading its body
+    pub async fn from_response(service: &str, response: Response) -> Self {
+        let status = response.status();
+        let retry_after = retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        Self::from_status(service, status, body, retry_after)
+    }
+
+    /// Like [`GazetteError::from_response`], reporting failures other than auth and
+    /// rate limits as AI provider errors
+    pub async fn from_ai_response(provider: &str, response: Response) -> Self {
+        match Self::from_response(provider, response).await {
+            Self::NotFound {
+                service,
+                status,
+                body,
+            }
+            | Self::Api {
+                service,
+                status,
+                body,
+            } => Self::AiProvider {
+                provider: service,
+                status: Some(status),
+                message: body,
+            },
+            other => other,
+        }
+    }
+
+    pub fn from_status(
+        service: &str,
+        status: StatusCode,
+        body: String,
+        retry_after: Option<Duration>,
+    ) -> Self {
+        let service = service.to_string();
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                service,
+                status,
+                body,
+                retry_after,
+            },
+            // GitHub answers an exhausted rate limit with 403 and reset headers
+            StatusCode::FORBIDDEN if retry_after.is_some() => Self::RateLimited {
+                service,
+                status,
+                body,
+                retry_after,
+            },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth {
+                service,
+                status,
+                body,
+            },
+            StatusCode::NOT_FOUND => Self::NotFound {
+                service,
+                status,
+                body,
+            },
+            _ => Self::Api {
+                service,
+                status,
+                body,
+            },
+        }
+    }
+
+    /// The same request may succeed later: throttling, server errors and overloaded AI providers
+    pub fn is_retryable(&self) -> bool {
+        let transient = |status: &StatusCode| status.is_server_error() || status.as_u16() == 529;
+        match self {
+            Self::RateLimited { .. } => true,
+            Self::Api { status, .. } => transient(status),
+            Self::AiProvider { status, .. } => status.as_ref().is_some_and(transient),
+            _ => false,
+        }
+    }
+
+    /// Fixing the error needs a new or updated credential
+    pub fn needs_credentials(&self) -> bool {
+        matches!(self, Self::MissingCredential(_) | Self::Auth { .. })
+    }
+
+    /// What the user can do about the error, when there is something beyond the message
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::MissingCredential(key) => Some(format!(
+                "set {} in the environment, .env or gazette's credentials menu",
+                key
+            )),
+            Self::Auth { service, .. } => Some(format!(
+                "check that the {} credentials are valid and have access",
+                service
+            )),
+            Self::RateLimited {
+                retry_after: Some(wait),
+                ..
+            } => Some(format!("rate limited; retry in {}s", wait.as_secs())),
+            Self::RateLimited { .. } => Some("rate limited; retry later".to_string()),
+            Self::NotFound { service, .. } => Some(format!(
+                "check the name and that the {} credentials can see it",
+                service
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Reads how long to wait from `Retry-After` or GitHub's rate limit reset headers
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    if let Some(seconds) = header(RETRY_AFTER.as_str()).and_then(|v| v.parse().ok()) {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    if header("x-ratelimit-remaining") == Some("0") {
+        let reset: i64 = header("x-ratelimit-reset")?.parse().ok()?;
+        let wait = (reset - Utc::now().timestamp()).max(0);
+        return Some(Duration::from_secs(wait as u64));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statuses_are_classified() {
+        let classify = |status, retry_after| {
+            GazetteError::from_status("GitHub", status, String::new(), retry_after)
+        };
+
+        assert!(classify(StatusCode::UNAUTHORIZED, None).needs_credentials());
+        assert!(classify(StatusCode::FORBIDDEN, None).needs_credentials());
+        let limited = classify(StatusCode::FORBIDDEN, Some(Duration::from_secs(30)));
+        assert!(matches!(limited, GazetteError::RateLimited { .. }));
+        assert!(limited.is_retryable());
+        assert!(matches!(
+            classify(StatusCode::NOT_FOUND, None),
+            GazetteError::NotFound { .. }
+        ));
+        assert!(classify(StatusCode::BAD_GATEWAY, None).is_retryable());
+        assert!(!classify(StatusCode::UNPROCESSABLE_ENTITY, None).is_retryable());
+    }
+}
@@ -0,0 +1,229 @@
+use std::path::Path;
+
+use anyhow::Context;
+use serde_json::{Value, json};
+
+use crate::ai::StructuredChangelog;
+use crate::changelog::PrContext;
+use crate::config::Repo;
+use crate::delivery::DeliveryOutcome;
+use crate::jira::{self, JiraClient};
+use crate::markdown;
+use crate::store::Store;
+
+/// The changelog entries a Jira issue is behind, posted back to it as a comment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Excerpt {
+    pub key: String,
+    /// The entries as plain text, markdown links reduced to their labels
+    pub entries: Vec<String>,
+    /// Numbers and URLs of the PRs linked to the issue
+    pub prs: Vec<(u64, String)>,
+}
+
+/// One excerpt per Jira issue the PRs link to and the changelog has entries for
+/// Structured changelogs are matched by their entries' references, freeform ones by the bullet
+/// lines naming the issue or one of its PRs
+pub fn excerpts(
+    content: &str,
+    structured: Option<&StructuredChangelog>,
+    contexts: &[PrContext],
+) -> Vec<Excerpt> {
+    jira::linked_keys(contexts)
+        .into_iter()
+        .filter_map(|key| {
+            let prs: Vec<(u64, String)> = contexts
+                .iter()
+                .filter(|ctx| ctx.jira_issues.iter().any(|issue| issue.key == key))
+                .map(|ctx| (ctx.pr.number, ctx.pr.html_url.clone()))
+                .collect();
+            let entries: Vec<String> = match structured {
+                Some(structured) => structured
+                    .categories
+                    .iter()
+                    .flat_map(|category| &category.entries)
+                    .filter(|entry| {
+                        entry.jira_keys.contains(&key)
+                            || prs.iter().any(|(n, _)| entry.pr_numbers.contains(n))
+                    })
+                    .map(|entry| entry.summary.trim().to_string())
+                    .collect(),
+                None => markdown::list_items(content)
+                    .filter(|line| {
+                        line.contains(&key) || prs.iter().any(|(n, _)| mentions(line, *n))
+                    })
+                    .map(markdown::without_links)
+                    .collect(),
+            };
+            (!entries.is_empty()).then_some(Excerpt { key, entries, prs })
+        })
+        .collect()
+}
+
+impl Excerpt {
+    /// The comment in Atlassian Document Format: the entries, then links to the PRs and, when
+    /// it was delivered somewhere linkable, the full changelog
+    pub fn comment(&self, repo: &Repo, period: &str, changelog_url: Option<&str>) -> Value {
+        let intro = format!(
+            "Included in the changelog of {} for {}:",
+            repo.full_name(),
+            period
+        );
+        let items: Vec<Value> = self
+            .entries
+            .iter()
+            .map(|entry| json!({ "type": "listItem", "content": [paragraph(vec![text(entry)])] }))
+            .collect();
+
+        let mut links = Vec::new();
+        for (number, url) in &self.prs {
+            if !links.is_empty() {
+                links.push(text(", "));
+            }
+            links.push(link(&format!("#{}", number), url));
+        }
+        if let Some(url) = changelog_url {
+            if !links.is_empty() {
+                links.push(text(" · "));
+            }
+            links.push(link("Full changelog", url));
+        }
+
+        let mut content = vec![
+            paragraph(vec![text(&intro)]),
+            json!({ "type": "bulletList", "content": items }),
+        ];
+        if !links.is_empty() {
+            content.push(paragraph(links));
+        }
+        json!({ "type": "doc", "version": 1, "content": content })
+    }
+}
+
+/// Comments each excerpt on its issue, with one outcome per comment like a delivery's
+/// `changelog_url` is the first web link a delivery returned, if any. Issues already commented
+/// for the changelog saved at `path`, e.g. by a regeneration of the same day, are skipped
+pub async fn post_comments(
+    repo: &Repo,
+    period: &str,
+    path: &Path,
+    excerpts: &[Excerpt],
+    changelog_url: Option<&str>,
+) -> Vec<DeliveryOutcome> {
+    let clients = JiraClient::new().and_then(|jira| Ok((jira, Store::open()?)));
+    let (jira, store) = match clients {
+        Ok(clients) => clients,
+        Err(e) => {
+            return vec![DeliveryOutcome {
+                sink: "Jira comments".to_string(),
+                result: Err(e),
+            }];
+        }
+    };
+
+    let mut outcomes = Vec::new();
+    for excerpt in excerpts {
+        let sink = format!("Jira comment on {}", excerpt.key);
+        match store.jira_commented(&excerpt.key, path) {
+            Ok(true) => continue,
+            Ok(false) => {}
+            Err(e) => {
+                outcomes.push(DeliveryOutcome {
+                    sink,
+                    result: Err(e),
+                });
+                continue;
+            }
+        }
+
+        let body = excerpt.comment(repo, period, changelog_url);
+        // A comment that can't be recorded would be posted again by the next run
+        let result = match jira.add_comment(&excerpt.key, &body).await {
+            Ok(url) => store
+                .record_jira_comment(&excerpt.key, path)
+                .map(|_| Some(url))
+                .with_context(|| {
+                    format!(
+                        "Commented on {}, but failed to record it; a re-run comments again",
+                        excerpt.key
+                    )
+                }),
+            Err(e) => Err(e),
+        };
+        outcomes.push(DeliveryOutcome { sink, result });
+    }
+    outcomes
+}
+
+/// Whether `line` references PR `number` as "#<number>", not as the start of a longer one
+fn mentions(line: &str, number: u64) -> bool {
+    let reference = format!("#{}", number);
+    line.match_indices(&reference)
+        .any(|(at, _)| !line[at + reference.len()..].starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn paragraph(content: Vec<Value>) -> Value {
+    json!({ "type": "paragraph", "content": content })
+}
+
+fn text(text: &str) -> Value {
+    json!({ "type": "text", "text": text })
+}
+
+fn link(label: &str, href: &str) -> Value {
+    json!({ "type": "text", "text": label, "marks": [{ "type": "link", "attrs": { "href": href } }] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::JiraIssue;
+
+    fn context(number: u64, keys: &[&str]) -> PrContext {
+        let mut ctx = PrContext::fixture(number, &format!("PR {}", number));
+        ctx.jira_issues = keys
+            .iter()
+            .map(|key| {
+                serde_json::from_value::<JiraIssue>(json!({
+                    "key": key,
+                    "fields": { "summary": "Issue" },
+                }))
+                .unwrap()
+            })
+            .collect();
+        ctx
+    }
+
+    #[test]
+    fn test_issues_get_the_entries_of_their_prs() {
+        let contexts = [context(12, &["AUTH-7"]), context(120, &[])];
+        let content = "# Changelog\n\n## Features\n\n- SSO logins ([#12](https://github.com/acme/backend/pull/12))\n- Exports (#120)\n";
+
+        let freeform = excerpts(content, None, &contexts);
+        assert_eq!(
+            freeform,
+            [Excerpt {
+                key: "AUTH-7".to_string(),
+                entries: vec!["SSO logins (#12)".to_string()],
+                prs: vec![(12, "https://github.com/acme/backend/pull/12".to_string())],
+            }]
+        );
+
+        let structured = StructuredChangelog::parse(
+            r#"{"categories":[{"name":"Features","entries":[{"summary":"Add SSO logins","pr_numbers":[12],"jira_keys":[]},{"summary":"Exports","pr_numbers":[120]}]}]}"#,
+        )
+        .unwrap();
+        let from_structured = excerpts(content, Some(&structured), &contexts);
+        assert_eq!(from_structured[0].entries, ["Add SSO logins"]);
+
+        let comment = from_structured[0].comment(
+            &Repo::new("acme", "web"),
+            "last 7 days",
+            Some("https://gist.github.com/1"),
+        );
+        assert_eq!(
+            comment["content"][2]["content"][2]["marks"][0]["attrs"]["href"],
+            "https://gist.github.com/1"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use pulldown_cmark::{Parser, html};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Repo, TimePeriod};
+
+const FEED_DIR: &str = "feeds";
+const FEED_ENTRIES_FILE: &str = "feed_entries.json";
+const COMBINED_FEED_FILE: &str = "all.atom";
+
+/// Maximum number of entries kept per feed
+const MAX_FEED_ENTRIES: usize = 50;
+
+/// A generated changelog published to the Atom feeds
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FeedEntry {
+    pub id: String,
+    pub repo: String,
+    pub title: String,
+    pub updated: DateTime<Utc>,
+    pub markdown: String,
+}
+
+/// Entries backing the Atom feeds, persisted to feeds/feed_entries.json
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FeedStore {
+    #[serde(default)]
+    pub entries: Vec<FeedEntry>,
+}
+
+impl FeedStore {
+    pub fn load() -> Result<Self> {
+        let path = Path::new(FEED_DIR).join(FEED_ENTRIES_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).context("Failed to read feed entries")?;
+        let store = serde_json::from_str(&content).context("Failed to parse feed entries")?;
+
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(FEED_DIR).context("Failed to create feeds directory")?;
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize feed entries")?;
+        fs::write(Path::new(FEED_DIR).join(FEED_ENTRIES_FILE), content)
+            .context("Failed to write feed entries")?;
+        Ok(())
+    }
+
+    /// Entries for a single repo (or all repos), newest first, capped at MAX_FEED_ENTRIES
+    fn entries_for(&self, repo: Option<&str>) -> Vec<&FeedEntry> {
+        let mut entries: Vec<&FeedEntry> = self
+            .entries
+            .iter()
+            .filter(|e| repo.is_none_or(|r| e.repo == r))
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.updated));
+        entries.truncate(MAX_FEED_ENTRIES);
+        entries
+    }
+}
+
+/// Appends a changelog to the repo's feed and the combined feed, returning the repo feed path
+pub fn publish(repo: &Repo, period: TimePeriod, markdown: &str) -> Result<PathBuf> {
+    let mut store = FeedStore::load()?;
+    let now = Utc::now();
+    let repo_name = repo.full_name();
+
+    store.entries.push(FeedEntry {
+        id: format!(
+            "tag:gazette,{}:{}:{}",
+            now.format("%Y-%m-%d"),
+            repo_name,
+            now.timestamp()
+        ),
+        repo: repo_name.clone(),
+        title: format!("{} — {}", repo_name, period.description()),
+        updated: now,
+        markdown: markdown.to_string(),
+    });
+
+    // Drop entries that fell out of their repo feed, so the store doesn't grow forever
+    let kept: Vec<String> = store
+        .entries_for(Some(&repo_name))
+        .into_iter()
+        .map(|e| e.id.clone())
+        .collect();
+    store
+        .entries
+        .retain(|e| e.repo != repo_name || kept.contains(&e.id));
+    store.save()?;
+
+    let repo_feed = Path::new(FEED_DIR).join(feed_filename(repo));
+    write_feed(
+        &repo_feed,
+        &format!("Gazette: {}", repo_name),
+        &format!("urn:gazette:feed:{}", repo_name),
+        &store.entries_for(Some(&repo_name)),
+    )?;
+
+    write_feed(
+        &Path::new(FEED_DIR).join(COMBINED_FEED_FILE),
+        "Gazette: all repositories",
+        "urn:gazette:feed:all",
+        &store.entries_for(None),
+    )?;
+
+    Ok(repo_feed)
+}
+
+fn feed_filename(repo: &Repo) -> String {
+    format!("{}_{}.atom", repo.owner, repo.name)
+}
+
+fn write_feed(path: &Path, title: &str, id: &str, entries: &[&FeedEntry]) -> Result<()> {
+    let updated = entries.first().map(|e| e.updated).unwrap_or_else(Utc::now);
+
+    fs::write(path, render_atom(title, id, updated, entries)).context("Failed to write feed")?;
+    Ok(())
+}
+
+/// Renders an Atom 1.0 document, with the changelog markdown converted to HTML content
+pub fn render_atom(
+    title: &str,
+    id: &str,
+    updated: DateTime<Utc>,
+    entries: &[&FeedEntry],
+) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    output.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    output.push_str(&format!("  <title>{}</title>\n", escape_xml(title)));
+    output.push_str(&format!("  <id>{}</id>\n", escape_xml(id)));
+    output.push_str(&format!("  <updated>{}</updated>\n", updated.to_rfc3339()));
+    output.push_str("  <generator>gazette</generator>\n");
+
+    for entry in entries {
+        let mut content = String::new();
+        html::push_html(&mut content, Parser::new(&entry.markdown));
+
+        output.push_str("  <entry>\n");
+        output.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        output.push_str(&format!("    <id>{}</id>\n", escape_xml(&entry.id)));
+        output.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            entry.updated.to_rfc3339()
+        ));
+        output.push_str(&format!(
+            "    <author><name>{}</name></author>\n",
+            escape_xml(&entry.repo)
+        ));
+        output.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape_xml(&content)
+        ));
+        output.push_str("  </entry>\n");
+    }
+
+    output.push_str("</feed>\n");
+    output
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_atom_escapes_content() {
+        let entry = FeedEntry {
+            id: "tag:gazette,2026-02-10:acme/backend:1".to_string(),
+            repo: "acme/backend".to_string(),
+            title: "acme/backend — last 24 hours".to_string(),
+            updated: Utc::now(),
+            markdown: "## Features\n- Add <b>OAuth2</b> & more".to_string(),
+        };
+
+        let feed = render_atom("Gazette", "urn:gazette:feed:all", entry.updated, &[&entry]);
+
+        assert!(feed.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(feed.contains("&lt;h2&gt;Features&lt;/h2&gt;"));
+        assert!(feed.contains("&amp;amp; more"));
+        assert!(!feed.contains("<b>"));
+    }
+}
@@ -0,0 +1,1620 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::config::{Repo, TimePeriod};
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::source::PrSource;
+use crate::store::Store;
+use crate::tls;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITHUB_API_VERSION: &str = "2022-11-28";
+/// Upper bound on pages of closed PRs fetched for a single window
+const MAX_PR_PAGES: u32 = 10;
+/// Pages of 100 changed files read per PR, GitHub's own limit
+const MAX_FILE_PAGES: u32 = 30;
+/// Pages of 100 commits read per PR, GitHub lists at most 250
+const MAX_COMMIT_PAGES: u32 = 3;
+/// Most recent deployments whose status is checked for a single window
+const MAX_DEPLOYMENTS: usize = 30;
+/// PRs of a milestone fetched at the same time
+const MILESTONE_PR_CONCURRENCY: usize = 8;
+
+/// GitHub API client
+pub struct GitHubClient {
+    client: reqwest::Client,
+    /// REST API root; the GraphQL endpoint lives at `<base_url>/graphql`
+    base_url: String,
+}
+
+/// Represents a Pull Request from GitHub API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    /// When the PR was opened, the start of its lead time
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub user: Option<GitHubUser>,
+    pub html_url: String,
+    /// The commit the PR landed as on the base branch
+    #[serde(default)]
+    pub merge_commit_sha: Option<String>,
+    /// The author's relationship to the repo, e.g. `FIRST_TIME_CONTRIBUTOR`
+    #[serde(default)]
+    pub author_association: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<GitHubLabel>,
+}
+
+/// A PR as listed by the pulls endpoint, with the timestamp used to stop paging
+#[derive(Deserialize)]
+struct ListedPullRequest {
+    updated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pr: PullRequest,
+}
+
+/// Merged PRs listed back to some point, with their last update times
+#[derive(Default)]
+struct PrListing {
+    prs: Vec<(DateTime<Utc>, PullRequest)>,
+    /// False when the page cap was hit before reaching the point
+    complete: bool,
+    oldest_seen: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubUser {
+    pub login: String,
+}
+
+/// A file changed by a PR, as listed by the PR files endpoint
+#[derive(Deserialize)]
+struct PrFile {
+    filename: String,
+    /// Set for renames, whose old location counts as touched too
+    previous_filename: Option<String>,
+}
+
+/// A commit as listed by the PR commits endpoint
+#[derive(Deserialize)]
+struct ListedCommit {
+    commit: CommitDetail,
+}
+
+#[derive(Deserialize)]
+struct CommitDetail {
+    message: String,
+}
+
+/// Size of a PR as reported by the single-PR endpoint
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub struct PrStats {
+    pub additions: u64,
+    pub deletions: u64,
+    pub changed_files: u64,
+}
+
+/// A comment on a PR's conversation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrComment {
+    pub author: String,
+    pub body: String,
+    /// Reactions of any kind
+    pub reactions: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A review submitted on a PR
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubReview {
+    pub user: Option<GitHubUser>,
+    /// APPROVED, CHANGES_REQUESTED, COMMENTED or DISMISSED
+    pub state: String,
+}
+
+/// A comment as listed by the issue comments endpoint
+#[derive(Deserialize)]
+struct ListedComment {
+    #[serde(default)]
+    body: String,
+    user: Option<GitHubUser>,
+    created_at: DateTime<Utc>,
+    reactions: Option<CommentReactions>,
+}
+
+#[derive(Deserialize)]
+struct CommentReactions {
+    total_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubLabel {
+    pub name: String,
+}
+
+/// A GitHub milestone, e.g. a planned release
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubMilestone {
+    pub number: u64,
+    pub title: String,
+    /// open or closed
+    pub state: String,
+    pub open_issues: u64,
+    pub closed_issues: u64,
+}
+
+/// A GitHub release, e.g. the one a changelog is named after
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GitHubRelease {
+    #[serde(default)]
+    pub id: u64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    /// None for drafts
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub html_url: String,
+}
+
+/// A security advisory the repo's maintainers published
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityAdvisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub summary: String,
+    /// low, medium, high or critical
+    pub severity: Option<String>,
+    pub html_url: String,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// A Dependabot alert on a vulnerable dependency of the repo
+#[derive(Debug, Clone, Deserialize)]
+pub struct DependabotAlert {
+    pub html_url: String,
+    pub fixed_at: Option<DateTime<Utc>>,
+    pub security_advisory: AlertAdvisory,
+    pub dependency: AlertDependency,
+}
+
+/// The advisory a Dependabot alert was raised for
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertAdvisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub summary: String,
+    pub severity: Option<String>,
+}
+
+/// The vulnerable dependency of a Dependabot alert
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertDependency {
+    pub package: AlertPackage,
+}
+
+/// A package, e.g. "lodash" in the "npm" ecosystem
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertPackage {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+/// An issue or PR as listed by the issues endpoint
+#[derive(Deserialize)]
+struct ListedIssue {
+    number: u64,
+    /// Only set on PRs
+    pull_request: Option<IssuePullRequest>,
+}
+
+#[derive(Deserialize)]
+struct IssuePullRequest {
+    merged_at: Option<DateTime<Utc>>,
+}
+
+/// A deployment of a commit to an environment such as "production"
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubDeployment {
+    pub id: u64,
+    pub sha: String,
+    pub environment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A GitHub Actions (or other app) check run on a commit
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    /// queued, in_progress or completed
+    pub status: String,
+    /// success, failure, timed_out, cancelled... once completed
+    pub conclusion: Option<String>,
+}
+
+/// A commit status posted through the legacy statuses API, e.g. by an external CI
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitStatus {
+    pub context: String,
+    /// success, pending, failure or error
+    pub state: String,
+}
+
+/// The check runs and commit statuses reported for a commit
+#[derive(Debug, Clone, Default)]
+pub struct CommitChecks {
+    pub check_runs: Vec<CheckRun>,
+    pub statuses: Vec<CommitStatus>,
+}
+
+#[derive(Deserialize)]
+struct CheckRunsResponse {
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Deserialize)]
+struct CombinedStatus {
+    statuses: Vec<CommitStatus>,
+}
+
+#[derive(Deserialize)]
+struct DeploymentStatus {
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct Comparison {
+    status: String,
+}
+
+/// A GitHub rate-limit bucket: requests allowed per hour, left, and when it refills
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub reset: DateTime<Utc>,
+}
+
+/// The rate-limit buckets gazette draws from
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimits {
+    /// REST API requests, used for PRs, files and deliveries
+    pub core: RateLimit,
+    pub graphql: Option<RateLimit>,
+    pub search: Option<RateLimit>,
+}
+
+#[derive(Deserialize)]
+struct RateLimitResponse {
+    resources: RateLimits,
+}
+
+/// The account a token belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenOwner {
+    pub login: String,
+    /// Scopes of a classic token; None for fine-grained tokens and apps, which don't report them
+    pub scopes: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct RepositoryInfo {
+    default_branch: String,
+}
+
+#[derive(Deserialize)]
+struct GitRef {
+    object: GitObject,
+}
+
+#[derive(Deserialize)]
+struct GitObject {
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct FileInfo {
+    sha: String,
+}
+
+#[derive(Serialize)]
+struct CreateRefRequest<'a> {
+    #[serde(rename = "ref")]
+    git_ref: String,
+    sha: &'a str,
+}
+
+#[derive(Serialize)]
+struct PutFileRequest<'a> {
+    message: &'a str,
+    content: String,
+    branch: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CreatePullRequest<'a> {
+    title: &'a str,
+    head: &'a str,
+    base: &'a str,
+    body: &'a str,
+}
+
+#[derive(Serialize)]
+struct CreateGistRequest<'a> {
+    description: &'a str,
+    public: bool,
+    files: HashMap<&'a str, GistFile<'a>>,
+}
+
+#[derive(Serialize)]
+struct GistFile<'a> {
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct CreatedGist {
+    html_url: String,
+}
+
+#[derive(Serialize)]
+struct GraphQLRequest<'a> {
+    query: &'a str,
+    variables: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct GraphQLResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<GraphQLError>>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiscussionRepositoryData {
+    repository: Option<DiscussionRepository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiscussionRepository {
+    id: String,
+    discussion_categories: NodeList<DiscussionCategory>,
+}
+
+#[derive(Deserialize)]
+struct NodeList<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct DiscussionCategory {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateDiscussionData {
+    create_discussion: CreateDiscussionPayload,
+}
+
+#[derive(Deserialize)]
+struct CreateDiscussionPayload {
+    discussion: CreatedDiscussion,
+}
+
+#[derive(Deserialize)]
+struct CreatedDiscussion {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct ProjectStatusData {
+    repository: Option<ProjectStatusRepository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectStatusRepository {
+    pull_request: Option<ProjectStatusPullRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectStatusPullRequest {
+    closing_issues_references: NodeList<ProjectItemOwner>,
+    project_items: NodeList<ProjectItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectItemOwner {
+    project_items: NodeList<ProjectItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectItem {
+    field_value_by_name: Option<ProjectFieldValue>,
+}
+
+/// Only single-select values have a name; other field types come back empty
+#[derive(Deserialize)]
+struct ProjectFieldValue {
+    name: Option<String>,
+}
+
+/// A discussion of the repo, with its chosen answer if any
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubDiscussion {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub body: String,
+    pub category: String,
+    pub created_at: DateTime<Utc>,
+    pub answer: Option<String>,
+    pub answer_chosen_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct DiscussionsData {
+    repository: Option<DiscussionsRepository>,
+}
+
+#[derive(Deserialize)]
+struct DiscussionsRepository {
+    discussions: NodeList<ListedDiscussion>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListedDiscussion {
+    number: u64,
+    title: String,
+    url: String,
+    #[serde(default)]
+    body: String,
+    category: ListedDiscussionCategory,
+    created_at: DateTime<Utc>,
+    answer: Option<DiscussionAnswer>,
+    answer_chosen_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct ListedDiscussionCategory {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct DiscussionAnswer {
+    body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatedPullRequest {
+    pub number: u64,
+    pub html_url: String,
+}
+
+impl GitHubClient {
+    /// Creates a new GitHub client using the GITHUB_TOKEN secret
+    pub fn new() -> Result<Self> {
+        let token = secrets::require("GITHUB_TOKEN")?;
+
+        Self::with_token(&token)
+    }
+
+    /// Creates a new GitHub client with a specific token
+    pub fn with_token(token: &str) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github+json"),
+        );
+
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).context("Invalid token format")?,
+        );
+
+        headers.insert(
+            "X-GitHub-Api-Version",
+            HeaderValue::from_static(GITHUB_API_VERSION),
+        );
+
+        headers.insert(USER_AGENT, HeaderValue::from_static("gazette-rs-cli"));
+
+        let client = tls::client_builder()
+            .default_headers(headers)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url: GITHUB_API_URL.to_string(),
+        })
+    }
+
+    /// Sends requests to `base_url` instead of api.github.com, e.g. a mock server
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Fetches merged PRs within the specified time period
+    /// Calls `on_page` with the page number before each page is requested
+    pub async fn get_merged_prs(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        on_page: &(dyn Fn(u32) + Send + Sync),
+    ) -> Result<Vec<PullRequest>> {
+        let (start, end) = period.bounds();
+        let listing = self.list_merged_since(repo, start, on_page).await?;
+
+        Ok(listing
+            .prs
+            .into_iter()
+            .map(|(_, pr)| pr)
+            .filter(|pr| {
+                pr.merged_at
+                    .is_some_and(|merged| merged > start && merged <= end)
+            })
+            .collect())
+    }
+
+    /// Like [`GitHubClient::get_merged_prs`], reusing the PRs cached by earlier runs so only
+    /// PRs updated since the last sync are fetched
+    pub async fn get_merged_prs_cached(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        on_page: &(dyn Fn(u32) + Send + Sync),
+    ) -> Result<Vec<PullRequest>> {
+        let (start, end) = period.bounds();
+        let synced_at = Utc::now();
+
+        // The cache only answers windows it fully covers; anything older is fetched again
+        let sync = Store::open()?.pr_sync(repo)?;
+        let (since, covered_since) = match sync {
+            Some(sync) if sync.covered_since <= start => (sync.synced_at, sync.covered_since),
+            _ => (start, start),
+        };
+
+        let listing = self.list_merged_since(repo, since, on_page).await?;
+        // Hitting the page cap leaves a gap below the oldest PR seen
+        let covered_since = match listing.oldest_seen {
+            Some(oldest) if !listing.complete => oldest.max(covered_since),
+            _ => covered_since,
+        };
+
+        let mut store = Store::open()?;
+        store.cache_prs(repo, &listing.prs, synced_at, covered_since)?;
+        store.cached_merged_prs(repo, start, end)
+    }
+
+    /// Lists the merged PRs updated after `since`, most recently updated first
+    async fn list_merged_since(
+        &self,
+        repo: &Repo,
+        since: DateTime<Utc>,
+        on_page: &(dyn Fn(u32) + Send + Sync),
+    ) -> Result<PrListing> {
+        let url = format!("{}/repos/{}/{}/pulls", self.base_url, repo.owner, repo.name);
+        let mut listing = PrListing::default();
+
+        for page in 1..=MAX_PR_PAGES {
+            on_page(page);
+
+            let response = self
+                .client
+                .get(&url)
+                .query(&[
+                    ("state", "closed"),
+                    ("sort", "updated"),
+                    ("direction", "desc"),
+                    ("per_page", "100"),
+                    ("page", &page.to_string()),
+                ])
+                .send()
+                .await
+                .context("Failed to fetch PRs from GitHub")?;
+
+            if !response.status().is_success() {
+                return Err(GazetteError::from_response("GitHub", response).await.into());
+            }
+
+            let prs: Vec<ListedPullRequest> = response
+                .json()
+                .await
+                .context("Failed to parse GitHub PR response")?;
+
+            // PRs come most recently updated first, and a merge updates the PR, so once a
+            // page reaches PRs last updated before `since` nothing later can be newer
+            listing.complete =
+                prs.len() < 100 || prs.last().is_some_and(|last| last.updated_at <= since);
+            if let Some(last) = prs.last() {
+                listing.oldest_seen = Some(last.updated_at);
+            }
+
+            listing.prs.extend(
+                prs.into_iter()
+                    .filter(|listed| listed.pr.merged_at.is_some() && listed.updated_at > since)
+                    .map(|listed| (listed.updated_at, listed.pr)),
+            );
+
+            if listing.complete {
+                break;
+            }
+        }
+
+        Ok(listing)
+    }
+
+    /// Lists the repo's milestones, open and closed, most recently due first
+    pub async fn get_milestones(&self, repo: &Repo) -> Result<Vec<GitHubMilestone>> {
+        let url = format!(
+            "{}/repos/{}/{}/milestones",
+            self.base_url, repo.owner, repo.name
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[
+                ("state", "all"),
+                ("sort", "due_on"),
+                ("direction", "desc"),
+                ("per_page", "100"),
+            ])
+            .send()
+            .await
+            .context("Failed to fetch milestones from GitHub")?;
+        parse_response(response).await
+    }
+
+    /// Lists the repo's published releases, newest first
+    pub async fn get_releases(&self, repo: &Repo) -> Result<Vec<GitHubRelease>> {
+        let url = format!(
+            "{}/repos/{}/{}/releases",
+            self.base_url, repo.owner, repo.name
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch releases from GitHub")?;
+        let releases: Vec<GitHubRelease> = parse_response(response).await?;
+
+        Ok(releases
+            .into_iter()
+            .filter(|release| !release.draft && release.published_at.is_some())
+            .collect())
+    }
+
+    /// Finds the repo's newest draft release, e.g. the one release-drafter keeps up to date
+    /// Drafts are only listed to tokens with push access
+    pub async fn get_draft_release(&self, repo: &Repo) -> Result<Option<GitHubRelease>> {
+        let url = format!(
+            "{}/repos/{}/{}/releases",
+            self.base_url, repo.owner, repo.name
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch releases from GitHub")?;
+        let releases: Vec<GitHubRelease> = parse_response(response).await?;
+
+        Ok(releases.into_iter().find(|release| release.draft))
+    }
+
+    /// Replaces the body of release `id`, leaving its tag, name and draft state alone
+    /// Returns the URL of the release
+    pub async fn update_release_body(&self, repo: &Repo, id: u64, body: &str) -> Result<String> {
+        let url = format!(
+            "{}/repos/{}/{}/releases/{}",
+            self.base_url, repo.owner, repo.name, id
+        );
+
+        let response = self
+            .client
+            .patch(&url)
+            .json(&serde_json::json!({ "body": body }))
+            .send()
+            .await
+            .context("Failed to update the release on GitHub")?;
+        let release: GitHubRelease = parse_response(response).await?;
+        Ok(release.html_url)
+    }
+
+    /// Lists the security advisories published for the repo, newest first
+    /// The token needs the `repository_advisories:read` or `repo` scope
+    pub async fn get_security_advisories(&self, repo: &Repo) -> Result<Vec<SecurityAdvisory>> {
+        let url = format!(
+            "{}/repos/{}/{}/security-advisories",
+            self.base_url, repo.owner, repo.name
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("state", "published"), ("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch security advisories from GitHub")?;
+        parse_response(response).await
+    }
+
+    /// Lists the repo's fixed Dependabot alerts, most recently updated first
+    /// The token needs the `security_events` or `vulnerability_alerts:read` scope
+    pub async fn get_fixed_dependabot_alerts(&self, repo: &Repo) -> Result<Vec<DependabotAlert>> {
+        let url = format!(
+            "{}/repos/{}/{}/dependabot/alerts",
+            self.base_url, repo.owner, repo.name
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("state", "fixed"), ("sort", "updated"), ("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch Dependabot alerts from GitHub")?;
+        parse_response(response).await
+    }
+
+    /// Fetches the merged PRs attached to the milestone with this title, whenever they were
+    /// merged; a repo without the milestone has none
+    /// Calls `on_page` with the page number before each page is requested
+    pub async fn get_milestone_prs(
+        &self,
+        repo: &Repo,
+        title: &str,
+        on_page: &(dyn Fn(u32) + Send + Sync),
+    ) -> Result<Vec<PullRequest>> {
+        let milestones = self.get_milestones(repo).await?;
+        let Some(milestone) = milestones.iter().find(|m| m.title == title) else {
+            return Ok(Vec::new());
+        };
+
+        let url = format!(
+            "{}/repos/{}/{}/issues",
+            self.base_url, repo.owner, repo.name
+        );
+        let mut numbers = Vec::new();
+        for page in 1..=MAX_PR_PAGES {
+            on_page(page);
+
+            let response = self
+                .client
+                .get(&url)
+                .query(&[
+                    ("milestone", milestone.number.to_string().as_str()),
+                    ("state", "closed"),
+                    ("per_page", "100"),
+                    ("page", &page.to_string()),
+                ])
+                .send()
+                .await
+                .context("Failed to fetch milestone issues from GitHub")?;
+            let issues: Vec<ListedIssue> = parse_response(response).await?;
+
+            let complete = issues.len() < 100;
+            numbers.extend(
+                issues
+                    .into_iter()
+                    .filter(|issue| {
+                        issue
+                            .pull_request
+                            .as_ref()
+                            .is_some_and(|pr| pr.merged_at.is_some())
+                    })
+                    .map(|issue| issue.number),
+            );
+            if complete {
+                break;
+            }
+        }
+
+        // The issues endpoint leaves out PR fields such as the merge commit
+        stream::iter(numbers)
+            .map(|number| self.get_pull_request(repo, number))
+            .buffered(MILESTONE_PR_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// Fetches a single PR
+    pub async fn get_pull_request(&self, repo: &Repo, number: u64) -> Result<PullRequest> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.base_url, repo.owner, repo.name, number
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch PR from GitHub")?;
+        parse_response(response).await
+    }
+
+    /// Lists the paths a PR changed, including the old paths of renamed files
+    /// GitHub lists at most 3000 files per PR
+    pub async fn get_pr_files(&self, repo: &Repo, number: u64) -> Result<Vec<String>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/files",
+            self.base_url, repo.owner, repo.name, number
+        );
+        let mut files = Vec::new();
+
+        for page in 1..=MAX_FILE_PAGES {
+            let response = self
+                .client
+                .get(&url)
+                .query(&[("per_page", "100"), ("page", &page.to_string())])
+                .send()
+                .await
+                .context("Failed to fetch PR files from GitHub")?;
+            let listed: Vec<PrFile> = parse_response(response).await?;
+            let last_page = listed.len() < 100;
+
+            for file in listed {
+                files.push(file.filename);
+                files.extend(file.previous_filename);
+            }
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Lists the messages of a PR's commits, oldest first
+    pub async fn get_pr_commit_messages(&self, repo: &Repo, number: u64) -> Result<Vec<String>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/commits",
+            self.base_url, repo.owner, repo.name, number
+        );
+        let mut messages = Vec::new();
+
+        for page in 1..=MAX_COMMIT_PAGES {
+            let response = self
+                .client
+                .get(&url)
+                .query(&[("per_page", "100"), ("page", &page.to_string())])
+                .send()
+                .await
+                .context("Failed to fetch PR commits from GitHub")?;
+            let listed: Vec<ListedCommit> = parse_response(response).await?;
+            let last_page = listed.len() < 100;
+
+            messages.extend(listed.into_iter().map(|commit| commit.commit.message));
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Like `get_pr_files`, but answers from the state database for PRs looked up before
+    pub async fn get_pr_files_cached(&self, repo: &Repo, number: u64) -> Result<Vec<String>> {
+        if let Some(files) = Store::open()?.pr_files(repo, number)? {
+            return Ok(files);
+        }
+
+        let files = self.get_pr_files(repo, number).await?;
+        Store::open()?.cache_pr_files(repo, number, &files)?;
+        Ok(files)
+    }
+
+    /// Fetches a PR's size: lines added and removed and files changed
+    pub async fn get_pr_stats(&self, repo: &Repo, number: u64) -> Result<PrStats> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.base_url, repo.owner, repo.name, number
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch PR from GitHub")?;
+        parse_response(response).await
+    }
+
+    /// Like `get_pr_stats`, but answers from the state database for PRs looked up before
+    pub async fn get_pr_stats_cached(&self, repo: &Repo, number: u64) -> Result<PrStats> {
+        if let Some(stats) = Store::open()?.pr_stats(repo, number)? {
+            return Ok(stats);
+        }
+
+        let stats = self.get_pr_stats(repo, number).await?;
+        Store::open()?.cache_pr_stats(repo, number, &stats)?;
+        Ok(stats)
+    }
+
+    /// Lists the comments on a PR's conversation, oldest first
+    pub async fn get_pr_comments(&self, repo: &Repo, number: u64) -> Result<Vec<PrComment>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/comments",
+            self.base_url, repo.owner, repo.name, number
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch PR comments from GitHub")?;
+        let listed: Vec<ListedComment> = parse_response(response).await?;
+
+        Ok(listed
+            .into_iter()
+            .map(|comment| PrComment {
+                author: comment.user.map(|user| user.login).unwrap_or_default(),
+                body: comment.body,
+                reactions: comment.reactions.map_or(0, |r| r.total_count),
+                created_at: comment.created_at,
+            })
+            .collect())
+    }
+
+    /// Lists the reviews submitted on a PR, oldest first
+    pub async fn get_pr_reviews(&self, repo: &Repo, number: u64) -> Result<Vec<GitHubReview>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews",
+            self.base_url, repo.owner, repo.name, number
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch PR reviews from GitHub")?;
+        parse_response(response).await
+    }
+
+    /// Fetches the check runs and commit statuses of a commit
+    pub async fn get_commit_checks(&self, repo: &Repo, sha: &str) -> Result<CommitChecks> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}",
+            self.base_url, repo.owner, repo.name, sha
+        );
+
+        let response = self
+            .client
+            .get(format!("{}/check-runs", url))
+            .query(&[("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch check runs from GitHub")?;
+        let runs: CheckRunsResponse = parse_response(response).await?;
+
+        let response = self
+            .client
+            .get(format!("{}/status", url))
+            .query(&[("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch commit statuses from GitHub")?;
+        let combined: CombinedStatus = parse_response(response).await?;
+
+        Ok(CommitChecks {
+            check_runs: runs.check_runs,
+            statuses: combined.statuses,
+        })
+    }
+
+    /// Lists the deployments to an environment created since `since` whose latest status is
+    /// success, oldest first
+    pub async fn get_successful_deployments(
+        &self,
+        repo: &Repo,
+        environment: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<GitHubDeployment>> {
+        let url = format!(
+            "{}/repos/{}/{}/deployments",
+            self.base_url, repo.owner, repo.name
+        );
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("environment", environment), ("per_page", "100")])
+            .send()
+            .await
+            .context("Failed to fetch deployments from GitHub")?;
+        let listed: Vec<GitHubDeployment> = parse_response(response).await?;
+
+        // Deployments are listed newest first
+        let mut deployments = Vec::new();
+        for deployment in listed
+            .into_iter()
+            .filter(|d| d.created_at >= since)
+            .take(MAX_DEPLOYMENTS)
+        {
+            let response = self
+                .client
+                .get(format!("{}/{}/statuses", url, deployment.id))
+                .query(&[("per_page", "1")])
+                .send()
+                .await
+                .context("Failed to fetch deployment statuses from GitHub")?;
+            let statuses: Vec<DeploymentStatus> = parse_response(response).await?;
+            if statuses.first().is_some_and(|s| s.state == "success") {
+                deployments.push(deployment);
+            }
+        }
+
+        deployments.reverse();
+        Ok(deployments)
+    }
+
+    /// True when commit `sha` is part of `head`'s history, e.g. a merge included in a deployment
+    pub async fn commit_reaches(&self, repo: &Repo, sha: &str, head: &str) -> Result<bool> {
+        let url = format!(
+            "{}/repos/{}/{}/compare/{}...{}",
+            self.base_url, repo.owner, repo.name, sha, head
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("per_page", "1")])
+            .send()
+            .await
+            .context("Failed to compare commits on GitHub")?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        let comparison: Comparison = parse_response(response).await?;
+
+        Ok(matches!(comparison.status.as_str(), "ahead" | "identical"))
+    }
+
+    /// Fetches the raw contents of a file from the repo's default branch
+    /// Returns None if the file doesn't exist
+    pub async fn get_file_contents(&self, repo: &Repo, path: &str) -> Result<Option<String>> {
+        let url = format!(
+            "{}/repos/{}/{}/contents/{}",
+            self.base_url, repo.owner, repo.name, path
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header(ACCEPT, "application/vnd.github.raw+json")
+            .send()
+            .await
+            .context("Failed to fetch file from GitHub")?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("GitHub", response).await.into());
+        }
+
+        let content = response
+            .text()
+            .await
+            .context("Failed to read file contents from GitHub")?;
+
+        Ok(Some(content))
+    }
+
+    /// Fetches the token's current rate limits; this request doesn't count against them
+    pub async fn get_rate_limits(&self) -> Result<RateLimits> {
+        let response = self
+            .client
+            .get(format!("{}/rate_limit", self.base_url))
+            .send()
+            .await
+            .context("Failed to fetch rate limits from GitHub")?;
+        let limits: RateLimitResponse = parse_response(response).await?;
+
+        Ok(limits.resources)
+    }
+
+    /// Returns the account the token belongs to, checking it works
+    pub async fn authenticated_user(&self) -> Result<TokenOwner> {
+        let response = self
+            .client
+            .get(format!("{}/user", self.base_url))
+            .send()
+            .await
+            .context("Failed to reach GitHub")?;
+        let scopes = response
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|value| value.to_str().ok())
+            .map(|scopes| {
+                scopes
+                    .split(',')
+                    .map(|scope| scope.trim().to_string())
+                    .filter(|scope| !scope.is_empty())
+                    .collect()
+            });
+        let user: GitHubUser = parse_response(response).await?;
+
+        Ok(TokenOwner {
+            login: user.login,
+            scopes,
+        })
+    }
+
+    /// Returns the name of the repo's default branch
+    pub async fn get_default_branch(&self, repo: &Repo) -> Result<String> {
+        let url = format!("{}/repos/{}/{}", self.base_url, repo.owner, repo.name);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch repository from GitHub")?;
+        let info: RepositoryInfo = parse_response(response).await?;
+
+        Ok(info.default_branch)
+    }
+
+    /// Creates a branch pointing at the head of `base`
+    pub async fn create_branch(&self, repo: &Repo, branch: &str, base: &str) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/{}/git/ref/heads/{}",
+            self.base_url, repo.owner, repo.name, base
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch branch from GitHub")?;
+        let base_ref: GitRef = parse_response(response).await?;
+
+        let url = format!(
+            "{}/repos/{}/{}/git/refs",
+            self.base_url, repo.owner, repo.name
+        );
+        let request = CreateRefRequest {
+            git_ref: format!("refs/heads/{}", branch),
+            sha: &base_ref.object.sha,
+        };
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to create branch on GitHub")?;
+        let _: GitRef = parse_response(response).await?;
+
+        Ok(())
+    }
+
+    /// Creates or updates a file on a branch in a single commit
+    pub async fn put_file(
+        &self,
+        repo: &Repo,
+        branch: &str,
+        path: &str,
+        content: &str,
+        message: &str,
+    ) -> Result<()> {
+        use base64::Engine;
+
+        let url = format!(
+            "{}/repos/{}/{}/contents/{}",
+            self.base_url, repo.owner, repo.name, path
+        );
+
+        // Updating an existing file requires its current blob sha
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("ref", branch)])
+            .send()
+            .await
+            .context("Failed to fetch file from GitHub")?;
+        let sha = if response.status() == reqwest::StatusCode::NOT_FOUND {
+            None
+        } else {
+            let info: FileInfo = parse_response(response).await?;
+            Some(info.sha)
+        };
+
+        let request = PutFileRequest {
+            message,
+            content: base64::engine::general_purpose::STANDARD.encode(content),
+            branch,
+            sha,
+        };
+        let response = self
+            .client
+            .put(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to commit file to GitHub")?;
+        let _: serde_json::Value = parse_response(response).await?;
+
+        Ok(())
+    }
+
+    /// Opens a pull request from `head` into `base`
+    pub async fn create_pull_request(
+        &self,
+        repo: &Repo,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<CreatedPullRequest> {
+        let url = format!("{}/repos/{}/{}/pulls", self.base_url, repo.owner, repo.name);
+        let request = CreatePullRequest {
+            title,
+            head,
+            base,
+            body,
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to create pull request on GitHub")?;
+
+        parse_response(response).await
+    }
+
+    /// Creates a single-file gist and returns its URL
+    pub async fn create_gist(
+        &self,
+        description: &str,
+        public: bool,
+        filename: &str,
+        content: &str,
+    ) -> Result<String> {
+        let request = CreateGistRequest {
+            description,
+            public,
+            files: HashMap::from([(filename, GistFile { content })]),
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/gists", self.base_url))
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to create gist on GitHub")?;
+
+        let gist: CreatedGist = parse_response(response).await?;
+        Ok(gist.html_url)
+    }
+
+    /// Runs a GraphQL query, surfacing GraphQL-level errors
+    async fn graphql<T: serde::de::DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T> {
+        let response = self
+            .client
+            .post(format!("{}/graphql", self.base_url))
+            .json(&GraphQLRequest { query, variables })
+            .send()
+            .await
+            .context("Failed to send GraphQL request to GitHub")?;
+
+        let response: GraphQLResponse<T> = parse_response(response).await?;
+
+        if let Some(errors) = response.errors
+            && !errors.is_empty()
+        {
+            let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+            anyhow::bail!("GitHub GraphQL error: {}", messages.join("; "));
+        }
+
+        response
+            .data
+            .context("GitHub GraphQL response contained no data")
+    }
+
+    /// Reads the single-select `field` (e.g. "Status") of the project items of the issues a PR
+    /// closes, falling back to the PR's own items; None when neither is on a board
+    pub async fn get_project_status(
+        &self,
+        repo: &Repo,
+        number: u64,
+        field: &str,
+    ) -> Result<Option<String>> {
+        let data: ProjectStatusData = self
+            .graphql(
+                r#"query($owner: String!, $name: String!, $number: Int!, $field: String!) {
+                    repository(owner: $owner, name: $name) {
+                        pullRequest(number: $number) {
+                            closingIssuesReferences(first: 5) {
+                                nodes {
+                                    projectItems(first: 5) {
+                                        nodes {
+                                            fieldValueByName(name: $field) {
+                                                ... on ProjectV2ItemFieldSingleSelectValue { name }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            projectItems(first: 5) {
+                                nodes {
+                                    fieldValueByName(name: $field) {
+                                        ... on ProjectV2ItemFieldSingleSelectValue { name }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }"#,
+                serde_json::json!({
+                    "owner": repo.owner,
+                    "name": repo.name,
+                    "number": number,
+                    "field": field,
+                }),
+            )
+            .await?;
+
+        let Some(pr) = data.repository.and_then(|r| r.pull_request) else {
+            return Ok(None);
+        };
+        let status = pr
+            .closing_issues_references
+            .nodes
+            .into_iter()
+            .flat_map(|issue| issue.project_items.nodes)
+            .chain(pr.project_items.nodes)
+            .find_map(|item| item.field_value_by_name.and_then(|value| value.name));
+        Ok(status)
+    }
+
+    /// Lists the repo's most recently updated discussions, with their chosen answers
+    pub async fn get_discussions(&self, repo: &Repo) -> Result<Vec<GitHubDiscussion>> {
+        let data: DiscussionsData = self
+            .graphql(
+                r#"query($owner: String!, $name: String!) {
+                    repository(owner: $owner, name: $name) {
+                        discussions(first: 50, orderBy: { field: UPDATED_AT, direction: DESC }) {
+                            nodes {
+                                number
+                                title
+                                url
+                                body
+                                category { name }
+                                createdAt
+                                answer { body }
+                                answerChosenAt
+                            }
+                        }
+                    }
+                }"#,
+                serde_json::json!({ "owner": repo.owner, "name": repo.name }),
+            )
+            .await?;
+
+        let Some(repository) = data.repository else {
+            return Ok(Vec::new());
+        };
+        Ok(repository
+            .discussions
+            .nodes
+            .into_iter()
+            .map(|discussion| GitHubDiscussion {
+                number: discussion.number,
+                title: discussion.title,
+                url: discussion.url,
+                body: discussion.body,
+                category: discussion.category.name,
+                created_at: discussion.created_at,
+                answer: discussion.answer.map(|answer| answer.body),
+                answer_chosen_at: discussion.answer_chosen_at,
+            })
+            .collect())
+    }
+
+    /// Creates a discussion in the given category and returns its URL
+    pub async fn create_discussion(
+        &self,
+        repo: &Repo,
+        category: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<String> {
+        let data: DiscussionRepositoryData = self
+            .graphql(
+                r#"query($owner: String!, $name: String!) {
+                    repository(owner: $owner, name: $name) {
+                        id
+                        discussionCategories(first: 50) { nodes { id name } }
+                    }
+                }"#,
+                serde_json::json!({ "owner": repo.owner, "name": repo.name }),
+            )
+            .await?;
+
+        let repository = data
+            .repository
+            .with_context(|| format!("Repository {} not found", repo.full_name()))?;
+
+        let category_id = repository
+            .discussion_categories
+            .nodes
+            .into_iter()
+            .find(|c| c.name.eq_ignore_ascii_case(category))
+            .map(|c| c.id)
+            .with_context(|| {
+                format!(
+                    "Discussion category '{}' not found in {}",
+                    category,
+                    repo.full_name()
+                )
+            })?;
+
+        let data: CreateDiscussionData = self
+            .graphql(
+                r#"mutation($repositoryId: ID!, $categoryId: ID!, $title: String!, $body: String!) {
+                    createDiscussion(input: {
+                        repositoryId: $repositoryId,
+                        categoryId: $categoryId,
+                        title: $title,
+                        body: $body
+                    }) {
+                        discussion { url }
+                    }
+                }"#,
+                serde_json::json!({
+                    "repositoryId": repository.id,
+                    "categoryId": category_id,
+                    "title": title,
+                    "body": body,
+                }),
+            )
+            .await?;
+
+        Ok(data.create_discussion.discussion.url)
+    }
+}
+
+/// Fails with the response body on non-success statuses, otherwise parses the JSON body
+async fn parse_response<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    if !response.status().is_success() {
+        return Err(GazetteError::from_response("GitHub", response).await.into());
+    }
+
+    response
+        .json()
+        .await
+        .context("Failed to parse GitHub response")
+}
+
+#[async_trait]
+impl PrSource for GitHubClient {
+    async fn merged_prs(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        on_page: &(dyn Fn(u32) + Send + Sync),
+    ) -> Result<Vec<PullRequest>> {
+        match period {
+            TimePeriod::Milestone { title } => self.get_milestone_prs(repo, &title, on_page).await,
+            _ => self.get_merged_prs_cached(repo, period, on_page).await,
+        }
+    }
+
+    async fn file_contents(&self, repo: &Repo, path: &str) -> Result<Option<String>> {
+        self.get_file_contents(repo, path).await
+    }
+
+    async fn changed_files(&self, repo: &Repo, number: u64) -> Result<Vec<String>> {
+        self.get_pr_files_cached(repo, number).await
+    }
+
+    async fn pr_stats(&self, repo: &Repo, number: u64) -> Result<PrStats> {
+        self.get_pr_stats_cached(repo, number).await
+    }
+
+    async fn deployments(
+        &self,
+        repo: &Repo,
+        environment: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<GitHubDeployment>> {
+        self.get_successful_deployments(repo, environment, since)
+            .await
+    }
+
+    async fn commit_reaches(&self, repo: &Repo, sha: &str, head: &str) -> Result<bool> {
+        GitHubClient::commit_reaches(self, repo, sha, head).await
+    }
+
+    async fn commit_checks(&self, repo: &Repo, sha: &str) -> Result<CommitChecks> {
+        self.get_commit_checks(repo, sha).await
+    }
+
+    async fn project_status(
+        &self,
+        repo: &Repo,
+        number: u64,
+        field: &str,
+    ) -> Result<Option<String>> {
+        self.get_project_status(repo, number, field).await
+    }
+
+    async fn releases(&self, repo: &Repo) -> Result<Vec<GitHubRelease>> {
+        self.get_releases(repo).await
+    }
+
+    async fn security_advisories(&self, repo: &Repo) -> Result<Vec<SecurityAdvisory>> {
+        self.get_security_advisories(repo).await
+    }
+
+    async fn fixed_dependabot_alerts(&self, repo: &Repo) -> Result<Vec<DependabotAlert>> {
+        self.get_fixed_dependabot_alerts(repo).await
+    }
+
+    async fn discussions(&self, repo: &Repo) -> Result<Vec<GitHubDiscussion>> {
+        self.get_discussions(repo).await
+    }
+
+    async fn pr_comments(&self, repo: &Repo, number: u64) -> Result<Vec<PrComment>> {
+        self.get_pr_comments(repo, number).await
+    }
+
+    async fn pr_reviews(&self, repo: &Repo, number: u64) -> Result<Vec<GitHubReview>> {
+        self.get_pr_reviews(repo, number).await
+    }
+
+    async fn commit_messages(&self, repo: &Repo, number: u64) -> Result<Vec<String>> {
+        self.get_pr_commit_messages(repo, number).await
+    }
+}
@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::clock;
+use crate::config::{Repo, TimePeriod};
+use crate::store::Store;
+
+/// A changelog saved by a previous run
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    pub repo: Repo,
+    pub path: PathBuf,
+    /// Path the file has when committed to the repo, used when re-delivering
+    pub repo_path: String,
+    pub generated_at: DateTime<Utc>,
+    pub period: String,
+}
+
+impl std::fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}  {}  ({})",
+            clock::format(self.generated_at, "%Y-%m-%d %H:%M"),
+            self.repo.full_name(),
+            self.period
+        )
+    }
+}
+
+/// Index of generated changelogs, also the format of the legacy history.json
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct History {
+    #[serde(default)]
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    pub fn load() -> Result<Self> {
+        Ok(Self {
+            entries: Store::open()?.history()?,
+        })
+    }
+
+    /// Entries whose file still exists, newest first
+    pub fn available(&self) -> Vec<HistoryEntry> {
+        let mut entries: Vec<HistoryEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.path.exists())
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.generated_at));
+        entries
+    }
+}
+
+/// Adds a saved changelog to the history, replacing an older entry for the same file
+pub fn record(repo: &Repo, path: &Path, repo_path: &str, period: TimePeriod) -> Result<()> {
+    Store::open()?.record_history(&HistoryEntry {
+        repo: repo.clone(),
+        path: path.to_path_buf(),
+        repo_path: repo_path.to_string(),
+        generated_at: Utc::now(),
+        period: period.description(),
+    })
+}
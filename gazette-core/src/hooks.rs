@@ -0,0 +1,168 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use crate::changelog::PrContext;
+use crate::config::Repo;
+use crate::delivery::{Delivery, OutputSink};
+
+/// Builds a shell invocation of a hook command, with `args` as its positional parameters
+fn shell(command: &str, args: &[&str]) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command).args(args);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command).arg("gazette").args(args);
+        cmd
+    }
+}
+
+/// Runs the pre-generation hook: the PR contexts go to its stdin as JSON and the
+/// (possibly filtered or edited) contexts are read back from its stdout
+pub fn run_pre_generate(
+    command: &str,
+    repo: &Repo,
+    contexts: Vec<PrContext>,
+) -> Result<Vec<PrContext>> {
+    let input = serde_json::to_vec(&contexts).context("Failed to serialize PR context")?;
+
+    let mut child = shell(command, &[])
+        .env("GAZETTE_REPO", repo.full_name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Failed to run pre-generation hook `{}`", command))?;
+
+    // Write from another thread so a hook streaming output before reading all input can't deadlock
+    let mut stdin = child
+        .stdin
+        .take()
+        .context("Pre-generation hook has no stdin")?;
+    let writer = thread::spawn(move || stdin.write_all(&input));
+
+    let output = child
+        .wait_with_output()
+        .context("Failed to wait for pre-generation hook")?;
+    // A hook may ignore its input entirely, which closes the pipe early
+    let _ = writer.join();
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Pre-generation hook `{}` exited with {}",
+            command,
+            output.status
+        );
+    }
+
+    serde_json::from_slice(&output.stdout).context(
+        "Pre-generation hook must print the PR context JSON it was given, filtered or edited",
+    )
+}
+
+/// Runs the post-generation command with the saved changelog's path as `$1`,
+/// reported with the delivery channels
+pub struct PostGenerateHook {
+    command: String,
+}
+
+impl PostGenerateHook {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+        }
+    }
+
+    /// Runs the command on a blocking thread, capturing its output so it doesn't garble the
+    /// progress display; a failure carries what it printed to stderr
+    async fn run(&self, repo: &Repo, path: &Path) -> Result<()> {
+        let path = path.to_string_lossy().to_string();
+        let mut command = shell(&self.command, &[&path]);
+        command
+            .env("GAZETTE_REPO", repo.full_name())
+            .env("GAZETTE_CHANGELOG", &path)
+            .stdin(Stdio::null());
+
+        let output = tokio::task::spawn_blocking(move || command.output())
+            .await
+            .context("Post-generation hook panicked")?
+            .with_context(|| format!("Failed to run post-generation hook `{}`", self.command))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            match stderr.trim() {
+                "" => anyhow::bail!("exited with {}", output.status),
+                stderr => anyhow::bail!("exited with {}: {}", output.status, stderr),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutputSink for PostGenerateHook {
+    fn name(&self) -> String {
+        "Post-generation hook".to_string()
+    }
+
+    async fn deliver(&self, delivery: &Delivery<'_>) -> Result<Option<String>> {
+        self.run(delivery.repo, delivery.path).await?;
+        Ok(None)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pre_generate_hook_filters_contexts() {
+        let repo = Repo::new("acme", "backend");
+        let contexts = vec![
+            PrContext::fixture(1, "Add SSO"),
+            PrContext::fixture(2, "Revert \"Add SSO\""),
+            PrContext::fixture(3, "Fix logins"),
+        ];
+
+        // The hook checks it was handed the revert, then prints back the other two PRs
+        let kept = std::env::temp_dir().join("gazette-test-hook-kept.json");
+        let others = [contexts[0].clone(), contexts[2].clone()];
+        std::fs::write(&kept, serde_json::to_vec(&others).unwrap()).unwrap();
+        let filter = format!("grep Revert > /dev/null && cat '{}'", kept.display());
+
+        let kept = run_pre_generate(&filter, &repo, contexts).unwrap();
+        let numbers: Vec<u64> = kept.iter().map(|ctx| ctx.pr.number).collect();
+        assert_eq!(numbers, [1, 3]);
+
+        let Err(error) = run_pre_generate("echo not json", &repo, kept) else {
+            panic!("invalid hook output should fail");
+        };
+        assert!(error.to_string().contains("must print the PR context JSON"));
+    }
+
+    #[tokio::test]
+    async fn test_post_generate_hook_reports_its_stderr() {
+        let repo = Repo::new("acme", "backend");
+        let path = Path::new("changelog_backend.md");
+
+        PostGenerateHook::new("test \"$1\" = changelog_backend.md && echo uploaded")
+            .run(&repo, path)
+            .await
+            .unwrap();
+
+        let error = PostGenerateHook::new("echo \"no route to $GAZETTE_REPO\" >&2; exit 3")
+            .run(&repo, path)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "exited with exit status: 3: no route to acme/backend"
+        );
+    }
+}
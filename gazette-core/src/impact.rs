@@ -0,0 +1,129 @@
+use std::fmt;
+
+use crate::breaking;
+use crate::changelog::PrContext;
+
+/// Labels that make a PR a big deal regardless of its size, matched case-insensitively
+const MAJOR_LABELS: [&str; 4] = ["major", "feature", "epic", "highlight"];
+
+/// Labels of changes readers rarely care about
+const MINOR_LABELS: [&str; 6] = [
+    "typo",
+    "docs",
+    "documentation",
+    "chore",
+    "dependencies",
+    "trivial",
+];
+
+/// Changed lines worth one point; files count two points each
+const LINES_PER_POINT: u64 = 25;
+
+/// Cap on the points a PR earns from its size, so generated files don't dwarf everything
+const MAX_SIZE_POINTS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImpactLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for ImpactLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
+
+/// How much a PR is likely to matter to readers, from its size and labels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Impact {
+    pub score: u64,
+    pub level: ImpactLevel,
+}
+
+/// Scores a PR whose size was fetched; None without size stats
+pub fn score(ctx: &PrContext) -> Option<Impact> {
+    let stats = ctx.stats?;
+    let lines = stats.additions + stats.deletions;
+    let mut score = (stats.changed_files * 2 + lines / LINES_PER_POINT).min(MAX_SIZE_POINTS);
+
+    let has_label = |names: &[&str]| {
+        ctx.pr.labels.iter().any(|label| {
+            names
+                .iter()
+                .any(|name| label.name.eq_ignore_ascii_case(name))
+        })
+    };
+    if has_label(&MAJOR_LABELS) || breaking::detect(&ctx.pr).is_some() {
+        score += 30;
+    }
+    if has_label(&MINOR_LABELS) {
+        score /= 2;
+    }
+
+    let level = match score {
+        40.. => ImpactLevel::High,
+        12.. => ImpactLevel::Medium,
+        _ => ImpactLevel::Low,
+    };
+    Some(Impact { score, level })
+}
+
+/// The "Impact:" line of a PR in the AI context
+pub fn describe(ctx: &PrContext) -> Option<String> {
+    let impact = score(ctx)?;
+    let stats = ctx.stats?;
+    Some(format!(
+        "Impact: {} (score {}; {} files, +{}/-{} lines)",
+        impact.level, impact.score, stats.changed_files, stats.additions, stats.deletions
+    ))
+}
+
+/// Prompt note asking for categories and entries ordered by the PRs' impact
+pub fn context_block(contexts: &[PrContext]) -> Option<String> {
+    if contexts.iter().all(|ctx| ctx.stats.is_none()) {
+        return None;
+    }
+
+    Some(
+        "## Impact\nEach PR has an impact score from its size and labels. Order the categories, and the entries within each, from the highest impact to the lowest, so large features aren't buried under typo fixes; breaking changes still come first.\n\n"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::PrStats;
+
+    fn context(files: u64, lines: u64, labels: &[&str]) -> PrContext {
+        let mut ctx = PrContext::fixture(1, "Add billing").labeled(labels);
+        ctx.stats = Some(PrStats {
+            additions: lines,
+            deletions: 0,
+            changed_files: files,
+        });
+        ctx
+    }
+
+    #[test]
+    fn test_size_and_labels_decide_the_level() {
+        let feature = score(&context(20, 1500, &[])).unwrap();
+        assert_eq!((feature.score, feature.level), (60, ImpactLevel::High));
+
+        let typo = score(&context(1, 2, &["typo"])).unwrap();
+        assert_eq!(typo.level, ImpactLevel::Low);
+
+        let flagged = score(&context(1, 10, &["Major"])).unwrap();
+        assert_eq!((flagged.score, flagged.level), (32, ImpactLevel::Medium));
+
+        let mut unmeasured = context(1, 1, &[]);
+        unmeasured.stats = None;
+        assert!(score(&unmeasured).is_none());
+    }
+}
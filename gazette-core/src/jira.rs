@@ -0,0 +1,464 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+
+use crate::changelog::PrContext;
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+use crate::tracker::IssueTracker;
+
+/// Jira API client
+pub struct JiraClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+/// Represents a Jira issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssue {
+    pub key: String,
+    pub fields: JiraFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraFields {
+    pub summary: String,
+    pub description: Option<JiraDescription>,
+    pub status: Option<JiraStatus>,
+    pub issuetype: Option<JiraIssueType>,
+    /// The epic a story or task belongs to, or a subtask's story
+    #[serde(default)]
+    pub parent: Option<JiraParent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraParent {
+    pub key: String,
+    pub fields: JiraParentFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraParentFields {
+    pub summary: String,
+    pub issuetype: Option<JiraIssueType>,
+}
+
+/// The epic an issue is part of, which changelog entries can be grouped by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Epic<'a> {
+    pub key: &'a str,
+    pub summary: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraDescription {
+    pub content: Option<Vec<JiraContent>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraContent {
+    #[serde(rename = "type")]
+    pub content_type: String,
+    pub content: Option<Vec<JiraTextContent>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraTextContent {
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraStatus {
+    pub name: String,
+    /// Jira's grouping of the workflow's statuses; missing from issues cached by older versions
+    #[serde(default, rename = "statusCategory")]
+    pub category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraStatusCategory {
+    /// "new", "indeterminate" or "done"
+    pub key: String,
+}
+
+/// A linked issue that hasn't reached a terminal status yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenIssue {
+    pub key: String,
+    pub status: String,
+}
+
+#[derive(Deserialize)]
+struct JiraUser {
+    #[serde(rename = "displayName")]
+    display_name: String,
+}
+
+/// A version (release) of a Jira project, which issues list as their fix version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraVersion {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub released: bool,
+}
+
+/// What a version is created or updated with
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionDraft {
+    pub name: String,
+    pub description: String,
+    pub released: bool,
+    /// YYYY-MM-DD, set for released versions
+    #[serde(rename = "releaseDate", skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JiraComment {
+    id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssueType {
+    pub name: String,
+}
+
+impl JiraClient {
+    /// Creates a new Jira client from the JIRA_* secrets
+    /// Requires: JIRA_URL (e.g., https://company.atlassian.net)
+    ///           JIRA_EMAIL and JIRA_API_TOKEN
+    pub fn new() -> Result<Self> {
+        let base_url = secrets::require("JIRA_URL")?;
+        let email = secrets::require("JIRA_EMAIL")?;
+        let api_token = secrets::require("JIRA_API_TOKEN")?;
+
+        Self::with_credentials(&base_url, &email, &api_token)
+    }
+
+    /// Creates a new Jira client with explicit credentials
+    pub fn with_credentials(base_url: &str, email: &str, api_token: &str) -> Result<Self> {
+        use base64::Engine;
+        let auth =
+            base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", email, api_token));
+
+        let mut headers = HeaderMap::new();
+
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Basic {}", auth))
+                .context("Invalid credentials format")?,
+        );
+
+        let client = tls::client_builder()
+            .default_headers(headers)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Returns the display name of the account the credentials belong to, checking they work
+    pub async fn current_user(&self) -> Result<String> {
+        let url = format!("{}/rest/api/3/myself", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to reach Jira")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("Jira", response).await.into());
+        }
+
+        let user: JiraUser = response
+            .json()
+            .await
+            .context("Failed to parse Jira user response")?;
+
+        Ok(user.display_name)
+    }
+
+    /// Fetches a Jira issue by key (e.g., "PROJECT-123")
+    /// Returns None if the issue doesn't exist
+    pub async fn get_issue(&self, issue_key: &str) -> Result<Option<JiraIssue>> {
+        let url = format!("{}/rest/api/3/issue/{}", self.base_url, issue_key);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch Jira issue")?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("Jira", response).await.into());
+        }
+
+        let issue: JiraIssue = response
+            .json()
+            .await
+            .context("Failed to parse Jira issue response")?;
+
+        Ok(Some(issue))
+    }
+
+    /// Creates the version of `project` named like `draft`, or updates it when it exists
+    pub async fn upsert_version(&self, project: &str, draft: &VersionDraft) -> Result<JiraVersion> {
+        let url = format!("{}/rest/api/3/project/{}/versions", self.base_url, project);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch Jira versions")?;
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("Jira", response).await.into());
+        }
+        let versions: Vec<JiraVersion> = response
+            .json()
+            .await
+            .context("Failed to parse Jira versions response")?;
+
+        let request = match versions.iter().find(|version| version.name == draft.name) {
+            Some(existing) => self
+                .client
+                .put(format!(
+                    "{}/rest/api/3/version/{}",
+                    self.base_url, existing.id
+                ))
+                .json(draft),
+            None => {
+                let mut body = serde_json::to_value(draft).context("Failed to encode version")?;
+                body["project"] = serde_json::Value::String(project.to_string());
+                self.client
+                    .post(format!("{}/rest/api/3/version", self.base_url))
+                    .json(&body)
+            }
+        };
+        let response = request
+            .send()
+            .await
+            .context("Failed to save Jira version")?;
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("Jira", response).await.into());
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse Jira version response")
+    }
+
+    /// Adds `version` to an issue's fix versions, keeping the ones it has
+    pub async fn add_fix_version(&self, issue_key: &str, version: &JiraVersion) -> Result<()> {
+        let url = format!("{}/rest/api/3/issue/{}", self.base_url, issue_key);
+
+        let response = self
+            .client
+            .put(&url)
+            .json(&serde_json::json!({
+                "update": { "fixVersions": [{ "add": { "id": version.id } }] }
+            }))
+            .send()
+            .await
+            .context("Failed to update Jira issue")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("Jira", response).await.into());
+        }
+
+        Ok(())
+    }
+
+    /// Link to a version's page in the Jira UI
+    pub fn version_url(&self, project: &str, version: &JiraVersion) -> String {
+        format!(
+            "{}/projects/{}/versions/{}",
+            self.base_url, project, version.id
+        )
+    }
+
+    /// Comments on an issue with an Atlassian Document Format `body`, returning the comment's URL
+    pub async fn add_comment(&self, issue_key: &str, body: &serde_json::Value) -> Result<String> {
+        let url = format!("{}/rest/api/3/issue/{}/comment", self.base_url, issue_key);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({ "body": body }))
+            .send()
+            .await
+            .context("Failed to comment on Jira issue")?;
+
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("Jira", response).await.into());
+        }
+
+        let comment: JiraComment = response
+            .json()
+            .await
+            .context("Failed to parse Jira comment response")?;
+
+        Ok(format!(
+            "{}/browse/{}?focusedCommentId={}",
+            self.base_url, issue_key, comment.id
+        ))
+    }
+}
+
+#[async_trait]
+impl IssueTracker for JiraClient {
+    fn extract_keys(&self, text: &str) -> Vec<String> {
+        extract_jira_keys(text)
+    }
+
+    async fn resolve(&self, key: &str) -> Result<Option<JiraIssue>> {
+        self.get_issue(key).await
+    }
+}
+
+/// Extracts Jira issue keys from text (e.g., "PROJECT-123")
+/// Returns all matches found in the text
+pub fn extract_jira_keys(text: &str) -> Vec<String> {
+    let re = Regex::new(r"[A-Z][A-Z0-9]+-\d+").expect("Invalid regex");
+    re.find_iter(text).map(|m| m.as_str().to_string()).collect()
+}
+
+impl JiraIssue {
+    /// Whether the issue is in the terminal status category (Done, Resolved, Closed...); None
+    /// when its status category isn't known
+    pub fn is_done(&self) -> Option<bool> {
+        let category = self.fields.status.as_ref()?.category.as_ref()?;
+        Some(category.key == "done")
+    }
+
+    /// The issue's epic: its parent when that is an epic, or the issue itself when it is one
+    pub fn epic(&self) -> Option<Epic<'_>> {
+        let is_epic = |issuetype: Option<&JiraIssueType>| {
+            issuetype.is_some_and(|issuetype| issuetype.name.eq_ignore_ascii_case("epic"))
+        };
+        if is_epic(self.fields.issuetype.as_ref()) {
+            return Some(Epic {
+                key: &self.key,
+                summary: &self.fields.summary,
+            });
+        }
+        self.fields
+            .parent
+            .as_ref()
+            .filter(|parent| is_epic(parent.fields.issuetype.as_ref()))
+            .map(|parent| Epic {
+                key: &parent.key,
+                summary: &parent.fields.summary,
+            })
+    }
+
+    /// Extracts plain text description from Jira's ADF format
+    pub fn description_text(&self) -> Option<String> {
+        self.fields.description.as_ref().and_then(|desc| {
+            desc.content.as_ref().map(|contents| {
+                contents
+                    .iter()
+                    .filter_map(|c| {
+                        c.content.as_ref().map(|texts| {
+                            texts
+                                .iter()
+                                .filter_map(|t| t.text.clone())
+                                .collect::<Vec<_>>()
+                                .join("")
+                        })
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+        })
+    }
+}
+
+/// Keys of the issues the PRs link to, in order and without repeats
+pub fn linked_keys(contexts: &[PrContext]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for issue in contexts.iter().flat_map(|ctx| &ctx.jira_issues) {
+        if !keys.contains(&issue.key) {
+            keys.push(issue.key.clone());
+        }
+    }
+    keys
+}
+
+/// One line per PR whose linked issues aren't done yet, e.g. to catch mis-linked tickets
+pub fn open_issue_warnings(contexts: &[PrContext]) -> Vec<String> {
+    contexts
+        .iter()
+        .filter(|ctx| !ctx.open_issues.is_empty())
+        .map(|ctx| {
+            let issues: Vec<String> = ctx
+                .open_issues
+                .iter()
+                .map(|issue| format!("{} ({})", issue.key, issue.status))
+                .collect();
+            format!(
+                "PR #{} is merged but links {}, not done yet",
+                ctx.pr.number,
+                issues.join(", ")
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_category_decides_whether_an_issue_is_done() {
+        let issue = |status: serde_json::Value| -> JiraIssue {
+            serde_json::from_value(serde_json::json!({
+                "key": "PROJ-1",
+                "fields": { "summary": "Billing", "status": status },
+            }))
+            .unwrap()
+        };
+
+        let resolved =
+            issue(serde_json::json!({ "name": "Resolved", "statusCategory": { "key": "done" } }));
+        assert_eq!(resolved.is_done(), Some(true));
+        let started = issue(
+            serde_json::json!({ "name": "In Progress", "statusCategory": { "key": "indeterminate" } }),
+        );
+        assert_eq!(started.is_done(), Some(false));
+        let cached = issue(serde_json::json!({ "name": "Done" }));
+        assert_eq!(cached.is_done(), None);
+    }
+
+    #[test]
+    fn test_extract_jira_keys() {
+        let text = "feat(PROJECT-123): implement feature [TEAM-456]";
+        let keys = extract_jira_keys(text);
+        assert_eq!(keys, vec!["PROJECT-123", "TEAM-456"]);
+    }
+
+    #[test]
+    fn test_extract_jira_keys_no_match() {
+        let text = "fix: some bug without ticket";
+        let keys = extract_jira_keys(text);
+        assert!(keys.is_empty());
+    }
+}
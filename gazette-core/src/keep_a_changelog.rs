@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use regex::Regex;
+
+const CHANGELOG_HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/).
+";
+
+const UNRELEASED_HEADING: &str = "## [Unreleased]";
+
+/// Where Keep a Changelog mode keeps the changelog in the repo
+pub const REPO_PATH: &str = "CHANGELOG.md";
+
+/// Change types defined by Keep a Changelog, in their canonical order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Section {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+
+impl Section {
+    pub fn all() -> [Self; 6] {
+        [
+            Self::Added,
+            Self::Changed,
+            Self::Deprecated,
+            Self::Removed,
+            Self::Fixed,
+            Self::Security,
+        ]
+    }
+
+    /// Maps a free-form category name (e.g., "Bug Fixes") to a Keep a Changelog section
+    pub fn from_category(name: &str) -> Self {
+        let name = name.to_lowercase();
+
+        if name.contains("secur") {
+            Self::Security
+        } else if name.contains("deprecat") {
+            Self::Deprecated
+        } else if name.contains("remov") {
+            Self::Removed
+        } else if name.contains("fix") || name.contains("bug") {
+            Self::Fixed
+        } else if name.contains("feat") || name.contains("add") || name.contains("new") {
+            Self::Added
+        } else {
+            Self::Changed
+        }
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added => write!(f, "Added"),
+            Self::Changed => write!(f, "Changed"),
+            Self::Deprecated => write!(f, "Deprecated"),
+            Self::Removed => write!(f, "Removed"),
+            Self::Fixed => write!(f, "Fixed"),
+            Self::Security => write!(f, "Security"),
+        }
+    }
+}
+
+/// Extracts bullet entries from a generated changelog, grouped by Keep a Changelog section
+/// Entries are assigned to sections based on the closest preceding heading
+pub fn extract_entries(markdown: &str) -> Vec<(Section, Vec<String>)> {
+    let mut grouped: Vec<(Section, Vec<String>)> = Vec::new();
+    let mut current = Section::Changed;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if let Some(heading) = trimmed.strip_prefix("##") {
+            current = Section::from_category(heading.trim_start_matches('#').trim());
+            continue;
+        }
+
+        if let Some(entry) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            let entry = format!("- {}", entry.trim());
+            match grouped.iter_mut().find(|(section, _)| *section == current) {
+                Some((_, entries)) => entries.push(entry),
+                None => grouped.push((current, vec![entry])),
+            }
+        }
+    }
+
+    grouped
+}
+
+/// Entries of a Keep a Changelog document's `[Unreleased]` section, leaving released ones out
+pub fn unreleased_entries(markdown: &str) -> Vec<(Section, Vec<String>)> {
+    let mut lines = markdown
+        .lines()
+        .skip_while(|l| !l.trim().to_lowercase().starts_with("## [unreleased]"))
+        .skip(1)
+        .take_while(|l| !l.starts_with("## "))
+        .peekable();
+    if lines.peek().is_none() {
+        return Vec::new();
+    }
+    extract_entries(&lines.collect::<Vec<_>>().join("\n"))
+}
+
+/// Numbers of the PRs a hand-written changelog already mentions, as "#123" or as links to
+/// the repo's PRs. GitHub numbers issues and PRs from one sequence, so a bare reference is
+/// never another PR's, while references into other repos ("acme/api#12") are skipped
+pub fn documented_prs(markdown: &str, full_name: &str) -> HashSet<u64> {
+    let bare = Regex::new(r"(?:^|[^\w/#&])#(\d+)\b").unwrap();
+    let link = Regex::new(&format!(
+        r"(?i)github\.com/{}/(?:pull|issues)/(\d+)",
+        regex::escape(full_name)
+    ))
+    .unwrap();
+
+    bare.captures_iter(markdown)
+        .chain(link.captures_iter(markdown))
+        .filter_map(|captures| captures[1].parse().ok())
+        .collect()
+}
+
+/// Merges the entries into the `[Unreleased]` section of an existing Keep a Changelog document
+/// Missing headings are created; entries already present in the section are skipped
+pub fn merge_unreleased(existing: Option<&str>, entries: &[(Section, Vec<String>)]) -> String {
+    let existing = existing
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or(CHANGELOG_HEADER);
+    let mut lines: Vec<String> = existing.lines().map(str::to_string).collect();
+
+    let is_release_heading = |line: &str| line.starts_with("## ");
+
+    // Locate (or create) the Unreleased section, which sits above all releases
+    let unreleased = match lines
+        .iter()
+        .position(|l| l.trim().to_lowercase().starts_with("## [unreleased]"))
+    {
+        Some(index) => index,
+        None => {
+            let index = lines
+                .iter()
+                .position(|l| is_release_heading(l))
+                .unwrap_or(lines.len());
+            let mut inserted = vec![UNRELEASED_HEADING.to_string(), String::new()];
+            if index == lines.len() && lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                inserted.insert(0, String::new());
+            }
+            lines.splice(index..index, inserted);
+            lines
+                .iter()
+                .position(|l| l == UNRELEASED_HEADING)
+                .expect("Unreleased heading was just inserted")
+        }
+    };
+
+    for section in Section::all() {
+        let Some((_, new_entries)) = entries.iter().find(|(s, _)| *s == section) else {
+            continue;
+        };
+
+        let section_end = lines[unreleased + 1..]
+            .iter()
+            .position(|l| is_release_heading(l))
+            .map(|i| unreleased + 1 + i)
+            .unwrap_or(lines.len());
+
+        let new_entries: Vec<String> = new_entries
+            .iter()
+            .filter(|entry| !lines[unreleased..section_end].contains(entry))
+            .cloned()
+            .collect();
+        if new_entries.is_empty() {
+            continue;
+        }
+
+        let heading = format!("### {}", section);
+        match lines[unreleased..section_end]
+            .iter()
+            .position(|l| l.trim() == heading)
+        {
+            Some(offset) => {
+                let start = unreleased + offset + 1;
+                let mut end = lines[start..section_end]
+                    .iter()
+                    .position(|l| l.starts_with("### "))
+                    .map(|i| start + i)
+                    .unwrap_or(section_end);
+                // Insert right after the last entry, before the blank lines separating headings
+                while end > start && lines[end - 1].trim().is_empty() {
+                    end -= 1;
+                }
+                lines.splice(end..end, new_entries);
+            }
+            None => {
+                let mut end = section_end;
+                while end > unreleased + 1 && lines[end - 1].trim().is_empty() {
+                    end -= 1;
+                }
+                let mut block = vec![String::new(), heading, String::new()];
+                block.extend(new_entries);
+                // Keep a blank line before the next release when there was none to reuse
+                if end == section_end && section_end < lines.len() {
+                    block.push(String::new());
+                }
+                lines.splice(end..end, block);
+            }
+        }
+    }
+
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_entries_maps_categories() {
+        let markdown = "# Changelog for acme/backend\n\n## Features\n- Add OAuth2 ([#1](u))\n\n## Bug Fixes\n* Fix leak\n\n## Improvements\n- Faster queries\n";
+        let entries = extract_entries(markdown);
+        assert_eq!(
+            entries,
+            vec![
+                (Section::Added, vec!["- Add OAuth2 ([#1](u))".to_string()]),
+                (Section::Fixed, vec!["- Fix leak".to_string()]),
+                (Section::Changed, vec!["- Faster queries".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_documented_prs_are_found_by_reference_and_link() {
+        let markdown = "## 1.2.0\n\n- Add OAuth2 (#12)\n- Fix leak, see https://github.com/Acme/backend/pull/15\n- Bump deps (acme/api#20, https://github.com/acme/api/pull/21)\n- Color &#35; and [#30](x)\n";
+
+        let mut prs: Vec<u64> = documented_prs(markdown, "acme/backend")
+            .into_iter()
+            .collect();
+        prs.sort();
+        assert_eq!(prs, [12, 15, 30]);
+    }
+
+    #[test]
+    fn test_merge_into_existing_unreleased() {
+        let existing = "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Old feature\n\n## [1.0.0] - 2026-01-01\n\n### Added\n\n- Initial release\n";
+        let entries = vec![
+            (
+                Section::Added,
+                vec!["- New feature".to_string(), "- Old feature".to_string()],
+            ),
+            (Section::Fixed, vec!["- Fix crash".to_string()]),
+        ];
+
+        let merged = merge_unreleased(Some(existing), &entries);
+
+        assert_eq!(
+            merged,
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Old feature\n- New feature\n\n### Fixed\n\n- Fix crash\n\n## [1.0.0] - 2026-01-01\n\n### Added\n\n- Initial release\n"
+        );
+    }
+
+    #[test]
+    fn test_unreleased_entries_skip_releases() {
+        let markdown = "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- New feature\n\n### Fixed\n\n- Fix crash\n\n## [1.0.0] - 2026-01-01\n\n### Added\n\n- Initial release\n";
+        assert_eq!(
+            unreleased_entries(markdown),
+            vec![
+                (Section::Added, vec!["- New feature".to_string()]),
+                (Section::Fixed, vec!["- Fix crash".to_string()]),
+            ]
+        );
+        assert!(unreleased_entries("# Changelog\n\n## [1.0.0]\n\n- Initial release\n").is_empty());
+    }
+
+    #[test]
+    fn test_merge_creates_document() {
+        let entries = vec![(Section::Added, vec!["- New feature".to_string()])];
+        let merged = merge_unreleased(None, &entries);
+        assert!(merged.starts_with("# Changelog\n"));
+        assert!(merged.ends_with("## [Unreleased]\n\n### Added\n\n- New feature\n"));
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use crate::ai::{ChangelogCategory, StructuredChangelog};
+use crate::changelog::PrContext;
+use crate::config::LabelMapping;
+
+/// The mapping of the first of a PR's labels that has one, matched case-insensitively
+pub fn mapping_for<'a>(
+    label_map: &'a BTreeMap<String, LabelMapping>,
+    ctx: &PrContext,
+) -> Option<&'a LabelMapping> {
+    ctx.pr.labels.iter().find_map(|label| {
+        label_map
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&label.name))
+            .map(|(_, mapping)| mapping)
+    })
+}
+
+/// A category's heading, with the emoji of the label mapping for it
+pub fn heading(label_map: &BTreeMap<String, LabelMapping>, category: &str) -> String {
+    let emoji = label_map
+        .values()
+        .find(|mapping| mapping.category.eq_ignore_ascii_case(category))
+        .and_then(|mapping| mapping.emoji.as_deref());
+    match emoji {
+        Some(emoji) if !category.starts_with(emoji) => format!("{} {}", emoji, category),
+        _ => category.to_string(),
+    }
+}
+
+/// The "Category:" line of a PR in the AI context
+pub fn describe(label_map: &BTreeMap<String, LabelMapping>, ctx: &PrContext) -> Option<String> {
+    let mapping = mapping_for(label_map, ctx)?;
+    Some(format!(
+        "Category: {}",
+        heading(label_map, &mapping.category)
+    ))
+}
+
+/// Moves entries whose PRs carry a mapped label into that label's category, adding it after
+/// the AI's categories when missing
+pub fn apply(
+    label_map: &BTreeMap<String, LabelMapping>,
+    changelog: StructuredChangelog,
+    contexts: &[PrContext],
+) -> StructuredChangelog {
+    if label_map.is_empty() {
+        return changelog;
+    }
+
+    // The AI may have titled a mapped category with its emoji already
+    let mut categories: Vec<ChangelogCategory> = changelog
+        .categories
+        .iter()
+        .map(|category| ChangelogCategory {
+            name: label_map
+                .values()
+                .find(|mapping| heading(label_map, &mapping.category) == category.name.trim())
+                .map(|mapping| mapping.category.clone())
+                .unwrap_or_else(|| category.name.clone()),
+            entries: Vec::new(),
+        })
+        .collect();
+
+    for (index, category) in changelog.categories.into_iter().enumerate() {
+        for entry in category.entries {
+            let mapped = contexts
+                .iter()
+                .filter(|ctx| entry.pr_numbers.contains(&ctx.pr.number))
+                .find_map(|ctx| mapping_for(label_map, ctx));
+            let target = match mapped {
+                Some(mapping) => match categories
+                    .iter()
+                    .position(|c| c.name.trim().eq_ignore_ascii_case(&mapping.category))
+                {
+                    Some(position) => position,
+                    None => {
+                        categories.push(ChangelogCategory {
+                            name: mapping.category.clone(),
+                            entries: Vec::new(),
+                        });
+                        categories.len() - 1
+                    }
+                },
+                None => index,
+            };
+            categories[target].entries.push(entry);
+        }
+    }
+
+    categories.retain(|category| !category.entries.is_empty());
+    StructuredChangelog { categories }
+}
+
+/// Prompt note asking to keep PRs in the categories their labels map them to
+pub fn context_block(
+    label_map: &BTreeMap<String, LabelMapping>,
+    contexts: &[PrContext],
+) -> Option<String> {
+    if !contexts
+        .iter()
+        .any(|ctx| mapping_for(label_map, ctx).is_some())
+    {
+        return None;
+    }
+
+    Some(
+        "## Label categories\nSome PRs give the category their labels put them in. Put them in that category, titled exactly as given including any emoji.\n\n"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ChangelogEntry;
+
+    fn context(number: u64, labels: &[&str]) -> PrContext {
+        PrContext::fixture(number, &format!("PR {}", number)).labeled(labels)
+    }
+
+    #[test]
+    fn test_mapped_labels_move_entries_and_add_emoji() {
+        let label_map = BTreeMap::from([(
+            "security".to_string(),
+            LabelMapping {
+                category: "Security".to_string(),
+                emoji: Some("🔒".to_string()),
+            },
+        )]);
+        let contexts = vec![context(1, &["Security"]), context(2, &["bug"])];
+        let entry = |number| ChangelogEntry {
+            summary: format!("Change {}", number),
+            pr_numbers: vec![number],
+            jira_keys: Vec::new(),
+        };
+        let changelog = StructuredChangelog {
+            categories: vec![
+                ChangelogCategory {
+                    name: "Fixes".to_string(),
+                    entries: vec![entry(1), entry(2)],
+                },
+                ChangelogCategory {
+                    name: "🔒 Security".to_string(),
+                    entries: vec![entry(3)],
+                },
+            ],
+        };
+
+        let applied = apply(&label_map, changelog, &contexts);
+        let names: Vec<&str> = applied.categories.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Fixes", "Security"]);
+        assert_eq!(applied.categories[1].entries.len(), 2);
+        assert_eq!(heading(&label_map, "Security"), "🔒 Security");
+        assert_eq!(heading(&label_map, "Fixes"), "Fixes");
+        assert_eq!(
+            describe(&label_map, &contexts[0]).as_deref(),
+            Some("Category: 🔒 Security")
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+use crate::error::GazetteError;
+
+/// The config file in the working directory, which settings changes are saved to
+pub const CONFIG_FILE: &str = "config.json";
+
+/// Project settings kept in version control, applied over the other files
+pub const PROJECT_FILE: &str = ".gazette.toml";
+
+/// Names the global config may have in the gazette config directory
+const GLOBAL_FILES: [&str; 2] = ["config.toml", "config.json"];
+
+/// One config file's settings, as they were read
+#[derive(Debug, Clone)]
+struct Layer {
+    path: PathBuf,
+    settings: Map<String, Value>,
+}
+
+/// The config files in effect, from the global one to the project one
+/// Later files replace the settings of earlier ones and add to their repos
+#[derive(Debug, Clone)]
+pub struct Layers {
+    /// Files applied before the one saved to
+    below: Vec<Layer>,
+    /// The file settings changes are saved to, which may not exist yet
+    target: Layer,
+    /// Files applied over the one saved to
+    above: Vec<Layer>,
+}
+
+impl Layers {
+    /// Finds the global config, config.json and .gazette.toml for the working directory
+    pub fn discover() -> Result<Self> {
+        Self::discover_in(Path::new(""), global_dir().as_deref())
+    }
+
+    /// Changes are saved to config.json in `dir` when it exists or when there is no global
+    /// config, and to the global config otherwise
+    pub fn discover_in(dir: &Path, global_dir: Option<&Path>) -> Result<Self> {
+        let global = global_dir
+            .into_iter()
+            .flat_map(|dir| GLOBAL_FILES.map(|name| dir.join(name)))
+            .find(|path| path.exists())
+            .map(|path| read(&path))
+            .transpose()?;
+        let local = dir.join(CONFIG_FILE);
+        let above = [dir.join(PROJECT_FILE)]
+            .into_iter()
+            .filter(|path| path.exists())
+            .map(|path| read(&path))
+            .collect::<Result<Vec<_>>>()?;
+
+        let (below, target) = match global {
+            Some(global) if !local.exists() => (Vec::new(), global),
+            global => (global.into_iter().collect(), read(&local)?),
+        };
+        Ok(Self {
+            below,
+            target,
+            above,
+        })
+    }
+
+    /// True when config.json is the only config file, so it holds every setting
+    pub fn is_single(&self) -> bool {
+        self.below.is_empty() && self.above.is_empty()
+    }
+
+    /// The file settings changes are saved to
+    pub fn target(&self) -> &Path {
+        &self.target.path
+    }
+
+    /// The config files that exist, for error messages, e.g. "config.json and .gazette.toml"
+    pub fn files(&self) -> String {
+        let files: Vec<String> = self
+            .all()
+            .filter(|layer| layer.path.exists())
+            .map(|layer| layer.path.display().to_string())
+            .collect();
+        match files.as_slice() {
+            [] => CONFIG_FILE.to_string(),
+            [file] => file.clone(),
+            [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+        }
+    }
+
+    /// The files' settings merged in order
+    pub fn merged(&self) -> Value {
+        let mut merged = Map::new();
+        for layer in self.all() {
+            merge(&mut merged, &layer.settings);
+        }
+        Value::Object(merged)
+    }
+
+    /// What the saved-to file holds after the settings changed from `before` to `after`
+    /// Settings and repos are only written when they changed, so the global and project
+    /// files' ones aren't copied into it
+    pub fn saved(&self, before: &Value, after: &Value) -> Value {
+        let mut saved = self.target.settings.clone();
+        let Value::Object(after) = after else {
+            return Value::Object(saved);
+        };
+        for (key, value) in after {
+            if key == "repos" {
+                let repos = self.saved_repos(value);
+                if !repos.is_empty() || saved.contains_key(key) {
+                    saved.insert(key.clone(), Value::Array(repos));
+                }
+            } else if before.get(key) != Some(value) {
+                saved.insert(key.clone(), value.clone());
+            }
+        }
+        Value::Object(saved)
+    }
+
+    /// The repos of the saved-to file, plus those subscribed since loading
+    fn saved_repos(&self, after: &Value) -> Vec<Value> {
+        let repos = |layer: &Layer| {
+            layer
+                .settings
+                .get("repos")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default()
+        };
+        let own = repos(&self.target);
+        let others: Vec<Value> = self
+            .below
+            .iter()
+            .chain(&self.above)
+            .flat_map(repos)
+            .collect();
+
+        after
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|repo| {
+                let key = repo_key(repo);
+                own.iter().any(|own| repo_key(own) == key) || !others.contains(repo)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn all(&self) -> impl Iterator<Item = &Layer> {
+        self.below
+            .iter()
+            .chain(std::iter::once(&self.target))
+            .chain(&self.above)
+    }
+}
+
+/// Drops unset settings, which TOML can't express; unset is the default anyway
+pub fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(without_nulls).collect()),
+        value => value,
+    }
+}
+
+/// `$XDG_CONFIG_HOME/gazette`, `~/.config/gazette`, or `%APPDATA%\gazette` on Windows
+pub fn global_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(base.join("gazette"))
+}
+
+/// Reads a JSON or TOML config file; a missing file has no settings
+fn read(path: &Path) -> Result<Layer> {
+    let settings = if path.exists() {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let parsed: Result<Value, String> = match path.extension() {
+            Some(ext) if ext == "toml" => toml::from_str(&content).map_err(|e| e.to_string()),
+            _ => serde_json::from_str(&content).map_err(|e| e.to_string()),
+        };
+        match parsed.map_err(|e| {
+            GazetteError::Config(format!("Failed to parse {}: {}", path.display(), e))
+        })? {
+            Value::Object(settings) => settings,
+            _ => {
+                return Err(GazetteError::Config(format!(
+                    "{} must hold a table of settings",
+                    path.display()
+                ))
+                .into());
+            }
+        }
+    } else {
+        Map::new()
+    };
+
+    Ok(Layer {
+        path: path.to_path_buf(),
+        settings,
+    })
+}
+
+/// Applies a file's settings: each replaces the earlier value, except repos, which are added
+/// and replace an earlier subscription of the same repo and scope
+fn merge(merged: &mut Map<String, Value>, layer: &Map<String, Value>) {
+    for (key, value) in layer {
+        match (key.as_str(), merged.get_mut(key), value) {
+            ("repos", Some(Value::Array(repos)), Value::Array(added)) => {
+                for repo in added {
+                    let key = repo_key(repo);
+                    match repos.iter_mut().find(|existing| repo_key(existing) == key) {
+                        Some(existing) => *existing = repo.clone(),
+                        None => repos.push(repo.clone()),
+                    }
+                }
+            }
+            _ => {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// owner, name and scope, which tell subscriptions apart
+fn repo_key(repo: &Value) -> (Option<&Value>, Option<&Value>, Option<&Value>) {
+    (repo.get("owner"), repo.get("name"), repo.get("scope"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_project_settings_override_and_aren_t_saved_back() {
+        let dir = env::temp_dir().join(format!("gazette-layers-{}", std::process::id()));
+        let global = dir.join("global");
+        fs::create_dir_all(&global).unwrap();
+        fs::write(
+            global.join("config.toml"),
+            "ai_provider = \"OpenAI\"\n\n[[repos]]\nowner = \"acme\"\nname = \"shared\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join(CONFIG_FILE),
+            r#"{"timezone": "UTC", "repos": [{"owner": "acme", "name": "backend"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join(PROJECT_FILE),
+            "timezone = \"Europe/Berlin\"\n\n[[repos]]\nowner = \"acme\"\nname = \"web\"\n",
+        )
+        .unwrap();
+
+        let layers = Layers::discover_in(&dir, Some(&global)).unwrap();
+        let before = layers.merged();
+        assert_eq!(before["ai_provider"], "OpenAI");
+        assert_eq!(before["timezone"], "Europe/Berlin");
+        let names: Vec<&Value> = before["repos"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| &r["name"])
+            .collect();
+        assert_eq!(names, ["shared", "backend", "web"]);
+
+        let mut after = before.clone();
+        after["no_ai"] = json!(true);
+        after["repos"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({"owner": "acme", "name": "api"}));
+        let saved = layers.saved(&before, &after);
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(
+            saved,
+            json!({
+                "timezone": "UTC",
+                "no_ai": true,
+                "repos": [{"owner": "acme", "name": "backend"}, {"owner": "acme", "name": "api"}]
+            })
+        );
+    }
+}
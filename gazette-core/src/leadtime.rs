@@ -0,0 +1,119 @@
+use chrono::Duration;
+use serde::Serialize;
+
+use crate::changelog::PrContext;
+use crate::github::PullRequest;
+
+/// Lead-time statistics over the PRs of a changelog, in hours
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeadTimeStats {
+    /// PRs with both an opening and a merge time
+    pub prs: usize,
+    pub median_hours: f64,
+    pub p75_hours: f64,
+    pub p90_hours: f64,
+    pub slowest_hours: f64,
+    /// The PR that took longest to merge
+    pub slowest_pr: u64,
+}
+
+/// Time from opening the PR to merging it
+pub fn lead_time(pr: &PullRequest) -> Option<Duration> {
+    let lead_time = pr.merged_at? - pr.created_at?;
+    (lead_time >= Duration::zero()).then_some(lead_time)
+}
+
+/// Lead time in fractional hours, as the JSON artifact reports it
+pub fn hours(lead_time: Duration) -> f64 {
+    (lead_time.num_minutes() as f64 / 60.0 * 10.0).round() / 10.0
+}
+
+/// Median and percentiles of the PRs' lead times, None when no PR has one
+pub fn stats(contexts: &[PrContext]) -> Option<LeadTimeStats> {
+    let mut times: Vec<(Duration, u64)> = contexts
+        .iter()
+        .filter_map(|ctx| Some((lead_time(&ctx.pr)?, ctx.pr.number)))
+        .collect();
+    if times.is_empty() {
+        return None;
+    }
+    times.sort();
+
+    let (slowest, slowest_pr) = times[times.len() - 1];
+    Some(LeadTimeStats {
+        prs: times.len(),
+        median_hours: hours(percentile(&times, 50)),
+        p75_hours: hours(percentile(&times, 75)),
+        p90_hours: hours(percentile(&times, 90)),
+        slowest_hours: hours(slowest),
+        slowest_pr,
+    })
+}
+
+/// Nearest-rank percentile of durations sorted ascending
+fn percentile(sorted: &[(Duration, u64)], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1].0
+}
+
+/// The statistics as a markdown section
+pub fn render(stats: &LeadTimeStats) -> String {
+    let prs = match stats.prs {
+        1 => "1 PR".to_string(),
+        n => format!("{} PRs", n),
+    };
+    format!(
+        "## Lead time\n\nFrom opening to merge, over {}:\n\n- Median: {}\n- 75th percentile: {}\n- 90th percentile: {}\n- Slowest: {} (#{})\n",
+        prs,
+        human(stats.median_hours),
+        human(stats.p75_hours),
+        human(stats.p90_hours),
+        human(stats.slowest_hours),
+        stats.slowest_pr
+    )
+}
+
+/// "45m", "5h", "2d 3h"
+fn human(hours: f64) -> String {
+    let minutes = (hours * 60.0).round() as i64;
+    if minutes < 60 {
+        return format!("{}m", minutes);
+    }
+    let hours = minutes / 60;
+    match (hours / 24, hours % 24) {
+        (0, hours) => format!("{}h", hours),
+        (days, 0) => format!("{}d", days),
+        (days, hours) => format!("{}d {}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn context(number: u64, hours: i64) -> PrContext {
+        let merged = Utc::now();
+        let mut ctx = PrContext::fixture(number, &format!("PR {}", number));
+        ctx.pr.created_at = Some(merged - Duration::hours(hours));
+        ctx.pr.merged_at = Some(merged);
+        ctx
+    }
+
+    #[test]
+    fn test_percentiles_of_lead_times() {
+        let contexts: Vec<PrContext> = [2, 5, 1, 30, 8, 3, 50, 4, 6, 12]
+            .into_iter()
+            .enumerate()
+            .map(|(i, hours)| context(i as u64 + 1, hours))
+            .collect();
+
+        let stats = stats(&contexts).unwrap();
+        assert_eq!(stats.prs, 10);
+        assert_eq!(stats.median_hours, 5.0);
+        assert_eq!(stats.p75_hours, 12.0);
+        assert_eq!(stats.p90_hours, 30.0);
+        assert_eq!(stats.slowest_pr, 7);
+        assert!(render(&stats).contains("- 90th percentile: 1d 6h\n- Slowest: 2d 2h (#7)"));
+    }
+}
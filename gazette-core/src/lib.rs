@@ -0,0 +1,134 @@
+//! Changelog generation from merged GitHub PRs, their Jira issues and an AI model.
+//!
+//! This is the engine behind the `gazette` CLI, for tools that want to generate
+//! changelogs without shelling out to it. [`changelog::ChangelogService`] is the
+//! entry point; it reads credentials from the environment (or the configured
+//! secret backend) and settings from `config.json` in the working directory, layered
+//! over the global config and under the project's `.gazette.toml`.
+//!
+//! ```no_run
+//! use gazette_core::changelog::ChangelogService;
+//! use gazette_core::config::{Config, Repo, TimePeriod};
+//!
+//! async fn nightly() -> anyhow::Result<()> {
+//!     let service = ChangelogService::from_config(&Config::load()?)?;
+//!     let repo = Repo::new("acme", "backend");
+//!
+//!     // Draft first to inspect or edit the markdown, then save and deliver it
+//!     let draft = service.draft_for_repo(&repo, TimePeriod::Last24Hours).await?;
+//!     let generated = service.publish(draft).await?;
+//!     println!("saved {}", generated.path.display());
+//!     Ok(())
+//! }
+//! ```
+
+/// AI providers and the prompts sent to them
+pub mod ai;
+/// The JSON artifact written alongside a changelog
+pub mod artifact;
+/// Project-board statuses of the issues PRs close
+pub mod board;
+/// The release-notes sections of PR descriptions written from a template
+pub mod body_sections;
+/// Spotting breaking changes from conventional-commit markers and labels
+pub mod breaking;
+/// Gathering PR context, drafting with the AI, then saving and delivering
+pub mod changelog;
+/// Charts of merges per day, in mermaid or plain text
+pub mod chart;
+/// Post-merge CI results of PRs' merge commits
+pub mod ci;
+/// The configured timezone for dates, times and calendar periods
+pub mod clock;
+/// Excerpts of PR comments, where the rationale for a change often lives
+pub mod comments;
+/// Diffing the PRs of two saved runs
+pub mod compare;
+/// Grouping of changes by the repo components their files belong to
+pub mod components;
+/// Settings persisted in config.json
+pub mod config;
+/// Thanks to the people behind a changelog's PRs
+pub mod contributors;
+/// Changelogs formatted from conventional-commit PR titles and labels, without an AI
+pub mod conventional;
+/// Channels a saved changelog is posted to
+pub mod delivery;
+/// Dependabot and Renovate PRs collapsed into one section of package bumps
+pub mod dependencies;
+/// Which merged PRs have reached a deployment environment
+pub mod deployments;
+/// Combined digests across several repos
+pub mod digest;
+/// GitHub Discussions started or answered in a period, listed alongside the PRs
+pub mod discussions;
+/// Errors callers can act on, e.g. by retrying or asking for credentials
+pub mod error;
+/// Changelog entries picked out per Jira issue, for commenting on the tickets
+pub mod excerpts;
+/// Atom feeds of saved changelogs
+pub mod feed;
+/// The GitHub API client
+pub mod github;
+/// Changelogs saved by earlier runs
+pub mod history;
+/// External commands run before drafting and after saving
+pub mod hooks;
+/// Impact scores ranking PRs by size and labels
+pub mod impact;
+/// The Jira API client
+pub mod jira;
+/// Merging entries into a Keep a Changelog CHANGELOG.md
+pub mod keep_a_changelog;
+/// GitHub labels mapped to categories and emoji
+pub mod labels;
+/// Config files stacked from the global one to the project's .gazette.toml
+pub mod layers;
+/// Lead time from opening to merge, and its percentiles over a changelog
+pub mod leadtime;
+/// The Linear API client, resolving team cycles into periods
+pub mod linear;
+/// Checking the PR and Jira links in AI output
+pub mod links;
+/// Normalizing changelogs to a markdown flavor
+pub mod markdown;
+/// Path filters scoping a subscription to part of a monorepo
+pub mod paths;
+/// Stages reported while a changelog is generated
+pub mod progress;
+/// Plain tables of the merged PRs in a period, without the AI
+pub mod report;
+/// Credits for the people who approved each PR
+pub mod reviewers;
+/// The JSON-lines log of each run's steps
+pub mod runlog;
+/// Cron schedules for daemon runs
+pub mod schedule;
+/// Credential storage backends
+pub mod secrets;
+/// Security advisories and Dependabot alerts resolved within a period
+pub mod security;
+/// A static site of saved changelogs
+pub mod site;
+/// The trait PR hosts implement to feed the changelog service
+pub mod source;
+/// PR, contributor and line counts for a changelog's stats section
+pub mod statistics;
+/// The gazette.db state database
+pub mod store;
+/// Per-PR summaries cached between two-pass runs
+pub mod summary_cache;
+/// Custom changelog categories and the hints mapping PRs to them
+pub mod taxonomy;
+/// Tera document templates
+pub mod template;
+/// Title patterns keeping routine PRs out of changelogs
+pub mod titles;
+/// Extra CA certificates trusted by the HTTP clients
+pub mod tls;
+/// The trait issue trackers implement to add ticket context to PRs
+pub mod tracker;
+/// AI token usage and cost tracking
+pub mod usage;
+/// Changelogs named and titled after the release of their period
+pub mod versions;
@@ -0,0 +1,180 @@
+use std::fmt;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::config::TimePeriod;
+use crate::error::GazetteError;
+use crate::secrets;
+use crate::tls;
+
+const LINEAR_API_URL: &str = "https://api.linear.app";
+
+/// Linear API client, used to line periods up with a team's cycles
+pub struct LinearClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+/// Which of a team's cycles a changelog covers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CyclePick {
+    /// The cycle in progress, up to now
+    Current,
+    /// The most recent cycle that has ended
+    LastCompleted,
+}
+
+impl CyclePick {
+    pub fn all() -> Vec<Self> {
+        vec![Self::Current, Self::LastCompleted]
+    }
+
+    /// Parses "current" or "last" as given to `--cycle`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('_', "-").as_str() {
+            "current" | "current-cycle" | "active" => Some(Self::Current),
+            "last" | "last-cycle" | "last-completed" | "previous" => Some(Self::LastCompleted),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CyclePick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Current => write!(f, "Current cycle"),
+            Self::LastCompleted => write!(f, "Last completed cycle"),
+        }
+    }
+}
+
+/// A team's cycle as Linear reports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearCycle {
+    pub number: u32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLResponse {
+    data: Option<TeamsData>,
+    #[serde(default)]
+    errors: Vec<GraphQLError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct TeamsData {
+    teams: Nodes<Team>,
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Team {
+    active_cycle: Option<LinearCycle>,
+    cycles: Nodes<LinearCycle>,
+}
+
+impl LinearClient {
+    /// Creates a client from the LINEAR_API_KEY secret, a personal API key
+    pub fn new() -> Result<Self> {
+        let api_key = secrets::require("LINEAR_API_KEY")?;
+        Self::with_api_key(LINEAR_API_URL, &api_key)
+    }
+
+    /// Creates a client for the API at `base_url`
+    pub fn with_api_key(base_url: &str, api_key: &str) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(api_key).context("Invalid Linear API key format")?,
+        );
+
+        let client = tls::client_builder()
+            .default_headers(headers)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Looks up the cycle `pick` names for the team with key `team`, e.g. "ENG"
+    pub async fn cycle(&self, team: &str, pick: CyclePick) -> Result<LinearCycle> {
+        let query = r#"query($key: String!) {
+            teams(filter: { key: { eq: $key } }) {
+                nodes {
+                    activeCycle { number startsAt endsAt }
+                    cycles(filter: { isPast: { eq: true } }, first: 250) {
+                        nodes { number startsAt endsAt }
+                    }
+                }
+            }
+        }"#;
+
+        let response = self
+            .client
+            .post(format!("{}/graphql", self.base_url))
+            .json(&json!({ "query": query, "variables": { "key": team } }))
+            .send()
+            .await
+            .context("Failed to reach Linear")?;
+        if !response.status().is_success() {
+            return Err(GazetteError::from_response("Linear", response).await.into());
+        }
+
+        let response: GraphQLResponse = response
+            .json()
+            .await
+            .context("Failed to parse Linear response")?;
+        if !response.errors.is_empty() {
+            let messages: Vec<String> = response.errors.into_iter().map(|e| e.message).collect();
+            anyhow::bail!("Linear GraphQL error: {}", messages.join("; "));
+        }
+
+        let team_data = response
+            .data
+            .and_then(|data| data.teams.nodes.into_iter().next())
+            .ok_or_else(|| GazetteError::Config(format!("No Linear team with the key {}", team)))?;
+        let cycle = match pick {
+            CyclePick::Current => team_data.active_cycle,
+            CyclePick::LastCompleted => team_data
+                .cycles
+                .nodes
+                .into_iter()
+                .max_by_key(|cycle| cycle.ends_at),
+        };
+        cycle.ok_or_else(|| {
+            let which = pick.to_string().to_lowercase();
+            GazetteError::Config(format!("Linear team {} has no {}", team, which)).into()
+        })
+    }
+
+    /// The period covering the cycle `pick` names for `team`
+    pub async fn period(&self, team: &str, pick: CyclePick) -> Result<TimePeriod> {
+        let cycle = self.cycle(team, pick).await?;
+        Ok(TimePeriod::Cycle {
+            team: team.to_string(),
+            number: cycle.number,
+            start: cycle.starts_at,
+            end: cycle.ends_at,
+        })
+    }
+}
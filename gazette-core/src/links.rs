@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::{Captures, Regex};
+
+/// PR and Jira references that actually exist in the generation context
+#[derive(Debug, Default)]
+pub struct KnownRefs {
+    /// owner/name of the repo the changelog is for; links to other repos' PRs aren't checked
+    pub repo: String,
+    /// PR number → canonical html_url
+    pub prs: HashMap<u64, String>,
+    pub jira_keys: HashSet<String>,
+    pub jira_base_url: Option<String>,
+}
+
+impl KnownRefs {
+    fn jira_url(&self, key: &str) -> Option<String> {
+        self.jira_base_url
+            .as_ref()
+            .map(|base| format!("{}/browse/{}", base.trim_end_matches('/'), key))
+    }
+}
+
+/// Counts of links changed by [`repair_links`]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LinkReport {
+    pub fixed: usize,
+    pub stripped: usize,
+}
+
+impl LinkReport {
+    pub fn is_clean(&self) -> bool {
+        self.fixed == 0 && self.stripped == 0
+    }
+}
+
+/// Verifies every PR and Jira link in the markdown against the known references
+/// Links pointing to the wrong URL are rewritten; links to unknown PRs or tickets are reduced to
+/// their plain text. Unrelated links, including ones to other repositories' PRs, are left untouched.
+pub fn repair_links(markdown: &str, known: &KnownRefs) -> (String, LinkReport) {
+    let link_re = Regex::new(r"\[([^\]\n]+)\]\(([^)\s]*)\)").expect("Invalid regex");
+    let pr_text_re = Regex::new(r"^(?:PR\s*)?#(\d+)$").expect("Invalid regex");
+    let pr_url_re = Regex::new(r"github\.com/([^/]+/[^/]+)/pull/(\d+)").expect("Invalid regex");
+    let jira_key_re = Regex::new(r"^[A-Z][A-Z0-9]+-\d+$").expect("Invalid regex");
+
+    let mut report = LinkReport::default();
+
+    let repaired = link_re.replace_all(markdown, |caps: &Captures| {
+        let text = &caps[1];
+        let url = &caps[2];
+        let original = caps[0].to_string();
+
+        // A PR of another repo, e.g. an upstream fix, can't be checked against the context
+        let pull = pr_url_re.captures(url);
+        if let Some(pull) = &pull
+            && !pull[1].eq_ignore_ascii_case(&known.repo)
+        {
+            return original;
+        }
+
+        // PR references, identified by link text or by a GitHub pull URL
+        let pr_number = pr_text_re
+            .captures(text.trim())
+            .map(|c| c[1].to_string())
+            .or_else(|| pull.map(|c| c[2].to_string()))
+            .and_then(|number| number.parse::<u64>().ok());
+
+        if let Some(number) = pr_number {
+            return match known.prs.get(&number) {
+                Some(real_url) if real_url == url => original,
+                Some(real_url) => {
+                    report.fixed += 1;
+                    format!("[{}]({})", text, real_url)
+                }
+                None => {
+                    report.stripped += 1;
+                    text.to_string()
+                }
+            };
+        }
+
+        if jira_key_re.is_match(text.trim()) {
+            let key = text.trim();
+            return match (known.jira_keys.contains(key), known.jira_url(key)) {
+                (true, Some(real_url)) if real_url == url => original,
+                (true, Some(real_url)) => {
+                    report.fixed += 1;
+                    format!("[{}]({})", text, real_url)
+                }
+                _ => {
+                    report.stripped += 1;
+                    text.to_string()
+                }
+            };
+        }
+
+        original
+    });
+
+    (repaired.into_owned(), report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known() -> KnownRefs {
+        KnownRefs {
+            repo: "acme/backend".to_string(),
+            prs: HashMap::from([(142, "https://github.com/acme/backend/pull/142".to_string())]),
+            jira_keys: HashSet::from(["AUTH-1".to_string()]),
+            jira_base_url: Some("https://acme.atlassian.net/".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_repair_links_fixes_and_strips() {
+        let markdown = "- Login ([#142](https://github.com/acme/backend/issues/142), [AUTH-1](https://x/AUTH-1))\n\
+                        - Ghost ([#999](https://github.com/acme/backend/pull/999), [FAKE-7](https://x/FAKE-7))\n\
+                        - Upstream [fix](https://github.com/tokio-rs/tokio/pull/7210)\n\
+                        - See [docs](https://docs.acme.dev)";
+
+        let (repaired, report) = repair_links(markdown, &known());
+
+        assert_eq!(
+            repaired,
+            "- Login ([#142](https://github.com/acme/backend/pull/142), [AUTH-1](https://acme.atlassian.net/browse/AUTH-1))\n\
+             - Ghost (#999, FAKE-7)\n\
+             - Upstream [fix](https://github.com/tokio-rs/tokio/pull/7210)\n\
+             - See [docs](https://docs.acme.dev)"
+        );
+        assert_eq!(
+            report,
+            LinkReport {
+                fixed: 2,
+                stripped: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_repair_links_keeps_valid_links() {
+        let markdown = "[#142](https://github.com/acme/backend/pull/142)";
+        let (repaired, report) = repair_links(markdown, &known());
+        assert_eq!(repaired, markdown);
+        assert!(report.is_clean());
+    }
+}
@@ -0,0 +1,278 @@
+use regex::{Captures, Regex};
+
+use crate::config::MarkdownFlavor;
+
+/// Rewrites a changelog into the constructs the configured flavor accepts
+/// GitHub-flavored markdown is left as the AI and the renderer wrote it
+pub fn normalize(content: &str, flavor: MarkdownFlavor) -> String {
+    match flavor {
+        MarkdownFlavor::Github => content.to_string(),
+        MarkdownFlavor::CommonMark => finish(&rewrite_prose(content, common_mark_line)),
+        MarkdownFlavor::Docs => finish(&rewrite_prose(content, docs_line)),
+    }
+}
+
+/// A frontmatter value: a quoted string or a list of them
+pub enum YamlValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+/// Prepends a `---` YAML frontmatter block with the fields in order; empty lists are left out
+pub fn with_frontmatter(content: &str, fields: &[(String, YamlValue)]) -> String {
+    let mut out = String::from("---\n");
+    for (key, value) in fields {
+        match value {
+            YamlValue::Text(text) => out.push_str(&format!("{}: {}\n", key, yaml_string(text))),
+            YamlValue::List(items) if items.is_empty() => {}
+            YamlValue::List(items) => {
+                out.push_str(&format!("{}:\n", key));
+                for item in items {
+                    out.push_str(&format!("  - {}\n", yaml_string(item)));
+                }
+            }
+        }
+    }
+    out.push_str("---\n\n");
+    out.push_str(content);
+    out
+}
+
+/// The changelog without a leading `---` frontmatter block, for renderers that would show it as text
+pub fn strip_frontmatter(content: &str) -> &str {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return content;
+    };
+    match rest.find("\n---\n") {
+        Some(end) => rest[end + 5..].trim_start_matches('\n'),
+        None => content,
+    }
+}
+
+/// Adds a changelog's sections to an existing one: entries a section of the same heading lacks
+/// go at its end, and new sections after the last one; the added title and frontmatter are dropped
+pub fn merge_sections(existing: &str, added: &str) -> String {
+    let mut merged = sections(existing);
+    for (heading, lines) in sections(strip_frontmatter(added)).into_iter().skip(1) {
+        match merged.iter_mut().skip(1).find(|(h, _)| *h == heading) {
+            Some((_, body)) => {
+                let missing: Vec<&str> = lines
+                    .into_iter()
+                    .filter(|line| !line.trim().is_empty() && !body.contains(line))
+                    .collect();
+                let end = body
+                    .iter()
+                    .rposition(|line| !line.trim().is_empty())
+                    .map_or(0, |last| last + 1);
+                body.splice(end..end, missing);
+            }
+            None => {
+                if let Some((_, body)) = merged.last_mut()
+                    && body.last().is_some_and(|line| !line.trim().is_empty())
+                {
+                    body.push("");
+                }
+                merged.push((heading, lines));
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for (heading, body) in merged {
+        for line in std::iter::once(heading)
+            .filter(|h| !h.is_empty())
+            .chain(body)
+        {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// The lines before the first `## ` heading, then each section's heading and lines
+fn sections(content: &str) -> Vec<(&str, Vec<&str>)> {
+    let mut sections = vec![("", Vec::new())];
+    let mut fenced = false;
+    for line in content.lines() {
+        if line.starts_with("```") {
+            fenced = !fenced;
+        }
+        if !fenced && line.starts_with("## ") {
+            sections.push((line, Vec::new()));
+        } else if let Some((_, body)) = sections.last_mut() {
+            body.push(line);
+        }
+    }
+    sections
+}
+
+/// The list items of a changelog, without their bullets
+pub fn list_items(content: &str) -> impl Iterator<Item = &str> {
+    strip_frontmatter(content).lines().filter_map(|line| {
+        let line = line.trim_start();
+        line.strip_prefix("- ").or_else(|| line.strip_prefix("* "))
+    })
+}
+
+/// A markdown line with its links reduced to their labels, for plain-text destinations
+pub fn without_links(line: &str) -> String {
+    let link = Regex::new(r"\[([^\]]*)\]\([^)]*\)").expect("Invalid regex");
+    link.replace_all(line, "$1").trim().to_string()
+}
+
+/// Prompt note for freeform changelogs, so the AI avoids what normalizing would strip
+pub fn prompt_hint(flavor: MarkdownFlavor) -> Option<&'static str> {
+    match flavor {
+        MarkdownFlavor::Github => None,
+        MarkdownFlavor::CommonMark | MarkdownFlavor::Docs => Some(
+            "## Markdown\nWrite strict CommonMark: \"-\" bullets, no tables, task lists, strikethrough, emoji shortcodes or raw HTML.\n\n",
+        ),
+    }
+}
+
+/// Applies `rewrite` to every line outside fenced code blocks
+fn rewrite_prose(content: &str, rewrite: fn(&str) -> String) -> String {
+    let mut in_fence = false;
+    content
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                return line.to_string();
+            }
+            if in_fence {
+                line.to_string()
+            } else {
+                rewrite(line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Strips GitHub extensions from a line and tidies the block syntax CommonMark is strict about
+fn common_mark_line(line: &str) -> String {
+    let heading = Regex::new(r"^(#{1,6})([^#\s])").unwrap();
+    let bullet = Regex::new(r"^(\s*)[*+](\s+)").unwrap();
+    let task = Regex::new(r"^(\s*)- \[[ xX]\] ").unwrap();
+    let strike = Regex::new(r"~~([^~]+)~~").unwrap();
+    let bare_url = Regex::new(r"(^|\s)(https?://[^\s<>()]+[^\s<>().,;:!?])").unwrap();
+
+    let line = line.trim_end();
+    let line = heading.replace(line, "$1 $2");
+    let line = bullet.replace(&line, "$1-$2");
+    let line = task.replace(&line, "$1- ");
+    map_outside_code(&line, |text| {
+        let text = strike.replace_all(text, "$1");
+        bare_url.replace_all(&text, "$1<$2>").into_owned()
+    })
+}
+
+/// CommonMark that MkDocs and Docusaurus (MDX) both render: MDX reads `<` and braces as JSX,
+/// so autolinks become plain links and the rest is escaped
+fn docs_line(line: &str) -> String {
+    let autolink = Regex::new(r"<(https?://[^\s<>]+)>").unwrap();
+
+    let line = common_mark_line(line);
+    map_outside_code(&line, |text| {
+        let mut links = Vec::new();
+        let text = autolink.replace_all(text, |caps: &Captures| {
+            links.push(format!("[{0}]({0})", &caps[1]));
+            format!("\u{0}{}\u{0}", links.len() - 1)
+        });
+
+        let escaped = text
+            .replace('{', "\\{")
+            .replace('}', "\\}")
+            .replace('<', "&lt;");
+
+        let placeholder = Regex::new(r"\x00(\d+)\x00").unwrap();
+        placeholder
+            .replace_all(&escaped, |caps: &Captures| {
+                links[caps[1].parse::<usize>().unwrap()].clone()
+            })
+            .into_owned()
+    })
+}
+
+/// Applies `rewrite` to the parts of a line outside `inline code` spans
+fn map_outside_code(line: &str, rewrite: impl Fn(&str) -> String) -> String {
+    line.split('`')
+        .enumerate()
+        .map(|(index, part)| {
+            if index % 2 == 0 {
+                rewrite(part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("`")
+}
+
+/// Collapses runs of blank lines and ends the document with a single newline
+fn finish(content: &str) -> String {
+    let blank_runs = Regex::new(r"\n{3,}").unwrap();
+    let mut out = blank_runs.replace_all(content.trim(), "\n\n").into_owned();
+    out.push('\n');
+    out
+}
+
+/// A double-quoted YAML scalar
+fn yaml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flavors_rewrite_prose_but_not_code() {
+        let content = "#Changelog\n\n\n\n* ~~Old~~ new login, see https://acme.dev/login.\n- [x] Use `a <b> {c}` in <https://acme.dev> for {config}\n```\n* {untouched}\n```\n";
+
+        assert_eq!(normalize(content, MarkdownFlavor::Github), content);
+        assert_eq!(
+            normalize(content, MarkdownFlavor::CommonMark),
+            "# Changelog\n\n- Old new login, see <https://acme.dev/login>.\n- Use `a <b> {c}` in <https://acme.dev> for {config}\n```\n* {untouched}\n```\n"
+        );
+        assert_eq!(
+            normalize(content, MarkdownFlavor::Docs),
+            "# Changelog\n\n- Old new login, see [https://acme.dev/login](https://acme.dev/login).\n- Use `a <b> {c}` in [https://acme.dev](https://acme.dev) for \\{config\\}\n```\n* {untouched}\n```\n"
+        );
+        assert_eq!(strip_frontmatter(content), content);
+    }
+
+    #[test]
+    fn test_frontmatter_is_prepended_and_stripped() {
+        let content = with_frontmatter(
+            "# Changelog\n",
+            &[
+                (
+                    "title".to_string(),
+                    YamlValue::Text("Changelog \"beta\"".to_string()),
+                ),
+                ("tags".to_string(), YamlValue::List(vec!["api".to_string()])),
+                ("aliases".to_string(), YamlValue::List(Vec::new())),
+            ],
+        );
+
+        assert_eq!(
+            content,
+            "---\ntitle: \"Changelog \\\"beta\\\"\"\ntags:\n  - \"api\"\n---\n\n# Changelog\n"
+        );
+        assert_eq!(strip_frontmatter(&content), "# Changelog\n");
+    }
+
+    #[test]
+    fn test_merged_sections_gain_only_missing_entries() {
+        let existing = "# Changelog - 2026-10-14\n\n## Features\n\n- Dark mode (#1)\n\n## Fixes\n\n- Login loop (#2)\n";
+        let added = "---\ntitle: \"Changelog\"\n---\n\n# Changelog - 2026-10-14\n\n## Features\n\n- Dark mode (#1)\n- Exports (#3)\n\n## Security\n\n- Patched TLS (#4)\n";
+
+        assert_eq!(
+            merge_sections(existing, added),
+            "# Changelog - 2026-10-14\n\n## Features\n\n- Dark mode (#1)\n- Exports (#3)\n\n## Fixes\n\n- Login loop (#2)\n\n## Security\n\n- Patched TLS (#4)\n"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// Decides whether a PR belongs to a path-scoped subscription from the files it changed
+/// Patterns without glob characters are directory prefixes, so "services/payments"
+/// covers everything below it
+pub struct PathFilter {
+    globs: GlobSet,
+    prefixes: Vec<String>,
+}
+
+impl PathFilter {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut globs = GlobSetBuilder::new();
+        let mut prefixes = Vec::new();
+
+        for pattern in patterns {
+            let pattern = pattern.trim().trim_start_matches("./");
+            if pattern.contains(['*', '?', '[', '{']) {
+                globs.add(
+                    Glob::new(pattern)
+                        .with_context(|| format!("Invalid path pattern '{}'", pattern))?,
+                );
+            } else if !pattern.is_empty() {
+                prefixes.push(pattern.trim_end_matches('/').to_string());
+            }
+        }
+
+        Ok(Self {
+            globs: globs.build().context("Invalid path patterns")?,
+            prefixes,
+        })
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.globs.is_match(path)
+            || self.prefixes.iter().any(|prefix| {
+                path == prefix
+                    || path
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+    }
+
+    /// True when any of the changed files is in scope
+    pub fn matches_any(&self, files: &[String]) -> bool {
+        files.iter().any(|file| self.matches(file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_globs_and_prefixes_match_changed_files() {
+        let filter = PathFilter::new(&[
+            "services/payments/**".to_string(),
+            "libs/billing".to_string(),
+            "*.proto".to_string(),
+        ])
+        .unwrap();
+
+        assert!(filter.matches("services/payments/src/main.rs"));
+        assert!(filter.matches("libs/billing/README.md"));
+        assert!(filter.matches("api.proto"));
+        assert!(!filter.matches("libs/billing-legacy/README.md"));
+        assert!(!filter.matches("services/search/src/main.rs"));
+        assert!(filter.matches_any(&["docs/intro.md".to_string(), "libs/billing".to_string()]));
+    }
+}
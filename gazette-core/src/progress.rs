@@ -0,0 +1,100 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::config::Repo;
+
+/// A step of generating a single repo's changelog
+#[derive(Debug, Clone, Copy)]
+pub enum Stage {
+    FetchingPrs {
+        page: u32,
+    },
+    CheckingPaths {
+        done: usize,
+        total: usize,
+    },
+    MeasuringPrs {
+        done: usize,
+        total: usize,
+    },
+    ReadingComments {
+        done: usize,
+        total: usize,
+    },
+    ReadingReviews {
+        done: usize,
+        total: usize,
+    },
+    ReadingCommits {
+        done: usize,
+        total: usize,
+    },
+    CheckingDeployments {
+        done: usize,
+        total: usize,
+    },
+    CheckingCi {
+        done: usize,
+        total: usize,
+    },
+    ReadingBoard {
+        done: usize,
+        total: usize,
+    },
+    EnrichingIssues {
+        done: usize,
+        total: usize,
+    },
+    SummarizingPrs {
+        done: usize,
+        total: usize,
+    },
+    /// Held back by the AI provider's requests-per-minute limit
+    QueuedForAi {
+        wait_secs: u64,
+    },
+    WaitingOnAi,
+    Saving,
+    Delivering,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FetchingPrs { page } => write!(f, "fetching merged PRs (page {})", page),
+            Self::CheckingPaths { done, total } => {
+                write!(f, "checking changed files {}/{}", done, total)
+            }
+            Self::MeasuringPrs { done, total } => write!(f, "measuring PR {}/{}", done, total),
+            Self::ReadingComments { done, total } => {
+                write!(f, "reading comments {}/{}", done, total)
+            }
+            Self::ReadingReviews { done, total } => {
+                write!(f, "reading reviews {}/{}", done, total)
+            }
+            Self::ReadingCommits { done, total } => {
+                write!(f, "reading commits {}/{}", done, total)
+            }
+            Self::CheckingDeployments { done, total } => {
+                write!(f, "checking deployment {}/{}", done, total)
+            }
+            Self::CheckingCi { done, total } => write!(f, "checking CI {}/{}", done, total),
+            Self::ReadingBoard { done, total } => {
+                write!(f, "reading board status {}/{}", done, total)
+            }
+            Self::EnrichingIssues { done, total } => {
+                write!(f, "enriching issue {}/{}", done, total)
+            }
+            Self::SummarizingPrs { done, total } => write!(f, "summarizing PR {}/{}", done, total),
+            Self::QueuedForAi { wait_secs } => {
+                write!(f, "queued for AI rate limit ({}s)", wait_secs)
+            }
+            Self::WaitingOnAi => write!(f, "waiting on AI"),
+            Self::Saving => write!(f, "saving"),
+            Self::Delivering => write!(f, "delivering"),
+        }
+    }
+}
+
+/// Callback notified as a generation moves through its stages
+pub type ProgressFn = Arc<dyn Fn(&Repo, Stage) + Send + Sync>;
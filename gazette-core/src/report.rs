@@ -0,0 +1,165 @@
+use crate::changelog::PrContext;
+use crate::clock;
+
+/// Titles longer than this are cut off in the terminal table
+const MAX_TITLE_WIDTH: usize = 60;
+
+/// One merged PR as listed by a report
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRow {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    /// Merge time in the configured timezone
+    pub merged: String,
+    pub jira_keys: Vec<String>,
+    pub url: String,
+}
+
+impl ReportRow {
+    fn cells(&self) -> [String; 6] {
+        [
+            format!("#{}", self.number),
+            self.merged.clone(),
+            self.author.clone(),
+            self.title.clone(),
+            self.jira_keys.join(", "),
+            self.url.clone(),
+        ]
+    }
+}
+
+const HEADERS: [&str; 6] = ["PR", "Merged", "Author", "Title", "Jira", "Link"];
+
+/// The rows of a report, oldest merge first
+pub fn rows(contexts: &[PrContext]) -> Vec<ReportRow> {
+    let mut contexts: Vec<&PrContext> = contexts.iter().collect();
+    contexts.sort_by_key(|ctx| ctx.pr.merged_at);
+
+    contexts
+        .into_iter()
+        .map(|ctx| ReportRow {
+            number: ctx.pr.number,
+            title: ctx.pr.title.trim().to_string(),
+            author: ctx
+                .pr
+                .user
+                .as_ref()
+                .map(|user| format!("@{}", user.login))
+                .unwrap_or_default(),
+            merged: ctx
+                .pr
+                .merged_at
+                .map(|at| clock::format(at, "%Y-%m-%d %H:%M"))
+                .unwrap_or_default(),
+            jira_keys: ctx
+                .jira_issues
+                .iter()
+                .map(|issue| issue.key.clone())
+                .collect(),
+            url: ctx.pr.html_url.clone(),
+        })
+        .collect()
+}
+
+/// Renders rows as a GitHub-flavored markdown table, e.g. to paste into a standup thread
+pub fn render_markdown(rows: &[ReportRow]) -> String {
+    // The PR number links to the PR, so the link column is left out
+    let headers = &HEADERS[..5];
+    let mut output = format!("| {} |\n", headers.join(" | "));
+    output.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+
+    for row in rows {
+        let cells = [
+            format!("[#{}]({})", row.number, row.url),
+            row.merged.clone(),
+            row.author.clone(),
+            row.title.replace('|', "\\|"),
+            row.jira_keys.join(", "),
+        ];
+        output.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    output
+}
+
+/// Renders rows as a plain-text table with aligned columns
+pub fn render_text(rows: &[ReportRow]) -> String {
+    let table: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| {
+            let mut cells = row.cells();
+            cells[3] = truncate(&cells[3], MAX_TITLE_WIDTH);
+            cells
+        })
+        .collect();
+
+    let mut widths = HEADERS.map(|header| header.chars().count());
+    for cells in &table {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", padded.join("  ").trim_end())
+    };
+
+    let mut output = line(&HEADERS.map(str::to_string));
+    output.push_str(&line(&widths.map(|width| "-".repeat(width))));
+    for cells in &table {
+        output.push_str(&line(cells));
+    }
+    output
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(number: u64, title: &str, keys: &[&str]) -> ReportRow {
+        ReportRow {
+            number,
+            title: title.to_string(),
+            author: "@alice".to_string(),
+            merged: "2026-10-12 09:30".to_string(),
+            jira_keys: keys.iter().map(|key| key.to_string()).collect(),
+            url: format!("https://github.com/acme/backend/pull/{}", number),
+        }
+    }
+
+    #[test]
+    fn test_tables_list_every_column() {
+        let rows = vec![
+            row(7, "Fix a | b parsing", &["SSD-1"]),
+            row(12, "Add export", &[]),
+        ];
+
+        let markdown = render_markdown(&rows);
+        assert!(
+            markdown
+                .starts_with("| PR | Merged | Author | Title | Jira |\n|---|---|---|---|---|\n")
+        );
+        assert!(markdown.contains(
+            "| [#7](https://github.com/acme/backend/pull/7) | 2026-10-12 09:30 | @alice | Fix a \\| b parsing | SSD-1 |\n"
+        ));
+
+        let text = render_text(&rows);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("PR   Merged            Author  Title"));
+        assert!(lines[3].starts_with("#12  2026-10-12 09:30  @alice  Add export"));
+    }
+}
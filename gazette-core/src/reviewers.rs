@@ -0,0 +1,139 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::ai::StructuredChangelog;
+use crate::changelog::PrContext;
+use crate::contributors;
+use crate::github::GitHubReview;
+
+/// `@login` of everyone whose latest verdict on the PR is an approval, leaving out bots
+/// Comments after an approval don't withdraw it, so only approvals, change requests and
+/// dismissals count as verdicts
+pub fn approvers(reviews: &[GitHubReview]) -> Vec<String> {
+    let mut verdicts: Vec<(&str, &str)> = Vec::new();
+    for review in reviews {
+        let Some(user) = &review.user else {
+            continue;
+        };
+        if review.state == "COMMENTED" || user.login.ends_with("[bot]") {
+            continue;
+        }
+        match verdicts.iter_mut().find(|(login, _)| *login == user.login) {
+            Some((_, state)) => *state = &review.state,
+            None => verdicts.push((&user.login, &review.state)),
+        }
+    }
+
+    verdicts
+        .into_iter()
+        .filter(|(_, state)| *state == "APPROVED")
+        .map(|(login, _)| format!("@{}", login))
+        .collect()
+}
+
+/// The "Approved by:" line of a PR in a freeform changelog's AI context
+pub fn describe(ctx: &PrContext) -> Option<String> {
+    if ctx.reviewers.is_empty() {
+        return None;
+    }
+    Some(format!("Approved by: {}", ctx.reviewers.join(", ")))
+}
+
+/// Ends each entry's summary with the approvers of its PRs, e.g. "…, reviewed by @alice"
+pub fn credit_entries(changelog: &mut StructuredChangelog, contexts: &[PrContext]) {
+    for entry in changelog
+        .categories
+        .iter_mut()
+        .flat_map(|category| &mut category.entries)
+    {
+        let mut names: Vec<String> = Vec::new();
+        for ctx in contexts
+            .iter()
+            .filter(|ctx| entry.pr_numbers.contains(&ctx.pr.number))
+        {
+            for name in &ctx.reviewers {
+                if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        if !names.is_empty() {
+            entry.summary = format!(
+                "{}, reviewed by {}",
+                entry.summary.trim().trim_end_matches('.'),
+                contributors::join(&names)
+            );
+        }
+    }
+}
+
+/// A section thanking the reviewers, most reviews first, None when nobody approved a PR
+pub fn appendix(contexts: &[PrContext]) -> Option<String> {
+    // Keyed case-insensitively so the same person isn't thanked twice
+    let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for name in contexts.iter().flat_map(|ctx| &ctx.reviewers) {
+        counts
+            .entry(name.to_lowercase())
+            .or_insert_with(|| (name.clone(), 0))
+            .1 += 1;
+    }
+    if counts.is_empty() {
+        return None;
+    }
+
+    let mut reviewers: Vec<(String, usize)> = counts.into_values().collect();
+    reviewers.sort_by_key(|(_, count)| Reverse(*count));
+    let names: Vec<String> = reviewers
+        .into_iter()
+        .map(|(name, count)| match count {
+            1 => format!("{} (1 PR)", name),
+            n => format!("{} ({} PRs)", name, n),
+        })
+        .collect();
+    Some(format!(
+        "## Reviewers\n\nThanks to {} for reviewing!\n",
+        contributors::join(&names)
+    ))
+}
+
+/// Prompt note asking a freeform changelog to credit each entry's approvers
+pub fn context_block(contexts: &[PrContext]) -> Option<String> {
+    if contexts.iter().all(|ctx| ctx.reviewers.is_empty()) {
+        return None;
+    }
+
+    Some(
+        "## Reviewer credits\nEnd each entry with \"reviewed by\" and the \"Approved by\" names of its PRs, e.g. \"Add SSO login, reviewed by @alice and @bob\".\n\n"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::GitHubUser;
+
+    fn review(login: &str, state: &str) -> GitHubReview {
+        GitHubReview {
+            user: Some(GitHubUser {
+                login: login.to_string(),
+            }),
+            state: state.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_latest_verdicts_decide_who_approved() {
+        let reviews = vec![
+            review("alice", "APPROVED"),
+            review("alice", "COMMENTED"),
+            review("bob", "APPROVED"),
+            review("bob", "DISMISSED"),
+            review("carol", "CHANGES_REQUESTED"),
+            review("carol", "APPROVED"),
+            review("renovate[bot]", "APPROVED"),
+        ];
+
+        assert_eq!(approvers(&reviews), ["@alice", "@carol"]);
+    }
+}
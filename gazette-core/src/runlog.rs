@@ -0,0 +1,173 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Repo;
+use crate::error::GazetteError;
+
+/// JSON-lines log of every step gazette ran, in the working directory
+pub const RUN_LOG_FILE: &str = "logs/gazette.log";
+
+/// Size at which the log is rotated to gazette.log.1
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Rotated files kept besides the current log
+const KEPT_LOGS: usize = 5;
+
+/// The part of a generation a log entry covers
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStep {
+    /// Fetching merged PRs and their issues
+    Gather,
+    /// Prompting the AI and rendering the draft
+    Draft,
+    /// Saving the changelog and delivering it
+    Publish,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Ok,
+    /// Nothing was merged in the period
+    NoPrs,
+    Failed,
+}
+
+/// One step for one repo, written as a line of the run log
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RunLogEntry {
+    /// When the run began; entries of the same CLI call, daemon tick or job share it
+    pub run_started: DateTime<Utc>,
+    pub logged_at: DateTime<Utc>,
+    pub repo: String,
+    pub step: RunStep,
+    pub outcome: RunOutcome,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prs: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Delivery sinks that failed, with their errors
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_deliveries: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunLogEntry {
+    pub fn new(
+        run_started: DateTime<Utc>,
+        repo: &Repo,
+        step: RunStep,
+        duration: Duration,
+        error: Option<&anyhow::Error>,
+    ) -> Self {
+        let outcome = match error {
+            None => RunOutcome::Ok,
+            Some(e)
+                if matches!(
+                    GazetteError::find(e),
+                    Some(GazetteError::EmptyPeriod { .. })
+                ) =>
+            {
+                RunOutcome::NoPrs
+            }
+            Some(_) => RunOutcome::Failed,
+        };
+
+        Self {
+            run_started,
+            logged_at: Utc::now(),
+            repo: repo.key(),
+            step,
+            outcome,
+            duration_ms: duration.as_millis() as u64,
+            prs: None,
+            ai_provider: None,
+            ai_model: None,
+            path: None,
+            failed_deliveries: Vec::new(),
+            error: error.map(|e| format!("{:#}", e)),
+        }
+    }
+}
+
+/// Appends an entry to the run log, rotating it once it grows past 1 MiB
+pub fn append(entry: &RunLogEntry) -> Result<()> {
+    append_to(Path::new(RUN_LOG_FILE), entry, MAX_LOG_BYTES)
+}
+
+fn append_to(path: &Path, entry: &RunLogEntry, max_bytes: u64) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create the log directory")?;
+    }
+    if fs::metadata(path).is_ok_and(|meta| meta.len() >= max_bytes) {
+        rotate(path)?;
+    }
+
+    let mut line = serde_json::to_string(entry).context("Failed to serialize run log entry")?;
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .context("Failed to write the run log")
+}
+
+/// Shifts gazette.log to gazette.log.1, .1 to .2 and so on, dropping the oldest
+fn rotate(path: &Path) -> Result<()> {
+    let numbered = |n: usize| PathBuf::from(format!("{}.{}", path.display(), n));
+
+    let _ = fs::remove_file(numbered(KEPT_LOGS));
+    for n in (1..KEPT_LOGS).rev() {
+        let from = numbered(n);
+        if from.exists() {
+            fs::rename(&from, numbered(n + 1)).context("Failed to rotate the run log")?;
+        }
+    }
+    fs::rename(path, numbered(1)).context("Failed to rotate the run log")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_rotates_once_full() {
+        let dir = std::env::temp_dir().join(format!("gazette-runlog-{}", std::process::id()));
+        let path = dir.join("gazette.log");
+        let entry = RunLogEntry::new(
+            Utc::now(),
+            &Repo::new("acme", "backend"),
+            RunStep::Gather,
+            Duration::from_millis(120),
+            None,
+        );
+
+        append_to(&path, &entry, 1).unwrap();
+        append_to(&path, &entry, 1).unwrap();
+        append_to(&path, &entry, 1).unwrap();
+
+        let current = fs::read_to_string(&path).unwrap();
+        assert_eq!(current.lines().count(), 1);
+        let parsed: RunLogEntry = serde_json::from_str(current.trim()).unwrap();
+        assert_eq!(parsed.outcome, RunOutcome::Ok);
+        assert!(dir.join("gazette.log.1").exists());
+        assert!(dir.join("gazette.log.2").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
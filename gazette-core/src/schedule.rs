@@ -0,0 +1,132 @@
+use std::str::FromStr;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local, Utc};
+use cron::Schedule;
+
+use crate::clock::{self, Zone};
+
+/// Cadences accepted in place of a cron expression, e.g. "hourly" for "@hourly"
+const CADENCES: [&str; 5] = ["hourly", "daily", "weekly", "monthly", "yearly"];
+
+/// Parses a cron expression, accepting the standard 5-field form (`0 9 * * MON`)
+/// as well as the 6/7-field form with seconds (and years), and cadences such as "weekly"
+/// The 5-field form numbers weekdays like standard cron, 0 or 7 for Sunday; the longer forms
+/// number them like the `cron` crate, 1 for Sunday to 7 for Saturday
+pub fn parse(expression: &str) -> Result<Schedule> {
+    let expression = expression.trim();
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let normalized = if CADENCES.contains(&expression.to_lowercase().as_str()) {
+        format!("@{}", expression.to_lowercase())
+    } else if let [minute, hour, day, month, weekday] = fields.as_slice() {
+        let weekday = crate_weekdays(weekday)
+            .with_context(|| format!("Invalid cron expression '{}'", expression))?;
+        format!("0 {} {} {} {} {}", minute, hour, day, month, weekday)
+    } else {
+        expression.to_string()
+    };
+
+    Schedule::from_str(&normalized)
+        .with_context(|| format!("Invalid cron expression '{}'", expression))
+}
+
+/// Renumbers a standard day-of-week field (0-7, Sunday first and last) for the `cron` crate,
+/// which counts 1 (Sunday) to 7 (Saturday); day names and `*` are kept as they are
+fn crate_weekdays(field: &str) -> Result<String> {
+    let mut items = Vec::new();
+    for item in field.split(',') {
+        let numeric = item
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | '/' | '*'));
+        if !numeric || item == "*" {
+            items.push(item.to_string());
+            continue;
+        }
+
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, step.parse::<usize>()?),
+            None => (item, 1),
+        };
+        if step == 0 {
+            bail!("A step of 0 in '{}'", item);
+        }
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (0, 6),
+            Some((start, end)) => (start.parse::<u32>()?, end.parse::<u32>()?),
+            None if step > 1 => (range.parse::<u32>()?, 6),
+            None => {
+                let day = range.parse::<u32>()?;
+                (day, day)
+            }
+        };
+        if end > 7 || start > end {
+            bail!("Days of the week run from 0 to 7 (Sunday), not '{}'", item);
+        }
+        items.extend(
+            (start..=end)
+                .step_by(step)
+                .map(|day| (day % 7 + 1).to_string()),
+        );
+    }
+    items.dedup();
+    Ok(items.join(","))
+}
+
+/// Returns the first occurrence strictly after `after`, evaluated in the configured timezone
+pub fn next_after(schedule: &Schedule, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    match clock::zone() {
+        Zone::System => schedule
+            .after(&after.with_timezone(&Local))
+            .next()
+            .map(|next| next.with_timezone(&Utc)),
+        Zone::Named(tz) => schedule
+            .after(&after.with_timezone(&tz))
+            .next()
+            .map(|next| next.with_timezone(&Utc)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Timelike, Weekday};
+
+    #[test]
+    fn test_parse_five_field_expression() {
+        let schedule = parse("0 9 * * MON").unwrap();
+        let next = next_after(&schedule, Utc::now())
+            .unwrap()
+            .with_timezone(&Local);
+
+        assert_eq!(next.weekday(), Weekday::Mon);
+        assert_eq!((next.hour(), next.minute(), next.second()), (9, 0, 0));
+        assert!(parse("every monday").is_err());
+
+        let hourly = parse("Hourly").unwrap();
+        let next = next_after(&hourly, Utc::now())
+            .unwrap()
+            .with_timezone(&Local);
+        assert_eq!((next.minute(), next.second()), (0, 0));
+    }
+
+    #[test]
+    fn test_numeric_weekdays_count_from_sunday() {
+        for (expression, weekday) in [
+            ("0 9 * * 1", Weekday::Mon),
+            ("0 9 * * 0", Weekday::Sun),
+            ("0 9 * * 7", Weekday::Sun),
+            ("0 9 * * 6", Weekday::Sat),
+        ] {
+            let schedule = parse(expression).unwrap();
+            let next = next_after(&schedule, Utc::now())
+                .unwrap()
+                .with_timezone(&Local);
+            assert_eq!(next.weekday(), weekday, "{}", expression);
+        }
+
+        assert_eq!(crate_weekdays("1-5").unwrap(), "2,3,4,5,6");
+        assert_eq!(crate_weekdays("5-7,MON").unwrap(), "6,7,1,MON");
+        assert_eq!(crate_weekdays("*/2").unwrap(), "1,3,5,7");
+        assert!(parse("0 9 * * 8").is_err());
+    }
+}
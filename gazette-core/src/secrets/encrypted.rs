@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::iter;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use age::secrecy::SecretString;
+use anyhow::{Context, Result, anyhow, bail};
+
+use super::SecretStore;
+
+const SECRETS_FILE: &str = "secrets.age";
+
+/// Unlocks the store without prompting, for headless runs
+const PASSPHRASE_ENV_VAR: &str = "GAZETTE_PASSPHRASE";
+
+/// Stores secrets in secrets.age, a passphrase-encrypted age file in the working directory
+pub struct EncryptedFileStore;
+
+/// Decrypted contents, kept for the rest of the run once unlocked
+struct Unlocked {
+    passphrase: SecretString,
+    values: BTreeMap<String, String>,
+}
+
+fn unlocked() -> &'static Mutex<Option<Unlocked>> {
+    static UNLOCKED: OnceLock<Mutex<Option<Unlocked>>> = OnceLock::new();
+    UNLOCKED.get_or_init(|| Mutex::new(None))
+}
+
+impl EncryptedFileStore {
+    /// Decrypts secrets.age with GAZETTE_PASSPHRASE, or else the passphrase `prompt` returns
+    /// Creates an empty store protected by a new passphrase when the file doesn't exist yet;
+    /// `prompt` is told which one it asks for
+    pub fn unlock(prompt: impl FnOnce(bool) -> Result<String>) -> Result<()> {
+        let mut guard = unlocked()
+            .lock()
+            .map_err(|_| anyhow!("Secrets store lock poisoned"))?;
+        if guard.is_some() {
+            return Ok(());
+        }
+
+        let path = Path::new(SECRETS_FILE);
+        let exists = path.exists();
+        let passphrase = match env::var(PASSPHRASE_ENV_VAR) {
+            Ok(value) if !value.is_empty() => SecretString::from(value),
+            _ => SecretString::from(prompt(!exists)?),
+        };
+
+        let values = if exists {
+            let encrypted = fs::read(path).context("Failed to read secrets.age")?;
+            let plaintext = decrypt(&encrypted, &passphrase)?;
+            serde_json::from_str(&plaintext).context("Failed to parse decrypted secrets")?
+        } else {
+            BTreeMap::new()
+        };
+
+        *guard = Some(Unlocked { passphrase, values });
+        Ok(())
+    }
+
+    /// Runs `f` against the unlocked contents, re-encrypting the file when `f` reports a change
+    /// Without GAZETTE_PASSPHRASE, the store must have been unlocked with a prompt first
+    fn with_unlocked<T>(f: impl FnOnce(&mut BTreeMap<String, String>) -> (T, bool)) -> Result<T> {
+        Self::unlock(|_| {
+            bail!(
+                "{} is not set and secrets.age wasn't unlocked",
+                PASSPHRASE_ENV_VAR
+            )
+        })?;
+
+        let mut guard = unlocked()
+            .lock()
+            .map_err(|_| anyhow!("Secrets store lock poisoned"))?;
+        let store = guard.as_mut().context("Secrets store is locked")?;
+
+        let (result, changed) = f(&mut store.values);
+        if changed {
+            let plaintext =
+                serde_json::to_string(&store.values).context("Failed to serialize secrets")?;
+            let encrypted = encrypt(&plaintext, &store.passphrase)?;
+            write_private(Path::new(SECRETS_FILE), &encrypted)
+                .context("Failed to write secrets.age")?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Self::with_unlocked(|values| (values.get(key).cloned(), false))
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        Self::with_unlocked(|values| {
+            let changed = values.get(key).map(String::as_str) != Some(value);
+            values.insert(key.to_string(), value.to_string());
+            ((), changed)
+        })
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        Self::with_unlocked(|values| ((), values.remove(key).is_some()))
+    }
+}
+
+/// Writes `contents` to a file only its owner can read, tightening an existing file's mode too
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        let file = options.open(path)?;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        (&file).write_all(contents)
+    }
+    #[cfg(not(unix))]
+    options.open(path)?.write_all(contents)
+}
+
+fn encrypt(plaintext: &str, passphrase: &SecretString) -> Result<Vec<u8>> {
+    let encryptor = age::Encryptor::with_user_passphrase(passphrase.clone());
+    let mut encrypted = Vec::new();
+
+    let mut writer = encryptor
+        .wrap_output(&mut encrypted)
+        .context("Failed to encrypt secrets")?;
+    writer
+        .write_all(plaintext.as_bytes())
+        .context("Failed to encrypt secrets")?;
+    writer.finish().context("Failed to encrypt secrets")?;
+
+    Ok(encrypted)
+}
+
+fn decrypt(encrypted: &[u8], passphrase: &SecretString) -> Result<String> {
+    let decryptor =
+        age::Decryptor::new(encrypted).context("secrets.age is not a valid age file")?;
+    let identity = age::scrypt::Identity::new(passphrase.clone());
+
+    let mut reader = decryptor
+        .decrypt(iter::once(&identity as &dyn age::Identity))
+        .map_err(|_| anyhow!("Failed to unlock secrets.age: wrong passphrase?"))?;
+    let mut plaintext = String::new();
+    reader
+        .read_to_string(&mut plaintext)
+        .context("Failed to decrypt secrets")?;
+
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let passphrase = SecretString::from("correct horse");
+        let encrypted = encrypt("{\"GITHUB_TOKEN\":\"ghp_x\"}", &passphrase).unwrap();
+
+        assert!(!String::from_utf8_lossy(&encrypted).contains("ghp_x"));
+        assert_eq!(
+            decrypt(&encrypted, &passphrase).unwrap(),
+            "{\"GITHUB_TOKEN\":\"ghp_x\"}"
+        );
+        assert!(decrypt(&encrypted, &SecretString::from("wrong")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secrets_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = env::temp_dir().join("gazette-test-secrets.age");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&path, b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        fs::remove_file(&path).unwrap();
+    }
+}
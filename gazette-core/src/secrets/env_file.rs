@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::SecretStore;
+
+const ENV_FILE: &str = ".env";
+
+/// Stores secrets as plaintext KEY=value lines in .env in the working directory
+pub struct EnvFileStore;
+
+impl EnvFileStore {
+    fn read_lines() -> Result<Vec<String>> {
+        let path = Path::new(ENV_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(path).context("Failed to read .env")?;
+        Ok(content.lines().map(str::to_string).collect())
+    }
+
+    fn write_lines(lines: &[String]) -> Result<()> {
+        let mut content = lines.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(ENV_FILE, content).context("Failed to write .env")
+    }
+}
+
+impl SecretStore for EnvFileStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        let path = Path::new(ENV_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        for item in dotenvy::from_path_iter(path).context("Failed to read .env")? {
+            let (name, value) = item.context("Failed to parse .env")?;
+            if name == key {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        let prefix = format!("{}=", key);
+        let mut lines: Vec<String> = Self::read_lines()?
+            .into_iter()
+            .filter(|line| !line.starts_with(&prefix))
+            .collect();
+        lines.push(format!("{}{}", prefix, value));
+
+        Self::write_lines(&lines)
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let prefix = format!("{}=", key);
+        let lines = Self::read_lines()?;
+        if !lines.iter().any(|line| line.starts_with(&prefix)) {
+            return Ok(());
+        }
+
+        let kept: Vec<String> = lines
+            .into_iter()
+            .filter(|line| !line.starts_with(&prefix))
+            .collect();
+        Self::write_lines(&kept)
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::{Context, Result};
+use keyring::{Entry, Error};
+
+use super::SecretStore;
+
+const SERVICE: &str = "gazette";
+
+/// Stores secrets in the OS keyring (Keychain, Credential Manager or Secret Service)
+pub struct KeyringStore;
+
+impl KeyringStore {
+    fn entry(key: &str) -> Result<Entry> {
+        Entry::new(SERVICE, key).context("Failed to open keyring entry")
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        match Self::entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {} from keyring", key)),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        Self::entry(key)?
+            .set_password(value)
+            .with_context(|| format!("Failed to write {} to keyring", key))
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        match Self::entry(key)?.delete_credential() {
+            Ok(()) | Err(Error::NoEntry) => Ok(()),
+            Err(e) => Err(e).with_context(|| format!("Failed to delete {} from keyring", key)),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+mod encrypted;
+mod env_file;
+mod keyring;
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::Result;
+
+use crate::config::{AIProvider, Config, DeliveryChannel, SecretBackend};
+use crate::error::GazetteError;
+
+pub use encrypted::EncryptedFileStore;
+pub use env_file::EnvFileStore;
+pub use keyring::KeyringStore;
+
+/// Persistent storage for tokens and API keys
+pub trait SecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>>;
+    fn set(&self, key: &str, value: &str) -> Result<()>;
+    fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Returns the store for the given backend
+pub fn store(backend: SecretBackend) -> Box<dyn SecretStore> {
+    match backend {
+        SecretBackend::Keyring => Box::new(KeyringStore),
+        SecretBackend::EnvFile => Box::new(EnvFileStore),
+        SecretBackend::EncryptedFile => Box::new(EncryptedFileStore),
+    }
+}
+
+/// Unlocks the configured store up front so a passphrase prompt doesn't interrupt a run
+/// `prompt` asks for the passphrase of secrets.age, see [`EncryptedFileStore::unlock`]
+pub fn unlock(prompt: impl FnOnce(bool) -> Result<String>) -> Result<()> {
+    if Config::load()?.secret_backend == SecretBackend::EncryptedFile {
+        EncryptedFileStore::unlock(prompt)?;
+    }
+    Ok(())
+}
+
+/// Values saved or read from the store during this run, and the keys the store didn't have
+/// Saved values take precedence over stale environment values
+fn session() -> &'static Mutex<HashMap<String, Option<String>>> {
+    static SESSION: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+    SESSION.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Looks up a secret in the environment (including .env), then in the configured store
+/// Empty values are treated as missing. The store is asked once per key and run, misses
+/// included
+pub fn get(key: &str) -> Option<String> {
+    let cached = session().lock().ok().and_then(|s| s.get(key).cloned());
+    if let Some(Some(value)) = cached {
+        return Some(value);
+    }
+
+    if let Ok(value) = env::var(key)
+        && !value.is_empty()
+    {
+        return Some(value);
+    }
+    if cached.is_some() {
+        return None;
+    }
+
+    let backend = Config::load().map(|c| c.secret_backend).unwrap_or_default();
+    let value = store(backend)
+        .get(key)
+        .ok()
+        .flatten()
+        .filter(|v| !v.is_empty());
+
+    if let Ok(mut session) = session().lock() {
+        session.insert(key.to_string(), value.clone());
+    }
+    value
+}
+
+/// Like [`get`], failing with [`GazetteError::MissingCredential`] when the secret isn't set
+pub fn require(key: &str) -> Result<String> {
+    get(key).ok_or_else(|| GazetteError::MissingCredential(key.to_string()).into())
+}
+
+/// Returns true if the secret is configured anywhere
+pub fn is_set(key: &str) -> bool {
+    get(key).is_some()
+}
+
+/// Saves a secret to the configured store and returns the backend it went to
+/// Falls back to the .env file when the OS keyring is unavailable
+pub fn save(key: &str, value: &str) -> Result<SecretBackend> {
+    let mut backend = Config::load()?.secret_backend;
+
+    if let Err(e) = store(backend).set(key, value) {
+        if backend != SecretBackend::Keyring {
+            return Err(e);
+        }
+        EnvFileStore.set(key, value)?;
+        backend = SecretBackend::EnvFile;
+    }
+
+    if let Ok(mut session) = session().lock() {
+        session.insert(key.to_string(), Some(value.to_string()));
+    }
+
+    Ok(backend)
+}
+
+/// Every secret gazette knows how to use
+pub fn known_keys() -> Vec<&'static str> {
+    let mut keys = vec![
+        "GITHUB_TOKEN",
+        "JIRA_URL",
+        "JIRA_EMAIL",
+        "JIRA_API_TOKEN",
+        "LINEAR_API_KEY",
+    ];
+    keys.extend(AIProvider::all().iter().map(|p| p.api_key_env_var()));
+    keys.extend(
+        DeliveryChannel::all()
+            .iter()
+            .flat_map(|c| c.credentials().iter().map(|(key, _)| *key)),
+    );
+    keys
+}
+
+/// Moves every known secret from one store to another, returning how many were moved
+pub fn migrate(from: SecretBackend, to: SecretBackend) -> Result<usize> {
+    let (source, target) = (store(from), store(to));
+    let mut moved = 0;
+
+    for key in known_keys() {
+        if let Some(value) = source.get(key)? {
+            target.set(key, &value)?;
+            source.delete(key)?;
+            moved += 1;
+        }
+    }
+
+    Ok(moved)
+}
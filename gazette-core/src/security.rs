@@ -0,0 +1,236 @@
+use chrono::{DateTime, Utc};
+
+use crate::ai::{ChangelogCategory, ChangelogEntry, StructuredChangelog};
+use crate::config::TimePeriod;
+use crate::github::{DependabotAlert, SecurityAdvisory};
+use crate::taxonomy::KEPT_CATEGORIES;
+
+/// Name of the section security fixes are listed in
+pub const SECTION_TITLE: &str = "Security";
+
+/// An advisory published or a vulnerable dependency fixed within the period
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityFix {
+    /// The CVE, or the GHSA ID when no CVE was assigned
+    pub id: String,
+    pub summary: String,
+    pub severity: Option<String>,
+    pub url: String,
+    /// The fixed dependency, e.g. "npm/lodash"; None for the repo's own advisories
+    pub package: Option<String>,
+}
+
+impl SecurityFix {
+    /// The fix as one markdown line, e.g. "**High** [CVE-2024-1234](…): Prototype pollution"
+    pub fn describe(&self) -> String {
+        let mut line = String::new();
+        if let Some(severity) = &self.severity {
+            line.push_str(&format!("**{}** ", capitalize(severity)));
+        }
+        line.push_str(&format!(
+            "[{}]({}): {}",
+            self.id,
+            self.url,
+            self.summary.trim()
+        ));
+        match &self.package {
+            Some(package) => line.push_str(&format!(" (fixed in dependency `{}`)", package)),
+            None => line.push_str(" (advisory published)"),
+        }
+        line
+    }
+}
+
+/// The repo's advisories published and Dependabot alerts fixed within the period, most
+/// severe first
+pub fn resolved_in(
+    period: &TimePeriod,
+    advisories: &[SecurityAdvisory],
+    alerts: &[DependabotAlert],
+) -> Vec<SecurityFix> {
+    let (start, end) = period.bounds();
+    let within = |at: Option<DateTime<Utc>>| at.is_some_and(|at| at > start && at <= end);
+
+    let published = advisories
+        .iter()
+        .filter(|advisory| within(advisory.published_at))
+        .map(|advisory| SecurityFix {
+            id: advisory
+                .cve_id
+                .clone()
+                .unwrap_or_else(|| advisory.ghsa_id.clone()),
+            summary: advisory.summary.clone(),
+            severity: advisory.severity.clone(),
+            url: advisory.html_url.clone(),
+            package: None,
+        });
+    let fixed = alerts
+        .iter()
+        .filter(|alert| within(alert.fixed_at))
+        .map(|alert| SecurityFix {
+            id: alert
+                .security_advisory
+                .cve_id
+                .clone()
+                .unwrap_or_else(|| alert.security_advisory.ghsa_id.clone()),
+            summary: alert.security_advisory.summary.clone(),
+            severity: alert.security_advisory.severity.clone(),
+            url: alert.html_url.clone(),
+            package: Some(format!(
+                "{}/{}",
+                alert.dependency.package.ecosystem, alert.dependency.package.name
+            )),
+        });
+
+    let mut fixes: Vec<SecurityFix> = published.chain(fixed).collect();
+    fixes.sort_by_key(|fix| std::cmp::Reverse(severity_rank(fix.severity.as_deref())));
+    fixes
+}
+
+/// Adds the fixes to the changelog's security category, or to a new one placed after
+/// the breaking changes
+pub fn apply(mut changelog: StructuredChangelog, fixes: &[SecurityFix]) -> StructuredChangelog {
+    if fixes.is_empty() {
+        return changelog;
+    }
+
+    let entries = fixes.iter().map(|fix| ChangelogEntry {
+        summary: fix.describe(),
+        pr_numbers: Vec::new(),
+        jira_keys: Vec::new(),
+    });
+    let existing = changelog.categories.iter_mut().find(|category| {
+        category
+            .name
+            .trim()
+            .to_lowercase()
+            .ends_with(&SECTION_TITLE.to_lowercase())
+    });
+    match existing {
+        Some(category) => {
+            let fixes: Vec<ChangelogEntry> = entries.collect();
+            category.entries.splice(0..0, fixes);
+        }
+        None => {
+            let position = changelog
+                .categories
+                .iter()
+                .take_while(|category| {
+                    KEPT_CATEGORIES
+                        .iter()
+                        .any(|name| category.name.trim().eq_ignore_ascii_case(name))
+                })
+                .count();
+            changelog.categories.insert(
+                position,
+                ChangelogCategory {
+                    name: SECTION_TITLE.to_string(),
+                    entries: entries.collect(),
+                },
+            );
+        }
+    }
+    changelog
+}
+
+/// Prompt note asking a freeform changelog for a section listing the fixes
+pub fn context_block(fixes: &[SecurityFix]) -> Option<String> {
+    if fixes.is_empty() {
+        return None;
+    }
+
+    let mut block = String::from(
+        "## Security fixes\nAdd a \"Security\" section right after any breaking changes listing each of these, keeping its severity, link and wording. Mention a fix again in another entry only when a listed PR made it.\n",
+    );
+    for fix in fixes {
+        block.push_str(&format!("- {}\n", fix.describe()));
+    }
+    block.push('\n');
+    Some(block)
+}
+
+fn severity_rank(severity: Option<&str>) -> u8 {
+    match severity.map(str::to_lowercase).as_deref() {
+        Some("critical") => 4,
+        Some("high") => 3,
+        Some("medium" | "moderate") => 2,
+        Some("low") => 1,
+        _ => 0,
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    use crate::github::{AlertAdvisory, AlertDependency, AlertPackage};
+
+    fn alert(cve: Option<&str>, severity: &str, days_ago: i64) -> DependabotAlert {
+        DependabotAlert {
+            html_url: "https://github.com/acme/backend/security/dependabot/3".to_string(),
+            fixed_at: Some(Utc::now() - Duration::days(days_ago)),
+            security_advisory: AlertAdvisory {
+                ghsa_id: "GHSA-xxxx-yyyy-zzzz".to_string(),
+                cve_id: cve.map(str::to_string),
+                summary: "Prototype pollution".to_string(),
+                severity: Some(severity.to_string()),
+            },
+            dependency: AlertDependency {
+                package: AlertPackage {
+                    ecosystem: "npm".to_string(),
+                    name: "lodash".to_string(),
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_fixes_of_the_period_go_after_breaking_changes() {
+        let advisory = SecurityAdvisory {
+            ghsa_id: "GHSA-aaaa-bbbb-cccc".to_string(),
+            cve_id: None,
+            summary: "Session fixation".to_string(),
+            severity: Some("medium".to_string()),
+            html_url: "https://github.com/acme/backend/security/advisories/GHSA-aaaa-bbbb-cccc"
+                .to_string(),
+            published_at: Some(Utc::now() - Duration::days(1)),
+        };
+        let alerts = vec![alert(Some("CVE-2024-1"), "high", 2), alert(None, "low", 30)];
+
+        let fixes = resolved_in(&TimePeriod::Last7Days, &[advisory], &alerts);
+        let ids: Vec<&str> = fixes.iter().map(|fix| fix.id.as_str()).collect();
+        assert_eq!(ids, ["CVE-2024-1", "GHSA-aaaa-bbbb-cccc"]);
+        assert_eq!(
+            fixes[0].describe(),
+            "**High** [CVE-2024-1](https://github.com/acme/backend/security/dependabot/3): Prototype pollution (fixed in dependency `npm/lodash`)"
+        );
+
+        let changelog = StructuredChangelog {
+            categories: vec![
+                ChangelogCategory {
+                    name: "Breaking Changes".to_string(),
+                    entries: Vec::new(),
+                },
+                ChangelogCategory {
+                    name: "Features".to_string(),
+                    entries: Vec::new(),
+                },
+            ],
+        };
+        let names: Vec<String> = apply(changelog, &fixes)
+            .categories
+            .into_iter()
+            .map(|category| category.name)
+            .collect();
+        assert_eq!(names, ["Breaking Changes", "Security", "Features"]);
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use pulldown_cmark::{Parser, html};
+
+use crate::config::Config;
+use crate::feed::escape_xml;
+use crate::markdown;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem;line-height:1.5;color:#1f2328}a{color:#0969da}nav{margin-bottom:1.5rem;font-size:.9rem}h1{border-bottom:1px solid #d0d7de;padding-bottom:.3rem}ul.index{list-style:none;padding:0}ul.index li{margin:.3rem 0}";
+
+/// A changelog previously saved to the working directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedChangelog {
+    /// Repo name as it appears in the filename
+    pub repo: String,
+    pub date: NaiveDate,
+    pub path: PathBuf,
+}
+
+/// Counts of what was written by `build`
+pub struct SiteSummary {
+    pub changelogs: usize,
+    pub repos: usize,
+    pub dates: usize,
+}
+
+/// Parses `changelog_<repo-name>_<date>.md` into its repo name and date
+pub fn parse_filename(filename: &str) -> Option<(String, NaiveDate)> {
+    let stem = filename.strip_prefix("changelog_")?.strip_suffix(".md")?;
+    let (repo, date) = stem.rsplit_once('_')?;
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+
+    (!repo.is_empty()).then(|| (repo.to_string(), date))
+}
+
+/// Finds all saved changelogs in `dir`
+pub fn discover(dir: &Path) -> Result<Vec<ArchivedChangelog>> {
+    let mut changelogs = Vec::new();
+
+    for entry in fs::read_dir(dir).context("Failed to read changelog directory")? {
+        let path = entry?.path();
+        let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if let Some((repo, date)) = parse_filename(filename) {
+            changelogs.push(ArchivedChangelog { repo, date, path });
+        }
+    }
+
+    Ok(changelogs)
+}
+
+/// Renders every saved changelog into a static site at `out_dir`
+/// Layout: index.html, <repo>/index.html, <repo>/<date>.html and dates/<date>.html
+pub fn build(out_dir: &Path) -> Result<SiteSummary> {
+    let changelogs = discover(Path::new("."))?;
+    let config = Config::load()?;
+
+    // Filenames only carry the repo name, so recover the owner from subscriptions
+    let display_name = |repo: &str| {
+        config
+            .repos
+            .iter()
+            .find(|r| r.file_stem() == repo)
+            .map(|r| r.key())
+            .unwrap_or_else(|| repo.to_string())
+    };
+
+    let mut by_repo: BTreeMap<&str, Vec<&ArchivedChangelog>> = BTreeMap::new();
+    let mut by_date: BTreeMap<NaiveDate, Vec<&ArchivedChangelog>> = BTreeMap::new();
+    for changelog in &changelogs {
+        by_repo.entry(&changelog.repo).or_default().push(changelog);
+        by_date.entry(changelog.date).or_default().push(changelog);
+    }
+
+    fs::create_dir_all(out_dir.join("dates")).context("Failed to create site directory")?;
+
+    for (repo, entries) in by_repo.iter_mut() {
+        entries.sort_by_key(|c| std::cmp::Reverse(c.date));
+        let repo_dir = out_dir.join(repo);
+        fs::create_dir_all(&repo_dir).context("Failed to create site directory")?;
+
+        for changelog in entries.iter() {
+            let markdown = fs::read_to_string(&changelog.path)
+                .with_context(|| format!("Failed to read {}", changelog.path.display()))?;
+            let title = format!("{} — {}", display_name(repo), changelog.date);
+            let body = format!(
+                "<nav><a href=\"../index.html\">All repos</a> · <a href=\"index.html\">{}</a> · <a href=\"../dates/{}.html\">{}</a></nav>\n{}",
+                escape_xml(&display_name(repo)),
+                changelog.date,
+                changelog.date,
+                markdown_to_html(&markdown)
+            );
+            write_page(
+                &repo_dir.join(format!("{}.html", changelog.date)),
+                &title,
+                &body,
+            )?;
+        }
+
+        let links: Vec<(String, String)> = entries
+            .iter()
+            .map(|c| (format!("{}.html", c.date), c.date.to_string()))
+            .collect();
+        let body = format!(
+            "<nav><a href=\"../index.html\">All repos</a></nav>\n<h1>{}</h1>\n{}",
+            escape_xml(&display_name(repo)),
+            link_list(&links)
+        );
+        write_page(&repo_dir.join("index.html"), &display_name(repo), &body)?;
+    }
+
+    for (date, entries) in by_date.iter_mut() {
+        entries.sort_by(|a, b| a.repo.cmp(&b.repo));
+        let mut body = format!(
+            "<nav><a href=\"../index.html\">All repos</a></nav>\n<h1>Changelogs for {}</h1>\n",
+            date
+        );
+        for changelog in entries.iter() {
+            let markdown = fs::read_to_string(&changelog.path)
+                .with_context(|| format!("Failed to read {}", changelog.path.display()))?;
+            body.push_str(&format!(
+                "<section>\n<p><a href=\"../{}/{}.html\">{}</a></p>\n{}</section>\n",
+                changelog.repo,
+                date,
+                escape_xml(&display_name(&changelog.repo)),
+                markdown_to_html(&markdown)
+            ));
+        }
+        write_page(
+            &out_dir.join("dates").join(format!("{}.html", date)),
+            &format!("Changelogs for {}", date),
+            &body,
+        )?;
+    }
+
+    let repo_links: Vec<(String, String)> = by_repo
+        .keys()
+        .map(|repo| (format!("{}/index.html", repo), display_name(repo)))
+        .collect();
+    let date_links: Vec<(String, String)> = by_date
+        .keys()
+        .rev()
+        .map(|date| (format!("dates/{}.html", date), date.to_string()))
+        .collect();
+    let body = format!(
+        "<h1>Gazette archive</h1>\n<h2>Repositories</h2>\n{}<h2>Dates</h2>\n{}",
+        link_list(&repo_links),
+        link_list(&date_links)
+    );
+    write_page(&out_dir.join("index.html"), "Gazette archive", &body)?;
+
+    Ok(SiteSummary {
+        changelogs: changelogs.len(),
+        repos: by_repo.len(),
+        dates: by_date.len(),
+    })
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let mut output = String::new();
+    html::push_html(
+        &mut output,
+        Parser::new(markdown::strip_frontmatter(markdown)),
+    );
+    output
+}
+
+fn link_list(links: &[(String, String)]) -> String {
+    let mut output = String::from("<ul class=\"index\">\n");
+    for (href, label) in links {
+        output.push_str(&format!(
+            "<li><a href=\"{}\">{}</a></li>\n",
+            escape_xml(href),
+            escape_xml(label)
+        ));
+    }
+    output.push_str("</ul>\n");
+    output
+}
+
+fn write_page(path: &Path, title: &str, body: &str) -> Result<()> {
+    let page = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_xml(title),
+        STYLE,
+        body
+    );
+
+    fs::write(path, page).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filename() {
+        assert_eq!(
+            parse_filename("changelog_my_repo_2026-02-10.md"),
+            Some((
+                "my_repo".to_string(),
+                NaiveDate::from_ymd_opt(2026, 2, 10).unwrap()
+            ))
+        );
+        assert_eq!(parse_filename("changelog_backend_2026-02-10.json"), None);
+        assert_eq!(parse_filename("CHANGELOG_backend.md"), None);
+        assert_eq!(parse_filename("changelog__2026-02-10.md"), None);
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::config::{Repo, TimePeriod};
+use crate::github::{
+    CommitChecks, DependabotAlert, GitHubDeployment, GitHubDiscussion, GitHubRelease, GitHubReview,
+    PrComment, PrStats, PullRequest, SecurityAdvisory,
+};
+
+/// Where a repo's merged changes come from, e.g. the GitHub API
+/// `ChangelogService` only talks to its source through this trait, so other hosts
+/// (or a fake in tests) can stand in for GitHub. Only `merged_prs` is required; the lookups
+/// enriching the context default to finding nothing, which leaves that context out
+#[async_trait]
+pub trait PrSource: Send + Sync {
+    /// Lists the changes merged into the repo within the period
+    /// Calls `on_page` with the page number before each page is requested
+    async fn merged_prs(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        on_page: &(dyn Fn(u32) + Send + Sync),
+    ) -> Result<Vec<PullRequest>>;
+
+    /// Reads a file from the repo's default branch, e.g. its CHANGELOG.md
+    /// Returns None if the file doesn't exist
+    async fn file_contents(&self, _repo: &Repo, _path: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Lists the paths a merged change touched, used to scope subscriptions to paths
+    async fn changed_files(&self, _repo: &Repo, _number: u64) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the comments on a merged change, oldest first
+    async fn pr_comments(&self, _repo: &Repo, _number: u64) -> Result<Vec<PrComment>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the reviews submitted on a merged change, oldest first
+    async fn pr_reviews(&self, _repo: &Repo, _number: u64) -> Result<Vec<GitHubReview>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the messages of a merged change's commits, oldest first
+    async fn commit_messages(&self, _repo: &Repo, _number: u64) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Measures a merged change, used to score its impact
+    async fn pr_stats(&self, _repo: &Repo, _number: u64) -> Result<PrStats> {
+        Ok(PrStats::default())
+    }
+
+    /// Lists the successful deployments to an environment since a point in time, oldest first
+    async fn deployments(
+        &self,
+        _repo: &Repo,
+        _environment: &str,
+        _since: DateTime<Utc>,
+    ) -> Result<Vec<GitHubDeployment>> {
+        Ok(Vec::new())
+    }
+
+    /// True when commit `sha` is part of `head`'s history
+    async fn commit_reaches(&self, _repo: &Repo, _sha: &str, _head: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Fetches the CI results reported for a commit
+    async fn commit_checks(&self, _repo: &Repo, _sha: &str) -> Result<CommitChecks> {
+        Ok(CommitChecks::default())
+    }
+
+    /// Reads a project-board field, e.g. "Status", of the PR's linked issue or the PR itself
+    async fn project_status(
+        &self,
+        _repo: &Repo,
+        _number: u64,
+        _field: &str,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Lists the repo's published releases, newest first, used to name changelogs after them
+    async fn releases(&self, _repo: &Repo) -> Result<Vec<GitHubRelease>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the security advisories published for the repo
+    async fn security_advisories(&self, _repo: &Repo) -> Result<Vec<SecurityAdvisory>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the repo's Dependabot alerts that were fixed
+    async fn fixed_dependabot_alerts(&self, _repo: &Repo) -> Result<Vec<DependabotAlert>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the repo's most recently updated discussions
+    async fn discussions(&self, _repo: &Repo) -> Result<Vec<GitHubDiscussion>> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::changelog::PrContext;
+use crate::clock;
+use crate::contributors::Contributors;
+
+/// Figures about a changelog's PRs, counted from the fetched data rather than by the AI
+#[derive(Debug, PartialEq)]
+pub struct Statistics {
+    pub prs: usize,
+    /// PR authors and co-authors, as the contributors section counts them
+    pub contributors: usize,
+    /// Lines added and deleted, None when the PR sizes weren't fetched
+    pub additions: Option<u64>,
+    pub deletions: Option<u64>,
+    /// The day with the most merges in the configured timezone, and how many
+    pub busiest_day: Option<(NaiveDate, usize)>,
+}
+
+impl Statistics {
+    pub fn collect(contexts: &[PrContext]) -> Self {
+        let sizes: Option<Vec<_>> = contexts.iter().map(|ctx| ctx.stats.as_ref()).collect();
+        let sizes = sizes.filter(|sizes| !sizes.is_empty());
+
+        let mut merges: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for merged_at in contexts.iter().filter_map(|ctx| ctx.pr.merged_at) {
+            *merges.entry(clock::zone().date_of(merged_at)).or_default() += 1;
+        }
+        // The earliest of equally busy days
+        let busiest_day = merges.into_iter().rev().max_by_key(|(_, count)| *count);
+
+        Self {
+            prs: contexts.len(),
+            contributors: Contributors::collect(contexts).names.len(),
+            additions: sizes.as_ref().map(|s| s.iter().map(|s| s.additions).sum()),
+            deletions: sizes.as_ref().map(|s| s.iter().map(|s| s.deletions).sum()),
+            busiest_day,
+        }
+    }
+
+    /// Renders the figures as a markdown section, None for a changelog without PRs
+    pub fn render(&self) -> Option<String> {
+        if self.prs == 0 {
+            return None;
+        }
+
+        let mut section = format!(
+            "## Stats\n\n- Pull requests: {}\n- Contributors: {}\n",
+            self.prs, self.contributors
+        );
+        if let (Some(additions), Some(deletions)) = (self.additions, self.deletions) {
+            section.push_str(&format!(
+                "- Lines changed: +{} / -{}\n",
+                additions, deletions
+            ));
+        }
+        if let Some((day, count)) = self.busiest_day {
+            let merges = match count {
+                1 => "1 merge".to_string(),
+                n => format!("{} merges", n),
+            };
+            section.push_str(&format!(
+                "- Busiest day: {} ({})\n",
+                day.format("%A, %B %-d"),
+                merges
+            ));
+        }
+        Some(section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    use crate::github::{GitHubUser, PrStats};
+
+    fn context(number: u64, author: &str, day: u32, additions: u64) -> PrContext {
+        let mut ctx = PrContext::fixture(number, &format!("PR {}", number));
+        ctx.pr.merged_at = Some(Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap());
+        ctx.pr.user = Some(GitHubUser {
+            login: author.to_string(),
+        });
+        ctx.stats = Some(PrStats {
+            additions,
+            deletions: 1,
+            changed_files: 1,
+        });
+        ctx
+    }
+
+    #[test]
+    fn test_figures_are_counted_from_the_prs() {
+        let contexts = vec![
+            context(1, "alice", 4, 10),
+            context(2, "bob", 5, 20),
+            context(3, "Alice", 5, 30),
+            context(4, "carol", 6, 40),
+        ];
+
+        let stats = Statistics::collect(&contexts);
+        assert_eq!(stats.contributors, 3);
+        assert_eq!(stats.additions, Some(100));
+        assert_eq!(
+            stats.render().unwrap(),
+            "## Stats\n\n- Pull requests: 4\n- Contributors: 3\n- Lines changed: +100 / -4\n- Busiest day: Tuesday, March 5 (2 merges)\n"
+        );
+    }
+}
@@ -0,0 +1,1053 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior, params};
+use serde::Deserialize;
+
+use crate::ai::{RateLimitInfo, TokenUsage};
+use crate::changelog::PrContext;
+use crate::compare::RunPr;
+use crate::config::{AIProvider, Repo, TimePeriod};
+use crate::github::{PrStats, PullRequest};
+use crate::history::HistoryEntry;
+use crate::jira::JiraIssue;
+use crate::summary_cache::CachedSummary;
+use crate::usage::UsageEntry;
+
+const DB_FILE: &str = "gazette.db";
+
+/// Cached Jira issues older than this are fetched again
+const JIRA_CACHE_TTL_HOURS: i64 = 6;
+
+/// Schema migrations, applied in order; the database's user_version counts the applied ones
+const MIGRATIONS: [&str; 10] = [
+    "
+    CREATE TABLE pr_snapshots (
+        repo TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        body TEXT,
+        author TEXT,
+        url TEXT NOT NULL,
+        merged_at TEXT,
+        jira_keys TEXT NOT NULL DEFAULT '',
+        captured_at TEXT NOT NULL,
+        PRIMARY KEY (repo, number)
+    );
+    CREATE INDEX pr_snapshots_merged_at ON pr_snapshots (merged_at);
+
+    CREATE TABLE last_runs (
+        repo TEXT PRIMARY KEY,
+        last_run TEXT NOT NULL
+    );
+
+    CREATE TABLE jira_issues (
+        key TEXT PRIMARY KEY,
+        issue TEXT NOT NULL,
+        fetched_at TEXT NOT NULL
+    );
+
+    CREATE TABLE usage (
+        id INTEGER PRIMARY KEY,
+        recorded_at TEXT NOT NULL,
+        provider TEXT NOT NULL,
+        model TEXT NOT NULL,
+        requests INTEGER NOT NULL,
+        prompt_tokens INTEGER NOT NULL,
+        completion_tokens INTEGER NOT NULL,
+        cost_usd REAL NOT NULL
+    );
+
+    CREATE TABLE history (
+        path TEXT PRIMARY KEY,
+        repo TEXT NOT NULL,
+        repo_path TEXT NOT NULL,
+        generated_at TEXT NOT NULL,
+        period TEXT NOT NULL
+    );
+
+    CREATE TABLE pr_summaries (
+        key TEXT PRIMARY KEY,
+        fingerprint INTEGER NOT NULL,
+        summary TEXT NOT NULL
+    );
+    ",
+    "
+    CREATE TABLE reported_prs (
+        repo TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        path TEXT NOT NULL,
+        reported_at TEXT NOT NULL,
+        PRIMARY KEY (repo, number)
+    );
+    ",
+    "
+    CREATE TABLE run_contexts (
+        repo TEXT PRIMARY KEY,
+        period TEXT NOT NULL,
+        contexts TEXT NOT NULL,
+        captured_at TEXT NOT NULL
+    );
+    ",
+    "
+    CREATE TABLE runs (
+        id INTEGER PRIMARY KEY,
+        repo TEXT NOT NULL,
+        period TEXT NOT NULL,
+        path TEXT NOT NULL,
+        prs TEXT NOT NULL,
+        created_at TEXT NOT NULL
+    );
+    CREATE INDEX runs_repo ON runs (repo, id);
+    ",
+    "
+    CREATE TABLE pr_cache (
+        repo TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        updated_at TEXT NOT NULL,
+        merged_at TEXT NOT NULL,
+        pr TEXT NOT NULL,
+        PRIMARY KEY (repo, number)
+    );
+    CREATE INDEX pr_cache_merged_at ON pr_cache (repo, merged_at);
+
+    CREATE TABLE pr_syncs (
+        repo TEXT PRIMARY KEY,
+        synced_at TEXT NOT NULL,
+        covered_since TEXT NOT NULL
+    );
+    ",
+    "
+    CREATE TABLE pr_files (
+        repo TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        files TEXT NOT NULL,
+        PRIMARY KEY (repo, number)
+    );
+    ",
+    "
+    CREATE TABLE ai_rate_limits (
+        provider TEXT PRIMARY KEY,
+        model TEXT NOT NULL,
+        info TEXT NOT NULL,
+        observed_at TEXT NOT NULL
+    );
+    ",
+    "
+    CREATE TABLE pr_stats (
+        repo TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        stats TEXT NOT NULL,
+        PRIMARY KEY (repo, number)
+    );
+    ",
+    "
+    CREATE TABLE jira_comments (
+        issue_key TEXT NOT NULL,
+        path TEXT NOT NULL,
+        posted_at TEXT NOT NULL,
+        PRIMARY KEY (issue_key, path)
+    );
+    ",
+    // One row per report rather than per PR, so a release-notes run or another window doesn't
+    // replace the report an earlier changelog made
+    "
+    CREATE TABLE reported_prs_by_file (
+        repo TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        path TEXT NOT NULL,
+        reported_at TEXT NOT NULL,
+        PRIMARY KEY (repo, number, path)
+    );
+    INSERT INTO reported_prs_by_file SELECT repo, number, path, reported_at FROM reported_prs;
+    DROP TABLE reported_prs;
+    ALTER TABLE reported_prs_by_file RENAME TO reported_prs;
+    ",
+];
+
+/// JSON state files from before the store, imported once and renamed to `<file>.migrated`
+const LEGACY_FILES: [&str; 4] = [
+    "usage.json",
+    "daemon_state.json",
+    "history.json",
+    "pr_summaries.json",
+];
+
+/// Layout of the daemon's daemon_state.json
+#[derive(Deserialize)]
+struct LegacyDaemonState {
+    #[serde(default)]
+    last_runs: HashMap<String, DateTime<Utc>>,
+}
+
+/// A merged PR captured by a previous run
+#[derive(Debug, Clone)]
+pub struct PrSnapshot {
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub author: Option<String>,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub jira_keys: Vec<String>,
+}
+
+/// How far back a repo's PR cache is complete
+#[derive(Debug, Clone, Copy)]
+pub struct PrSync {
+    /// When the cache was last brought up to date
+    pub synced_at: DateTime<Utc>,
+    /// Every merged PR updated after this is in the cache
+    pub covered_since: DateTime<Utc>,
+}
+
+/// The PR and Jira context a repo's last changelog was generated from
+pub struct RunContext {
+    pub period: TimePeriod,
+    pub contexts: Vec<PrContext>,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// The quota a provider reported with its latest response
+#[derive(Debug, Clone)]
+pub struct ObservedRateLimit {
+    pub model: String,
+    pub info: RateLimitInfo,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// A saved changelog run and the PRs it covered
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub id: i64,
+    /// Description of the period the run covered
+    pub period: String,
+    pub path: PathBuf,
+    pub prs: Vec<RunPr>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Where and when a PR was last included in a saved changelog
+#[derive(Debug, Clone)]
+pub struct PrReport {
+    pub number: u64,
+    pub path: PathBuf,
+    pub reported_at: DateTime<Utc>,
+}
+
+/// Embedded SQLite database holding gazette's run state, stored in gazette.db
+/// Connections are cheap, so callers open one per operation instead of sharing it
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Opens gazette.db, creating it and importing the legacy JSON files on first use
+    pub fn open() -> Result<Self> {
+        let conn = Connection::open(DB_FILE).context("Failed to open gazette.db")?;
+        conn.busy_timeout(Duration::from_secs(5))
+            .context("Failed to configure gazette.db")?;
+
+        let mut store = Self { conn };
+        store.migrate(true)?;
+        Ok(store)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        let mut store = Self {
+            conn: Connection::open_in_memory()?,
+        };
+        store.migrate(false)?;
+        Ok(store)
+    }
+
+    fn migrate(&mut self, import_legacy: bool) -> Result<()> {
+        // An immediate transaction keeps concurrent runs from migrating twice
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let version: i32 = tx.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        if version as usize >= MIGRATIONS.len() {
+            return Ok(());
+        }
+
+        for migration in &MIGRATIONS[version as usize..] {
+            tx.execute_batch(migration)
+                .context("Failed to migrate gazette.db schema")?;
+        }
+        let imported = if import_legacy && version == 0 {
+            import_legacy_files(&tx)?
+        } else {
+            Vec::new()
+        };
+        tx.pragma_update(None, "user_version", MIGRATIONS.len() as i32)?;
+        tx.commit().context("Failed to migrate gazette.db")?;
+
+        for file in imported {
+            let _ = fs::rename(file, format!("{}.migrated", file));
+        }
+
+        Ok(())
+    }
+
+    /// Saves the PRs a run was generated from, replacing older snapshots of the same PRs
+    pub fn record_prs(&mut self, repo: &Repo, contexts: &[PrContext]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        let now = Utc::now();
+
+        for ctx in contexts {
+            let jira_keys: Vec<&str> = ctx.jira_issues.iter().map(|i| i.key.as_str()).collect();
+            tx.execute(
+                "INSERT OR REPLACE INTO pr_snapshots
+                    (repo, number, title, body, author, url, merged_at, jira_keys, captured_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    repo.full_name(),
+                    ctx.pr.number as i64,
+                    ctx.pr.title,
+                    ctx.pr.body,
+                    ctx.pr.user.as_ref().map(|u| u.login.as_str()),
+                    ctx.pr.html_url,
+                    ctx.pr.merged_at,
+                    jira_keys.join(","),
+                    now,
+                ],
+            )?;
+        }
+
+        tx.commit().context("Failed to save PR snapshots")
+    }
+
+    /// Keeps the context of a run so the changelog can be regenerated without refetching it
+    pub fn save_run_context(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        contexts: &[PrContext],
+    ) -> Result<()> {
+        let period = serde_json::to_string(&period).context("Failed to serialize period")?;
+        let contexts = serde_json::to_string(contexts).context("Failed to serialize PR context")?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO run_contexts (repo, period, contexts, captured_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![repo.key(), period, contexts, Utc::now()],
+        )?;
+        Ok(())
+    }
+
+    pub fn run_context(&self, repo: &Repo) -> Result<Option<RunContext>> {
+        let row: Option<(String, String, DateTime<Utc>)> = self
+            .conn
+            .query_row(
+                "SELECT period, contexts, captured_at FROM run_contexts WHERE repo = ?1",
+                params![repo.key()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+
+        row.map(|(period, contexts, captured_at)| {
+            Ok(RunContext {
+                period: serde_json::from_str(&period).context("Failed to parse cached period")?,
+                contexts: serde_json::from_str(&contexts)
+                    .context("Failed to parse cached PR context")?,
+                captured_at,
+            })
+        })
+        .transpose()
+    }
+
+    pub fn record_run(
+        &self,
+        repo: &Repo,
+        period: TimePeriod,
+        path: &Path,
+        prs: &[RunPr],
+    ) -> Result<()> {
+        let prs = serde_json::to_string(prs).context("Failed to serialize run")?;
+        self.conn.execute(
+            "INSERT INTO runs (repo, period, path, prs, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                repo.key(),
+                period.description(),
+                path.to_string_lossy(),
+                prs,
+                Utc::now()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Saved runs of `repo`, newest first
+    pub fn runs(&self, repo: &Repo) -> Result<Vec<RunRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, period, path, prs, created_at FROM runs WHERE repo = ?1 ORDER BY id DESC",
+        )?;
+
+        let rows = stmt.query_map(params![repo.key()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get(4)?,
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (id, period, path, prs, created_at) = row?;
+            Ok(RunRecord {
+                id,
+                period,
+                path: PathBuf::from(path),
+                prs: serde_json::from_str(&prs).context("Failed to parse run")?,
+                created_at,
+            })
+        })
+        .collect()
+    }
+
+    /// PRs merged since `since`, optionally limited to one repo, oldest first
+    pub fn shipped_since(
+        &self,
+        since: DateTime<Utc>,
+        repo: Option<&str>,
+    ) -> Result<Vec<PrSnapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT repo, number, title, author, merged_at, jira_keys FROM pr_snapshots
+             WHERE merged_at > ?1 AND (?2 IS NULL OR repo = ?2)
+             ORDER BY repo, merged_at",
+        )?;
+
+        let rows = stmt.query_map(params![since, repo], |row| {
+            let jira_keys: String = row.get(5)?;
+            Ok(PrSnapshot {
+                repo: row.get(0)?,
+                number: row.get::<_, i64>(1)? as u64,
+                title: row.get(2)?,
+                author: row.get(3)?,
+                merged_at: row.get(4)?,
+                jira_keys: jira_keys
+                    .split(',')
+                    .filter(|k| !k.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<_>>()
+            .context("Failed to query PR snapshots")
+    }
+
+    /// Every report of `repo`'s PRs, one per saved file each PR appeared in
+    pub fn reported_prs(&self, repo: &Repo) -> Result<Vec<PrReport>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT number, path, reported_at FROM reported_prs WHERE repo = ?1")?;
+
+        let rows = stmt.query_map(params![repo.key()], |row| {
+            Ok(PrReport {
+                number: row.get::<_, i64>(0)? as u64,
+                path: PathBuf::from(row.get::<_, String>(1)?),
+                reported_at: row.get(2)?,
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<_>>()
+            .context("Failed to read reported PRs")
+    }
+
+    /// Records that the given PRs were included in the changelog saved at `path`
+    pub fn record_reported(&mut self, repo: &Repo, numbers: &[u64], path: &Path) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        let now = Utc::now();
+
+        for number in numbers {
+            tx.execute(
+                "INSERT OR REPLACE INTO reported_prs (repo, number, path, reported_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![repo.key(), *number as i64, path.to_string_lossy(), now],
+            )?;
+        }
+
+        tx.commit().context("Failed to record reported PRs")
+    }
+
+    pub fn last_runs(&self) -> Result<Vec<(String, DateTime<Utc>)>> {
+        let mut stmt = self.conn.prepare("SELECT repo, last_run FROM last_runs")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        rows.collect::<rusqlite::Result<_>>()
+            .context("Failed to read last runs")
+    }
+
+    pub fn set_last_runs<'a>(
+        &mut self,
+        runs: impl IntoIterator<Item = (&'a String, &'a DateTime<Utc>)>,
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for (repo, last_run) in runs {
+            tx.execute(
+                "INSERT OR REPLACE INTO last_runs (repo, last_run) VALUES (?1, ?2)",
+                params![repo, last_run],
+            )?;
+        }
+        tx.commit().context("Failed to save last runs")
+    }
+
+    /// Returns a cached Jira issue if it was fetched recently enough
+    pub fn jira_issue(&self, key: &str) -> Result<Option<JiraIssue>> {
+        let fresh_after = Utc::now() - chrono::Duration::hours(JIRA_CACHE_TTL_HOURS);
+        let issue: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT issue FROM jira_issues WHERE key = ?1 AND fetched_at > ?2",
+                params![key, fresh_after],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        issue
+            .map(|json| serde_json::from_str(&json).context("Failed to parse cached Jira issue"))
+            .transpose()
+    }
+
+    pub fn pr_sync(&self, repo: &Repo) -> Result<Option<PrSync>> {
+        let sync = self
+            .conn
+            .query_row(
+                "SELECT synced_at, covered_since FROM pr_syncs WHERE repo = ?1",
+                params![repo.full_name()],
+                |row| {
+                    Ok(PrSync {
+                        synced_at: row.get(0)?,
+                        covered_since: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(sync)
+    }
+
+    /// Merges freshly fetched PRs into the repo's PR cache and records the sync
+    pub fn cache_prs(
+        &mut self,
+        repo: &Repo,
+        prs: &[(DateTime<Utc>, PullRequest)],
+        synced_at: DateTime<Utc>,
+        covered_since: DateTime<Utc>,
+    ) -> Result<()> {
+        let name = repo.full_name();
+        let tx = self.conn.transaction()?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO pr_cache (repo, number, updated_at, merged_at, pr)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for (updated_at, pr) in prs {
+                let Some(merged_at) = pr.merged_at else {
+                    continue;
+                };
+                let json = serde_json::to_string(pr).context("Failed to serialize PR")?;
+                insert.execute(params![name, pr.number as i64, updated_at, merged_at, json])?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO pr_syncs (repo, synced_at, covered_since) VALUES (?1, ?2, ?3)",
+            params![name, synced_at, covered_since],
+        )?;
+        tx.commit().context("Failed to update the PR cache")
+    }
+
+    /// Cached PRs merged after `start` and up to `end`, most recently merged first
+    pub fn cached_merged_prs(
+        &self,
+        repo: &Repo,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<PullRequest>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pr FROM pr_cache WHERE repo = ?1 AND merged_at > ?2 AND merged_at <= ?3
+             ORDER BY merged_at DESC",
+        )?;
+        let rows = stmt.query_map(params![repo.full_name(), start, end], |row| {
+            row.get::<_, String>(0)
+        })?;
+
+        rows.map(|json| serde_json::from_str(&json?).context("Failed to parse cached PR"))
+            .collect()
+    }
+
+    /// The files a merged PR changed, as cached by an earlier lookup
+    pub fn pr_files(&self, repo: &Repo, number: u64) -> Result<Option<Vec<String>>> {
+        let files: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT files FROM pr_files WHERE repo = ?1 AND number = ?2",
+                params![repo.full_name(), number as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        files
+            .map(|json| serde_json::from_str(&json).context("Failed to parse cached PR files"))
+            .transpose()
+    }
+
+    /// Caches the files of a merged PR, which don't change anymore
+    pub fn cache_pr_files(&self, repo: &Repo, number: u64, files: &[String]) -> Result<()> {
+        let json = serde_json::to_string(files).context("Failed to serialize PR files")?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO pr_files (repo, number, files) VALUES (?1, ?2, ?3)",
+            params![repo.full_name(), number as i64, json],
+        )?;
+        Ok(())
+    }
+
+    pub fn pr_stats(&self, repo: &Repo, number: u64) -> Result<Option<PrStats>> {
+        let stats: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT stats FROM pr_stats WHERE repo = ?1 AND number = ?2",
+                params![repo.full_name(), number as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        stats
+            .map(|json| serde_json::from_str(&json).context("Failed to parse cached PR stats"))
+            .transpose()
+    }
+
+    /// Caches the size of a merged PR, which doesn't change anymore
+    pub fn cache_pr_stats(&self, repo: &Repo, number: u64, stats: &PrStats) -> Result<()> {
+        let json = serde_json::to_string(stats).context("Failed to serialize PR stats")?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO pr_stats (repo, number, stats) VALUES (?1, ?2, ?3)",
+            params![repo.full_name(), number as i64, json],
+        )?;
+        Ok(())
+    }
+
+    /// Whether the changelog saved at `path` was already commented on the Jira issue
+    pub fn jira_commented(&self, issue_key: &str, path: &Path) -> Result<bool> {
+        let posted: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT posted_at FROM jira_comments WHERE issue_key = ?1 AND path = ?2",
+                params![issue_key, path.to_string_lossy()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(posted.is_some())
+    }
+
+    pub fn record_jira_comment(&self, issue_key: &str, path: &Path) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO jira_comments (issue_key, path, posted_at) VALUES (?1, ?2, ?3)",
+            params![issue_key, path.to_string_lossy(), Utc::now()],
+        )?;
+        Ok(())
+    }
+
+    pub fn cache_jira_issue(&self, issue: &JiraIssue) -> Result<()> {
+        let json = serde_json::to_string(issue).context("Failed to serialize Jira issue")?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO jira_issues (key, issue, fetched_at) VALUES (?1, ?2, ?3)",
+            params![issue.key, json, Utc::now()],
+        )?;
+        Ok(())
+    }
+
+    /// Records a single AI request and returns its estimated cost
+    pub fn record_usage(
+        &self,
+        provider: AIProvider,
+        model: &str,
+        usage: &TokenUsage,
+    ) -> Result<Option<f64>> {
+        let cost = crate::usage::estimate_cost(provider, model, usage);
+        insert_usage(
+            &self.conn,
+            &UsageEntry {
+                provider,
+                model: model.to_string(),
+                requests: 1,
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                cost_usd: cost.unwrap_or(0.0),
+            },
+        )?;
+        Ok(cost)
+    }
+
+    /// Usage accumulated per provider/model, in the order each model was first used
+    /// Keeps the quota a provider reported with its latest response
+    pub fn record_ai_rate_limit(
+        &self,
+        provider: AIProvider,
+        model: &str,
+        info: &RateLimitInfo,
+    ) -> Result<()> {
+        let json = serde_json::to_string(info).context("Failed to serialize rate limit")?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO ai_rate_limits (provider, model, info, observed_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![provider_name(provider), model, json, Utc::now()],
+        )?;
+        Ok(())
+    }
+
+    pub fn ai_rate_limit(&self, provider: AIProvider) -> Result<Option<ObservedRateLimit>> {
+        let row: Option<(String, String, DateTime<Utc>)> = self
+            .conn
+            .query_row(
+                "SELECT model, info, observed_at FROM ai_rate_limits WHERE provider = ?1",
+                params![provider_name(provider)],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+
+        row.map(|(model, info, observed_at)| {
+            Ok(ObservedRateLimit {
+                model,
+                info: serde_json::from_str(&info).context("Failed to parse cached rate limit")?,
+                observed_at,
+            })
+        })
+        .transpose()
+    }
+
+    pub fn usage_by_model(&self) -> Result<Vec<UsageEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT provider, model, SUM(requests), SUM(prompt_tokens),
+                    SUM(completion_tokens), SUM(cost_usd)
+             FROM usage GROUP BY provider, model ORDER BY MIN(id)",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let provider: String = row.get(0)?;
+            Ok(UsageEntry {
+                provider: AIProvider::from_name(&provider).unwrap_or_default(),
+                model: row.get(1)?,
+                requests: row.get::<_, i64>(2)? as u64,
+                prompt_tokens: row.get::<_, i64>(3)? as u64,
+                completion_tokens: row.get::<_, i64>(4)? as u64,
+                cost_usd: row.get(5)?,
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<_>>()
+            .context("Failed to read AI usage")
+    }
+
+    /// All changelogs recorded in the history
+    pub fn history(&self) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT repo, path, repo_path, generated_at, period FROM history")?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (repo, path, repo_path, generated_at, period) = row?;
+            Ok(HistoryEntry {
+                repo: serde_json::from_str(&repo).context("Failed to parse history entry")?,
+                path: PathBuf::from(path),
+                repo_path,
+                generated_at,
+                period,
+            })
+        })
+        .collect()
+    }
+
+    /// Adds a changelog to the history, replacing an older entry for the same file
+    pub fn record_history(&self, entry: &HistoryEntry) -> Result<()> {
+        insert_history(&self.conn, entry)
+    }
+
+    pub fn summaries(&self) -> Result<Vec<(String, CachedSummary)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT key, fingerprint, summary FROM pr_summaries")?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                CachedSummary {
+                    fingerprint: row.get::<_, i64>(1)? as u64,
+                    summary: row.get(2)?,
+                },
+            ))
+        })?;
+
+        rows.collect::<rusqlite::Result<_>>()
+            .context("Failed to read PR summaries")
+    }
+
+    pub fn save_summaries<'a>(
+        &mut self,
+        summaries: impl IntoIterator<Item = (&'a String, &'a CachedSummary)>,
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for (key, cached) in summaries {
+            insert_summary(&tx, key, cached)?;
+        }
+        tx.commit().context("Failed to save PR summaries")
+    }
+}
+
+fn provider_name(provider: AIProvider) -> String {
+    format!("{:?}", provider)
+}
+
+fn insert_usage(conn: &Connection, entry: &UsageEntry) -> Result<()> {
+    conn.execute(
+        "INSERT INTO usage
+            (recorded_at, provider, model, requests, prompt_tokens, completion_tokens, cost_usd)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            Utc::now(),
+            provider_name(entry.provider),
+            entry.model,
+            entry.requests as i64,
+            entry.prompt_tokens as i64,
+            entry.completion_tokens as i64,
+            entry.cost_usd,
+        ],
+    )?;
+    Ok(())
+}
+
+fn insert_history(conn: &Connection, entry: &HistoryEntry) -> Result<()> {
+    let repo = serde_json::to_string(&entry.repo).context("Failed to serialize history entry")?;
+    conn.execute(
+        "INSERT OR REPLACE INTO history (path, repo, repo_path, generated_at, period)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            entry.path.to_string_lossy(),
+            repo,
+            entry.repo_path,
+            entry.generated_at,
+            entry.period,
+        ],
+    )?;
+    Ok(())
+}
+
+fn insert_summary(conn: &Connection, key: &str, cached: &CachedSummary) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO pr_summaries (key, fingerprint, summary) VALUES (?1, ?2, ?3)",
+        params![key, cached.fingerprint as i64, cached.summary],
+    )?;
+    Ok(())
+}
+
+/// Copies the contents of the legacy JSON files into the new schema
+/// Returns the files that were imported so they can be renamed once the migration commits
+fn import_legacy_files(tx: &Transaction<'_>) -> Result<Vec<&'static str>> {
+    let mut imported = Vec::new();
+
+    for file in LEGACY_FILES {
+        let path = Path::new(file);
+        if !path.exists() {
+            continue;
+        }
+
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {}", file))?;
+        let parse_error = || format!("Failed to import {}", file);
+
+        match file {
+            "usage.json" => {
+                let ledger: crate::usage::UsageLedger =
+                    serde_json::from_str(&content).with_context(parse_error)?;
+                for entry in &ledger.entries {
+                    insert_usage(tx, entry)?;
+                }
+            }
+            "daemon_state.json" => {
+                let state: LegacyDaemonState =
+                    serde_json::from_str(&content).with_context(parse_error)?;
+                for (repo, last_run) in &state.last_runs {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO last_runs (repo, last_run) VALUES (?1, ?2)",
+                        params![repo, last_run],
+                    )?;
+                }
+            }
+            "history.json" => {
+                let history: crate::history::History =
+                    serde_json::from_str(&content).with_context(parse_error)?;
+                for entry in &history.entries {
+                    insert_history(tx, entry)?;
+                }
+            }
+            "pr_summaries.json" => {
+                let cache: crate::summary_cache::SummaryCache =
+                    serde_json::from_str(&content).with_context(parse_error)?;
+                for (key, cached) in &cache.summaries {
+                    insert_summary(tx, key, cached)?;
+                }
+            }
+            _ => unreachable!("unknown legacy file"),
+        }
+
+        imported.push(file);
+    }
+
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_accumulates_per_model() {
+        let store = Store::open_in_memory().unwrap();
+        let usage = TokenUsage {
+            prompt_tokens: 1_000_000,
+            completion_tokens: 100_000,
+            cached_tokens: 0,
+            cache_write_tokens: 0,
+        };
+
+        let cost = store
+            .record_usage(AIProvider::OpenAI, "gpt-4o", &usage)
+            .unwrap();
+        store
+            .record_usage(AIProvider::OpenAI, "gpt-4o", &usage)
+            .unwrap();
+        store
+            .record_usage(AIProvider::Ollama, "llama3.2", &usage)
+            .unwrap();
+
+        let entries = store.usage_by_model().unwrap();
+        assert_eq!(cost, Some(3.5));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].provider, AIProvider::OpenAI);
+        assert_eq!(entries[0].requests, 2);
+        assert!((entries[0].cost_usd - 7.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_shipped_since_filters_by_merge_date() {
+        let mut store = Store::open_in_memory().unwrap();
+        let repo = Repo::new("acme", "backend");
+        let now = Utc::now();
+        let pr = |number: u64, days_ago: i64| {
+            let mut ctx = PrContext::fixture(number, &format!("PR {}", number));
+            ctx.pr.merged_at = Some(now - chrono::Duration::days(days_ago));
+            ctx
+        };
+
+        store
+            .record_prs(&repo, &[pr(1, 40), pr(2, 10), pr(3, 2)])
+            .unwrap();
+
+        let shipped = store
+            .shipped_since(now - chrono::Duration::days(30), None)
+            .unwrap();
+        let numbers: Vec<u64> = shipped.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![2, 3]);
+
+        let other = store
+            .shipped_since(now - chrono::Duration::days(30), Some("acme/frontend"))
+            .unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn test_run_context_roundtrip() {
+        let store = Store::open_in_memory().unwrap();
+        let repo = Repo::new("acme", "backend");
+        let mut context = PrContext::fixture(7, "Add OAuth2");
+        context.pr.body = Some("Closes AUTH-1".to_string());
+        context.pr.merged_at = Some(Utc::now());
+        context.previously_reported = true;
+        let contexts = vec![context];
+
+        assert!(store.run_context(&repo).unwrap().is_none());
+        store
+            .save_run_context(&repo, TimePeriod::Last12Hours, &contexts)
+            .unwrap();
+
+        let run = store.run_context(&repo).unwrap().unwrap();
+        assert_eq!(run.period, TimePeriod::Last12Hours);
+        assert_eq!(run.contexts.len(), 1);
+        assert_eq!(run.contexts[0].pr.title, "Add OAuth2");
+        assert!(run.contexts[0].previously_reported);
+    }
+
+    #[test]
+    fn test_jira_comments_are_recorded_per_changelog() {
+        let store = Store::open_in_memory().unwrap();
+        let today = Path::new("changelog_web_2026-10-14.md");
+        let tomorrow = Path::new("changelog_web_2026-10-15.md");
+
+        assert!(!store.jira_commented("AUTH-7", today).unwrap());
+        store.record_jira_comment("AUTH-7", today).unwrap();
+        store.record_jira_comment("AUTH-7", today).unwrap();
+        assert!(store.jira_commented("AUTH-7", today).unwrap());
+        assert!(!store.jira_commented("AUTH-7", tomorrow).unwrap());
+        assert!(!store.jira_commented("AUTH-8", today).unwrap());
+    }
+
+    #[test]
+    fn test_pr_cache_keeps_the_latest_copy_of_each_pr() {
+        let mut store = Store::open_in_memory().unwrap();
+        let repo = Repo::new("acme", "backend");
+        let now = Utc::now();
+        let pr = |number: u64, title: &str, hours_ago: i64| {
+            let merged_at = now - chrono::Duration::hours(hours_ago);
+            let pr = crate::github::PullRequest {
+                number,
+                title: title.to_string(),
+                merged_at: Some(merged_at),
+                ..Default::default()
+            };
+            (merged_at, pr)
+        };
+
+        assert!(store.pr_sync(&repo).unwrap().is_none());
+        let covered = now - chrono::Duration::days(2);
+        store
+            .cache_prs(
+                &repo,
+                &[pr(1, "Add SSO", 30), pr(2, "Fix login", 5)],
+                now,
+                covered,
+            )
+            .unwrap();
+        store
+            .cache_prs(&repo, &[pr(1, "Add SAML SSO", 30)], now, covered)
+            .unwrap();
+
+        let sync = store.pr_sync(&repo).unwrap().unwrap();
+        assert_eq!(sync.covered_since, covered);
+
+        let day = store
+            .cached_merged_prs(&repo, now - chrono::Duration::hours(24), now)
+            .unwrap();
+        assert_eq!(day.len(), 1);
+        assert_eq!(day[0].number, 2);
+
+        let all = store.cached_merged_prs(&repo, covered, now).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].title, "Add SAML SSO");
+    }
+}
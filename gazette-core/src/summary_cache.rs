@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Repo;
+use crate::store::Store;
+
+/// A per-PR summary produced by the first pass of two-pass generation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachedSummary {
+    /// Hash of the model and PR context the summary was generated from
+    pub fingerprint: u64,
+    pub summary: String,
+}
+
+/// Cache of per-PR summaries, also the format of the legacy pr_summaries.json
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SummaryCache {
+    #[serde(default)]
+    pub summaries: HashMap<String, CachedSummary>,
+}
+
+impl SummaryCache {
+    /// Loads the cached summaries from the store
+    pub fn load() -> Result<Self> {
+        Ok(Self {
+            summaries: Store::open()?.summaries()?.into_iter().collect(),
+        })
+    }
+
+    /// Writes the cached summaries back to the store
+    pub fn save(&self) -> Result<()> {
+        Store::open()?.save_summaries(&self.summaries)
+    }
+
+    /// Returns the cached summary if it was generated from the same model and context
+    pub fn get(&self, repo: &Repo, number: u64, fingerprint: u64) -> Option<&str> {
+        self.summaries
+            .get(&cache_key(repo, number))
+            .filter(|cached| cached.fingerprint == fingerprint)
+            .map(|cached| cached.summary.as_str())
+    }
+
+    pub fn insert(&mut self, repo: &Repo, number: u64, fingerprint: u64, summary: String) {
+        self.summaries.insert(
+            cache_key(repo, number),
+            CachedSummary {
+                fingerprint,
+                summary,
+            },
+        );
+    }
+}
+
+fn cache_key(repo: &Repo, number: u64) -> String {
+    format!("{}#{}", repo.full_name(), number)
+}
+
+/// Fingerprints the inputs of a per-PR summary so edits to the PR invalidate the cache
+pub fn fingerprint(model: &str, pr_context: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    model.hash(&mut hasher);
+    pr_context.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,198 @@
+use crate::ai::{ChangelogCategory, StructuredChangelog};
+use crate::changelog::PrContext;
+use crate::config::Category;
+use crate::conventional;
+
+/// Categories kept whatever the taxonomy, as upgrading users look for them first
+pub const KEPT_CATEGORIES: [&str; 2] = ["Breaking Changes", "Upgrade Steps"];
+
+/// The configured category a PR's conventional-commit type or labels map it to
+pub fn category_for<'a>(categories: &'a [Category], ctx: &PrContext) -> Option<&'a Category> {
+    let kind = conventional::commit_type(&ctx.pr.title);
+    categories
+        .iter()
+        .find(|category| {
+            kind.as_deref()
+                .is_some_and(|kind| category.types.iter().any(|t| t.eq_ignore_ascii_case(kind)))
+        })
+        .or_else(|| {
+            categories.iter().find(|category| {
+                ctx.pr.labels.iter().any(|label| {
+                    category
+                        .labels
+                        .iter()
+                        .any(|l| l.eq_ignore_ascii_case(&label.name))
+                })
+            })
+        })
+}
+
+/// Moves every entry into the configured categories, in their configured order
+///
+/// An entry goes by the mapping hints of its PRs first, then by the name the AI gave its
+/// category, and lands in the last category when neither matches
+pub fn apply(
+    categories: &[Category],
+    changelog: StructuredChangelog,
+    contexts: &[PrContext],
+) -> StructuredChangelog {
+    if categories.is_empty() {
+        return changelog;
+    }
+
+    let mut kept: Vec<ChangelogCategory> = Vec::new();
+    let mut sorted: Vec<ChangelogCategory> = categories
+        .iter()
+        .map(|category| ChangelogCategory {
+            name: category.name.clone(),
+            entries: Vec::new(),
+        })
+        .collect();
+
+    for category in changelog.categories {
+        if KEPT_CATEGORIES
+            .iter()
+            .any(|name| category.name.trim().eq_ignore_ascii_case(name))
+            && !categories
+                .iter()
+                .any(|c| c.name.eq_ignore_ascii_case(category.name.trim()))
+        {
+            kept.push(category);
+            continue;
+        }
+
+        for entry in category.entries {
+            let hinted = contexts
+                .iter()
+                .filter(|ctx| entry.pr_numbers.contains(&ctx.pr.number))
+                .find_map(|ctx| category_for(categories, ctx));
+            let index = hinted
+                .and_then(|hinted| categories.iter().position(|c| c.name == hinted.name))
+                .or_else(|| {
+                    categories
+                        .iter()
+                        .position(|c| c.name.eq_ignore_ascii_case(category.name.trim()))
+                })
+                .unwrap_or(categories.len() - 1);
+            sorted[index].entries.push(entry);
+        }
+    }
+
+    kept.extend(sorted);
+    kept.retain(|category| !category.entries.is_empty());
+    StructuredChangelog { categories: kept }
+}
+
+/// Prompt note listing the only categories the changelog may use
+pub fn context_block(categories: &[Category]) -> Option<String> {
+    if categories.is_empty() {
+        return None;
+    }
+
+    let mut block = String::from(
+        "## Categories\nUse exactly these categories, in this order, and no others; a \"Breaking Changes\" category still goes first when there are breaking changes. Omit empty ones and put changes that fit nowhere else in the last one.\n",
+    );
+    for category in categories {
+        let mut hints = Vec::new();
+        if let Some(description) = &category.description {
+            hints.push(description.clone());
+        }
+        if !category.types.is_empty() {
+            hints.push(format!("commit types {}", category.types.join(", ")));
+        }
+        if !category.labels.is_empty() {
+            hints.push(format!("labels {}", category.labels.join(", ")));
+        }
+        if hints.is_empty() {
+            block.push_str(&format!("- {}\n", category.name));
+        } else {
+            block.push_str(&format!("- {} ({})\n", category.name, hints.join("; ")));
+        }
+    }
+    block.push('\n');
+    Some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ChangelogEntry;
+
+    fn category(name: &str, types: &[&str], labels: &[&str]) -> Category {
+        Category {
+            name: name.to_string(),
+            description: None,
+            types: types.iter().map(|t| t.to_string()).collect(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    fn context(number: u64, title: &str, labels: &[&str]) -> PrContext {
+        PrContext::fixture(number, title).labeled(labels)
+    }
+
+    fn entry(number: u64) -> ChangelogEntry {
+        ChangelogEntry {
+            summary: format!("Change {}", number),
+            pr_numbers: vec![number],
+            jira_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_entries_land_in_the_configured_categories() {
+        let categories = vec![
+            category("Security", &[], &["security"]),
+            category("Performance", &["perf"], &[]),
+            category("Developer Experience", &[], &[]),
+        ];
+        let contexts = vec![
+            context(1, "perf: cache sessions", &[]),
+            context(2, "Patch XSS", &["Security"]),
+            context(3, "Faster builds", &[]),
+            context(4, "Drop v1", &[]),
+        ];
+        let changelog = StructuredChangelog {
+            categories: vec![
+                ChangelogCategory {
+                    name: "Breaking Changes".to_string(),
+                    entries: vec![entry(4)],
+                },
+                ChangelogCategory {
+                    name: "Chores".to_string(),
+                    entries: vec![entry(1), entry(3)],
+                },
+                ChangelogCategory {
+                    name: "developer experience".to_string(),
+                    entries: vec![entry(2)],
+                },
+            ],
+        };
+
+        let applied = apply(&categories, changelog, &contexts);
+        let layout: Vec<(&str, Vec<u64>)> = applied
+            .categories
+            .iter()
+            .map(|c| {
+                (
+                    c.name.as_str(),
+                    c.entries.iter().map(|e| e.pr_numbers[0]).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                ("Breaking Changes", vec![4]),
+                ("Security", vec![2]),
+                ("Performance", vec![1]),
+                ("Developer Experience", vec![3]),
+            ]
+        );
+        assert!(
+            context_block(&categories)
+                .unwrap()
+                .contains("- Performance (commit types perf)")
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use tera::Tera;
+
+use crate::ai::StructuredChangelog;
+use crate::changelog::{PrContext, entry_refs, render_entry};
+use crate::clock;
+use crate::config::{Repo, TimePeriod};
+
+/// Variables available to document templates
+#[derive(Serialize, Debug)]
+pub struct TemplateContext {
+    /// Full "owner/name" of the repository
+    pub repo: String,
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub date: String,
+    pub generated_at: String,
+    pub period: String,
+    pub ai_provider: String,
+    pub ai_model: String,
+    pub pr_count: usize,
+    /// The generated changelog markdown, the main slot of the document
+    pub changelog: String,
+    /// Categorized entries; empty unless the structured changelog format is used
+    pub categories: Vec<TemplateCategory>,
+    pub pull_requests: Vec<TemplatePullRequest>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TemplateCategory {
+    pub name: String,
+    pub entries: Vec<TemplateEntry>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TemplateEntry {
+    pub summary: String,
+    /// Markdown links to the referenced PRs and Jira issues
+    pub refs: Vec<String>,
+    /// The entry rendered as a markdown list item
+    pub line: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TemplatePullRequest {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+}
+
+/// Details of the run that aren't part of the PR context
+pub struct RunInfo<'a> {
+    pub repo: &'a Repo,
+    pub period: TimePeriod,
+    pub ai_provider: &'a str,
+    pub ai_model: &'a str,
+    /// Jira site the entries' issue keys link to
+    pub jira_base_url: Option<&'a str>,
+}
+
+impl TemplateContext {
+    pub fn new(
+        run: &RunInfo<'_>,
+        changelog: &str,
+        structured: Option<&StructuredChangelog>,
+        contexts: &[PrContext],
+    ) -> Self {
+        let categories = structured
+            .map(|s| {
+                s.categories
+                    .iter()
+                    .filter(|c| !c.entries.is_empty())
+                    .map(|c| TemplateCategory {
+                        name: c.name.trim().to_string(),
+                        entries: c
+                            .entries
+                            .iter()
+                            .map(|e| TemplateEntry {
+                                summary: e.summary.trim().to_string(),
+                                refs: entry_refs(e, contexts, run.jira_base_url),
+                                line: render_entry(e, contexts, run.jira_base_url),
+                            })
+                            .collect(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            repo: run.repo.full_name(),
+            repo_owner: run.repo.owner.clone(),
+            repo_name: run.repo.name.clone(),
+            date: clock::today().format("%Y-%m-%d").to_string(),
+            generated_at: Utc::now().to_rfc3339(),
+            period: run.period.description(),
+            ai_provider: run.ai_provider.to_string(),
+            ai_model: run.ai_model.to_string(),
+            pr_count: contexts.len(),
+            changelog: changelog.to_string(),
+            categories,
+            pull_requests: contexts
+                .iter()
+                .map(|ctx| TemplatePullRequest {
+                    number: ctx.pr.number,
+                    title: ctx.pr.title.clone(),
+                    url: ctx.pr.html_url.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Renders a Tera template source with the given context
+/// Autoescaping is disabled since the output is markdown, not HTML
+pub fn render(template: &str, context: &TemplateContext) -> Result<String> {
+    let context =
+        tera::Context::from_serialize(context).context("Failed to build template context")?;
+
+    Tera::one_off(template, &context, false).context("Failed to render document template")
+}
+
+/// Renders the template file at `path`
+pub fn render_file(path: &str, context: &TemplateContext) -> Result<String> {
+    let template =
+        fs::read_to_string(path).with_context(|| format!("Failed to read template {}", path))?;
+
+    render(&template, context)
+}
+
+/// Checks that a template file exists and parses
+pub fn validate_file(path: &str) -> Result<()> {
+    let template =
+        fs::read_to_string(path).with_context(|| format!("Failed to read template {}", path))?;
+
+    let mut tera = Tera::default();
+    tera.add_raw_template(path, &template)
+        .context("Invalid document template")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_injects_changelog_slot() {
+        let repo = Repo::new("acme", "backend");
+        let run = RunInfo {
+            repo: &repo,
+            period: TimePeriod::Last24Hours,
+            ai_provider: "OpenAI",
+            ai_model: "gpt-4o",
+            jira_base_url: None,
+        };
+        let context = TemplateContext::new(&run, "## Features\n- Add OAuth2\n", None, &[]);
+
+        let rendered = render(
+            "---\nrepo: {{ repo }}\n---\n{{ changelog }}\n_Generated by {{ ai_model }} for the {{ period }}_",
+            &context,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "---\nrepo: acme/backend\n---\n## Features\n- Add OAuth2\n\n_Generated by gpt-4o for the last 24 hours_"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::Regex;
+
+/// Prefix marking an exclusion pattern as a glob rather than a regex
+const GLOB_PREFIX: &str = "glob:";
+
+/// Keeps routine PRs, e.g. dependency bumps, out of changelogs by their titles
+/// Patterns are regexes such as "^chore", or case-insensitive globs such as "glob:Bump *"
+pub struct TitleFilter {
+    regexes: Vec<Regex>,
+    globs: GlobSet,
+}
+
+impl TitleFilter {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a String>) -> Result<Self> {
+        let mut regexes = Vec::new();
+        let mut globs = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            let pattern = pattern.trim();
+            if let Some(glob) = pattern.strip_prefix(GLOB_PREFIX) {
+                globs.add(
+                    GlobBuilder::new(glob.trim())
+                        .case_insensitive(true)
+                        .literal_separator(false)
+                        .build()
+                        .with_context(|| format!("Invalid title pattern '{}'", pattern))?,
+                );
+            } else if !pattern.is_empty() {
+                regexes.push(
+                    Regex::new(pattern)
+                        .with_context(|| format!("Invalid title pattern '{}'", pattern))?,
+                );
+            }
+        }
+
+        Ok(Self {
+            regexes,
+            globs: globs.build().context("Invalid title patterns")?,
+        })
+    }
+
+    /// True when the title matches any exclusion pattern
+    pub fn excludes(&self, title: &str) -> bool {
+        self.regexes.iter().any(|regex| regex.is_match(title)) || self.globs.is_match(title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regexes_and_globs_exclude_titles() {
+        let patterns = vec!["^chore".to_string(), "glob:bump * from *".to_string()];
+        let filter = TitleFilter::new(&patterns).unwrap();
+
+        assert!(filter.excludes("chore(deps): update lockfile"));
+        assert!(filter.excludes("Bump serde from 1.0.1 to 1.0.2"));
+        assert!(!filter.excludes("feat: add chore reminders"));
+        assert!(!filter.excludes("Bump the version"));
+        assert!(TitleFilter::new(&["(".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs;
+use std::sync::RwLock;
+
+use reqwest::{Certificate, ClientBuilder};
+
+use crate::error::GazetteError;
+
+/// Which certificate authorities the HTTP clients trust
+#[derive(Debug, Clone)]
+pub struct TlsOptions {
+    /// Extra root certificates, e.g. a corporate CA intercepting TLS
+    pub root_certificates: Vec<Certificate>,
+    /// Whether the system's certificate store is trusted as well
+    pub system_roots: bool,
+}
+
+static OPTIONS: RwLock<TlsOptions> = RwLock::new(TlsOptions {
+    root_certificates: Vec::new(),
+    system_roots: true,
+});
+
+impl TlsOptions {
+    /// Reads the root certificates from PEM files, each holding one certificate or a bundle
+    pub fn load(paths: &[String], system_roots: bool) -> Result<Self, GazetteError> {
+        let mut root_certificates = Vec::new();
+        for path in paths {
+            let pem = fs::read(path).map_err(|e| {
+                GazetteError::Config(format!("Failed to read CA certificate {}: {}", path, e))
+            })?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .ok()
+                .filter(|certificates| !certificates.is_empty())
+                .ok_or_else(|| {
+                    GazetteError::Config(format!("{} holds no PEM certificates", path))
+                })?;
+            root_certificates.extend(certificates);
+        }
+
+        if !system_roots && root_certificates.is_empty() {
+            return Err(GazetteError::Config(
+                "Add a CA certificate before turning off the system certificate store".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            root_certificates,
+            system_roots,
+        })
+    }
+}
+
+/// Makes every HTTP client created from now on trust `options`
+pub fn set_options(options: TlsOptions) {
+    *OPTIONS.write().unwrap_or_else(|e| e.into_inner()) = options;
+}
+
+/// A client builder trusting the configured certificate authorities
+pub fn client_builder() -> ClientBuilder {
+    let options = OPTIONS.read().unwrap_or_else(|e| e.into_inner());
+    let mut builder = reqwest::Client::builder().tls_built_in_root_certs(options.system_roots);
+    for certificate in &options.root_certificates {
+        builder = builder.add_root_certificate(certificate.clone());
+    }
+    builder
+}
+
+/// A client trusting the configured certificate authorities, in place of `reqwest::Client::new`
+pub fn client() -> reqwest::Client {
+    // Like `reqwest::Client::new`, which panics too; the certificates were parsed on load
+    client_builder()
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unusable_certificates_are_config_errors() {
+        let path = std::env::temp_dir().join("gazette-tls-test-not-a-certificate.pem");
+        fs::write(&path, "not a certificate").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let error = TlsOptions::load(std::slice::from_ref(&path), true).unwrap_err();
+        assert!(error.to_string().contains("holds no PEM certificates"));
+        assert!(TlsOptions::load(&["/nonexistent/ca.pem".to_string()], true).is_err());
+        assert!(TlsOptions::load(&[], false).is_err());
+        assert!(TlsOptions::load(&[], true).unwrap().system_roots);
+    }
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::jira::JiraIssue;
+
+/// An issue tracker whose tickets PRs reference, e.g. Jira
+/// Several can be active at once; each claims the keys it recognizes in a PR
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Issue keys this tracker recognizes in the text, e.g. "PROJ-123"
+    fn extract_keys(&self, text: &str) -> Vec<String>;
+
+    /// Looks an issue up by key, in the Jira issue shape the rest of the pipeline reads
+    /// Returns None if the issue doesn't exist
+    async fn resolve(&self, key: &str) -> Result<Option<JiraIssue>>;
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::ai::TokenUsage;
+use crate::config::AIProvider;
+use crate::store::Store;
+
+/// Returns the (prompt, completion) price in USD per million tokens for a model
+pub fn model_pricing(provider: AIProvider, model: &str) -> Option<(f64, f64)> {
+    if provider == AIProvider::Ollama {
+        return Some((0.0, 0.0));
+    }
+
+    let price = match model {
+        "gemini-2.0-flash" => (0.10, 0.40),
+        "gemini-2.0-flash-lite" => (0.075, 0.30),
+        "gemini-1.5-pro" => (1.25, 5.00),
+        "gemini-1.5-flash" => (0.075, 0.30),
+        "gpt-4o" => (2.50, 10.00),
+        "gpt-4o-mini" => (0.15, 0.60),
+        "gpt-4-turbo" => (10.00, 30.00),
+        "gpt-4" => (30.00, 60.00),
+        "gpt-3.5-turbo" => (0.50, 1.50),
+        "claude-sonnet-4-20250514" => (3.00, 15.00),
+        "claude-3-5-sonnet-20241022" => (3.00, 15.00),
+        "claude-3-5-haiku-20241022" => (0.80, 4.00),
+        "claude-3-opus-20240229" => (15.00, 75.00),
+        _ => return None,
+    };
+
+    Some(price)
+}
+
+/// Share of the prompt price a provider bills for tokens read from its prompt cache
+fn cached_price_factor(provider: AIProvider) -> f64 {
+    match provider {
+        AIProvider::Anthropic => 0.1,
+        AIProvider::Gemini => 0.25,
+        AIProvider::OpenAI => 0.5,
+        AIProvider::Ollama => 0.0,
+    }
+}
+
+/// Share of the prompt price billed for tokens written to the prompt cache, which only
+/// Anthropic reports
+const CACHE_WRITE_PRICE_FACTOR: f64 = 1.25;
+
+/// Estimates the cost in USD of a request, if the model's pricing is known
+pub fn estimate_cost(provider: AIProvider, model: &str, usage: &TokenUsage) -> Option<f64> {
+    model_pricing(provider, model).map(|(prompt_price, completion_price)| {
+        let cached = usage.cached_tokens.min(usage.prompt_tokens);
+        let written = usage.cache_write_tokens.min(usage.prompt_tokens - cached);
+        ((usage.prompt_tokens - cached - written) as f64 * prompt_price
+            + cached as f64 * prompt_price * cached_price_factor(provider)
+            + written as f64 * prompt_price * CACHE_WRITE_PRICE_FACTOR
+            + usage.completion_tokens as f64 * completion_price)
+            / 1_000_000.0
+    })
+}
+
+/// Accumulated usage for a single provider/model pair
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UsageEntry {
+    pub provider: AIProvider,
+    pub model: String,
+    #[serde(default)]
+    pub requests: u64,
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+/// Running cost ledger, also the format of the legacy usage.json
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UsageLedger {
+    #[serde(default)]
+    pub entries: Vec<UsageEntry>,
+}
+
+impl UsageLedger {
+    /// Loads the usage accumulated per provider/model from the store
+    pub fn load() -> Result<Self> {
+        Ok(Self {
+            entries: Store::open()?.usage_by_model()?,
+        })
+    }
+
+    pub fn total_cost(&self) -> f64 {
+        self.entries.iter().map(|e| e.cost_usd).sum()
+    }
+}
+
+/// Records a request in the store and returns its estimated cost
+pub fn record_usage(provider: AIProvider, model: &str, usage: &TokenUsage) -> Result<Option<f64>> {
+    Store::open()?.record_usage(provider, model, usage)
+}
+
+/// Formats a one-line usage summary for a single generation
+pub fn format_usage(usage: &TokenUsage, cost: Option<f64>) -> String {
+    let cost = cost
+        .map(|c| format!("~${:.4}", c))
+        .unwrap_or_else(|| "cost unknown".to_string());
+
+    let cached = if usage.cached_tokens > 0 {
+        format!(" ({} cached)", usage.cached_tokens)
+    } else {
+        String::new()
+    };
+    format!(
+        "{} prompt{} + {} completion tokens ({})",
+        usage.prompt_tokens, cached, usage.completion_tokens, cost
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_model_has_no_cost() {
+        let usage = TokenUsage {
+            prompt_tokens: 10,
+            completion_tokens: 10,
+            cached_tokens: 0,
+            cache_write_tokens: 0,
+        };
+        assert_eq!(estimate_cost(AIProvider::OpenAI, "gpt-9", &usage), None);
+    }
+
+    #[test]
+    fn test_cache_writes_cost_more_and_reads_less() {
+        let usage = TokenUsage {
+            prompt_tokens: 3_000_000,
+            completion_tokens: 0,
+            cached_tokens: 1_000_000,
+            cache_write_tokens: 1_000_000,
+        };
+        let cost = estimate_cost(AIProvider::Anthropic, "claude-sonnet-4-20250514", &usage);
+        assert!((cost.unwrap() - (3.0 + 0.3 + 3.75)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::clock;
+use crate::config::TimePeriod;
+use crate::github::GitHubRelease;
+
+/// File name of a version-anchored changelog, e.g. changelog_backend_v1.4.0.md
+pub const DEFAULT_FILE_PATTERN: &str = "{prefix}_{repo}_{version}.md";
+
+/// Title of a version-anchored changelog, e.g. "v1.4.0 — 2024-06-10"
+pub const DEFAULT_TITLE_PATTERN: &str = "{version} — {date}";
+
+/// The release a changelog of the period is named after: the milestone's namesake, or
+/// the latest one published within the period
+pub fn release_for<'a>(
+    period: &TimePeriod,
+    releases: &'a [GitHubRelease],
+) -> Option<&'a GitHubRelease> {
+    if let TimePeriod::Milestone { title } = period {
+        let title = title.trim();
+        return releases.iter().find(|release| {
+            release.tag_name.trim_start_matches('v') == title.trim_start_matches('v')
+                || release.name.as_deref().map(str::trim) == Some(title)
+        });
+    }
+
+    let (start, end) = period.bounds();
+    releases
+        .iter()
+        .filter(|release| {
+            release
+                .published_at
+                .is_some_and(|at| at >= start && at <= end)
+        })
+        .max_by_key(|release| release.published_at)
+}
+
+/// Fills in `{prefix}`, `{repo}`, `{version}` and `{date}`, the day the release was published
+pub fn expand(pattern: &str, release: &GitHubRelease, prefix: &str, repo: &str) -> String {
+    let date = release
+        .published_at
+        .map(|at| clock::format(at, "%Y-%m-%d"))
+        .unwrap_or_default();
+
+    pattern
+        .replace("{prefix}", prefix)
+        .replace("{repo}", repo)
+        .replace("{version}", &release.tag_name)
+        .replace("{date}", &date)
+}
+
+/// A file name from the pattern; tags such as "api/v2.0" can't put the file in a subdirectory
+pub fn file_name(pattern: &str, release: &GitHubRelease, prefix: &str, repo: &str) -> String {
+    let release = GitHubRelease {
+        tag_name: release.tag_name.replace(['/', '\\'], "-"),
+        ..release.clone()
+    };
+    expand(pattern, &release, prefix, repo)
+}
+
+/// Replaces the document's `#` title, or adds one when it has none
+pub fn retitle(content: &str, title: &str) -> String {
+    let heading = format!("# {}", title);
+    match content.lines().position(|line| line.starts_with("# ")) {
+        Some(index) => {
+            content
+                .lines()
+                .enumerate()
+                .map(|(i, line)| if i == index { heading.as_str() } else { line })
+                .collect::<Vec<_>>()
+                .join("\n")
+                + if content.ends_with('\n') { "\n" } else { "" }
+        }
+        None => format!("{}\n\n{}", heading, content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn release(tag: &str, days_ago: i64) -> GitHubRelease {
+        GitHubRelease {
+            id: 0,
+            tag_name: tag.to_string(),
+            name: None,
+            draft: false,
+            published_at: Some(Utc::now() - Duration::days(days_ago)),
+            html_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_latest_release_of_the_period_names_the_changelog() {
+        let releases = vec![
+            release("api/v1.5.0", 2),
+            release("v1.4.0", 5),
+            release("v1.3.0", 20),
+        ];
+
+        let found = release_for(&TimePeriod::Last7Days, &releases).unwrap();
+        assert_eq!(found.tag_name, "api/v1.5.0");
+        assert_eq!(
+            file_name(DEFAULT_FILE_PATTERN, found, "changelog", "backend"),
+            "changelog_backend_api-v1.5.0.md"
+        );
+        assert!(release_for(&TimePeriod::LastHour, &releases).is_none());
+
+        let milestone = TimePeriod::Milestone {
+            title: "1.3.0".to_string(),
+        };
+        assert_eq!(
+            release_for(&milestone, &releases).map(|r| r.tag_name.as_str()),
+            Some("v1.3.0")
+        );
+
+        assert_eq!(
+            retitle("# Changelog\n\n## Features\n", "v1.4.0"),
+            "# v1.4.0\n\n## Features\n"
+        );
+        assert_eq!(retitle("## Features", "v1.4.0"), "# v1.4.0\n\n## Features");
+    }
+}
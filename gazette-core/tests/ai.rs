@@ -0,0 +1,301 @@
+use gazette_core::ai::{
+    AIClient, AnthropicClient, GeminiClient, OllamaClient, OpenAIClient, RateLimitInfo,
+    StructuredChangelog, TokenUsage,
+};
+use gazette_core::error::GazetteError;
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_openai_completion_and_usage_are_parsed() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("authorization", "Bearer sk-test"))
+        .and(body_partial_json(json!({ "model": "gpt-4o-mini" })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-ratelimit-limit-requests", "500")
+                .insert_header("x-ratelimit-remaining-requests", "499")
+                .insert_header("x-ratelimit-reset-requests", "120ms")
+                .insert_header("x-ratelimit-remaining-tokens", "29850")
+                .set_body_json(json!({
+                    "choices": [{ "message": { "content": "## Features\n- Add SSO" } }],
+                    "usage": { "prompt_tokens": 120, "completion_tokens": 30 }
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let client = OpenAIClient::with_api_key("gpt-4o-mini", "sk-test").with_base_url(&server.uri());
+    let completion = client
+        .generate("Write a changelog")
+        .await
+        .expect("completion");
+
+    assert_eq!(completion.text, "## Features\n- Add SSO");
+    assert_eq!(
+        completion.usage,
+        Some(TokenUsage {
+            prompt_tokens: 120,
+            completion_tokens: 30,
+            cached_tokens: 0,
+            cache_write_tokens: 0
+        })
+    );
+    assert_eq!(
+        completion.rate_limit,
+        Some(RateLimitInfo {
+            requests_remaining: Some(499),
+            requests_limit: Some(500),
+            tokens_remaining: Some(29850),
+            tokens_limit: None,
+            requests_reset: Some("120ms".to_string()),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_anthropic_errors_carry_the_status() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .and(header("x-api-key", "sk-ant-test"))
+        .respond_with(
+            ResponseTemplate::new(529)
+                .set_body_json(json!({ "error": { "message": "Overloaded" } })),
+        )
+        .mount(&server)
+        .await;
+
+    let client =
+        AnthropicClient::with_api_key("claude-haiku", "sk-ant-test").with_base_url(&server.uri());
+    let error = client
+        .generate("Write a changelog")
+        .await
+        .expect_err("overloaded");
+
+    assert!(
+        error.to_string().contains("Anthropic API error (529"),
+        "{}",
+        error
+    );
+    let error = GazetteError::find(&error);
+    assert!(matches!(error, Some(GazetteError::AiProvider { .. })));
+    assert!(error.is_some_and(GazetteError::is_retryable));
+}
+
+#[tokio::test]
+async fn test_anthropic_caches_the_instructions_as_a_system_block() {
+    let server = MockServer::start().await;
+    // Haiku models only cache prefixes of 2048 tokens or more
+    let instructions = "Summarize the PR for the changelog. ".repeat(300);
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .and(body_partial_json(json!({
+            "system": [{
+                "type": "text",
+                "text": instructions,
+                "cache_control": { "type": "ephemeral" }
+            }],
+            "messages": [{ "role": "user", "content": "## PR #1: Add SSO" }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "content": [{ "type": "text", "text": "Adds SSO login." }],
+            "usage": {
+                "input_tokens": 20,
+                "output_tokens": 5,
+                "cache_creation_input_tokens": 100,
+                "cache_read_input_tokens": 2500
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let client =
+        AnthropicClient::with_api_key("claude-haiku", "sk-ant-test").with_base_url(&server.uri());
+    let completion = client
+        .generate_cached(&instructions, "## PR #1: Add SSO")
+        .await
+        .expect("completion");
+
+    assert_eq!(completion.text, "Adds SSO login.");
+    assert_eq!(
+        completion.usage,
+        Some(TokenUsage {
+            prompt_tokens: 2620,
+            completion_tokens: 5,
+            cached_tokens: 2500,
+            cache_write_tokens: 100
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_gemini_joins_the_parts_of_the_first_candidate() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/gemini-2.0-flash:generateContent"))
+        .and(query_param("key", "gemini-test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{ "content": { "parts": [{ "text": "## Fixes\n" }, { "text": "- Fix login" }] } }],
+            "usageMetadata": { "promptTokenCount": 50, "candidatesTokenCount": 8 }
+        })))
+        .mount(&server)
+        .await;
+
+    let client =
+        GeminiClient::with_api_key("gemini-2.0-flash", "gemini-test").with_base_url(&server.uri());
+    let completion = client
+        .generate("Write a changelog")
+        .await
+        .expect("completion");
+
+    assert_eq!(completion.text, "## Fixes\n- Fix login");
+    assert_eq!(completion.usage.map(|u| u.total()), Some(58));
+}
+
+#[tokio::test]
+async fn test_openai_lists_only_chat_models() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(header("authorization", "Bearer sk-test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [
+                { "id": "gpt-4o" },
+                { "id": "text-embedding-3-small" },
+                { "id": "o3-mini" },
+                { "id": "gpt-4o-realtime-preview" },
+                { "id": "dall-e-3" },
+                { "id": "gpt-4.1" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = OpenAIClient::with_api_key("gpt-4o", "sk-test").with_base_url(&server.uri());
+    let models = client.list_models().await.expect("models");
+
+    assert_eq!(
+        models,
+        Some(vec![
+            "gpt-4.1".to_string(),
+            "gpt-4o".to_string(),
+            "o3-mini".to_string()
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_gemini_constrains_structured_changelogs_to_the_schema() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/gemini-2.0-flash:generateContent"))
+        .and(body_partial_json(json!({
+            "generationConfig": {
+                "responseMimeType": "application/json",
+                "responseSchema": {
+                    "type": "OBJECT",
+                    "required": ["categories"],
+                    "properties": {
+                        "categories": {
+                            "type": "ARRAY",
+                            "items": { "type": "OBJECT", "required": ["name", "entries"] }
+                        }
+                    }
+                }
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "candidates": [{ "content": { "parts": [{ "text": "{\"categories\":[]}" }] } }]
+        })))
+        .mount(&server)
+        .await;
+
+    let client =
+        GeminiClient::with_api_key("gemini-2.0-flash", "gemini-test").with_base_url(&server.uri());
+    let completion = client
+        .generate_json("Categorize the PRs", &StructuredChangelog::schema())
+        .await
+        .expect("completion");
+
+    assert_eq!(completion.text, "{\"categories\":[]}");
+}
+
+#[tokio::test]
+async fn test_ollama_reports_errors_in_the_body() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/generate"))
+        .and(body_partial_json(
+            json!({ "model": "llama3", "stream": false }),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "error": "model not found" })),
+        )
+        .mount(&server)
+        .await;
+
+    let client = OllamaClient::with_host("llama3", &server.uri());
+    let error = client
+        .generate("Write a changelog")
+        .await
+        .expect_err("missing model");
+
+    assert!(error.to_string().contains("model not found"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_openai_request_options_follow_the_model() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "choices": [{ "message": { "content": "{\"categories\":[]}" } }]
+        })))
+        .mount(&server)
+        .await;
+
+    for model in ["o3-mini", "gpt-4o-2024-05-13", "gpt-4o-2024-08-06"] {
+        OpenAIClient::with_api_key(model, "sk-test")
+            .with_base_url(&server.uri())
+            .generate_json("Categorize the PRs", &StructuredChangelog::schema())
+            .await
+            .expect("completion");
+    }
+
+    let requests = server.received_requests().await.unwrap();
+    let sent: Vec<serde_json::Value> = requests
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect();
+    let options: Vec<(Option<f64>, &str)> = sent
+        .iter()
+        .map(|body| {
+            (
+                body["temperature"].as_f64(),
+                body["response_format"]["type"].as_str().unwrap(),
+            )
+        })
+        .collect();
+
+    // Reasoning models reject a temperature, and the first gpt-4o snapshot a schema
+    assert_eq!(
+        options,
+        [
+            (None, "json_schema"),
+            (Some(0.7), "json_object"),
+            (Some(0.7), "json_schema")
+        ]
+    );
+}
@@ -0,0 +1,283 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use gazette_core::config::Repo;
+use gazette_core::delivery::{Delivery, DiscussionPublisher, GistPublisher, PullRequestPublisher};
+use gazette_core::github::GitHubClient;
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(server: &MockServer) -> GitHubClient {
+    GitHubClient::with_token("test-token")
+        .expect("client")
+        .with_base_url(&server.uri())
+}
+
+fn delivery<'a>(repo: &'a Repo, content: &'a str, path: &'a Path) -> Delivery<'a> {
+    Delivery {
+        repo,
+        content,
+        period: "last 7 days".to_string(),
+        path,
+        repo_path: "changelogs/changelog_backend.md".to_string(),
+        jira_keys: Vec::new(),
+        version: None,
+    }
+}
+
+/// The changelog saved where the pull request publisher reads it from
+fn saved(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(name);
+    fs::write(&path, content).unwrap();
+    path
+}
+
+/// Mocks the base branch lookup and branch creation, with `develop` as the default branch
+async fn mount_branch(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "default_branch": "develop"
+        })))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/git/ref/heads/develop"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": { "sha": "base-sha" }
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/repos/acme/backend/git/refs"))
+        .and(body_partial_json(json!({ "sha": "base-sha" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "object": { "sha": "base-sha" }
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+/// The JSON body of the one request made with `method` to `path`
+async fn sent(server: &MockServer, method: &str, path: &str) -> Value {
+    let requests = server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|request| request.method.as_str() == method && request.url.path() == path)
+        .expect("request sent");
+    serde_json::from_slice(&request.body).unwrap()
+}
+
+#[tokio::test]
+async fn test_pull_request_creates_the_file_against_the_default_branch() {
+    let server = MockServer::start().await;
+    mount_branch(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/repos/acme/backend/contents/changelogs/changelog_backend.md",
+        ))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/repos/acme/backend/contents/changelogs/changelog_backend.md",
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/repos/acme/backend/pulls"))
+        .and(body_partial_json(json!({ "base": "develop" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "number": 12,
+            "html_url": "https://github.com/acme/backend/pull/12"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Longer than a pull request body may be, so the body is cut while the file isn't
+    let content = format!("# Changelog\n{}", "é".repeat(70_000));
+    let path = saved("gazette-test-pr-create.md", &content);
+    let repo = Repo::new("acme", "backend");
+    let url = PullRequestPublisher::with_client(client(&server))
+        .publish(&delivery(&repo, &content, &path))
+        .await
+        .unwrap();
+    assert_eq!(url, "https://github.com/acme/backend/pull/12");
+
+    let put = sent(
+        &server,
+        "PUT",
+        "/repos/acme/backend/contents/changelogs/changelog_backend.md",
+    )
+    .await;
+    assert!(put.get("sha").is_none(), "a new file has no blob sha");
+    assert!(
+        put["branch"]
+            .as_str()
+            .unwrap()
+            .starts_with("gazette/changelog-")
+    );
+
+    let pull = sent(&server, "POST", "/repos/acme/backend/pulls").await;
+    let body = pull["body"].as_str().unwrap();
+    assert_eq!(body.chars().count(), 65536);
+    assert!(content.starts_with(body));
+    fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_pull_request_updates_an_existing_file_by_its_sha() {
+    let server = MockServer::start().await;
+    mount_branch(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/repos/acme/backend/contents/changelogs/changelog_backend.md",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "sha": "blob-sha" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(
+            "/repos/acme/backend/contents/changelogs/changelog_backend.md",
+        ))
+        .and(body_partial_json(json!({ "sha": "blob-sha" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/repos/acme/backend/pulls"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "number": 13,
+            "html_url": "https://github.com/acme/backend/pull/13"
+        })))
+        .mount(&server)
+        .await;
+
+    let content = "# Changelog\n- SSO\n";
+    let path = saved("gazette-test-pr-update.md", content);
+    let repo = Repo::new("acme", "backend");
+    PullRequestPublisher::with_client(client(&server))
+        .publish(&delivery(&repo, content, &path))
+        .await
+        .unwrap();
+
+    let put = sent(
+        &server,
+        "PUT",
+        "/repos/acme/backend/contents/changelogs/changelog_backend.md",
+    )
+    .await;
+    assert_eq!(put["content"], "IyBDaGFuZ2Vsb2cKLSBTU08K");
+    fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_discussion_is_posted_to_the_target_repo_category() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("discussionCategories"))
+        .and(body_partial_json(json!({
+            "variables": { "owner": "acme", "name": "announcements" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "repository": {
+                    "id": "R_1",
+                    "discussionCategories": {
+                        "nodes": [
+                            { "id": "C_1", "name": "General" },
+                            { "id": "C_2", "name": "Announcements" }
+                        ]
+                    }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_string_contains("createDiscussion"))
+        .and(body_partial_json(json!({
+            "variables": { "repositoryId": "R_1", "categoryId": "C_2", "body": "- SSO\n" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "createDiscussion": {
+                    "discussion": { "url": "https://github.com/acme/announcements/discussions/4" }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let repo = Repo::new("acme", "backend");
+    let target = Some(Repo::new("acme", "announcements"));
+    let path = Path::new("changelog_backend.md");
+    let url = DiscussionPublisher::with_client(client(&server), "announcements", target)
+        .publish(&delivery(&repo, "- SSO\n", path))
+        .await
+        .unwrap();
+    assert_eq!(url, "https://github.com/acme/announcements/discussions/4");
+
+    let Err(error) = DiscussionPublisher::with_client(
+        client(&server),
+        "Releases",
+        Some(Repo::new("acme", "announcements")),
+    )
+    .publish(&delivery(&repo, "- SSO\n", path))
+    .await
+    else {
+        panic!("a missing category should fail");
+    };
+    assert_eq!(
+        error.to_string(),
+        "Discussion category 'Releases' not found in acme/announcements"
+    );
+}
+
+#[tokio::test]
+async fn test_gist_is_named_after_the_saved_file() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/gists"))
+        .and(body_partial_json(json!({
+            "public": false,
+            "files": { "changelog_backend_2026-10-14.md": { "content": "- SSO\n" } }
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "html_url": "https://gist.github.com/octocat/abc"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let repo = Repo::new("acme", "backend");
+    let path = Path::new("out/changelog_backend_2026-10-14.md");
+    let url = GistPublisher::with_client(client(&server), false)
+        .publish(&delivery(&repo, "- SSO\n", path))
+        .await
+        .unwrap();
+    assert_eq!(url, "https://gist.github.com/octocat/abc");
+
+    let gist = sent(&server, "POST", "/gists").await;
+    assert!(
+        gist["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("Changelog for acme/backend — ")
+    );
+}
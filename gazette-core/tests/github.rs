@@ -0,0 +1,382 @@
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use gazette_core::config::{Repo, TimePeriod};
+use gazette_core::error::GazetteError;
+use gazette_core::github::GitHubClient;
+use serde_json::{Value, json};
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn at(timestamp: &str) -> DateTime<Utc> {
+    timestamp.parse().expect("valid timestamp")
+}
+
+fn window() -> TimePeriod {
+    TimePeriod::Window {
+        start: at("2026-03-01T00:00:00Z"),
+        end: at("2026-03-02T00:00:00Z"),
+    }
+}
+
+fn pr(number: u64, merged_at: Option<&str>, updated_at: &str) -> Value {
+    json!({
+        "number": number,
+        "title": format!("PR {}", number),
+        "body": null,
+        "merged_at": merged_at,
+        "updated_at": updated_at,
+        "user": { "login": "octocat" },
+        "html_url": format!("https://github.com/acme/backend/pull/{}", number),
+    })
+}
+
+async fn client(server: &MockServer) -> GitHubClient {
+    GitHubClient::with_token("test-token")
+        .expect("client")
+        .with_base_url(&server.uri())
+}
+
+#[tokio::test]
+async fn test_merged_prs_follow_pagination_until_the_window_is_exhausted() {
+    let server = MockServer::start().await;
+
+    // A full first page, half of it merged inside the window and half closed unmerged
+    let first: Vec<Value> = (1..=100)
+        .map(|n| {
+            let merged = (n % 2 == 1).then_some("2026-03-01T12:00:00Z");
+            pr(n, merged, "2026-03-01T12:00:00Z")
+        })
+        .collect();
+    // The second page reaches PRs last updated before the window, so paging stops there
+    let second = vec![
+        pr(101, Some("2026-03-01T01:00:00Z"), "2026-03-01T01:00:00Z"),
+        pr(102, Some("2026-02-27T09:00:00Z"), "2026-02-27T09:00:00Z"),
+    ];
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/pulls"))
+        .and(query_param("state", "closed"))
+        .and(query_param("page", "1"))
+        .and(header("authorization", "Bearer test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/pulls"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(second))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let pages = Mutex::new(Vec::new());
+    let on_page = |page| pages.lock().unwrap().push(page);
+    let prs = client(&server)
+        .await
+        .get_merged_prs(&Repo::new("acme", "backend"), window(), &on_page)
+        .await
+        .expect("merged PRs");
+
+    assert_eq!(prs.len(), 51);
+    assert!(prs.iter().all(|pr| pr.merged_at.is_some()));
+    assert!(prs.iter().any(|pr| pr.number == 101));
+    assert!(!prs.iter().any(|pr| pr.number == 102));
+    assert_eq!(prs[0].user.as_ref().unwrap().login, "octocat");
+    assert_eq!(*pages.lock().unwrap(), vec![1, 2]);
+}
+
+#[tokio::test]
+async fn test_api_errors_carry_the_status_and_body() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/pulls"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("Bad credentials"))
+        .mount(&server)
+        .await;
+
+    let error = client(&server)
+        .await
+        .get_merged_prs(&Repo::new("acme", "backend"), window(), &|_| {})
+        .await
+        .expect_err("unauthorized");
+
+    let message = error.to_string();
+    assert!(message.contains("GitHub API error (401"), "{}", message);
+    assert!(message.contains("Bad credentials"), "{}", message);
+    assert!(matches!(
+        GazetteError::find(&error),
+        Some(GazetteError::Auth { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_token_owner_reports_the_classic_scopes() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/user"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oauth-scopes", "repo, read:org")
+                .set_body_json(json!({ "login": "octocat" })),
+        )
+        .mount(&server)
+        .await;
+
+    let owner = client(&server)
+        .await
+        .authenticated_user()
+        .await
+        .expect("owner");
+
+    assert_eq!(owner.login, "octocat");
+    assert_eq!(
+        owner.scopes,
+        Some(vec!["repo".to_string(), "read:org".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn test_file_contents_are_none_for_missing_files() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/contents/CHANGELOG.md"))
+        .and(header("accept", "application/vnd.github.raw+json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("# Changelog\n"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/frontend/contents/CHANGELOG.md"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let client = client(&server).await;
+    let existing = client
+        .get_file_contents(&Repo::new("acme", "backend"), "CHANGELOG.md")
+        .await
+        .expect("contents");
+    let missing = client
+        .get_file_contents(&Repo::new("acme", "frontend"), "CHANGELOG.md")
+        .await
+        .expect("contents");
+
+    assert_eq!(existing.as_deref(), Some("# Changelog\n"));
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn test_pr_files_include_the_old_paths_of_renames() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/monorepo/pulls/42/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "filename": "services/payments/src/lib.rs" },
+            {
+                "filename": "libs/billing/invoice.rs",
+                "previous_filename": "services/payments/src/invoice.rs"
+            },
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let files = client(&server)
+        .await
+        .get_pr_files(&Repo::new("acme", "monorepo"), 42)
+        .await
+        .expect("PR files");
+
+    assert_eq!(
+        files,
+        vec![
+            "services/payments/src/lib.rs",
+            "libs/billing/invoice.rs",
+            "services/payments/src/invoice.rs",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_pr_commit_messages_carry_their_trailers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/pulls/7/commits"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "sha": "a1", "commit": { "message": "Add retries" } },
+            {
+                "sha": "b2",
+                "commit": {
+                    "message": "Pair on backoff\n\nCo-authored-by: Mei <42+mei@users.noreply.github.com>"
+                }
+            },
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let messages = client(&server)
+        .await
+        .get_pr_commit_messages(&Repo::new("acme", "backend"), 7)
+        .await
+        .expect("PR commits");
+
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        gazette_core::contributors::co_authors(&messages[1]),
+        vec!["@mei"]
+    );
+}
+
+#[tokio::test]
+async fn test_draft_release_body_is_replaced_in_place() {
+    let server = MockServer::start().await;
+    let repo = Repo::new("acme", "backend");
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "id": 3, "tag_name": "v2.1.0", "name": "v2.1.0", "draft": true, "published_at": null },
+            {
+                "id": 2,
+                "tag_name": "v2.0.0",
+                "name": "v2.0.0",
+                "draft": false,
+                "published_at": "2026-03-01T10:00:00Z"
+            },
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/repos/acme/backend/releases/3"))
+        .and(wiremock::matchers::body_json(
+            json!({ "body": "## Features\n- SSO\n" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 3,
+            "tag_name": "v2.1.0",
+            "draft": true,
+            "published_at": null,
+            "html_url": "https://github.com/acme/backend/releases/tag/untagged-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let github = client(&server).await;
+    let draft = github
+        .get_draft_release(&repo)
+        .await
+        .expect("releases")
+        .expect("a draft");
+    assert_eq!(draft.tag_name, "v2.1.0");
+    assert_eq!(github.get_releases(&repo).await.expect("releases").len(), 1);
+
+    let url = github
+        .update_release_body(&repo, draft.id, "## Features\n- SSO\n")
+        .await
+        .expect("updated release");
+    assert_eq!(
+        url,
+        "https://github.com/acme/backend/releases/tag/untagged-1"
+    );
+}
+
+#[tokio::test]
+async fn test_milestone_prs_are_the_merged_ones_attached_to_it() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/milestones"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "number": 3, "title": "v2.4", "state": "open", "open_issues": 4, "closed_issues": 1 },
+            { "number": 2, "title": "v2.3", "state": "closed", "open_issues": 0, "closed_issues": 3 },
+        ])))
+        .mount(&server)
+        .await;
+    // An issue, a PR closed without merging and a merged PR
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/issues"))
+        .and(query_param("milestone", "2"))
+        .and(query_param("state", "closed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "number": 7 },
+            { "number": 8, "pull_request": { "merged_at": null } },
+            { "number": 9, "pull_request": { "merged_at": "2025-11-03T10:00:00Z" } },
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/pulls/9"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pr(
+            9,
+            Some("2025-11-03T10:00:00Z"),
+            "2025-11-03T10:00:00Z",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server).await;
+    let repo = Repo::new("acme", "backend");
+    let prs = client
+        .get_milestone_prs(&repo, "v2.3", &|_| {})
+        .await
+        .expect("milestone PRs");
+    assert_eq!(prs.iter().map(|pr| pr.number).collect::<Vec<_>>(), vec![9]);
+
+    let missing = client
+        .get_milestone_prs(&repo, "v9", &|_| {})
+        .await
+        .expect("no milestone");
+    assert!(missing.is_empty());
+}
+
+#[tokio::test]
+async fn test_fixed_dependabot_alerts_carry_their_advisory_and_package() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/backend/dependabot/alerts"))
+        .and(query_param("state", "fixed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "number": 3,
+            "state": "fixed",
+            "html_url": "https://github.com/acme/backend/security/dependabot/3",
+            "fixed_at": "2026-03-01T10:00:00Z",
+            "security_advisory": {
+                "ghsa_id": "GHSA-xxxx-yyyy-zzzz",
+                "cve_id": "CVE-2026-1234",
+                "summary": "Prototype pollution",
+                "severity": "high"
+            },
+            "dependency": {
+                "package": { "ecosystem": "npm", "name": "lodash" },
+                "manifest_path": "package-lock.json"
+            }
+        }])))
+        .mount(&server)
+        .await;
+
+    let alerts = client(&server)
+        .await
+        .get_fixed_dependabot_alerts(&Repo::new("acme", "backend"))
+        .await
+        .expect("alerts");
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].fixed_at, Some(at("2026-03-01T10:00:00Z")));
+    assert_eq!(
+        alerts[0].security_advisory.cve_id.as_deref(),
+        Some("CVE-2026-1234")
+    );
+    assert_eq!(alerts[0].dependency.package.name, "lodash");
+}
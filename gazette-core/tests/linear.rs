@@ -0,0 +1,66 @@
+use chrono::{TimeZone, Utc};
+use gazette_core::config::TimePeriod;
+use gazette_core::linear::{CyclePick, LinearClient};
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_cycles_resolve_into_periods() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(header("authorization", "lin_api_key"))
+        .and(body_partial_json(json!({ "variables": { "key": "ENG" } })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "teams": { "nodes": [{
+                "activeCycle": {
+                    "number": 42, "startsAt": "2026-10-12T00:00:00Z", "endsAt": "2099-10-26T00:00:00Z"
+                },
+                "cycles": { "nodes": [
+                    { "number": 40, "startsAt": "2026-09-14T00:00:00Z", "endsAt": "2026-09-28T00:00:00Z" },
+                    { "number": 41, "startsAt": "2026-09-28T00:00:00Z", "endsAt": "2026-10-12T00:00:00Z" }
+                ] }
+            }] } }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_partial_json(json!({ "variables": { "key": "NOPE" } })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "data": { "teams": { "nodes": [] } } })),
+        )
+        .mount(&server)
+        .await;
+
+    let client = LinearClient::with_api_key(&server.uri(), "lin_api_key").expect("client");
+
+    let last = client
+        .period("ENG", CyclePick::LastCompleted)
+        .await
+        .expect("last cycle");
+    let (start, end) = last.bounds();
+    assert_eq!(start, Utc.with_ymd_and_hms(2026, 9, 28, 0, 0, 0).unwrap());
+    assert_eq!(end, Utc.with_ymd_and_hms(2026, 10, 12, 0, 0, 0).unwrap());
+    assert_eq!(last.description(), "ENG cycle 41");
+
+    let current = client
+        .period("ENG", CyclePick::Current)
+        .await
+        .expect("current cycle");
+    assert!(matches!(current, TimePeriod::Cycle { number: 42, .. }));
+    assert!(current.bounds().1 <= Utc::now());
+
+    let error = client
+        .period("NOPE", CyclePick::Current)
+        .await
+        .expect_err("unknown team");
+    assert!(
+        error
+            .to_string()
+            .contains("No Linear team with the key NOPE")
+    );
+}
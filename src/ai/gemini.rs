@@ -1,117 +0,0 @@
-use std::env;
-
-use anyhow::{Context, Result};
-use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
-
-use super::AIClient;
-
-const GEMINI_API_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
-
-/// Gemini API client
-pub struct GeminiClient {
-    client: reqwest::Client,
-    api_key: String,
-    model: String,
-}
-
-#[derive(Serialize)]
-struct GeminiRequest {
-    contents: Vec<Content>,
-}
-
-#[derive(Serialize)]
-struct Content {
-    parts: Vec<Part>,
-}
-
-#[derive(Serialize)]
-struct Part {
-    text: String,
-}
-
-#[derive(Deserialize)]
-struct GeminiResponse {
-    candidates: Option<Vec<Candidate>>,
-}
-
-#[derive(Deserialize)]
-struct Candidate {
-    content: CandidateContent,
-}
-
-#[derive(Deserialize)]
-struct CandidateContent {
-    parts: Vec<CandidatePart>,
-}
-
-#[derive(Deserialize)]
-struct CandidatePart {
-    text: String,
-}
-
-impl GeminiClient {
-    /// Creates a new Gemini client from environment variable GEMINI_API_KEY
-    pub fn new(model: &str) -> Result<Self> {
-        let api_key =
-            env::var("GEMINI_API_KEY").context("GEMINI_API_KEY not found in environment")?;
-
-        Ok(Self {
-            client: reqwest::Client::new(),
-            api_key,
-            model: model.to_string(),
-        })
-    }
-}
-
-#[async_trait]
-impl AIClient for GeminiClient {
-    async fn generate(&self, prompt: &str) -> Result<String> {
-        let url = format!(
-            "{}/{}:generateContent?key={}",
-            GEMINI_API_URL, self.model, self.api_key
-        );
-
-        let request = GeminiRequest {
-            contents: vec![Content {
-                parts: vec![Part {
-                    text: prompt.to_string(),
-                }],
-            }],
-        };
-
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send request to Gemini API")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Gemini API error ({}): {}", status, body);
-        }
-
-        let gemini_response: GeminiResponse = response
-            .json()
-            .await
-            .context("Failed to parse Gemini response")?;
-
-        let text = gemini_response
-            .candidates
-            .and_then(|c| c.into_iter().next())
-            .map(|c| {
-                c.content
-                    .parts
-                    .into_iter()
-                    .map(|p| p.text)
-                    .collect::<Vec<_>>()
-                    .join("")
-            })
-            .unwrap_or_default();
-
-        Ok(text)
-    }
-}
@@ -1,111 +0,0 @@
-use std::env;
-
-use anyhow::{Context, Result};
-use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
-
-use super::AIClient;
-
-const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
-
-/// OpenAI API client
-pub struct OpenAIClient {
-    client: reqwest::Client,
-    api_key: String,
-    model: String,
-}
-
-#[derive(Serialize)]
-struct OpenAIRequest {
-    model: String,
-    messages: Vec<Message>,
-    temperature: f32,
-}
-
-#[derive(Serialize)]
-struct Message {
-    role: String,
-    content: String,
-}
-
-#[derive(Deserialize)]
-struct OpenAIResponse {
-    choices: Option<Vec<Choice>>,
-    error: Option<OpenAIError>,
-}
-
-#[derive(Deserialize)]
-struct Choice {
-    message: ResponseMessage,
-}
-
-#[derive(Deserialize)]
-struct ResponseMessage {
-    content: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct OpenAIError {
-    message: String,
-}
-
-impl OpenAIClient {
-    /// Creates a new OpenAI client from environment variable OPENAI_API_KEY
-    pub fn new(model: &str) -> Result<Self> {
-        let api_key =
-            env::var("OPENAI_API_KEY").context("OPENAI_API_KEY not found in environment")?;
-
-        Ok(Self {
-            client: reqwest::Client::new(),
-            api_key,
-            model: model.to_string(),
-        })
-    }
-}
-
-#[async_trait]
-impl AIClient for OpenAIClient {
-    async fn generate(&self, prompt: &str) -> Result<String> {
-        let request = OpenAIRequest {
-            model: self.model.clone(),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
-            temperature: 0.7,
-        };
-
-        let response = self
-            .client
-            .post(OPENAI_API_URL)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send request to OpenAI API")?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("OpenAI API error ({}): {}", status, body);
-        }
-
-        let openai_response: OpenAIResponse = response
-            .json()
-            .await
-            .context("Failed to parse OpenAI response")?;
-
-        if let Some(error) = openai_response.error {
-            anyhow::bail!("OpenAI API error: {}", error.message);
-        }
-
-        let text = openai_response
-            .choices
-            .and_then(|c| c.into_iter().next())
-            .and_then(|c| c.message.content)
-            .unwrap_or_default();
-
-        Ok(text)
-    }
-}
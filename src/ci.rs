@@ -0,0 +1,74 @@
+use std::env;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use anyhow::{Context, Result};
+
+/// Severity of a GitHub Actions workflow annotation
+#[derive(Debug, Clone, Copy)]
+pub enum Annotation {
+    Notice,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Notice => write!(f, "notice"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Emits a workflow command that GitHub Actions shows as an annotation on the run
+pub fn annotate(level: Annotation, message: &str) {
+    println!("::{}::{}", level, escape_data(message));
+}
+
+/// Appends markdown to the job summary, when running in GitHub Actions
+pub fn append_summary(markdown: &str) -> Result<()> {
+    append_to_env_file("GITHUB_STEP_SUMMARY", &format!("{}\n", markdown.trim_end()))
+}
+
+/// Sets a step output, when running in GitHub Actions
+pub fn set_output(name: &str, value: &str) -> Result<()> {
+    append_to_env_file("GITHUB_OUTPUT", &format!("{}={}\n", name, value))
+}
+
+fn append_to_env_file(env_var: &str, content: &str) -> Result<()> {
+    let Ok(path) = env::var(env_var) else {
+        return Ok(());
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", env_var))?;
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Failed to write {}", env_var))
+}
+
+/// Escapes a workflow command message so newlines don't end the command early
+fn escape_data(message: &str) -> String {
+    message
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_data() {
+        assert_eq!(
+            escape_data("100% done\nnext line"),
+            "100%25 done%0Anext line"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(
@@ -9,4 +11,269 @@ use clap::Parser;
 pub struct Cli {
     #[arg(long)]
     pub help_only: bool,
+
+    /// Plain output for logs, screen readers and dumb terminals: no colors, spinners, screen
+    /// clearing or full-screen interface. NO_COLOR and TERM=dumb turn it on as well
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generate changelogs without the interactive menu
+    Generate(GenerateArgs),
+    /// Keep running and generate changelogs for all subscriptions on an interval
+    Daemon {
+        /// Minutes between runs; overrides the configured interval
+        #[arg(long)]
+        interval: Option<u64>,
+    },
+    /// Install the daemon as a systemd (Linux) or launchd (macOS) user service
+    Service {
+        #[command(subcommand)]
+        action: ServiceAction,
+    },
+    /// Render all saved changelogs into a static HTML archive site
+    Site {
+        /// Directory the site is written to
+        #[arg(long, default_value = "site")]
+        out: PathBuf,
+    },
+    /// Regenerate a repo's changelog from the PR and Jira context stored by its last run,
+    /// re-invoking only the AI
+    Regenerate(RegenerateArgs),
+    /// Diff the PRs of two saved runs of a repo into a "what changed since the last gazette" section
+    Compare {
+        /// Repository whose runs are compared ("owner/name")
+        #[arg(long)]
+        repo: String,
+        /// ID of the older run; defaults to the run before --to
+        #[arg(long)]
+        from: Option<i64>,
+        /// ID of the newer run; defaults to the latest run
+        #[arg(long)]
+        to: Option<i64>,
+        /// List the repo's saved runs and their IDs instead
+        #[arg(long)]
+        list: bool,
+        /// Write the delta section to this path instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// List the PRs recorded as merged in the last days, e.g. what shipped last month
+    Shipped {
+        /// Only list PRs of this repository (owner/name)
+        #[arg(short, long)]
+        repo: Option<String>,
+        /// How many days back to look
+        #[arg(short, long, default_value_t = 30)]
+        days: u64,
+    },
+    /// List the merged PRs of a period with their authors and Jira keys, without the AI
+    Report(ReportArgs),
+    /// Show GitHub rate limits, Jira health and the AI provider's quota before a big run
+    Limits,
+    /// Show accumulated AI token usage and estimated cost
+    Usage,
+    /// List the models of each configured AI provider, marking the one in use
+    Models {
+        #[command(subcommand)]
+        action: Option<ModelsAction>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ModelsAction {
+    /// Generate changelogs with this model from now on
+    Set {
+        /// Model name, e.g. gpt-4o
+        name: String,
+        /// Provider the model belongs to; found from the model lists when left out
+        #[arg(long)]
+        provider: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ServiceAction {
+    /// Install and start the daemon service for the current directory
+    Install {
+        /// Print the service definition instead of installing it
+        #[arg(long)]
+        print: bool,
+    },
+    /// Stop the daemon service and remove it
+    Uninstall,
+}
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// Repository to generate for ("owner/name"); defaults to all subscribed repos
+    #[arg(long)]
+    pub repo: Option<String>,
+
+    /// Generate for the repos tagged with this group, plus a combined digest
+    #[arg(long, conflicts_with = "repo")]
+    pub group: Option<String>,
+
+    /// Combine the changelogs into a digest opening with an AI executive summary
+    #[arg(long)]
+    pub digest: bool,
+
+    /// Generate for every grouped repo, then write one digest per group plus an index of them
+    #[arg(long, conflicts_with_all = ["repo", "group", "digest", "output"])]
+    pub by_group: bool,
+
+    /// Print the changelog to stdout instead of saving and delivering it
+    #[arg(long)]
+    pub stdout: bool,
+
+    /// GitHub Actions mode: no prompts, annotations, job summary and step outputs; --repo defaults to GITHUB_REPOSITORY
+    #[arg(long, conflicts_with = "stdout")]
+    pub ci: bool,
+
+    /// Write the changelog (or a group's digest) to this path
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Overwrite changelog files that already exist instead of handling them as configured
+    #[arg(long)]
+    pub force: bool,
+
+    /// AI provider (gemini, openai, anthropic, ollama), overriding the config
+    #[arg(long, env = "GAZETTE_AI_PROVIDER")]
+    pub provider: Option<String>,
+
+    /// AI model, overriding the config
+    #[arg(long, env = "GAZETTE_AI_MODEL")]
+    pub model: Option<String>,
+
+    /// Include PRs merged in the last N hours, overriding the configured time period
+    #[arg(long, env = "GAZETTE_HOURS", conflicts_with_all = ["period", "since"])]
+    pub hours: Option<i64>,
+
+    /// Time period preset (1h, 6h, 12h, 24h, 7d, 30d, this-week, last-week), overriding the config;
+    /// repeat it for several windows in one run, e.g. a daily digest and a weekly rollup
+    #[arg(long, env = "GAZETTE_PERIOD", conflicts_with = "since")]
+    pub period: Vec<String>,
+
+    /// Include PRs merged since this date (YYYY-MM-DD), overriding the configured time period
+    #[arg(long, env = "GAZETTE_SINCE")]
+    pub since: Option<String>,
+
+    /// Include every merged PR attached to the GitHub milestone with this title, instead of a time period
+    #[arg(long, conflicts_with_all = ["hours", "period", "since"])]
+    pub milestone: Option<String>,
+
+    /// Include PRs merged in a Linear cycle of the team (current, last), instead of a time period
+    #[arg(long, conflicts_with_all = ["hours", "period", "since", "milestone"])]
+    pub cycle: Option<String>,
+
+    /// Key of the Linear team --cycle refers to, overriding linear_team
+    #[arg(long, requires = "cycle")]
+    pub linear_team: Option<String>,
+
+    /// Cover the PRs merged since the last published release and write the changelog into the
+    /// repo's draft GitHub release, e.g. the one release-drafter keeps; needs a single --repo
+    #[arg(
+        long,
+        conflicts_with_all = ["hours", "period", "since", "milestone", "cycle", "group", "digest", "by_group"]
+    )]
+    pub draft_release: bool,
+
+    /// Generate a changelog or public release notes (changelog, release-notes) for every repo
+    #[arg(long, env = "GAZETTE_MODE")]
+    pub mode: Option<String>,
+
+    /// Format the changelog from conventional-commit PR titles and labels, without an AI provider
+    #[arg(long, conflicts_with_all = ["provider", "model"])]
+    pub no_ai: bool,
+
+    /// Skip Jira lookups for this run, even with Jira credentials configured
+    #[arg(long)]
+    pub no_jira: bool,
+
+    /// Skip every lookup beyond the PRs themselves (Jira, sizes, deployments, CI, board status)
+    #[arg(long)]
+    pub no_enrichment: bool,
+}
+
+#[derive(Args)]
+pub struct ReportArgs {
+    /// Repository to list ("owner/name"); defaults to all subscribed repos
+    #[arg(long)]
+    pub repo: Option<String>,
+
+    /// List the repos tagged with this group
+    #[arg(long, conflicts_with = "repo")]
+    pub group: Option<String>,
+
+    /// Include PRs merged in the last N hours, overriding the configured time period
+    #[arg(long, env = "GAZETTE_HOURS", conflicts_with_all = ["period", "since"])]
+    pub hours: Option<i64>,
+
+    /// Time period preset (1h, 6h, 12h, 24h, 7d, 30d, this-week, last-week), overriding the config
+    #[arg(long, env = "GAZETTE_PERIOD", conflicts_with = "since")]
+    pub period: Option<String>,
+
+    /// Include PRs merged since this date (YYYY-MM-DD), overriding the configured time period
+    #[arg(long, env = "GAZETTE_SINCE")]
+    pub since: Option<String>,
+
+    /// Include every merged PR attached to the GitHub milestone with this title, instead of a time period
+    #[arg(long, conflicts_with_all = ["hours", "period", "since"])]
+    pub milestone: Option<String>,
+
+    /// Include PRs merged in a Linear cycle of the team (current, last), instead of a time period
+    #[arg(long, conflicts_with_all = ["hours", "period", "since", "milestone"])]
+    pub cycle: Option<String>,
+
+    /// Key of the Linear team --cycle refers to, overriding linear_team
+    #[arg(long, requires = "cycle")]
+    pub linear_team: Option<String>,
+
+    /// Print markdown tables instead of aligned plain text
+    #[arg(long)]
+    pub markdown: bool,
+
+    /// Write the report to this path instead of stdout
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+    /// Skip Jira lookups, listing no Jira keys
+    #[arg(long)]
+    pub no_jira: bool,
+}
+
+#[derive(Args)]
+pub struct RegenerateArgs {
+    /// Repository whose last run is regenerated ("owner/name")
+    #[arg(long)]
+    pub repo: String,
+
+    /// Print the changelog to stdout instead of saving and delivering it
+    #[arg(long)]
+    pub stdout: bool,
+
+    /// Write the changelog to this path
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Overwrite the changelog file if it already exists
+    #[arg(long)]
+    pub force: bool,
+
+    /// AI provider (gemini, openai, anthropic, ollama), overriding the config
+    #[arg(long, env = "GAZETTE_AI_PROVIDER")]
+    pub provider: Option<String>,
+
+    /// AI model, overriding the config
+    #[arg(long, env = "GAZETTE_AI_MODEL")]
+    pub model: Option<String>,
+
+    /// Extra instructions appended to the prompt, e.g. "keep it to five bullets"
+    #[arg(long)]
+    pub instructions: Option<String>,
 }
@@ -0,0 +1,1259 @@
+use std::env;
+use std::fs;
+use std::io::{Write, stdout};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+
+use crate::ai::{self, TokenUsage};
+use crate::changelog::ChangelogService;
+use crate::ci::{self, Annotation};
+use crate::cli::{GenerateArgs, RegenerateArgs, ReportArgs};
+use crate::clock;
+use crate::compare;
+use crate::config::{
+    AIProvider, Config, GenerationMode, Repo, TimePeriod, changelog_service, parse_since_date,
+};
+use crate::delivery::{DeliveryOutcome, DraftReleasePublisher};
+use crate::digest::{self, GroupDigest};
+use crate::error::GazetteError;
+use crate::github::{GitHubClient, GitHubRelease};
+use crate::jira::JiraClient;
+use crate::linear::{CyclePick, LinearClient};
+use crate::links::LinkReport;
+use crate::menu;
+use crate::progress::RepoProgress;
+use crate::report;
+use crate::secrets;
+use crate::site;
+use crate::store::Store;
+use crate::style::Paint;
+use crate::usage;
+
+/// Outcome of generating a single repo's changelog from the command line
+struct RepoRun {
+    content: String,
+    /// Where the changelog was saved; None when it was only drafted
+    path: Option<PathBuf>,
+    usage: Option<TokenUsage>,
+    cost: Option<f64>,
+    links: LinkReport,
+    warnings: Vec<String>,
+    deliveries: Vec<DeliveryOutcome>,
+}
+
+/// Generates changelogs non-interactively, for scripts and pipelines
+/// With `--stdout`, only the changelog markdown is written to stdout; status goes to stderr
+/// A group or `--digest` run also produces a combined digest, and a `--by-group` run one per
+/// group plus an index; these replace the per-repo output on stdout
+pub async fn generate(args: GenerateArgs) -> Result<()> {
+    let mut config = Config::load()?;
+    apply_overrides(&args, &mut config)?;
+    apply_cycle_override(
+        &mut config,
+        args.cycle.as_deref(),
+        args.linear_team.as_deref(),
+    )
+    .await?;
+
+    let repo = args.repo.clone().or_else(|| {
+        (args.ci && args.group.is_none())
+            .then(|| env::var("GITHUB_REPOSITORY").ok())
+            .flatten()
+    });
+    let group = args.group.as_deref();
+    let repos = if args.by_group {
+        grouped_repos(&config)?
+    } else {
+        select_repos(&config, repo.as_deref(), group)?
+    };
+    let digest_name = group.or(args.digest.then_some("team"));
+    if args.output.is_some() && digest_name.is_none() && repos.len() > 1 {
+        config_error("--output needs a single repo (--repo), a --group or --digest")?;
+    }
+    let draft = if args.draft_release {
+        Some(apply_draft_release(&mut config, &repos).await?)
+    } else {
+        None
+    };
+    let windows = windows(&args, &config)?;
+    if args.output.is_some() && windows.len() > 1 {
+        config_error("--output needs a single --period")?;
+    }
+
+    let mut failed = 0;
+    let mut generated = false;
+    let mut printed = false;
+    let mut saved_paths = Vec::new();
+    for (window, period) in &windows {
+        let run = generate_window(
+            &args,
+            &config,
+            &repos,
+            period,
+            window.as_deref(),
+            draft.as_ref(),
+            &mut printed,
+        )
+        .await?;
+        failed += run.failed;
+        generated |= run.generated;
+        saved_paths.extend(run.saved_paths);
+    }
+
+    if args.ci {
+        ci::set_output("changelog-generated", &generated.to_string())?;
+        if let [path] = saved_paths.as_slice() {
+            ci::set_output("changelog-path", &path.display().to_string())?;
+        }
+    }
+
+    if failed > 0 {
+        bail!(
+            "{} of {} changelogs failed",
+            failed,
+            repos.len() * windows.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// What generating one window of a run left behind
+struct WindowRun {
+    failed: usize,
+    /// Whether any changelog was generated
+    generated: bool,
+    saved_paths: Vec<PathBuf>,
+}
+
+/// Generates the changelogs (and digests) of one window for every repo
+/// Several windows of a run each name their files after the window, e.g. digest_team_7d_<date>.md
+/// With `draft`, the changelog is also written into that draft release
+async fn generate_window(
+    args: &GenerateArgs,
+    config: &Config,
+    repos: &[Repo],
+    period: &TimePeriod,
+    window: Option<&str>,
+    draft: Option<&GitHubRelease>,
+    printed: &mut bool,
+) -> Result<WindowRun> {
+    let group = args.group.as_deref();
+    let digest_name = group.or(args.digest.then_some("team"));
+    let combined_output = digest_name.is_some() || args.by_group;
+
+    let progress = RepoProgress::new();
+    let mut service = changelog_service(config)?
+        .with_progress(progress.reporter())
+        .with_collision_resolver(menu::collision_prompt());
+    if let (Some(output), None) = (&args.output, digest_name) {
+        service = service.with_output(output.clone());
+    }
+    if let Some(window) = window {
+        service = service.with_window(window);
+    }
+    if args.force {
+        service = service.with_force();
+    }
+    if let Some(draft) = draft {
+        service = service.with_sink(Box::new(DraftReleasePublisher::new(draft.clone())?));
+    }
+    if let Some(name) = &args.mode {
+        let mode = GenerationMode::from_name(name)
+            .with_context(|| format!("Unknown mode '{}'. Use changelog or release-notes", name))?;
+        service = service.with_mode(mode);
+    }
+
+    eprintln!(
+        "{}",
+        format!(
+            "Generating changelogs for {} repo(s) from {}...",
+            repos.len(),
+            period.description()
+        )
+        .dimmed()
+    );
+
+    let service = &service;
+    let progress = &progress;
+    let draft_only = args.stdout;
+    let parallel = config.get_max_parallel_repos();
+    let futures = repos.iter().map(|repo| async move {
+        let result = if draft_only {
+            service
+                .draft_for_repo(repo, period.clone())
+                .await
+                .map(|draft| RepoRun {
+                    content: draft.content,
+                    path: None,
+                    usage: draft.usage,
+                    cost: draft.cost,
+                    links: draft.links,
+                    warnings: draft.warnings,
+                    deliveries: Vec::new(),
+                })
+        } else {
+            service
+                .generate_for_repo(repo, period.clone())
+                .await
+                .map(|generated| RepoRun {
+                    content: generated.content,
+                    path: Some(generated.path),
+                    usage: generated.usage,
+                    cost: generated.cost,
+                    links: generated.links,
+                    warnings: generated.warnings,
+                    deliveries: generated.deliveries,
+                })
+        };
+        progress.finish(repo);
+        (repo, result)
+    });
+    let results: Vec<_> = stream::iter(futures).buffered(parallel).collect().await;
+
+    let mut failed = 0;
+    let mut digest_parts = Vec::new();
+    let mut saved_paths = Vec::new();
+    for (repo, result) in results {
+        match result {
+            Ok(run) => {
+                if args.stdout && !combined_output {
+                    let mut out = stdout().lock();
+                    if *printed {
+                        writeln!(out)?;
+                    }
+                    write!(out, "{}", run.content)?;
+                    out.flush()?;
+                    *printed = true;
+                }
+                let saved = run
+                    .path
+                    .as_ref()
+                    .map(|p| format!(" → {}", p.display()))
+                    .unwrap_or_default();
+                eprintln!("{} {}{}", "✔".green(), repo.key().cyan(), saved);
+                if let Some(tokens) = &run.usage {
+                    eprintln!(
+                        "  {}",
+                        format!("AI usage: {}", usage::format_usage(tokens, run.cost)).dimmed()
+                    );
+                }
+                for outcome in &run.deliveries {
+                    if let Err(e) = &outcome.result {
+                        eprintln!("    {} {}: {}", "✖".red(), outcome.sink, e);
+                        if args.ci {
+                            ci::annotate(
+                                Annotation::Warning,
+                                &format!("{}: delivery to {} failed: {}", repo, outcome.sink, e),
+                            );
+                        }
+                    }
+                }
+                for warning in &run.warnings {
+                    eprintln!("    {} {}", "⚠".yellow(), warning);
+                    if args.ci {
+                        ci::annotate(Annotation::Warning, &format!("{}: {}", repo, warning));
+                    }
+                }
+                if args.ci && run.links.stripped > 0 {
+                    ci::annotate(
+                        Annotation::Warning,
+                        &format!(
+                            "{}: removed {} hallucinated links from the changelog",
+                            repo, run.links.stripped
+                        ),
+                    );
+                }
+                if args.ci && !combined_output {
+                    ci::append_summary(&run.content)?;
+                }
+                saved_paths.extend(run.path);
+                digest_parts.push((repo.clone(), run.content));
+            }
+            // A quiet period is not a failure for scheduled workflows
+            Err(e)
+                if args.ci
+                    && matches!(
+                        GazetteError::find(&e),
+                        Some(GazetteError::EmptyPeriod { .. })
+                    ) =>
+            {
+                eprintln!("{} {} → {}", "·".dimmed(), repo.key().cyan(), e);
+                ci::annotate(Annotation::Notice, &format!("{}: {}", repo, e));
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("{} {} → {}", "✖".red(), repo.key().cyan(), e);
+                let hint = GazetteError::find(&e).and_then(GazetteError::hint);
+                if let Some(hint) = &hint {
+                    eprintln!("  {}", hint.dimmed());
+                }
+                if args.ci {
+                    let hint = hint.map(|hint| format!(" ({})", hint)).unwrap_or_default();
+                    ci::annotate(Annotation::Error, &format!("{}: {}{}", repo, e, hint));
+                }
+            }
+        }
+    }
+
+    if let Some(name) = digest_name
+        && !digest_parts.is_empty()
+    {
+        let title = format!("{} digest — {}", name, period.description());
+        let summarize = args.digest || config.executive_summary;
+        let combined = build_digest(service, &title, period, &digest_parts, summarize).await;
+
+        if args.stdout {
+            let mut out = stdout().lock();
+            write!(out, "{}", combined)?;
+            out.flush()?;
+        } else {
+            let path = match &args.output {
+                Some(output) => {
+                    fs::write(output, &combined).context("Failed to write digest file")?;
+                    output.clone()
+                }
+                None => digest::save(&windowed(name, window), &combined)?,
+            };
+            eprintln!("{} {}", "✔ Digest →".green(), path.display());
+            saved_paths = vec![path];
+        }
+        if args.ci {
+            ci::append_summary(&combined)?;
+        }
+    }
+
+    if args.by_group && !digest_parts.is_empty() {
+        let mut digests = Vec::new();
+        for (group, parts) in digest::by_group(&digest_parts) {
+            let title = format!("{} digest — {}", group, period.description());
+            let combined =
+                build_digest(service, &title, period, &parts, config.executive_summary).await;
+
+            if args.stdout {
+                let mut out = stdout().lock();
+                if *printed {
+                    writeln!(out)?;
+                }
+                write!(out, "{}", combined)?;
+                out.flush()?;
+                *printed = true;
+            } else {
+                let path = digest::save(&windowed(&group, window), &combined)?;
+                eprintln!(
+                    "{} {} → {}",
+                    "✔".green(),
+                    format!("{} digest", group).cyan(),
+                    path.display()
+                );
+                digests.push(GroupDigest {
+                    group,
+                    path,
+                    repos: parts.into_iter().map(|(repo, _)| repo).collect(),
+                });
+            }
+        }
+
+        if !digests.is_empty() {
+            let title = format!("Team digests — {}", period.description());
+            let index = digest::index(&title, &digests);
+            let path = match window {
+                Some(_) => digest::save(&windowed("index", window), &index)?,
+                None => digest::save_index(&index)?,
+            };
+            eprintln!("{} {}", "✔ Index →".green(), path.display());
+            if args.ci {
+                ci::append_summary(&index)?;
+            }
+            saved_paths = vec![path];
+        }
+    }
+
+    Ok(WindowRun {
+        failed,
+        generated: !digest_parts.is_empty(),
+        saved_paths,
+    })
+}
+
+/// Regenerates a repo's changelog from its last run's stored context, then saves and
+/// delivers it like `generate` does; with `--stdout` it is only printed
+pub async fn regenerate(args: RegenerateArgs) -> Result<()> {
+    let mut config = Config::load()?;
+    apply_ai_overrides(&mut config, args.provider.as_deref(), args.model.as_deref())?;
+
+    let repo = resolve_repo(&config, &args.repo)?;
+    let progress = RepoProgress::new();
+    let mut service = changelog_service(&config)?
+        .with_progress(progress.reporter())
+        .with_collision_resolver(menu::collision_prompt());
+    if let Some(output) = args.output {
+        service = service.with_output(output);
+    }
+    if args.force {
+        service = service.with_force();
+    }
+    if let Some(instructions) = args.instructions {
+        service = service.with_instructions(instructions);
+    }
+
+    eprintln!(
+        "{}",
+        format!(
+            "Regenerating {} from the last run's context with {}...",
+            repo.key(),
+            config.get_ai_model()
+        )
+        .dimmed()
+    );
+
+    let draft = service.regenerate_for_repo(&repo).await;
+    progress.finish(&repo);
+    let draft = draft?;
+    let (usage, cost) = (draft.usage, draft.cost);
+
+    if args.stdout {
+        let mut out = stdout().lock();
+        write!(out, "{}", draft.content)?;
+        out.flush()?;
+    } else {
+        let generated = service.publish(draft).await;
+        progress.finish(&repo);
+        let generated = generated?;
+        eprintln!(
+            "{} {} → {}",
+            "✔".green(),
+            repo.key().cyan(),
+            generated.path.display()
+        );
+        for outcome in &generated.deliveries {
+            if let Err(e) = &outcome.result {
+                eprintln!("    {} {}: {}", "✖".red(), outcome.sink, e);
+            }
+        }
+    }
+
+    if let Some(tokens) = &usage {
+        eprintln!(
+            "  {}",
+            format!("AI usage: {}", usage::format_usage(tokens, cost)).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Lists the merged PRs of the period as tables, without the AI
+/// The PRs are gathered like for a changelog, so this is also what the AI would be given
+pub async fn report(args: ReportArgs) -> Result<()> {
+    let mut config = Config::load()?;
+    apply_period_overrides(
+        &mut config,
+        args.hours,
+        args.period.as_deref(),
+        args.since.as_deref(),
+        args.milestone.as_deref(),
+    )?;
+    apply_cycle_override(
+        &mut config,
+        args.cycle.as_deref(),
+        args.linear_team.as_deref(),
+    )
+    .await?;
+    // Gathering never reaches the AI, so its credentials aren't needed
+    config.no_ai = true;
+    config.no_jira |= args.no_jira;
+
+    let repos = select_repos(&config, args.repo.as_deref(), args.group.as_deref())?;
+    let period = config.time_period.clone();
+    let service = changelog_service(&config)?;
+
+    eprintln!(
+        "{}",
+        format!(
+            "Listing PRs merged in the {} for {} repo(s)...",
+            period.description(),
+            repos.len()
+        )
+        .dimmed()
+    );
+
+    let service = &service;
+    let period = &period;
+    let results: Vec<_> =
+        stream::iter(repos.iter().map(|repo| async move {
+            (repo, service.gather_for_repo(repo, period.clone()).await)
+        }))
+        .buffered(config.get_max_parallel_repos())
+        .collect()
+        .await;
+
+    let mut output = String::new();
+    let mut failed = 0;
+    for (repo, result) in results {
+        let contexts = match result {
+            Ok(contexts) => contexts,
+            Err(e)
+                if matches!(
+                    GazetteError::find(&e),
+                    Some(GazetteError::EmptyPeriod { .. })
+                ) =>
+            {
+                Vec::new()
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("{} {} → {}", "✖".red(), repo.key().cyan(), e);
+                if let Some(hint) = GazetteError::find(&e).and_then(GazetteError::hint) {
+                    eprintln!("  {}", hint.dimmed());
+                }
+                continue;
+            }
+        };
+
+        let rows = report::rows(&contexts);
+        let heading = format!(
+            "{} — {} PRs merged in the {}",
+            repo.key(),
+            rows.len(),
+            period.description()
+        );
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        if args.markdown {
+            output.push_str(&format!("## {}\n\n", heading));
+            if !rows.is_empty() {
+                output.push_str(&report::render_markdown(&rows));
+            }
+        } else {
+            output.push_str(&format!("{}\n\n", heading));
+            if !rows.is_empty() {
+                output.push_str(&report::render_text(&rows));
+            }
+        }
+    }
+
+    match &args.output {
+        Some(path) => {
+            fs::write(path, &output).context("Failed to write report")?;
+            eprintln!("{} {}", "✔ Report saved to".green(), path.display());
+        }
+        None => {
+            let mut out = stdout().lock();
+            write!(out, "{}", output)?;
+            out.flush()?;
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} of {} repos could not be listed", failed, repos.len());
+    }
+
+    Ok(())
+}
+
+/// Combines changelogs into a digest, opening with an executive summary when `summarize` is set
+async fn build_digest(
+    service: &ChangelogService,
+    title: &str,
+    period: &TimePeriod,
+    parts: &[(Repo, String)],
+    summarize: bool,
+) -> String {
+    let summary = if summarize {
+        match service.summarize_digest(title, period.clone(), parts).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                eprintln!("{} {}", "✖ Executive summary →".red(), e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(tokens) = summary.as_ref().and_then(|s| s.usage.as_ref()) {
+        eprintln!(
+            "  {}",
+            format!(
+                "Summary AI usage: {}",
+                usage::format_usage(tokens, summary.as_ref().and_then(|s| s.cost))
+            )
+            .dimmed()
+        );
+    }
+
+    digest::combine(title, summary.as_ref().map(|s| s.text.as_str()), parts)
+}
+
+/// The subscriptions tagged with at least one group, for a `--by-group` run
+fn grouped_repos(config: &Config) -> Result<Vec<Repo>> {
+    let repos: Vec<Repo> = config
+        .repos
+        .iter()
+        .filter(|repo| !repo.groups.is_empty())
+        .cloned()
+        .collect();
+
+    if repos.is_empty() {
+        config_error("No repo groups. Tag repos under \"Manage repo groups\" first")?;
+    }
+    Ok(repos)
+}
+
+/// The repos a run covers: the given one, a group's, or every subscription
+fn select_repos(config: &Config, repo: Option<&str>, group: Option<&str>) -> Result<Vec<Repo>> {
+    let repos = match (repo, group) {
+        (Some(full_name), _) => vec![resolve_repo(config, full_name)?],
+        (None, Some(group)) => config.repos_in_group(group),
+        (None, None) => config.repos.clone(),
+    };
+
+    if repos.is_empty() {
+        match group {
+            Some(group) => config_error(format!("No subscribed repos in group '{}'", group))?,
+            None => config_error(
+                "No subscribed repos. Subscribe to a repo first or pass --repo owner/name",
+            )?,
+        }
+    }
+    Ok(repos)
+}
+
+/// Applies the provider, model and period flags on top of the loaded config
+fn apply_overrides(args: &GenerateArgs, config: &mut Config) -> Result<()> {
+    apply_ai_overrides(config, args.provider.as_deref(), args.model.as_deref())?;
+    if args.no_ai {
+        config.no_ai = true;
+    }
+    config.no_jira |= args.no_jira;
+    config.no_enrichment |= args.no_enrichment;
+    // Several periods are windows of their own, read by `windows`
+    apply_period_overrides(
+        config,
+        args.hours,
+        match args.period.as_slice() {
+            [period] => Some(period),
+            _ => None,
+        },
+        args.since.as_deref(),
+        args.milestone.as_deref(),
+    )
+}
+
+/// The windows a generate run covers, widest first so the PRs it fetches and caches answer the
+/// narrower ones; a run of a single window is unnamed and covers the configured period
+fn windows(args: &GenerateArgs, config: &Config) -> Result<Vec<(Option<String>, TimePeriod)>> {
+    if args.period.len() < 2 {
+        return Ok(vec![(None, config.time_period.clone())]);
+    }
+
+    let mut windows: Vec<(Option<String>, TimePeriod)> = Vec::new();
+    for name in &args.period {
+        let name = name.trim().to_lowercase();
+        if !windows
+            .iter()
+            .any(|(window, _)| window.as_ref() == Some(&name))
+        {
+            let period = parse_period(&name)?;
+            windows.push((Some(name), period));
+        }
+    }
+    windows.sort_by_key(|(_, period)| period.bounds().0);
+    Ok(windows)
+}
+
+/// A digest's file name, after the window in a run of several
+fn windowed(name: &str, window: Option<&str>) -> String {
+    match window {
+        Some(window) => format!("{}_{}", name, window),
+        None => name.to_string(),
+    }
+}
+
+/// Applies the --hours, --period, --since and --milestone flags, which clap keeps mutually
+/// exclusive
+fn apply_period_overrides(
+    config: &mut Config,
+    hours: Option<i64>,
+    period: Option<&str>,
+    since: Option<&str>,
+    milestone: Option<&str>,
+) -> Result<()> {
+    if let Some(hours) = hours {
+        if hours <= 0 {
+            config_error("--hours must be greater than zero")?;
+        }
+        config.time_period = TimePeriod::Custom {
+            seconds: hours * 3600,
+        };
+    }
+    if let Some(name) = period {
+        config.time_period = parse_period(name)?;
+    }
+    if let Some(since) = since {
+        config.time_period = TimePeriod::SinceDate {
+            date: parse_since_date(since)?,
+        };
+    }
+    if let Some(title) = milestone {
+        config.time_period = TimePeriod::Milestone {
+            title: title.to_string(),
+        };
+    }
+    Ok(())
+}
+
+/// Applies --cycle, looking up the Linear team's cycle
+async fn apply_cycle_override(
+    config: &mut Config,
+    cycle: Option<&str>,
+    team: Option<&str>,
+) -> Result<()> {
+    let Some(name) = cycle else {
+        return Ok(());
+    };
+    let pick = CyclePick::from_name(name)
+        .with_context(|| format!("Unknown cycle '{}'. Use current or last", name))?;
+    let Some(team) = team.or(config.linear_team.as_deref()) else {
+        return config_error("--cycle needs --linear-team or a configured linear_team");
+    };
+
+    config.time_period = LinearClient::new()?
+        .period(&team.trim().to_uppercase(), pick)
+        .await?;
+    Ok(())
+}
+
+/// Applies --draft-release: finds the repo's draft release and covers the PRs merged since the
+/// latest published one, or the configured period when there is none yet
+async fn apply_draft_release(config: &mut Config, repos: &[Repo]) -> Result<GitHubRelease> {
+    let [repo] = repos else {
+        return config_error("--draft-release needs a single repo (--repo)");
+    };
+
+    let github = GitHubClient::new()?;
+    let Some(draft) = github.get_draft_release(repo).await? else {
+        return config_error(format!(
+            "{} has no draft release; create one or let release-drafter open it",
+            repo.full_name()
+        ));
+    };
+    let latest = github
+        .get_releases(repo)
+        .await?
+        .into_iter()
+        .filter_map(|release| release.published_at)
+        .max();
+    if let Some(start) = latest {
+        config.time_period = TimePeriod::Window {
+            start,
+            end: Utc::now(),
+        };
+    }
+
+    eprintln!(
+        "{}",
+        format!(
+            "Found draft release {} of {}",
+            draft.tag_name,
+            repo.full_name()
+        )
+        .dimmed()
+    );
+    Ok(draft)
+}
+
+fn parse_period(name: &str) -> Result<TimePeriod> {
+    TimePeriod::from_name(name).with_context(|| {
+        let names: Vec<&str> = TimePeriod::NAMES.iter().map(|(name, _)| *name).collect();
+        let (last, rest) = names.split_last().expect("at least one period name");
+        format!(
+            "Unknown period '{}'. Use {} or {}",
+            name,
+            rest.join(", "),
+            last
+        )
+    })
+}
+
+fn apply_ai_overrides(
+    config: &mut Config,
+    provider: Option<&str>,
+    model: Option<&str>,
+) -> Result<()> {
+    if let Some(name) = provider {
+        config.ai_provider = AIProvider::from_name(name).with_context(|| {
+            format!(
+                "Unknown AI provider '{}'. Use gemini, openai, anthropic or ollama",
+                name
+            )
+        })?;
+        // The configured model belongs to the configured provider
+        config.ai_model = None;
+    }
+    if let Some(model) = model {
+        config.ai_model = Some(model.to_string());
+    }
+    Ok(())
+}
+
+/// Lists the models of the current provider and every other one with credentials, live where
+/// the provider can list them
+pub async fn models() -> Result<()> {
+    let config = Config::load()?;
+    let current_model = config.get_ai_model();
+
+    let providers: Vec<AIProvider> = AIProvider::all()
+        .into_iter()
+        .filter(|provider| *provider == config.ai_provider || ai::is_provider_configured(*provider))
+        .collect();
+    let lists = futures::future::join_all(providers.iter().map(|p| provider_models(*p))).await;
+
+    for (provider, (models, note)) in providers.into_iter().zip(lists) {
+        let current = provider == config.ai_provider;
+        let heading = match &note {
+            Some(note) => format!("({})", note),
+            None => "(live)".to_string(),
+        };
+        println!(
+            "\n{} {}",
+            provider.to_string().underline(),
+            heading.dimmed()
+        );
+
+        let mut models = models;
+        if current && !models.iter().any(|m| is_model(m, &current_model)) {
+            models.insert(0, current_model.clone());
+        }
+        for model in models {
+            if current && is_model(&model, &current_model) {
+                println!(
+                    "  {} {} {}",
+                    "●".green(),
+                    model.cyan(),
+                    "(current)".dimmed()
+                );
+            } else {
+                println!("  {} {}", "·".dimmed(), model);
+            }
+        }
+    }
+
+    println!("\n{}", "Switch with `gazette models set <name>`".dimmed());
+    Ok(())
+}
+
+/// Makes `name` the configured model, switching to the provider offering it
+pub async fn set_model(name: &str, provider: Option<&str>) -> Result<()> {
+    let mut config = Config::load()?;
+    let name = name.trim();
+
+    let provider = match provider {
+        Some(provider) => AIProvider::from_name(provider).with_context(|| {
+            format!(
+                "Unknown AI provider '{}'. Use gemini, openai, anthropic or ollama",
+                provider
+            )
+        })?,
+        None => find_provider(name, config.ai_provider).await,
+    };
+
+    config.ai_provider = provider;
+    config.ai_model = Some(name.to_string());
+    config.save()?;
+
+    println!(
+        "{} {} {}",
+        "✔ AI model set to".green(),
+        name.cyan(),
+        format!("({})", provider.short_name()).dimmed()
+    );
+    if provider != AIProvider::Ollama && !ai::is_provider_configured(provider) {
+        println!(
+            "{}",
+            format!(
+                "⚠ {} isn't set; add it before generating",
+                provider.api_key_env_var()
+            )
+            .yellow()
+        );
+    }
+    Ok(())
+}
+
+/// The provider whose built-in or live model list has `name`, else the current provider
+async fn find_provider(name: &str, current: AIProvider) -> AIProvider {
+    let mut providers = AIProvider::all();
+    // The current provider wins when several offer a model of that name
+    providers.sort_by_key(|provider| *provider != current);
+
+    if let Some(provider) = providers
+        .iter()
+        .find(|provider| provider.available_models().contains(&name))
+    {
+        return *provider;
+    }
+    for provider in providers {
+        if provider != current && !ai::is_provider_configured(provider) {
+            continue;
+        }
+        let (models, _) = provider_models(provider).await;
+        if models.iter().any(|model| is_model(model, name)) {
+            return provider;
+        }
+    }
+    current
+}
+
+/// A provider's models, live when it lists them, with a note when the built-in list stands in
+async fn provider_models(provider: AIProvider) -> (Vec<String>, Option<String>) {
+    let live = match ai::create_ai_client(provider, provider.default_model()) {
+        Ok(client) => client.list_models().await,
+        Err(e) => Err(e),
+    };
+    let built_in = || {
+        provider
+            .available_models()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    };
+    match live {
+        Ok(Some(models)) if !models.is_empty() => (models, None),
+        Ok(_) => (built_in(), Some("built-in list".to_string())),
+        Err(e) => (built_in(), Some(format!("built-in list; {}", e))),
+    }
+}
+
+/// True when a listed model is `name`, Ollama's ":latest" tag being implied
+fn is_model(listed: &str, name: &str) -> bool {
+    listed == name || listed.strip_suffix(":latest") == Some(name)
+}
+
+/// Builds the static archive site from the changelogs saved in the working directory
+pub fn site(out: &Path) -> Result<()> {
+    let summary = site::build(out)?;
+
+    if summary.changelogs == 0 {
+        println!(
+            "{}",
+            "No saved changelogs found in this directory.".yellow()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} changelogs across {} repos and {} dates → {}",
+        "✔ Rendered".green(),
+        summary.changelogs.to_string().cyan(),
+        summary.repos.to_string().cyan(),
+        summary.dates.to_string().cyan(),
+        out.join("index.html").display().to_string().cyan()
+    );
+
+    Ok(())
+}
+
+/// Prints the delta between two saved runs of a repo, by default its two latest
+pub fn compare(
+    full_name: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+    list: bool,
+    output: Option<&Path>,
+) -> Result<()> {
+    let config = Config::load()?;
+    let repo = resolve_repo(&config, full_name)?;
+    let runs = Store::open()?.runs(&repo)?;
+
+    if list {
+        if runs.is_empty() {
+            println!("{}", "No saved runs for this repo yet.".yellow());
+        }
+        for run in &runs {
+            println!(
+                "  {} {}  {} PRs  {}  {}",
+                format!("#{}", run.id).cyan(),
+                clock::format(run.created_at, "%Y-%m-%d %H:%M"),
+                run.prs.len(),
+                run.period.dimmed(),
+                run.path.display().to_string().dimmed()
+            );
+        }
+        return Ok(());
+    }
+
+    let find = |id: i64| {
+        runs.iter()
+            .find(|run| run.id == id)
+            .with_context(|| format!("No run #{} for {}", id, repo.key()))
+    };
+    // Runs are newest first, so the run before another one comes right after it
+    let after = match to {
+        Some(id) => find(id)?,
+        None => runs
+            .first()
+            .with_context(|| format!("No saved runs for {}", repo.key()))?,
+    };
+    let before = match from {
+        Some(id) => find(id)?,
+        None => runs
+            .iter()
+            .find(|run| run.id < after.id)
+            .with_context(|| format!("Run #{} has no earlier run to compare with", after.id))?,
+    };
+
+    let section = compare::render(&compare::diff(&before.prs, &after.prs));
+
+    eprintln!(
+        "{}",
+        format!("Comparing run #{} with run #{}", before.id, after.id).dimmed()
+    );
+    match output {
+        Some(path) => {
+            fs::write(path, &section).context("Failed to write delta section")?;
+            eprintln!("{} {}", "✔ Delta saved to".green(), path.display());
+        }
+        None => print!("{}", section),
+    }
+
+    Ok(())
+}
+
+/// Lists the PRs merged in the last `days` days, as recorded by previous runs
+pub fn shipped(repo: Option<&str>, days: u64) -> Result<()> {
+    let since = Utc::now() - chrono::Duration::days(days as i64);
+    let prs = Store::open()?.shipped_since(since, repo)?;
+
+    if prs.is_empty() {
+        println!(
+            "{}",
+            format!("No recorded PRs merged in the last {} days.", days).yellow()
+        );
+        return Ok(());
+    }
+
+    let mut current_repo = None;
+    for pr in &prs {
+        if current_repo != Some(&pr.repo) {
+            println!("\n{}", pr.repo.underline());
+            current_repo = Some(&pr.repo);
+        }
+
+        let merged = pr
+            .merged_at
+            .map(|at| clock::format(at, "%Y-%m-%d"))
+            .unwrap_or_default();
+        let keys = if pr.jira_keys.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", pr.jira_keys.join(", "))
+        };
+
+        let author = pr
+            .author
+            .as_ref()
+            .map(|login| format!(" by @{}", login))
+            .unwrap_or_default();
+
+        println!(
+            "  {} {} {}{} {}{}",
+            "•".green(),
+            merged.dimmed(),
+            format!("#{}", pr.number).cyan(),
+            keys.dimmed(),
+            pr.title,
+            author.dimmed()
+        );
+    }
+
+    println!(
+        "\n  {} {}\n",
+        prs.len().to_string().bold(),
+        format!("PRs merged in the last {} days", days).dimmed()
+    );
+
+    Ok(())
+}
+
+/// Prints the accumulated usage per provider/model
+pub fn usage_summary() -> Result<()> {
+    let ledger = usage::UsageLedger::load()?;
+
+    if ledger.entries.is_empty() {
+        println!("{}", "No AI usage recorded yet.".yellow());
+        return Ok(());
+    }
+
+    println!("\n{}", "AI usage:".underline());
+    for entry in &ledger.entries {
+        println!(
+            "  {} {} {}",
+            "•".green(),
+            entry.provider.short_name().cyan(),
+            format!("({})", entry.model).dimmed()
+        );
+        println!(
+            "    {} requests, {} prompt + {} completion tokens, ~${:.4}",
+            entry.requests, entry.prompt_tokens, entry.completion_tokens, entry.cost_usd
+        );
+    }
+    println!(
+        "\n  {} {}",
+        "Total:".bold(),
+        format!("~${:.4}", ledger.total_cost()).yellow()
+    );
+    println!();
+
+    Ok(())
+}
+
+/// Estimate of the GitHub requests one repo's run takes at the least: a couple of pages
+/// of merged PRs, the release, milestone, advisory and Dependabot lookups, the repo files read for
+/// context (CODEOWNERS, the previous changelog) and a delivery or two. Enrichment that reads each
+/// PR's files, stats or reviews adds a request per PR on top, so falling short of this is a sure
+/// failure while clearing it is not a guarantee
+const REQUESTS_PER_REPO: u64 = 15;
+
+/// Shows the GitHub rate limits, whether Jira answers and the AI provider's last reported
+/// quota, to tell whether a big run is safe to start
+pub async fn limits() -> Result<()> {
+    let config = Config::load()?;
+
+    println!("\n{}", "GitHub".underline());
+    match GitHubClient::new() {
+        Ok(github) => match github.get_rate_limits().await {
+            Ok(limits) => {
+                let buckets = [
+                    ("core", Some(&limits.core)),
+                    ("graphql", limits.graphql.as_ref()),
+                    ("search", limits.search.as_ref()),
+                ];
+                for (name, limit) in buckets {
+                    if let Some(limit) = limit {
+                        println!(
+                            "  {} {:<8} {} / {} left, resets at {}",
+                            "•".green(),
+                            name,
+                            limit.remaining.to_string().cyan(),
+                            limit.limit,
+                            clock::format(limit.reset, "%H:%M")
+                        );
+                    }
+                }
+
+                let needed = config.repos.len() as u64 * REQUESTS_PER_REPO;
+                if limits.core.remaining < needed {
+                    println!(
+                        "  {}",
+                        format!(
+                            "⚠ A run over all {} repos needs about {} requests; wait for the reset or use a group",
+                            config.repos.len(),
+                            needed
+                        )
+                        .yellow()
+                    );
+                }
+            }
+            Err(e) => println!("  {} {}", "✖".red(), e),
+        },
+        Err(e) => println!("  {} {}", "✖".red(), e),
+    }
+
+    println!("\n{}", "Jira".underline());
+    if secrets::is_set("JIRA_URL") {
+        match JiraClient::new() {
+            Ok(jira) => match jira.current_user().await {
+                Ok(name) => println!("  {} Connected as {}", "✔".green(), name.cyan()),
+                Err(e) => println!("  {} {}", "✖".red(), e),
+            },
+            Err(e) => println!("  {} {}", "✖".red(), e),
+        }
+    } else {
+        println!("  {} {}", "·".dimmed(), "Not configured".dimmed());
+    }
+
+    let provider = config.ai_provider;
+    println!(
+        "\n{} {}",
+        "AI provider".underline(),
+        format!("({}, {})", provider.short_name(), config.get_ai_model()).dimmed()
+    );
+    if config.no_ai {
+        println!("  {} {}", "·".dimmed(), "AI-free formatting is on".dimmed());
+    } else if !matches!(provider, AIProvider::OpenAI | AIProvider::Anthropic) {
+        println!(
+            "  {} {}",
+            "·".dimmed(),
+            format!("{} doesn't report its quota", provider.short_name()).dimmed()
+        );
+    } else {
+        match Store::open()?.ai_rate_limit(provider)? {
+            Some(observed) => {
+                let info = &observed.info;
+                let pair = |left: Option<u64>, limit: Option<u64>| match (left, limit) {
+                    (Some(left), Some(limit)) => format!("{} / {}", left, limit),
+                    (Some(left), None) => left.to_string(),
+                    _ => "unknown".to_string(),
+                };
+                println!(
+                    "  {} requests {} left{}",
+                    "•".green(),
+                    pair(info.requests_remaining, info.requests_limit).cyan(),
+                    info.requests_reset
+                        .as_ref()
+                        .map(|reset| format!(", resets {}", reset))
+                        .unwrap_or_default()
+                );
+                println!(
+                    "  {} tokens {} left",
+                    "•".green(),
+                    pair(info.tokens_remaining, info.tokens_limit).cyan()
+                );
+                println!(
+                    "  {}",
+                    format!(
+                        "As reported with the last {} response, {}",
+                        observed.model,
+                        clock::format(observed.observed_at, "%Y-%m-%d %H:%M")
+                    )
+                    .dimmed()
+                );
+            }
+            None => println!(
+                "  {} {}",
+                "·".dimmed(),
+                format!(
+                    "No quota seen yet; {} reports it with each response",
+                    provider.short_name()
+                )
+                .dimmed()
+            ),
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Uses the subscribed repo's settings when available, so per-repo options still apply
+fn resolve_repo(config: &Config, full_name: &str) -> Result<Repo> {
+    let repo = Repo::from_full_name(full_name)
+        .context("Invalid format. Use 'owner/name' (e.g., rust-lang/rust)")?;
+
+    Ok(config
+        .repos
+        .iter()
+        .find(|r| r.key() == repo.key())
+        .cloned()
+        .unwrap_or(repo))
+}
+
+/// Fails with a [`GazetteError::Config`] for flags or config that can't be used together
+fn config_error<T>(message: impl Into<String>) -> Result<T> {
+    Err(GazetteError::Config(message.into()).into())
+}
@@ -1,323 +1,156 @@
 use std::fmt;
-use std::fs;
-use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::{Context, Result};
-use chrono::Duration;
-use inquire::{Select, Text};
-use owo_colors::OwoColorize;
-use serde::{Deserialize, Serialize};
+use chrono::{NaiveDate, Utc};
+use inquire::{Confirm, MultiSelect, Select, Text};
 
-const CONFIG_FILE: &str = "config.json";
+pub use gazette_core::config::*;
+use gazette_core::titles::TitleFilter;
+use gazette_core::tls::{self, TlsOptions};
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
-pub enum AIProvider {
-    #[default]
-    Gemini,
-    OpenAI,
-    Anthropic,
-    Ollama,
-}
-
-impl AIProvider {
-    /// Returns all available AI providers
-    pub fn all() -> Vec<Self> {
-        vec![Self::Gemini, Self::OpenAI, Self::Anthropic, Self::Ollama]
-    }
-
-    /// Returns the environment variable name for the API key
-    pub fn api_key_env_var(&self) -> &'static str {
-        match self {
-            Self::Gemini => "GEMINI_API_KEY",
-            Self::OpenAI => "OPENAI_API_KEY",
-            Self::Anthropic => "ANTHROPIC_API_KEY",
-            Self::Ollama => "OLLAMA_HOST",
-        }
-    }
+use crate::changelog::ChangelogService;
+use crate::clock;
+use crate::delivery::{self, JiraVersionPublisher, OutputSink};
+use crate::error::GazetteError;
+use crate::fuzzy;
+use crate::jira::JiraClient;
+use crate::style::Paint;
 
-    /// Returns a user-friendly prompt for the API key
-    pub fn api_key_prompt(&self) -> &'static str {
-        match self {
-            Self::Gemini => "Enter your Gemini API key:",
-            Self::OpenAI => "Enter your OpenAI API key:",
-            Self::Anthropic => "Enter your Anthropic API key:",
-            Self::Ollama => "Enter your Ollama host (default: http://localhost:11434):",
-        }
-    }
+pub fn subscribe_repo() -> Result<()> {
+    let input = Text::new("Repo (owner/name):")
+        .with_help_message(
+            "Add @scope (e.g., acme/monorepo@payments) to subscribe to part of a repo again",
+        )
+        .prompt()?;
 
-    /// Returns default value for the credential (if any)
-    pub fn default_value(&self) -> Option<&'static str> {
-        match self {
-            Self::Ollama => Some("http://localhost:11434"),
-            _ => None,
-        }
-    }
+    let repo = Repo::from_full_name(&input)
+        .context("Invalid format. Use 'owner/name' (e.g., rust-lang/rust)")?;
 
-    /// Returns available models for this provider
-    pub fn available_models(&self) -> Vec<&'static str> {
-        match self {
-            Self::Gemini => vec![
-                "gemini-2.0-flash",
-                "gemini-2.0-flash-lite",
-                "gemini-1.5-pro",
-                "gemini-1.5-flash",
-            ],
-            Self::OpenAI => vec![
-                "gpt-4o",
-                "gpt-4o-mini",
-                "gpt-4-turbo",
-                "gpt-4",
-                "gpt-3.5-turbo",
-            ],
-            Self::Anthropic => vec![
-                "claude-sonnet-4-20250514",
-                "claude-3-5-sonnet-20241022",
-                "claude-3-5-haiku-20241022",
-                "claude-3-opus-20240229",
-            ],
-            Self::Ollama => vec![
-                "llama3.2",
-                "llama3.1",
-                "mistral",
-                "codellama",
-                "deepseek-coder",
-            ],
-        }
-    }
+    let mut config = Config::load()?;
 
-    /// Returns the default model for this provider
-    pub fn default_model(&self) -> &'static str {
-        match self {
-            Self::Gemini => "gemini-2.0-flash",
-            Self::OpenAI => "gpt-4o",
-            Self::Anthropic => "claude-sonnet-4-20250514",
-            Self::Ollama => "llama3.2",
-        }
+    // Check if already subscribed
+    if config.repos.iter().any(|r| r.key() == repo.key()) {
+        println!("{} {}", "Already subscribed to".yellow(), repo.key().cyan());
+        return Ok(());
     }
 
-    /// Returns a short name for display
-    pub fn short_name(&self) -> &'static str {
-        match self {
-            Self::Gemini => "Gemini",
-            Self::OpenAI => "OpenAI",
-            Self::Anthropic => "Claude",
-            Self::Ollama => "Ollama",
-        }
-    }
-}
+    config.repos.push(repo.clone());
+    config.save()?;
 
-impl fmt::Display for AIProvider {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Gemini => write!(f, "Gemini (Google)"),
-            Self::OpenAI => write!(f, "OpenAI (GPT)"),
-            Self::Anthropic => write!(f, "Anthropic (Claude)"),
-            Self::Ollama => write!(f, "Ollama (Local)"),
-        }
-    }
-}
+    println!("{} {}", "✔ Subscribed to".green(), repo.key().cyan());
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
-#[serde(tag = "type", content = "value")]
-pub enum TimePeriod {
-    LastHour,
-    Last6Hours,
-    Last12Hours,
-    #[default]
-    Last24Hours,
-    Custom {
-        seconds: i64,
-    },
+    Ok(())
 }
 
-impl TimePeriod {
-    /// Returns the duration for this time period
-    pub fn to_duration(&self) -> Duration {
-        match self {
-            Self::LastHour => Duration::hours(1),
-            Self::Last6Hours => Duration::hours(6),
-            Self::Last12Hours => Duration::hours(12),
-            Self::Last24Hours => Duration::hours(24),
-            Self::Custom { seconds } => Duration::seconds(*seconds),
-        }
-    }
-
-    /// Human-readable description
-    pub fn description(&self) -> String {
-        match self {
-            Self::LastHour => "last hour".to_string(),
-            Self::Last6Hours => "last 6 hours".to_string(),
-            Self::Last12Hours => "last 12 hours".to_string(),
-            Self::Last24Hours => "last 24 hours".to_string(),
-            Self::Custom { seconds } => {
-                let hours = seconds / 3600;
-                let mins = (seconds % 3600) / 60;
-                let secs = seconds % 60;
-                format!("last {:02}:{:02}:{:02}", hours, mins, secs)
-            }
-        }
-    }
-}
+pub fn unsubscribe_repo() -> Result<()> {
+    let mut config = Config::load()?;
 
-impl fmt::Display for TimePeriod {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::LastHour => write!(f, "Last hour"),
-            Self::Last6Hours => write!(f, "Last 6 hours"),
-            Self::Last12Hours => write!(f, "Last 12 hours"),
-            Self::Last24Hours => write!(f, "Last 24 hours"),
-            Self::Custom { seconds } => {
-                let hours = seconds / 3600;
-                let mins = (seconds % 3600) / 60;
-                let secs = seconds % 60;
-                write!(f, "Custom ({:02}:{:02}:{:02})", hours, mins, secs)
-            }
-        }
+    if config.repos.is_empty() {
+        println!("{}", "No subscribed repos.".yellow());
+        return Ok(());
     }
-}
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct Repo {
-    pub owner: String,
-    pub name: String,
-}
+    let repos = config.repos.clone();
+    let selected = Select::new("Select repo to unsubscribe:", repos)
+        .with_filter(&fuzzy::filter)
+        .prompt()?;
 
-impl Repo {
-    pub fn new(owner: impl Into<String>, name: impl Into<String>) -> Self {
-        Self {
-            owner: owner.into(),
-            name: name.into(),
-        }
-    }
+    config.repos.retain(|r| r.key() != selected.key());
 
-    /// Parses "owner/name" format into a Repo
-    pub fn from_full_name(full_name: &str) -> Option<Self> {
-        let parts: Vec<&str> = full_name.split('/').collect();
-        if parts.len() == 2 {
-            Some(Self::new(parts[0], parts[1]))
-        } else {
-            None
-        }
-    }
+    config.save()?;
 
-    pub fn full_name(&self) -> String {
-        format!("{}/{}", self.owner, self.name)
-    }
-}
+    println!(
+        "{} {}",
+        "✔ Unsubscribed from".green(),
+        selected.key().cyan()
+    );
 
-impl fmt::Display for Repo {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}/{}", self.owner, self.name)
-    }
+    Ok(())
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct Config {
-    #[serde(default)]
-    pub repos: Vec<Repo>,
-    #[serde(default)]
-    pub time_period: TimePeriod,
-    #[serde(default)]
-    pub ai_provider: AIProvider,
-    #[serde(default)]
-    pub ai_model: Option<String>,
-}
+pub fn configure_repo_groups() -> Result<()> {
+    let mut config = Config::load()?;
 
-impl Config {
-    /// Returns the AI model, falling back to provider default
-    pub fn get_ai_model(&self) -> String {
-        self.ai_model
-            .clone()
-            .unwrap_or_else(|| self.ai_provider.default_model().to_string())
+    if config.repos.is_empty() {
+        println!("{}", "No subscribed repos.".yellow());
+        return Ok(());
     }
-}
 
-impl Config {
-    /// Loads config from config.json, migrating from repos.json if needed
-    pub fn load() -> Result<Self> {
-        let config_path = Path::new(CONFIG_FILE);
-        let old_repos_path = Path::new("repos.json");
-
-        // Migrate from old repos.json if it exists
-        if !config_path.exists() && old_repos_path.exists() {
-            let content =
-                fs::read_to_string(old_repos_path).context("Failed to read repos.json")?;
-            let repos: Vec<Repo> =
-                serde_json::from_str(&content).context("Failed to parse repos.json")?;
-
-            let config = Config {
-                repos,
-                time_period: TimePeriod::default(),
-                ai_provider: AIProvider::default(),
-                ai_model: None,
-            };
-            config.save()?;
-
-            // Remove old file after migration
-            fs::remove_file(old_repos_path).ok();
-
-            println!("{}", "Migrated repos.json to config.json".green());
-            return Ok(config);
-        }
-
-        if !config_path.exists() {
-            return Ok(Config::default());
-        }
-
-        let content = fs::read_to_string(config_path).context("Failed to read config.json")?;
-        let config: Config =
-            serde_json::from_str(&content).context("Failed to parse config.json")?;
+    let selected = Select::new("Select repo to tag:", config.repos.clone())
+        .with_filter(&fuzzy::filter)
+        .prompt()?;
+    let input = Text::new("Groups (comma-separated, empty for none):")
+        .with_initial_value(&selected.groups.join(", "))
+        .prompt()?;
+    let groups = parse_groups(&input);
 
-        Ok(config)
+    if let Some(repo) = config.repos.iter_mut().find(|r| r.key() == selected.key()) {
+        repo.groups = groups.clone();
     }
+    config.save()?;
 
-    /// Saves config to config.json
-    pub fn save(&self) -> Result<()> {
-        let content = serde_json::to_string_pretty(self).context("Failed to serialize config")?;
-        fs::write(CONFIG_FILE, content).context("Failed to write config.json")?;
-        Ok(())
+    if groups.is_empty() {
+        println!(
+            "{} {}",
+            "✔ Removed all groups from".green(),
+            selected.key().cyan()
+        );
+    } else {
+        println!(
+            "{} {} {}",
+            "✔ Tagged".green(),
+            selected.key().cyan(),
+            format!("[{}]", groups.join(", ")).dimmed()
+        );
     }
-}
 
-pub fn load_repos() -> Result<Vec<Repo>> {
-    Ok(Config::load()?.repos)
+    Ok(())
 }
 
-pub fn load_time_period() -> Result<TimePeriod> {
-    Ok(Config::load()?.time_period)
-}
+pub fn configure_repo_paths() -> Result<()> {
+    let mut config = Config::load()?;
 
-pub fn subscribe_repo() -> Result<()> {
-    let input = Text::new("Repo (owner/name):").prompt()?;
+    if config.repos.is_empty() {
+        println!("{}", "No subscribed repos.".yellow());
+        return Ok(());
+    }
 
-    let repo = Repo::from_full_name(&input)
-        .context("Invalid format. Use 'owner/name' (e.g., rust-lang/rust)")?;
+    let selected = Select::new("Select repo to scope:", config.repos.clone())
+        .with_filter(&fuzzy::filter)
+        .prompt()?;
+    let input = Text::new("Paths (comma-separated prefixes or globs, empty for the whole repo):")
+        .with_initial_value(&selected.paths.join(", "))
+        .with_placeholder("services/payments/**, libs/billing")
+        .with_help_message("Only PRs changing a file under these paths are included")
+        .prompt()?;
+    let paths = parse_groups(&input);
+    gazette_core::paths::PathFilter::new(&paths)?;
 
-    let mut config = Config::load()?;
+    if let Some(repo) = config.repos.iter_mut().find(|r| r.key() == selected.key()) {
+        repo.paths = paths.clone();
+    }
+    config.save()?;
 
-    // Check if already subscribed
-    if config
-        .repos
-        .iter()
-        .any(|r| r.owner == repo.owner && r.name == repo.name)
-    {
+    if paths.is_empty() {
         println!(
             "{} {}",
-            "Already subscribed to".yellow(),
-            repo.full_name().cyan()
+            "✔ Including every PR of".green(),
+            selected.key().cyan()
+        );
+    } else {
+        println!(
+            "{} {} {}",
+            "✔ Scoped".green(),
+            selected.key().cyan(),
+            format!("to {}", paths.join(", ")).dimmed()
         );
-        return Ok(());
     }
 
-    config.repos.push(repo.clone());
-    config.save()?;
-
-    println!("{} {}", "✔ Subscribed to".green(), repo.full_name().cyan());
-
     Ok(())
 }
 
-pub fn unsubscribe_repo() -> Result<()> {
+pub fn configure_components() -> Result<()> {
     let mut config = Config::load()?;
 
     if config.repos.is_empty() {
@@ -325,24 +158,55 @@ pub fn unsubscribe_repo() -> Result<()> {
         return Ok(());
     }
 
-    let repos = config.repos.clone();
-    let selected = Select::new("Select repo to unsubscribe:", repos).prompt()?;
-
-    config
-        .repos
-        .retain(|r| !(r.owner == selected.owner && r.name == selected.name));
+    let selected = Select::new("Select repo:", config.repos.clone())
+        .with_filter(&fuzzy::filter)
+        .prompt()?;
+    let input = Text::new("Components (Name = paths; ..., empty to disable):")
+        .with_initial_value(&gazette_core::components::format_mapping(
+            &selected.components,
+        ))
+        .with_placeholder("API = crates/api/**; Frontend = web/**")
+        .with_help_message(
+            "Changelog entries are grouped by the components their PRs changed files in",
+        )
+        .prompt()?;
+    let components = gazette_core::components::parse_mapping(&input)?;
 
+    if let Some(repo) = config.repos.iter_mut().find(|r| r.key() == selected.key()) {
+        repo.components = components.clone();
+    }
     config.save()?;
 
-    println!(
-        "{} {}",
-        "✔ Unsubscribed from".green(),
-        selected.full_name().cyan()
-    );
+    if components.is_empty() {
+        println!(
+            "{} {}",
+            "✔ Component grouping disabled for".green(),
+            selected.key().cyan()
+        );
+    } else {
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        println!(
+            "{} {} {}",
+            "✔ Grouping".green(),
+            selected.key().cyan(),
+            format!("by {}", names.join(", ")).dimmed()
+        );
+    }
 
     Ok(())
 }
 
+/// Parses a comma-separated group list, dropping blanks and duplicates
+pub fn parse_groups(input: &str) -> Vec<String> {
+    let mut groups: Vec<String> = Vec::new();
+    for group in input.split(',').map(str::trim).filter(|g| !g.is_empty()) {
+        if !groups.iter().any(|g| g.eq_ignore_ascii_case(group)) {
+            groups.push(group.to_string());
+        }
+    }
+    groups
+}
+
 pub fn list_repos() -> Result<()> {
     let config = Config::load()?;
 
@@ -353,7 +217,16 @@ pub fn list_repos() -> Result<()> {
 
     println!("\n{}", "Subscribed repositories:".underline());
     for repo in &config.repos {
-        println!("  {} {}", "•".green(), repo.full_name().cyan());
+        if repo.groups.is_empty() {
+            println!("  {} {}", "•".green(), repo.key().cyan());
+        } else {
+            println!(
+                "  {} {} {}",
+                "•".green(),
+                repo.key().cyan(),
+                format!("[{}]", repo.groups.join(", ")).dimmed()
+            );
+        }
     }
     println!();
 
@@ -363,6 +236,7 @@ pub fn list_repos() -> Result<()> {
 #[derive(Debug, Clone)]
 enum TimePeriodOption {
     Preset(TimePeriod),
+    SinceDate,
     Custom,
 }
 
@@ -370,6 +244,7 @@ impl fmt::Display for TimePeriodOption {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Preset(period) => write!(f, "{}", period),
+            Self::SinceDate => write!(f, "Since a date..."),
             Self::Custom => write!(f, "Custom..."),
         }
     }
@@ -380,23 +255,23 @@ pub fn configure_time_period() -> Result<()> {
 
     println!("Current period: {}", config.time_period.to_string().cyan());
 
-    let options = vec![
-        TimePeriodOption::Preset(TimePeriod::LastHour),
-        TimePeriodOption::Preset(TimePeriod::Last6Hours),
-        TimePeriodOption::Preset(TimePeriod::Last12Hours),
-        TimePeriodOption::Preset(TimePeriod::Last24Hours),
-        TimePeriodOption::Custom,
-    ];
+    let mut options: Vec<TimePeriodOption> = TimePeriod::presets()
+        .into_iter()
+        .map(TimePeriodOption::Preset)
+        .collect();
+    options.push(TimePeriodOption::SinceDate);
+    options.push(TimePeriodOption::Custom);
 
     let selection = Select::new("Select time period:", options).prompt()?;
 
     let new_period = match selection {
         TimePeriodOption::Preset(period) => period,
+        TimePeriodOption::SinceDate => prompt_since_date()?,
         TimePeriodOption::Custom => prompt_custom_period()?,
     };
 
     let mut config = Config::load()?;
-    config.time_period = new_period;
+    config.time_period = new_period.clone();
     config.save()?;
 
     println!(
@@ -408,6 +283,104 @@ pub fn configure_time_period() -> Result<()> {
     Ok(())
 }
 
+pub fn configure_timezone() -> Result<()> {
+    let config = Config::load()?;
+
+    println!("Current timezone: {}", config.zone()?.to_string().cyan());
+
+    let input = Text::new("Timezone (IANA name, empty for the system timezone):")
+        .with_default(config.timezone.as_deref().unwrap_or_default())
+        .with_placeholder("Europe/Berlin")
+        .with_help_message(
+            "Used for calendar periods, dates in file names and headers, merge times and schedules",
+        )
+        .prompt()?;
+
+    let timezone = Some(input.trim().to_string()).filter(|name| !name.is_empty());
+    let zone = match &timezone {
+        Some(name) => clock::Zone::parse(name)?,
+        None => clock::Zone::System,
+    };
+
+    let mut config = Config::load()?;
+    config.timezone = timezone;
+    config.save()?;
+    clock::set_zone(zone);
+
+    println!(
+        "{} {}",
+        "✔ Timezone set to".green(),
+        zone.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_tls() -> Result<()> {
+    let config = Config::load()?;
+
+    let input = Text::new("Extra CA certificates (PEM file paths, comma-separated):")
+        .with_default(&config.ca_certificates.join(", "))
+        .with_placeholder("/etc/ssl/certs/corporate-ca.pem")
+        .with_help_message(
+            "Trusted by GitHub, Jira, AI and delivery requests, e.g. behind TLS interception",
+        )
+        .prompt()?;
+    let ca_certificates: Vec<String> = input
+        .split(',')
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect();
+
+    let no_system_roots = !ca_certificates.is_empty()
+        && Confirm::new("Trust only these certificates, not the system's certificate store?")
+            .with_default(config.no_system_roots)
+            .prompt()?;
+
+    let options = TlsOptions::load(&ca_certificates, !no_system_roots)?;
+
+    let mut config = Config::load()?;
+    config.ca_certificates = ca_certificates;
+    config.no_system_roots = no_system_roots;
+    config.save()?;
+    tls::set_options(options);
+
+    let trusted = match (config.ca_certificates.len(), no_system_roots) {
+        (0, _) => "the system certificate store".to_string(),
+        (n, true) => format!("{} CA certificate file(s) only", n),
+        (n, false) => format!(
+            "the system certificate store and {} CA certificate file(s)",
+            n
+        ),
+    };
+    println!("{} {}", "✔ HTTPS requests trust".green(), trusted.cyan());
+
+    Ok(())
+}
+
+fn prompt_since_date() -> Result<TimePeriod> {
+    let input = Text::new("Include PRs merged since (YYYY-MM-DD):")
+        .with_placeholder("2026-01-01")
+        .prompt()?;
+
+    let date = parse_since_date(&input)?;
+    Ok(TimePeriod::SinceDate { date })
+}
+
+/// Parses a YYYY-MM-DD date that isn't in the future
+pub fn parse_since_date(input: &str) -> Result<NaiveDate> {
+    let date = NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").map_err(|_| {
+        GazetteError::Config(format!(
+            "Invalid date '{}'. Use YYYY-MM-DD (e.g., 2026-01-01)",
+            input.trim()
+        ))
+    })?;
+    if date > clock::today() {
+        return Err(GazetteError::Config("The date can't be in the future".to_string()).into());
+    }
+    Ok(date)
+}
+
 fn prompt_custom_period() -> Result<TimePeriod> {
     let input = Text::new("Time period (HH:MM:SS):")
         .with_default("01:00:00")
@@ -417,7 +390,10 @@ fn prompt_custom_period() -> Result<TimePeriod> {
     let parts: Vec<&str> = input.split(':').collect();
 
     if parts.len() != 3 {
-        anyhow::bail!("Invalid format. Use HH:MM:SS (e.g., 01:30:00)");
+        return Err(GazetteError::Config(
+            "Invalid format. Use HH:MM:SS (e.g., 01:30:00)".to_string(),
+        )
+        .into());
     }
 
     let hours: i64 = parts[0].parse().unwrap_or(0);
@@ -427,7 +403,7 @@ fn prompt_custom_period() -> Result<TimePeriod> {
     let total_seconds = hours * 3600 + minutes * 60 + secs;
 
     if total_seconds <= 0 {
-        anyhow::bail!("Time period must be greater than 0");
+        return Err(GazetteError::Config("Time period must be greater than 0".to_string()).into());
     }
 
     Ok(TimePeriod::Custom {
@@ -435,10 +411,6 @@ fn prompt_custom_period() -> Result<TimePeriod> {
     })
 }
 
-pub fn load_ai_provider() -> Result<AIProvider> {
-    Ok(Config::load()?.ai_provider)
-}
-
 pub fn configure_ai_provider() -> Result<AIProvider> {
     use crate::menu::credentials::ensure_provider_api_key;
 
@@ -495,3 +467,1352 @@ pub fn configure_ai_model() -> Result<()> {
 
     Ok(())
 }
+
+pub fn configure_changelog_format() -> Result<()> {
+    let config = Config::load()?;
+
+    println!(
+        "Current changelog format: {}",
+        config.changelog_format.to_string().cyan()
+    );
+
+    let selection = Select::new("Select changelog format:", ChangelogFormat::all()).prompt()?;
+
+    let mut config = Config::load()?;
+    config.changelog_format = selection;
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Changelog format set to".green(),
+        selection.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_markdown_flavor() -> Result<()> {
+    let config = Config::load()?;
+
+    println!(
+        "Current markdown flavor: {}",
+        config.markdown_flavor.to_string().cyan()
+    );
+
+    let selection = Select::new("Select markdown flavor:", MarkdownFlavor::all())
+        .with_help_message("Saved changelogs are normalized to it, e.g. for a docs pipeline")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.markdown_flavor = selection;
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Markdown flavor set to".green(),
+        selection.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_frontmatter() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Start saved changelogs with YAML frontmatter?")
+        .with_default(config.frontmatter)
+        .with_help_message(
+            "Title, date, repo and tags, for Hugo, Jekyll or Docusaurus content folders",
+        )
+        .prompt()?;
+    let tags = if enabled {
+        let input = Text::new("Tags (comma-separated, besides the repo's groups):")
+            .with_initial_value(&config.frontmatter_tags.join(", "))
+            .prompt()?;
+        parse_groups(&input)
+    } else {
+        config.frontmatter_tags.clone()
+    };
+
+    let mut config = Config::load()?;
+    config.frontmatter = enabled;
+    config.frontmatter_tags = tags;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ YAML frontmatter".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_version_naming() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Name changelogs after the release published in their period?")
+        .with_default(config.version_naming)
+        .with_help_message("Periods without a release keep the date-based name")
+        .prompt()?;
+    let (file_pattern, title_pattern) = if enabled {
+        let file_pattern = Text::new("File name pattern:")
+            .with_initial_value(&config.get_version_file_pattern())
+            .with_help_message("{prefix}, {repo}, {version} and {date} are filled in")
+            .prompt()?;
+        let title_pattern = Text::new("Title pattern:")
+            .with_initial_value(&config.get_version_title_pattern())
+            .prompt()?;
+        (Some(file_pattern), Some(title_pattern))
+    } else {
+        (
+            config.version_file_pattern.clone(),
+            config.version_title_pattern.clone(),
+        )
+    };
+
+    let mut config = Config::load()?;
+    config.version_naming = enabled;
+    config.version_file_pattern = file_pattern.filter(|p| !p.trim().is_empty());
+    config.version_title_pattern = title_pattern.filter(|p| !p.trim().is_empty());
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Version-anchored naming".green(), state.cyan());
+
+    Ok(())
+}
+
+/// A repo's generation mode as offered in the settings, where None follows the configured mode
+struct RepoMode(Option<GenerationMode>);
+
+impl fmt::Display for RepoMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(mode) => write!(f, "{}", mode),
+            None => write!(f, "Use the default mode"),
+        }
+    }
+}
+
+pub fn configure_generation_mode() -> Result<()> {
+    let config = Config::load()?;
+
+    let mut targets = vec!["Default for all repos".to_string()];
+    targets.extend(config.repos.iter().map(|repo| match repo.mode {
+        Some(mode) => format!("{} ({})", repo.key(), mode.file_prefix()),
+        None => repo.key(),
+    }));
+    let target = Select::new("Set the generation mode for:", targets)
+        .with_filter(&fuzzy::filter)
+        .raw_prompt()?;
+
+    let mut config = Config::load()?;
+    if target.index == 0 {
+        println!(
+            "Current mode: {}",
+            config.generation_mode.to_string().cyan()
+        );
+        let mode = Select::new("Generate:", GenerationMode::all()).prompt()?;
+        config.generation_mode = mode;
+        config.save()?;
+        println!(
+            "{} {}",
+            "✔ Generation mode set to".green(),
+            mode.to_string().cyan()
+        );
+        return Ok(());
+    }
+
+    let options = std::iter::once(RepoMode(None))
+        .chain(GenerationMode::all().into_iter().map(Some).map(RepoMode))
+        .collect();
+    let mode = Select::new("Generate:", options).prompt()?;
+    let Some(repo) = config.repos.get_mut(target.index - 1) else {
+        return Ok(());
+    };
+    repo.mode = mode.0;
+    let name = repo.key();
+    config.save()?;
+
+    println!(
+        "{} {} {}",
+        "✔ Mode for".green(),
+        name.cyan(),
+        format!("set to {}", mode).green()
+    );
+
+    Ok(())
+}
+
+pub fn configure_duplicate_prs() -> Result<()> {
+    let config = Config::load()?;
+
+    println!(
+        "Previously reported PRs: {}",
+        config.duplicate_prs.to_string().cyan()
+    );
+
+    let selection = Select::new(
+        "What should happen to PRs already included in an earlier changelog?",
+        DuplicatePrs::all(),
+    )
+    .with_help_message("Useful when consecutive windows overlap, e.g. daily runs over 36 hours")
+    .prompt()?;
+
+    let cross_check = selection != DuplicatePrs::Include
+        && Confirm::new("Also count PRs the repo's own CHANGELOG.md mentions as reported?")
+            .with_default(config.cross_check_changelog)
+            .with_help_message("Avoids repeating notes the maintainers already wrote")
+            .prompt()?;
+
+    let mut config = Config::load()?;
+    config.duplicate_prs = selection;
+    config.cross_check_changelog = cross_check;
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Previously reported PRs:".green(),
+        selection.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_existing_changelog() -> Result<()> {
+    let config = Config::load()?;
+
+    println!(
+        "Existing changelog files: {}",
+        config.existing_changelog.to_string().cyan()
+    );
+
+    let selection = Select::new(
+        "What should happen when the changelog file of a run already exists?",
+        ExistingChangelog::all(),
+    )
+    .with_help_message("E.g. on a second run the same day; --force overwrites the file instead")
+    .prompt()?;
+
+    let mut config = Config::load()?;
+    config.existing_changelog = selection;
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Existing changelog files:".green(),
+        selection.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_title_exclusions() -> Result<()> {
+    let config = Config::load()?;
+
+    let mut targets = vec!["Default for all repos".to_string()];
+    targets.extend(config.repos.iter().map(|repo| {
+        if repo.exclude_titles.is_empty() {
+            repo.key()
+        } else {
+            format!("{} ({} patterns)", repo.key(), repo.exclude_titles.len())
+        }
+    }));
+    let target = Select::new("Exclude PRs by title for:", targets)
+        .with_filter(&fuzzy::filter)
+        .raw_prompt()?;
+
+    let current = match target.index {
+        0 => &config.exclude_titles,
+        index => &config.repos[index - 1].exclude_titles,
+    };
+    let input = Text::new("Title patterns (comma-separated):")
+        .with_initial_value(&current.join(", "))
+        .with_help_message("Regexes such as ^chore, or globs such as glob:Bump * from *")
+        .prompt()?;
+    let patterns: Vec<String> = input
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+        .collect();
+    TitleFilter::new(&patterns)?;
+
+    let mut config = Config::load()?;
+    let name = if target.index == 0 {
+        config.exclude_titles = patterns;
+        "all repos".to_string()
+    } else {
+        let Some(repo) = config.repos.get_mut(target.index - 1) else {
+            return Ok(());
+        };
+        repo.exclude_titles = patterns;
+        repo.key()
+    };
+    config.save()?;
+
+    println!("{} {}", "✔ Title exclusions saved for".green(), name.cyan());
+
+    Ok(())
+}
+
+pub fn configure_jira_comments() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Comment on each Jira issue with its changelog entries?")
+        .with_default(config.jira_comments)
+        .with_help_message(
+            "Posted after delivery, with links to the PRs; the token needs to add comments",
+        )
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.jira_comments = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Jira comments".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_group_by_epic() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Group changelog entries by their Jira epic?")
+        .with_default(config.group_by_epic)
+        .with_help_message(
+            "Epics with several entries get a heading like \"Checkout revamp: 4 changes\"",
+        )
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.group_by_epic = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Epic grouping".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_pr_body_sections() -> Result<()> {
+    let config = Config::load()?;
+
+    let input = Text::new(
+        "PR description sections (headings or comment pairs; ..., empty for all):",
+    )
+    .with_initial_value(&gazette_core::body_sections::format_rules(
+        &config.pr_body_sections,
+    ))
+    .with_placeholder("Release Notes; <!-- release-notes --> <!-- /release-notes -->")
+    .with_help_message(
+        "Only these parts of a PR description go to the AI; descriptions without them go whole",
+    )
+    .prompt()?;
+    let rules = gazette_core::body_sections::parse_rules(&input)?;
+
+    let mut config = Config::load()?;
+    config.pr_body_sections = rules.clone();
+    config.save()?;
+
+    if rules.is_empty() {
+        println!("{}", "✔ Whole PR descriptions go to the AI".green());
+    } else {
+        println!(
+            "{} {}",
+            "✔ PR description sections saved:".green(),
+            gazette_core::body_sections::format_rules(&rules).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn configure_select_prs() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Choose which PRs to include before drafting a single repo?")
+        .with_default(config.select_prs)
+        .with_help_message("Drop reverts or chores from one changelog without a permanent filter")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.select_prs = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ PR selection".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_two_pass_generation() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Summarize each PR individually before composing the changelog?")
+        .with_default(config.two_pass_generation)
+        .with_help_message("Better quality for long PR bodies, at the cost of more AI requests")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.two_pass_generation = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Two-pass generation".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_json_artifact() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Write a JSON artifact alongside each markdown changelog?")
+        .with_default(config.json_artifact)
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.json_artifact = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ JSON artifact".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_atom_feed() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Publish generated changelogs to Atom feeds in feeds/?")
+        .with_default(config.atom_feed)
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.atom_feed = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Atom feed".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_no_ai() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Format changelogs from PR titles and labels instead of the AI?")
+        .with_help_message("Groups conventional-commit titles into Features, Fixes and Chores")
+        .with_default(config.no_ai)
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.no_ai = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ AI-free formatting".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_jira_status_filter() -> Result<()> {
+    let config = Config::load()?;
+
+    let done_only = Confirm::new("Only give the AI Jira issues that are done?")
+        .with_default(config.jira_done_only)
+        .with_help_message(
+            "Issues in Jira's Done status category, such as Done, Resolved or Closed",
+        )
+        .prompt()?;
+    let warn = Confirm::new("Warn about merged PRs whose linked issues aren't done yet?")
+        .with_default(config.warn_open_issues)
+        .with_help_message("Helps catch mis-linked tickets before publishing")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.jira_done_only = done_only;
+    config.warn_open_issues = warn;
+    config.save()?;
+
+    let state = |enabled| if enabled { "enabled" } else { "disabled" };
+    println!(
+        "{} {}",
+        "✔ Done-only Jira context".green(),
+        state(done_only).cyan()
+    );
+    println!("{} {}", "✔ Open issue warnings".green(), state(warn).cyan());
+
+    Ok(())
+}
+
+/// Lookups the settings menu skipped for the rest of the interactive session
+static SKIP_JIRA: AtomicBool = AtomicBool::new(false);
+static SKIP_ENRICHMENT: AtomicBool = AtomicBool::new(false);
+
+/// The config, with the lookups skipped from the settings menu for this session
+pub fn session_config() -> Result<Config> {
+    let mut config = Config::load()?;
+    config.no_jira |= SKIP_JIRA.load(Ordering::Relaxed);
+    config.no_enrichment |= SKIP_ENRICHMENT.load(Ordering::Relaxed);
+    Ok(config)
+}
+
+/// Creates the sink for a delivery channel, the Jira version one with the configured project
+pub fn channel_sink(channel: DeliveryChannel, config: &Config) -> Result<Box<dyn OutputSink>> {
+    match channel {
+        DeliveryChannel::JiraVersion => Ok(Box::new(JiraVersionPublisher::new(
+            JiraClient::new().context("Jira version delivery needs Jira credentials")?,
+            config.jira_version_project.clone(),
+        ))),
+        _ => delivery::sink_for(channel),
+    }
+}
+
+/// The changelog service for `config`, with the delivery sinks the core leaves to us
+pub fn changelog_service(config: &Config) -> Result<ChangelogService> {
+    let mut service = ChangelogService::from_config(config)?;
+    if config
+        .delivery_channels
+        .contains(&DeliveryChannel::JiraVersion)
+    {
+        service = service.with_sink(channel_sink(DeliveryChannel::JiraVersion, config)?);
+    }
+    Ok(service)
+}
+
+/// Skips Jira or every enrichment lookup until gazette exits; config.json is left alone
+pub fn configure_integrations() -> Result<()> {
+    let config = session_config()?;
+
+    let no_jira = Confirm::new("Skip Jira lookups, e.g. while Jira is down?")
+        .with_default(config.no_jira)
+        .with_help_message("Changelogs are drafted from the PRs alone; credentials stay stored")
+        .prompt()?;
+    let no_enrichment = Confirm::new("Skip all lookups beyond the PRs themselves?")
+        .with_default(config.no_enrichment)
+        .with_help_message("Jira, PR sizes, deployments, CI results and board statuses")
+        .prompt()?;
+
+    SKIP_JIRA.store(no_jira, Ordering::Relaxed);
+    SKIP_ENRICHMENT.store(no_enrichment, Ordering::Relaxed);
+
+    let state = |skipped| {
+        if skipped {
+            "skipped this session"
+        } else {
+            "enabled"
+        }
+    };
+    println!(
+        "{} {}",
+        "✔ Jira lookups".green(),
+        state(no_jira || no_enrichment).cyan()
+    );
+    println!(
+        "{} {}",
+        "✔ PR enrichment".green(),
+        state(no_enrichment).cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_contributor_shoutouts() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Thank the PR authors and co-authors at the end of each changelog?")
+        .with_default(config.contributor_shoutouts)
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.contributor_shoutouts = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Contributor shoutouts".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_lead_time_stats() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Append lead-time stats (PR opened to merged) to each changelog?")
+        .with_default(config.lead_time_stats)
+        .with_help_message("Median, 75th and 90th percentiles; also added to the JSON artifact")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.lead_time_stats = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Lead-time stats".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_stats_appendix() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Append PR, contributor and line counts to each changelog?")
+        .with_default(config.stats_appendix)
+        .with_help_message("Line counts take one GitHub request per PR")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.stats_appendix = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Stats section".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_dependency_grouping() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("List Dependabot and Renovate PRs as package bumps in one section?")
+        .with_default(config.group_dependency_updates)
+        .with_help_message("They skip the AI and show the old and new versions from their titles")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.group_dependency_updates = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!(
+        "{} {}",
+        "✔ Dependency update grouping".green(),
+        state.cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_activity_chart() -> Result<()> {
+    let config = Config::load()?;
+
+    println!(
+        "Activity chart: {}",
+        config.activity_chart.to_string().cyan()
+    );
+
+    let selection = Select::new(
+        "How should the merges per day be charted?",
+        ActivityChart::all(),
+    )
+    .with_help_message("Mermaid renders on GitHub and most docs sites; ASCII reads anywhere")
+    .prompt()?;
+
+    let mut config = Config::load()?;
+    config.activity_chart = selection;
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Activity chart:".green(),
+        selection.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_reviewer_credits() -> Result<()> {
+    let config = Config::load()?;
+
+    println!(
+        "Reviewer credits: {}",
+        config.reviewer_credits.to_string().cyan()
+    );
+
+    let selection = Select::new(
+        "How should the people who approved each PR be credited?",
+        ReviewerCredits::all(),
+    )
+    .with_help_message("Takes one GitHub request per PR")
+    .prompt()?;
+
+    let mut config = Config::load()?;
+    config.reviewer_credits = selection;
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Reviewer credits:".green(),
+        selection.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_impact_scoring() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Score PRs by size and labels and list the biggest changes first?")
+        .with_default(config.impact_scoring)
+        .with_help_message("Fetches each PR's size from GitHub once; sizes are cached")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.impact_scoring = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Impact scoring".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_project_status_field() -> Result<()> {
+    let config = Config::load()?;
+
+    let field = Text::new("Project board field to annotate entries with (empty for none):")
+        .with_initial_value(config.project_status_field.as_deref().unwrap_or_default())
+        .with_help_message(
+            "A single-select field of a GitHub project such as \"Status\"; Done and Shipped aren't shown",
+        )
+        .prompt()?;
+    let field = field.trim();
+
+    let mut config = Config::load()?;
+    config.project_status_field = (!field.is_empty()).then(|| field.to_string());
+    config.save()?;
+
+    if field.is_empty() {
+        println!("{}", "✔ Board status annotations disabled".green());
+    } else {
+        println!(
+            "{} {}",
+            "✔ Annotating entries with the board field".green(),
+            field.cyan()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn configure_ci_status() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Flag PRs whose CI failed after merging?")
+        .with_default(config.ci_status)
+        .with_help_message("Reads the check runs and commit statuses of each PR's merge commit")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.ci_status = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ CI status checks".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_security_advisories() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled =
+        Confirm::new("List the security advisories and Dependabot alerts resolved in each period?")
+            .with_default(config.security_advisories)
+            .with_help_message(
+                "The token needs the security_events and repository_advisories:read scopes",
+            )
+            .prompt()?;
+
+    let mut config = Config::load()?;
+    config.security_advisories = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Security section".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_discussions() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("List the discussions started or answered in each period?")
+        .with_default(config.discussions)
+        .with_help_message("Brings product decisions and RFC outcomes in alongside the PRs")
+        .prompt()?;
+    let categories = if enabled {
+        let input = Text::new("Discussion categories (comma-separated, empty for all):")
+            .with_initial_value(&config.discussion_categories.join(", "))
+            .prompt()?;
+        parse_groups(&input)
+    } else {
+        config.discussion_categories.clone()
+    };
+
+    let mut config = Config::load()?;
+    config.discussions = enabled;
+    config.discussion_categories = categories;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Discussions".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_pr_comments() -> Result<()> {
+    let config = Config::load()?;
+
+    let input = Text::new("Comments to excerpt per PR:")
+        .with_default(&config.pr_comments.to_string())
+        .with_help_message("0 leaves comments out; each one costs a GitHub request per PR")
+        .prompt()?;
+    let count: usize = input
+        .trim()
+        .parse()
+        .ok()
+        .context("Enter a whole number, or 0 to leave comments out")?;
+    let order = if count > 0 {
+        Select::new("Which comments?", CommentOrder::all()).prompt()?
+    } else {
+        config.pr_comment_order
+    };
+
+    let mut config = Config::load()?;
+    config.pr_comments = count;
+    config.pr_comment_order = order;
+    config.save()?;
+
+    if count == 0 {
+        println!("{} {}", "✔ PR comments".green(), "disabled".cyan());
+    } else {
+        println!(
+            "{} {}",
+            "✔ PR comments excerpted:".green(),
+            format!("{} ({})", count, order.to_string().to_lowercase()).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn configure_deployment_environment() -> Result<()> {
+    let config = Config::load()?;
+
+    let environment = Text::new("Deployment environment to track (empty for none):")
+        .with_initial_value(config.deployment_environment.as_deref().unwrap_or_default())
+        .with_help_message(
+            "PRs not yet in a successful GitHub deployment to it are marked as merged only",
+        )
+        .prompt()?;
+    let environment = environment.trim();
+
+    let mut config = Config::load()?;
+    config.deployment_environment = (!environment.is_empty()).then(|| environment.to_string());
+    config.save()?;
+
+    if environment.is_empty() {
+        println!("{}", "✔ Deployment tracking disabled".green());
+    } else {
+        println!(
+            "{} {}",
+            "✔ Tracking deployments to".green(),
+            environment.cyan()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn configure_executive_summary() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Open group digests with an AI-written summary for leadership?")
+        .with_default(config.executive_summary)
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.executive_summary = enabled;
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Digest executive summary".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_delivery_channels() -> Result<()> {
+    use crate::menu::credentials::ensure_delivery_credentials;
+
+    let config = Config::load()?;
+    let options = DeliveryChannel::all();
+    let defaults: Vec<usize> = options
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| config.delivery_channels.contains(c))
+        .map(|(i, _)| i)
+        .collect();
+
+    let selection = MultiSelect::new("Deliver changelogs to:", options)
+        .with_default(&defaults)
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.delivery_channels = selection.clone();
+    config.save()?;
+
+    // Ensure credentials are configured for every selected channel
+    for channel in &selection {
+        ensure_delivery_credentials(*channel)?;
+    }
+
+    if selection.contains(&DeliveryChannel::PullRequest) {
+        let dir = Text::new("Directory to commit changelogs to:")
+            .with_default(&config.get_pull_request_dir())
+            .with_help_message("Ignored in CHANGELOG.md mode, which updates CHANGELOG.md")
+            .prompt()?;
+        config.pull_request_dir = Some(dir.trim().trim_matches('/').to_string());
+        config.save()?;
+    }
+
+    if selection.contains(&DeliveryChannel::Discussion) {
+        let category = Text::new("Discussion category:")
+            .with_default(&config.get_discussion_category())
+            .prompt()?;
+        let repo = Text::new("Central repo for discussions (owner/name, empty for each repo):")
+            .with_initial_value(config.discussion_repo.as_deref().unwrap_or_default())
+            .prompt()?;
+        let repo = repo.trim();
+
+        if !repo.is_empty() && Repo::from_full_name(repo).is_none() {
+            return Err(GazetteError::Config(
+                "Invalid format. Use 'owner/name' (e.g., acme/announcements)".to_string(),
+            )
+            .into());
+        }
+
+        config.discussion_category = Some(category.trim().to_string());
+        config.discussion_repo = (!repo.is_empty()).then(|| repo.to_string());
+        config.save()?;
+    }
+
+    if selection.contains(&DeliveryChannel::Gist) {
+        config.gist_public = Confirm::new("Make gists public?")
+            .with_default(config.gist_public)
+            .with_help_message("Secret gists are unlisted but visible to anyone with the link")
+            .prompt()?;
+        config.save()?;
+    }
+
+    if selection.contains(&DeliveryChannel::Directory) {
+        let dir = Text::new("Directory to copy changelogs to:")
+            .with_initial_value(config.delivery_dir.as_deref().unwrap_or_default())
+            .prompt()?;
+        let dir = dir.trim();
+
+        if dir.is_empty() {
+            return Err(GazetteError::Config(
+                "The directory delivery channel needs a directory".to_string(),
+            )
+            .into());
+        }
+
+        config.delivery_dir = Some(dir.to_string());
+        config.save()?;
+    }
+
+    if selection.contains(&DeliveryChannel::JiraVersion) {
+        let project = Text::new("Jira project key to release in (empty for the issues' projects):")
+            .with_initial_value(config.jira_version_project.as_deref().unwrap_or_default())
+            .prompt()?;
+        let project = project.trim().to_uppercase();
+
+        config.jira_version_project = (!project.is_empty()).then_some(project);
+        config.save()?;
+    }
+
+    if selection.is_empty() {
+        println!("{}", "✔ Delivery disabled".green());
+    } else {
+        let names: Vec<String> = selection.iter().map(|c| c.to_string()).collect();
+        println!("{} {}", "✔ Delivering to".green(), names.join(", ").cyan());
+    }
+
+    Ok(())
+}
+
+pub fn configure_keep_a_changelog() -> Result<()> {
+    let config = Config::load()?;
+
+    let enabled = Confirm::new("Merge entries into the repo's CHANGELOG.md (Keep a Changelog)?")
+        .with_default(config.keep_a_changelog)
+        .with_help_message("Otherwise a dated standalone changelog file is written per run")
+        .prompt()?;
+
+    let mut config = Config::load()?;
+    config.keep_a_changelog = enabled;
+
+    if enabled && !config.repos.is_empty() {
+        let configure_paths = Confirm::new("Set a local CHANGELOG.md path for a repo?")
+            .with_default(false)
+            .with_help_message("Repos without a local path get CHANGELOG.md fetched from GitHub")
+            .prompt()?;
+
+        if configure_paths {
+            let selected = Select::new("Select repo:", config.repos.clone())
+                .with_filter(&fuzzy::filter)
+                .prompt()?;
+            let current = selected.changelog_path.clone().unwrap_or_default();
+            let path = Text::new("Local CHANGELOG.md path (empty to fetch from GitHub):")
+                .with_initial_value(&current)
+                .prompt()?;
+            let path = path.trim();
+
+            if let Some(repo) = config.repos.iter_mut().find(|r| r.key() == selected.key()) {
+                repo.changelog_path = (!path.is_empty()).then(|| path.to_string());
+            }
+        }
+    }
+    config.save()?;
+
+    let state = if enabled { "enabled" } else { "disabled" };
+    println!("{} {}", "✔ Keep a Changelog mode".green(), state.cyan());
+
+    Ok(())
+}
+
+pub fn configure_document_template() -> Result<()> {
+    let config = Config::load()?;
+
+    let path = Text::new("Document template path (empty to save the changelog as-is):")
+        .with_initial_value(config.document_template.as_deref().unwrap_or_default())
+        .with_help_message("A Tera template; the changelog is available as {{ changelog }}")
+        .prompt()?;
+    let path = path.trim();
+
+    if !path.is_empty() {
+        crate::template::validate_file(path)?;
+    }
+
+    let mut config = Config::load()?;
+    config.document_template = (!path.is_empty()).then(|| path.to_string());
+    config.save()?;
+
+    if path.is_empty() {
+        println!("{}", "✔ Document template disabled".green());
+    } else {
+        println!("{} {}", "✔ Document template set to".green(), path.cyan());
+    }
+
+    Ok(())
+}
+
+pub fn configure_hooks() -> Result<()> {
+    let config = Config::load()?;
+
+    let pre = Text::new("Pre-generation hook command (empty for none):")
+        .with_initial_value(config.pre_generate_hook.as_deref().unwrap_or_default())
+        .with_help_message(
+            "Reads the PR context JSON on stdin and prints the context to draft from",
+        )
+        .prompt()?;
+    let post = Text::new("Post-generation hook command (empty for none):")
+        .with_initial_value(config.post_generate_hook.as_deref().unwrap_or_default())
+        .with_help_message("Run with the saved changelog's path as $1 and in $GAZETTE_CHANGELOG")
+        .prompt()?;
+    let (pre, post) = (pre.trim(), post.trim());
+
+    let mut config = Config::load()?;
+    config.pre_generate_hook = (!pre.is_empty()).then(|| pre.to_string());
+    config.post_generate_hook = (!post.is_empty()).then(|| post.to_string());
+    config.save()?;
+
+    for (label, command) in [("Pre-generation hook", pre), ("Post-generation hook", post)] {
+        if command.is_empty() {
+            println!("{} {}", format!("✔ {}", label).green(), "disabled".cyan());
+        } else {
+            println!(
+                "{} {}",
+                format!("✔ {} set to", label).green(),
+                command.cyan()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub fn configure_secret_backend() -> Result<()> {
+    let config = Config::load()?;
+    let options = SecretBackend::all();
+    let current = options
+        .iter()
+        .position(|b| *b == config.secret_backend)
+        .unwrap_or(0);
+
+    let selection = Select::new("Store credentials in:", options)
+        .with_starting_cursor(current)
+        .prompt()?;
+
+    if selection == config.secret_backend {
+        println!(
+            "{} {}",
+            "✔ Credentials stored in".green(),
+            selection.to_string().cyan()
+        );
+        return Ok(());
+    }
+
+    let migrate = Confirm::new(&format!(
+        "Move saved credentials from {} to {}?",
+        config.secret_backend, selection
+    ))
+    .with_default(true)
+    .prompt()?;
+
+    // Later saves go to secrets.age without asking, so the passphrase is asked for now
+    if selection == SecretBackend::EncryptedFile
+        || config.secret_backend == SecretBackend::EncryptedFile
+    {
+        crate::secrets::EncryptedFileStore::unlock(crate::menu::credentials::prompt_passphrase)?;
+    }
+
+    if migrate {
+        let moved = crate::secrets::migrate(config.secret_backend, selection)?;
+        println!(
+            "{} {}",
+            "✔ Credentials moved:".green(),
+            moved.to_string().cyan()
+        );
+    }
+
+    let mut config = Config::load()?;
+    config.secret_backend = selection;
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Credentials stored in".green(),
+        selection.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_daemon_interval() -> Result<()> {
+    let config = Config::load()?;
+
+    let input = Text::new("Minutes between daemon runs:")
+        .with_default(&config.get_daemon_interval_minutes().to_string())
+        .prompt()?;
+    let minutes: u64 = input
+        .trim()
+        .parse()
+        .ok()
+        .filter(|m| *m > 0)
+        .context("Enter a whole number of minutes greater than zero")?;
+
+    let mut config = Config::load()?;
+    config.daemon_interval_minutes = Some(minutes);
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Daemon runs every".green(),
+        format!("{} minutes", minutes).cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_ai_rate_limit() -> Result<()> {
+    let config = Config::load()?;
+    let provider = config.ai_provider;
+
+    let input = Text::new(&format!(
+        "Requests per minute to send {} (empty for no limit):",
+        provider
+    ))
+    .with_default(
+        &config
+            .ai_rate_limit()
+            .map(|rpm| rpm.to_string())
+            .unwrap_or_default(),
+    )
+    .with_help_message("Calls over the limit wait their turn, shown as queued in the progress")
+    .prompt()?;
+    let limit: Option<u32> = match input.trim() {
+        "" => None,
+        number => Some(
+            number
+                .parse()
+                .ok()
+                .filter(|n| *n > 0)
+                .context("Enter a whole number greater than zero")?,
+        ),
+    };
+
+    let mut config = Config::load()?;
+    match limit {
+        Some(rpm) => config.ai_requests_per_minute.insert(provider, rpm),
+        None => config.ai_requests_per_minute.remove(&provider),
+    };
+    config.save()?;
+
+    let limit = limit
+        .map(|rpm| format!("{} requests per minute", rpm))
+        .unwrap_or_else(|| "no limit".to_string());
+    println!(
+        "{} {}",
+        format!("✔ {} rate limit:", provider).green(),
+        limit.cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_max_parallel_repos() -> Result<()> {
+    let config = Config::load()?;
+
+    let input = Text::new("Repos to generate at the same time:")
+        .with_default(&config.get_max_parallel_repos().to_string())
+        .with_help_message(
+            "Lower it if GitHub or the AI provider starts rate limiting runs over many repos",
+        )
+        .prompt()?;
+    let limit: usize = input
+        .trim()
+        .parse()
+        .ok()
+        .filter(|n| *n > 0)
+        .context("Enter a whole number greater than zero")?;
+
+    let mut config = Config::load()?;
+    config.max_parallel_repos = Some(limit);
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Repos generated at the same time:".green(),
+        limit.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+pub fn configure_issue_concurrency() -> Result<()> {
+    let config = Config::load()?;
+
+    let input = Text::new("Issue keys to look up at the same time:")
+        .with_default(&config.get_issue_concurrency().to_string())
+        .with_help_message("Lower it if Jira starts rejecting requests")
+        .prompt()?;
+    let limit: usize = input
+        .trim()
+        .parse()
+        .ok()
+        .filter(|n| *n > 0)
+        .context("Enter a whole number greater than zero")?;
+
+    let mut config = Config::load()?;
+    config.issue_concurrency = Some(limit);
+    config.save()?;
+
+    println!(
+        "{} {}",
+        "✔ Issue keys looked up at the same time:".green(),
+        limit.to_string().cyan()
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+enum ScheduleTarget {
+    Repo(Repo),
+    Group(String),
+}
+
+impl fmt::Display for ScheduleTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Repo(repo) => write!(f, "Repo {}", repo.key()),
+            Self::Group(group) => write!(f, "Group {}", group),
+        }
+    }
+}
+
+pub fn configure_daemon_schedules() -> Result<()> {
+    let mut config = Config::load()?;
+
+    if config.repos.is_empty() {
+        println!("{}", "No subscribed repos.".yellow());
+        return Ok(());
+    }
+
+    let mut targets: Vec<ScheduleTarget> = config
+        .groups()
+        .into_iter()
+        .map(ScheduleTarget::Group)
+        .collect();
+    targets.extend(config.repos.iter().cloned().map(ScheduleTarget::Repo));
+
+    let target = Select::new("Schedule for:", targets)
+        .with_filter(&fuzzy::filter)
+        .prompt()?;
+    let current = match &target {
+        ScheduleTarget::Repo(repo) => repo.schedule.clone(),
+        ScheduleTarget::Group(group) => config.group_schedules.get(group).cloned(),
+    };
+
+    let input = Text::new("Cron expression (empty to use the daemon interval):")
+        .with_initial_value(current.as_deref().unwrap_or_default())
+        .with_help_message(
+            "e.g. 0 9 * * MON for Mondays at 09:00 local time, or hourly, daily, weekly",
+        )
+        .prompt()?;
+    let expression = input.trim();
+
+    let next_run = if expression.is_empty() {
+        None
+    } else {
+        let schedule = crate::schedule::parse(expression)?;
+        crate::schedule::next_after(&schedule, Utc::now())
+    };
+    let expression = (!expression.is_empty()).then(|| expression.to_string());
+
+    match &target {
+        ScheduleTarget::Repo(selected) => {
+            if let Some(repo) = config.repos.iter_mut().find(|r| r.key() == selected.key()) {
+                repo.schedule = expression.clone();
+            }
+        }
+        ScheduleTarget::Group(group) => match &expression {
+            Some(expression) => {
+                config
+                    .group_schedules
+                    .insert(group.clone(), expression.clone());
+            }
+            None => {
+                config.group_schedules.remove(group);
+            }
+        },
+    }
+    config.save()?;
+
+    match next_run {
+        Some(next) => println!(
+            "{} {} {}",
+            "✔ Scheduled".green(),
+            target.to_string().cyan(),
+            format!("(next run {})", clock::format(next, "%Y-%m-%d %H:%M")).dimmed()
+        ),
+        None => println!(
+            "{} {}",
+            "✔ Using the daemon interval for".green(),
+            target.to_string().cyan()
+        ),
+    }
+
+    Ok(())
+}
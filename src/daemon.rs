@@ -0,0 +1,335 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::changelog::{ChangelogService, GeneratedChangelog};
+use crate::clock;
+use crate::config::{Config, Repo, TimePeriod, changelog_service};
+use crate::error::GazetteError;
+use crate::schedule;
+use crate::store::Store;
+use crate::style::Paint;
+
+/// When each repo was last covered by the daemon, also the format of the legacy daemon_state.json
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DaemonState {
+    #[serde(default)]
+    pub last_runs: HashMap<String, DateTime<Utc>>,
+}
+
+impl DaemonState {
+    pub fn load() -> Result<Self> {
+        Ok(Self {
+            last_runs: Store::open()?.last_runs()?.into_iter().collect(),
+        })
+    }
+
+    /// Records that `repo` is covered up to `last_run`, leaving the other repos' runs as they are
+    pub fn record(repo: &Repo, last_run: DateTime<Utc>) -> Result<()> {
+        Store::open()?.set_last_runs([(&repo.key(), &last_run)])
+    }
+
+    /// The window the next run should cover: from the end of the last covered window,
+    /// or the configured period for repos the daemon hasn't seen yet
+    pub fn window_for(&self, repo: &Repo, fallback: TimePeriod, now: DateTime<Utc>) -> TimePeriod {
+        let start = self
+            .last_runs
+            .get(&repo.key())
+            .copied()
+            .unwrap_or_else(|| fallback.bounds_at(now).0);
+
+        TimePeriod::Window { start, end: now }
+    }
+}
+
+/// Generates and delivers changelogs for all subscriptions, each on its cron schedule
+/// or every `interval_minutes` when it has none
+/// Every repo keeps its own timer, so a slow generation doesn't hold up the others, and a repo
+/// is never generated twice at once. Ctrl+C, or the SIGTERM a service manager stops it with, stops
+/// the daemon once the running generations finish
+pub async fn run(interval_minutes: u64) -> Result<()> {
+    let interval = chrono::Duration::minutes(interval_minutes.max(1) as i64);
+    let started = Utc::now();
+    // Failed repos are retried after one interval instead of immediately, or when a rate limit resets
+    let mut retry_at: HashMap<String, DateTime<Utc>> = HashMap::new();
+    let mut running = FuturesUnordered::new();
+    let mut in_flight: HashSet<String> = HashSet::new();
+    // Invalid schedules already warned about, as (repo, expression)
+    let mut warned: HashSet<(String, String)> = HashSet::new();
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    println!(
+        "{} {}",
+        "Gazette daemon running, default interval".green(),
+        format!("{} minutes", interval_minutes.max(1)).cyan()
+    );
+    println!("{}", "Press Ctrl+C to stop.".dimmed());
+
+    loop {
+        // Reload every time so new subscriptions and schedules are picked up
+        let config = Config::load()?;
+        let state = DaemonState::load()?;
+        let now = Utc::now();
+
+        // A hand-edited schedule that doesn't parse leaves its repo on the interval; say so once
+        for repo in &config.repos {
+            if let Some(expression) = config.schedule_for(repo)
+                && let Err(e) = schedule::parse(&expression)
+                && warned.insert((repo.key(), expression.clone()))
+            {
+                log(&format!(
+                    "{} {} `{}`: {}; it runs every interval instead",
+                    "⚠ Ignoring the schedule of".yellow(),
+                    repo.key(),
+                    expression,
+                    e
+                ));
+            }
+        }
+
+        let due_at = |repo: &Repo, in_flight: &HashSet<String>| {
+            let name = repo.key();
+            if in_flight.contains(&name) {
+                return None;
+            }
+            if let Some(retry) = retry_at.get(&name) {
+                return Some(*retry);
+            }
+            let schedule = config
+                .schedule_for(repo)
+                .and_then(|expression| schedule::parse(&expression).ok());
+            next_run(
+                schedule.as_ref(),
+                state.last_runs.get(&name).copied(),
+                interval,
+                started,
+            )
+        };
+
+        let due: Vec<&Repo> = config
+            .repos
+            .iter()
+            .filter(|repo| due_at(repo, &in_flight).is_some_and(|at| at <= now))
+            .take(
+                config
+                    .get_max_parallel_repos()
+                    .saturating_sub(in_flight.len()),
+            )
+            .collect();
+        if !due.is_empty() {
+            // Repos started together share a service, and so a run_started in the run log
+            match changelog_service(&config) {
+                Ok(service) => {
+                    let service = Rc::new(service);
+                    for repo in due {
+                        in_flight.insert(repo.key());
+                        let period = state.window_for(repo, config.time_period.clone(), now);
+                        running.push(generate(service.clone(), repo.clone(), period, now));
+                    }
+                }
+                Err(e) => log(&format!("{} {}", "✖ Run failed:".red(), e)),
+            }
+        }
+
+        // Sleep until the next idle repo is due, waking at least once per interval
+        let wake = config
+            .repos
+            .iter()
+            .filter_map(|repo| due_at(repo, &in_flight))
+            .min()
+            .unwrap_or(now + interval)
+            .min(Utc::now() + interval);
+        let wait = (wake - Utc::now())
+            .to_std()
+            .unwrap_or_default()
+            .max(Duration::from_secs(1));
+
+        tokio::select! {
+            Some((repo, window_end, result)) = running.next(), if !running.is_empty() => {
+                in_flight.remove(&repo.key());
+                finish(&repo, window_end, result, &mut retry_at, interval);
+            }
+            _ = tokio::time::sleep(wait) => {}
+            _ = &mut shutdown => {
+                if !running.is_empty() {
+                    log(&format!("{}", "Waiting for the running generations to finish...".dimmed()));
+                }
+                while let Some((repo, window_end, result)) = running.next().await {
+                    finish(&repo, window_end, result, &mut retry_at, interval);
+                }
+                println!("\n{}", "Daemon stopped.".dimmed());
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// When a repo is next due: the first schedule occurrence after its last run,
+/// or one interval after it; repos never run before are due at daemon start (or their first occurrence)
+pub fn next_run(
+    schedule: Option<&Schedule>,
+    last_run: Option<DateTime<Utc>>,
+    interval: chrono::Duration,
+    started: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    match schedule {
+        Some(schedule) => schedule::next_after(schedule, last_run.unwrap_or(started)),
+        None => Some(last_run.map(|last| last + interval).unwrap_or(started)),
+    }
+}
+
+/// Generates and delivers one repo's changelog for the window ending at `window_end`
+async fn generate(
+    service: Rc<ChangelogService>,
+    repo: Repo,
+    period: TimePeriod,
+    window_end: DateTime<Utc>,
+) -> (Repo, DateTime<Utc>, Result<GeneratedChangelog>) {
+    let result = service.generate_for_repo(&repo, period).await;
+    (repo, window_end, result)
+}
+
+/// Logs a repo's generation and advances its window on success; a failed repo is retried after
+/// one interval, or once a rate-limited service says it may be
+fn finish(
+    repo: &Repo,
+    window_end: DateTime<Utc>,
+    result: Result<GeneratedChangelog>,
+    retry_at: &mut HashMap<String, DateTime<Utc>>,
+    interval: chrono::Duration,
+) {
+    match result {
+        Ok(generated) => {
+            log(&format!(
+                "{} {} → {}",
+                "✔".green(),
+                repo.key().cyan(),
+                generated.path.display()
+            ));
+            for outcome in &generated.deliveries {
+                if let Err(e) = &outcome.result {
+                    log(&format!("    {} {}: {}", "✖".red(), outcome.sink, e));
+                }
+            }
+        }
+        Err(e)
+            if matches!(
+                GazetteError::find(&e),
+                Some(GazetteError::EmptyPeriod { .. })
+            ) =>
+        {
+            log(&format!(
+                "{} {} → no merged PRs",
+                "·".dimmed(),
+                repo.key().cyan()
+            ));
+        }
+        Err(e) => {
+            // Keep the old window start so the next run covers this one too
+            log(&format!("{} {} → {}", "✖".red(), repo.key().cyan(), e));
+            let error = GazetteError::find(&e);
+            if let Some(hint) = error.and_then(GazetteError::hint) {
+                log(&format!("    {}", hint.dimmed()));
+            }
+            let wait = match error {
+                Some(GazetteError::RateLimited { retry_after, .. }) => *retry_after,
+                _ => None,
+            }
+            .and_then(|wait| chrono::Duration::from_std(wait).ok())
+            .unwrap_or(interval);
+            retry_at.insert(repo.key(), Utc::now() + wait);
+            return;
+        }
+    }
+
+    retry_at.remove(&repo.key());
+    if let Err(e) = DaemonState::record(repo, window_end) {
+        log(&format!(
+            "{} {}",
+            "✖ Failed to save the daemon state:".red(),
+            e
+        ));
+    }
+}
+
+/// Resolves on Ctrl+C, or on SIGTERM from systemd or launchd stopping the service
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+fn log(message: &str) {
+    println!(
+        "{} {}",
+        format!("[{}]", clock::format(Utc::now(), "%Y-%m-%d %H:%M:%S")).dimmed(),
+        message
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use super::*;
+
+    #[test]
+    fn test_window_continues_from_last_run() {
+        let repo = Repo::new("acme", "backend");
+        let now = Utc::now();
+        let last = now - chrono::Duration::minutes(90);
+
+        let mut state = DaemonState::default();
+        assert_eq!(
+            state.window_for(&repo, TimePeriod::LastHour, now),
+            TimePeriod::Window {
+                start: now - chrono::Duration::hours(1),
+                end: now
+            }
+        );
+
+        state.last_runs.insert(repo.key(), last);
+        assert_eq!(
+            state.window_for(&repo, TimePeriod::LastHour, now),
+            TimePeriod::Window {
+                start: last,
+                end: now
+            }
+        );
+    }
+
+    #[test]
+    fn test_next_run_prefers_schedule() {
+        let now = Utc::now();
+        let last = now - chrono::Duration::minutes(90);
+        let hour = chrono::Duration::hours(1);
+
+        assert_eq!(next_run(None, None, hour, now), Some(now));
+        assert_eq!(next_run(None, Some(last), hour, now), Some(last + hour));
+
+        let daily = schedule::parse("30 4 * * *").unwrap();
+        let next = next_run(Some(&daily), Some(last), hour, now).unwrap();
+        assert!(next > last && next <= last + chrono::Duration::days(1));
+        assert_eq!(
+            next.with_timezone(&Local).format("%H:%M").to_string(),
+            "04:30"
+        );
+    }
+}
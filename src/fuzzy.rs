@@ -0,0 +1,29 @@
+/// Whether every character of the query appears in the candidate in order,
+/// ignoring case, so "acbe" finds "acme/backend"
+pub fn matches(query: &str, candidate: &str) -> bool {
+    let mut candidate = candidate.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| candidate.any(|c| c == q))
+}
+
+/// Prompt filter for `Select` and `MultiSelect` matching options by `matches`
+pub fn filter<T>(input: &str, _option: &T, string_value: &str, _index: usize) -> bool {
+    matches(input, string_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_subsequence_ignoring_case() {
+        assert!(matches("acbe", "acme/backend"));
+        assert!(matches("ACME back", "acme/backend"));
+        assert!(matches("", "acme/backend"));
+        assert!(!matches("frontend", "acme/backend"));
+        assert!(!matches("dnekcab", "acme/backend"));
+    }
+}
@@ -1,111 +1,89 @@
-mod ai;
-mod changelog;
+mod ci;
 mod cli;
-pub mod config;
-pub mod github;
-pub mod jira;
+mod commands;
+mod config;
+mod daemon;
+mod fuzzy;
 mod menu;
-
-use std::io::{Write, stdout};
+mod progress;
+mod render;
+mod service;
+mod style;
+mod tui;
 
 use anyhow::Result;
 use clap::Parser;
-use crossterm::{
-    execute,
-    terminal::{Clear, ClearType},
-};
-use inquire::Select;
-use owo_colors::OwoColorize;
 
-use cli::Cli;
-use config::{
-    Config, configure_ai_model, configure_ai_provider, configure_time_period, list_repos,
-    subscribe_repo, unsubscribe_repo,
+use gazette_core::{
+    ai, changelog, clock, compare, delivery, digest, error, feed, github, history, jira, linear,
+    links, report, schedule, secrets, site, store, template, usage,
 };
-use menu::{MainMenuOption, credentials, menu_changelog, menu_credentials};
+
+use cli::{Cli, Command, ModelsAction, ServiceAction};
+use config::Config;
+use menu::credentials;
+use style::Paint;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let _args = Cli::parse();
+    let args = Cli::parse();
+
+    if args.plain || style::plain_requested() {
+        style::set_plain(true);
+        inquire::set_global_render_config(inquire::ui::RenderConfig::empty());
+    }
 
     // Load .env file if it exists
     let _ = dotenvy::dotenv();
 
-    // Load or request all credentials
-    credentials::load_all_credentials()?;
-
-    run_main_loop().await
-}
-
-fn clear_screen() {
-    let _ = execute!(stdout(), Clear(ClearType::All));
-    // Move cursor to top-left
-    print!("\x1B[H");
-    let _ = stdout().flush();
-}
-
-fn print_banner() {
-    println!(
-        "{}",
-        "
-     ▗▄▄▖ ▗▄▖ ▗▄▄▄▄▖▗▄▄▄▖▗▄▄▄▖▗▄▄▄▖▗▄▄▄▖
-    ▐▌   ▐▌ ▐▌   ▗▞▘▐▌     █    █  ▐▌   
-    ▐▌▝▜▌▐▛▀▜▌ ▗▞▘  ▐▛▀▀▘  █    █  ▐▛▀▀▘
-    ▝▚▄▞▘▐▌ ▐▌▐▙▄▄▄▖▐▙▄▄▖  █    █  ▐▙▄▄▖                      
-    "
-        .green()
-        .bold()
-    );
-
-    // Show current configuration
-    if let Ok(config) = Config::load() {
-        println!();
-        print!("{}", "  Period: ".dimmed());
-        println!("{}", config.time_period.to_string().cyan());
-        print!("{}", "  AI: ".dimmed());
-        println!(
-            "{} {}",
-            config.ai_provider.short_name().cyan(),
-            format!("({})", config.get_ai_model()).dimmed()
-        );
+    if Config::migrate_repos_json()? {
+        println!("{}", "Migrated repos.json to config.json".green());
     }
-    println!();
-}
+    Config::load()?.apply_process_settings()?;
+    secrets::unlock(credentials::prompt_passphrase)?;
 
-async fn run_main_loop() -> Result<()> {
-    loop {
-        clear_screen();
-        print_banner();
+    if let Some(command) = args.command {
+        return run_command(command).await;
+    }
 
-        let ans = Select::new("Choose an option:", MainMenuOption::all()).prompt()?;
+    // Load or request all credentials
+    credentials::load_all_credentials()?;
 
-        clear_screen();
-        print_banner();
+    tui::run().await
+}
 
-        match ans {
-            MainMenuOption::Subscribe => subscribe_repo()?,
-            MainMenuOption::Unsubscribe => unsubscribe_repo()?,
-            MainMenuOption::ListRepos => list_repos()?,
-            MainMenuOption::ConfigureTimePeriod => configure_time_period()?,
-            MainMenuOption::ChangeAIProvider => {
-                configure_ai_provider()?;
-            }
-            MainMenuOption::ChangeAIModel => {
-                configure_ai_model()?;
-            }
-            MainMenuOption::GenerateChangelog => menu_changelog().await?,
-            MainMenuOption::UpdateCredentials => menu_credentials()?,
-            MainMenuOption::Exit => {
-                clear_screen();
-                println!("Goodbye!");
-                break;
-            }
+async fn run_command(command: Command) -> Result<()> {
+    match command {
+        Command::Generate(args) => commands::generate(args).await,
+        Command::Daemon { interval } => {
+            let interval = match interval {
+                Some(minutes) => minutes,
+                None => Config::load()?.get_daemon_interval_minutes(),
+            };
+            daemon::run(interval).await
         }
-
-        // Wait for user to press Enter before returning to menu
-        println!("\n{}", "Press Enter to continue...".dimmed());
-        let _ = std::io::stdin().read_line(&mut String::new());
+        Command::Service { action } => match action {
+            ServiceAction::Install { print } => service::install(print),
+            ServiceAction::Uninstall => service::uninstall(),
+        },
+        Command::Site { out } => commands::site(&out),
+        Command::Regenerate(args) => commands::regenerate(args).await,
+        Command::Compare {
+            repo,
+            from,
+            to,
+            list,
+            output,
+        } => commands::compare(&repo, from, to, list, output.as_deref()),
+        Command::Shipped { repo, days } => commands::shipped(repo.as_deref(), days),
+        Command::Report(args) => commands::report(args).await,
+        Command::Limits => commands::limits().await,
+        Command::Usage => commands::usage_summary(),
+        Command::Models { action } => match action {
+            None => commands::models().await,
+            Some(ModelsAction::Set { name, provider }) => {
+                commands::set_model(&name, provider.as_deref()).await
+            }
+        },
     }
-
-    Ok(())
 }
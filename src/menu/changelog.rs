@@ -1,18 +1,57 @@
 use std::fmt;
-use std::sync::Arc;
+use std::io::{IsTerminal, stderr, stdin};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
-use futures::future::join_all;
-use inquire::Select;
-use owo_colors::OwoColorize;
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use inquire::{Confirm, MultiSelect, Select, Text};
 
-use crate::changelog::ChangelogService;
-use crate::config::{Repo, load_repos, load_time_period};
+use crate::changelog::{
+    ChangelogDraft, ChangelogService, Collision, CollisionResolver, GeneratedChangelog, PrContext,
+};
+use crate::config::{
+    Config, Repo, TimePeriod, changelog_service, load_repos, load_time_period, session_config,
+};
+use crate::digest::{self, GroupDigest};
+use crate::error::GazetteError;
+use crate::fuzzy;
+use crate::github::{GitHubClient, GitHubMilestone};
+use crate::linear::{CyclePick, LinearClient};
+use crate::menu::credentials::offer_credential_update;
+use crate::progress::RepoProgress;
+use crate::render;
+use crate::secrets;
+use crate::style::Paint;
+use crate::usage;
+
+/// Asks what to do with an existing changelog file, when existing_changelog is Prompt
+/// Without a terminal to ask in, a numbered copy is saved
+pub fn collision_prompt() -> CollisionResolver {
+    Arc::new(|path: &Path| {
+        if !stdin().is_terminal() || !stderr().is_terminal() {
+            return Collision::Copy;
+        }
+        // Repos generated in parallel ask one at a time
+        static PROMPT: Mutex<()> = Mutex::new(());
+        let _asking = PROMPT.lock().unwrap_or_else(|e| e.into_inner());
+        let message = format!("{} already exists", path.display());
+        Select::new(&message, Collision::all())
+            .prompt()
+            .unwrap_or(Collision::Copy)
+    })
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum ChangelogOption {
     SingleRepo,
+    Milestone,
+    Cycle,
+    Group,
+    EachGroup,
     AllRepos,
+    Regenerate,
     Back,
 }
 
@@ -20,7 +59,12 @@ impl fmt::Display for ChangelogOption {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::SingleRepo => write!(f, "Generate changelog for a single repo"),
+            Self::Milestone => write!(f, "Generate changelog for a milestone"),
+            Self::Cycle => write!(f, "Generate changelog for a Linear cycle"),
+            Self::Group => write!(f, "Generate changelogs for a repo group"),
+            Self::EachGroup => write!(f, "Generate a digest for every repo group"),
             Self::AllRepos => write!(f, "Generate changelog for all subscribed repos"),
+            Self::Regenerate => write!(f, "Regenerate a changelog from the last run's context"),
             Self::Back => write!(f, "Back to main menu"),
         }
     }
@@ -28,68 +72,195 @@ impl fmt::Display for ChangelogOption {
 
 impl ChangelogOption {
     pub fn all() -> Vec<Self> {
-        vec![Self::SingleRepo, Self::AllRepos, Self::Back]
+        vec![
+            Self::SingleRepo,
+            Self::Milestone,
+            Self::Cycle,
+            Self::Group,
+            Self::EachGroup,
+            Self::AllRepos,
+            Self::Regenerate,
+            Self::Back,
+        ]
     }
 }
 
 /// Wrapper for repo selection with a Back option
 #[derive(Debug, Clone)]
 enum RepoSelection {
-    Repo(Repo),
+    Repo(Box<Repo>),
     Back,
 }
 
 impl fmt::Display for RepoSelection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Repo(repo) => write!(f, "{}", repo.full_name()),
+            Self::Repo(repo) => write!(f, "{}", repo.key()),
             Self::Back => write!(f, "← Back"),
         }
     }
 }
 
 pub async fn menu_changelog() -> Result<()> {
-    let ans = Select::new("Generation type:", ChangelogOption::all()).prompt()?;
+    // Cycles are only offered once Linear is set up
+    let options = ChangelogOption::all()
+        .into_iter()
+        .filter(|option| {
+            !matches!(option, ChangelogOption::Cycle) || secrets::is_set("LINEAR_API_KEY")
+        })
+        .collect();
+    let ans = Select::new("Generation type:", options).prompt()?;
 
     match ans {
         ChangelogOption::SingleRepo => {
-            let repos = load_repos()?;
+            if let Some(repo) = select_repo()? {
+                generate_changelog_single(&repo, load_time_period()?).await?;
+            }
+        }
+        ChangelogOption::Milestone => {
+            if let Some(repo) = select_repo()?
+                && let Some(title) = select_milestone(&repo).await?
+            {
+                generate_changelog_single(&repo, TimePeriod::Milestone { title }).await?;
+            }
+        }
+        ChangelogOption::Cycle => {
+            if let Some(repo) = select_repo()? {
+                let period = select_cycle().await?;
+                generate_changelog_single(&repo, period).await?;
+            }
+        }
+        ChangelogOption::Group => {
+            let config = Config::load()?;
+            let groups = config.groups();
 
-            if repos.is_empty() {
+            if groups.is_empty() {
                 println!(
                     "{}",
-                    "No subscribed repos. Subscribe to a repo first.".yellow()
+                    "No repo groups. Tag repos under \"Manage repo groups\" first.".yellow()
                 );
                 return Ok(());
             }
 
-            let mut options: Vec<RepoSelection> =
-                repos.into_iter().map(RepoSelection::Repo).collect();
-            options.push(RepoSelection::Back);
-
-            let selection = Select::new("Select a repo:", options).prompt()?;
+            let group = Select::new("Select a group:", groups).prompt()?;
+            println!(
+                "{} {}",
+                "Generating changelogs for group".italic(),
+                group.yellow()
+            );
+            generate_changelog_many(config.repos_in_group(&group), DigestScope::Group(&group))
+                .await?;
+        }
+        ChangelogOption::EachGroup => {
+            let repos: Vec<Repo> = load_repos()?
+                .into_iter()
+                .filter(|repo| !repo.groups.is_empty())
+                .collect();
 
-            match selection {
-                RepoSelection::Repo(repo) => generate_changelog_single(&repo).await?,
-                RepoSelection::Back => return Ok(()),
+            if repos.is_empty() {
+                println!(
+                    "{}",
+                    "No repo groups. Tag repos under \"Manage repo groups\" first.".yellow()
+                );
+                return Ok(());
             }
+
+            println!("{}", "Generating a digest per group...".italic());
+            generate_changelog_many(repos, DigestScope::EachGroup).await?;
         }
         ChangelogOption::AllRepos => {
             println!("{}", "Generating full report...".italic());
-            generate_changelog_all().await?;
+            generate_changelog_many(load_repos()?, DigestScope::None).await?;
+        }
+        ChangelogOption::Regenerate => {
+            if let Some(repo) = select_repo()? {
+                regenerate_changelog(&repo).await?;
+            }
         }
         ChangelogOption::Back => return Ok(()),
     }
     Ok(())
 }
 
-async fn generate_changelog_single(repo: &Repo) -> Result<()> {
-    let period = load_time_period()?;
+/// Asks for one of the subscribed repos; None when there are none or the user goes back
+fn select_repo() -> Result<Option<Repo>> {
+    let repos = load_repos()?;
+
+    if repos.is_empty() {
+        println!(
+            "{}",
+            "No subscribed repos. Subscribe to a repo first.".yellow()
+        );
+        return Ok(None);
+    }
+
+    let mut options: Vec<RepoSelection> = repos
+        .into_iter()
+        .map(|repo| RepoSelection::Repo(Box::new(repo)))
+        .collect();
+    options.push(RepoSelection::Back);
+
+    let selection = Select::new("Select a repo:", options)
+        .with_filter(&fuzzy::filter)
+        .prompt()?;
+    match selection {
+        RepoSelection::Repo(repo) => Ok(Some(*repo)),
+        RepoSelection::Back => Ok(None),
+    }
+}
+
+/// A milestone as shown in the selection prompt
+struct MilestoneChoice(GitHubMilestone);
+
+impl fmt::Display for MilestoneChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let milestone = &self.0;
+        write!(
+            f,
+            "{} ({}, {} closed / {} open)",
+            milestone.title, milestone.state, milestone.closed_issues, milestone.open_issues
+        )
+    }
+}
+
+/// Asks for one of the repo's milestones; None when it has none
+async fn select_milestone(repo: &Repo) -> Result<Option<String>> {
+    let milestones = GitHubClient::new()?.get_milestones(repo).await?;
+    if milestones.is_empty() {
+        println!("{}", format!("{} has no milestones.", repo.key()).yellow());
+        return Ok(None);
+    }
+
+    let choices = milestones.into_iter().map(MilestoneChoice).collect();
+    let choice = Select::new("Select a milestone:", choices)
+        .with_filter(&fuzzy::filter)
+        .prompt()?;
+    Ok(Some(choice.0.title))
+}
+
+/// Asks for a Linear team and one of its cycles, remembering the team for next time
+async fn select_cycle() -> Result<TimePeriod> {
+    let mut config = Config::load()?;
+    let team = Text::new("Linear team key:")
+        .with_initial_value(config.linear_team.as_deref().unwrap_or_default())
+        .with_help_message("E.g. ENG, as in the team's issue keys")
+        .prompt()?;
+    let team = team.trim().to_uppercase();
+    let pick = Select::new("Which cycle?", CyclePick::all()).prompt()?;
+
+    let period = LinearClient::new()?.period(&team, pick).await?;
+    if config.linear_team.as_deref() != Some(team.as_str()) {
+        config.linear_team = Some(team);
+        config.save()?;
+    }
+    Ok(period)
+}
 
+async fn generate_changelog_single(repo: &Repo, period: TimePeriod) -> Result<()> {
     println!(
         "{} {}",
         "Generating changelog for".cyan(),
-        repo.full_name().yellow()
+        repo.key().yellow()
     );
 
     println!(
@@ -97,26 +268,268 @@ async fn generate_changelog_single(repo: &Repo) -> Result<()> {
         format!("  → Fetching merged PRs from {}...", period.description()).dimmed()
     );
 
-    let service = ChangelogService::new()?;
+    let progress = RepoProgress::new();
+    let service = changelog_service(&session_config()?)?
+        .with_progress(progress.reporter())
+        .with_collision_resolver(collision_prompt());
 
-    match service.generate_for_repo(repo, period).await {
-        Ok(path) => {
-            println!(
-                "\n{} {}",
-                "✔ Changelog saved to:".green().bold(),
-                path.display().to_string().cyan()
-            );
+    let result = if Config::load()?.select_prs {
+        let gathered = service.gather_for_repo(repo, period.clone()).await;
+        progress.finish(repo);
+        match gathered {
+            Ok(contexts) => {
+                let contexts = select_prs(contexts)?;
+                if contexts.is_empty() {
+                    println!("{}", "No PRs selected; nothing to draft.".yellow());
+                    return Ok(());
+                }
+                service.draft_gathered(repo, period, contexts).await
+            }
+            Err(e) => Err(e),
         }
+    } else {
+        service.draft_for_repo(repo, period).await
+    };
+    progress.finish(repo);
+
+    match result {
+        Ok(draft) => review_and_publish(&service, &progress, draft).await?,
         Err(e) => {
             println!("{} {}", "✖ Error:".red().bold(), e);
+            if let Some(error) = GazetteError::find(&e) {
+                if let Some(hint) = error.hint() {
+                    println!("  {}", hint.dimmed());
+                }
+                offer_credential_update(error)?;
+            }
         }
     }
 
     Ok(())
 }
 
-async fn generate_changelog_all() -> Result<()> {
-    let repos = load_repos()?;
+/// A fetched PR as shown in the selection prompt
+struct PrChoice(PrContext);
+
+impl fmt::Display for PrChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pr = &self.0.pr;
+        write!(f, "#{} {}", pr.number, pr.title)?;
+        if let Some(user) = &pr.user {
+            write!(f, " (@{})", user.login)?;
+        }
+        if self.0.previously_reported {
+            write!(f, " [previously reported]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lets the user drop PRs, such as reverts or chores, from this changelog only
+fn select_prs(contexts: Vec<PrContext>) -> Result<Vec<PrContext>> {
+    let choices: Vec<PrChoice> = contexts.into_iter().map(PrChoice).collect();
+    let all: Vec<usize> = (0..choices.len()).collect();
+
+    let selected = MultiSelect::new("PRs to include:", choices)
+        .with_filter(&fuzzy::filter)
+        .with_default(&all)
+        .with_page_size(15)
+        .with_help_message("space to toggle, → all, ← none, enter to confirm")
+        .prompt()?;
+
+    Ok(selected.into_iter().map(|choice| choice.0).collect())
+}
+
+/// Re-invokes the AI on the context stored by the repo's last run, optionally with
+/// another model or extra instructions, then saves and delivers the result
+async fn regenerate_changelog(repo: &Repo) -> Result<()> {
+    let mut config = session_config()?;
+
+    let model = Text::new("AI model:")
+        .with_default(&config.get_ai_model())
+        .with_help_message("Try another model of the configured provider")
+        .prompt()?;
+    let instructions = Text::new("Extra instructions (optional):")
+        .with_help_message("e.g. \"more concise, lead with user-facing changes\"")
+        .prompt()?;
+
+    config.ai_model = Some(model.trim().to_string());
+    let progress = RepoProgress::new();
+    let mut service = changelog_service(&config)?
+        .with_progress(progress.reporter())
+        .with_collision_resolver(collision_prompt());
+    if !instructions.trim().is_empty() {
+        service = service.with_instructions(instructions.trim().to_string());
+    }
+
+    println!(
+        "{} {}",
+        "Regenerating changelog for".cyan(),
+        repo.key().yellow()
+    );
+
+    let result = service.regenerate_for_repo(repo).await;
+    progress.finish(repo);
+
+    match result {
+        Ok(draft) => review_and_publish(&service, &progress, draft).await?,
+        Err(e) => println!("{} {}", "✖ Error:".red().bold(), e),
+    }
+
+    Ok(())
+}
+
+/// What to do with a draft after previewing it
+#[derive(Debug, Clone, Copy)]
+enum ReviewAction {
+    Accept,
+    Edit,
+    Regenerate,
+    Discard,
+}
+
+impl fmt::Display for ReviewAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accept => write!(f, "Accept and save"),
+            Self::Edit => write!(f, "Edit in $EDITOR"),
+            Self::Regenerate => write!(f, "Regenerate with a hint"),
+            Self::Discard => write!(f, "Discard"),
+        }
+    }
+}
+
+impl ReviewAction {
+    fn all() -> Vec<Self> {
+        vec![Self::Accept, Self::Edit, Self::Regenerate, Self::Discard]
+    }
+}
+
+/// Previews the draft and lets the user accept, edit, regenerate or discard it
+/// before anything is written or delivered
+async fn review_and_publish(
+    service: &ChangelogService,
+    progress: &RepoProgress,
+    mut draft: ChangelogDraft,
+) -> Result<()> {
+    loop {
+        render::show_in_pager(&render::render_terminal(&draft.content))?;
+        for warning in &draft.warnings {
+            println!("{} {}", "⚠".yellow(), warning.yellow());
+        }
+
+        match Select::new("What now?", ReviewAction::all()).prompt()? {
+            ReviewAction::Accept => break,
+            ReviewAction::Edit => {
+                draft.content = render::edit_in_editor(&draft.content, &draft.repo.name)?;
+                // The categorized entries no longer match hand-edited markdown
+                draft.structured = None;
+            }
+            ReviewAction::Regenerate => {
+                let hint = Text::new("Hint for the AI:")
+                    .with_help_message("e.g. \"be more concise\"")
+                    .prompt()?;
+                let result = service.redraft(&draft, hint.trim()).await;
+                progress.finish(&draft.repo);
+                match result {
+                    Ok(redrafted) => draft = redrafted,
+                    Err(e) => println!("{} {}", "✖ Error:".red().bold(), e),
+                }
+            }
+            ReviewAction::Discard => {
+                println!("{}", "Discarded; nothing was saved.".yellow());
+                return Ok(());
+            }
+        }
+    }
+
+    let repo = draft.repo.clone();
+    let result = service.publish(draft).await;
+    progress.finish(&repo);
+
+    match result {
+        Ok(generated) => report_saved(&generated)?,
+        Err(e) => println!("{} {}", "✖ Error:".red().bold(), e),
+    }
+
+    Ok(())
+}
+
+/// Prints where a generated changelog went
+fn report_saved(generated: &GeneratedChangelog) -> Result<()> {
+    println!(
+        "\n{} {}",
+        "✔ Changelog saved to:".green().bold(),
+        generated.path.display().to_string().cyan()
+    );
+    if let Some(artifact) = &generated.artifact {
+        println!(
+            "{} {}",
+            "✔ JSON artifact saved to:".green(),
+            artifact.display().to_string().cyan()
+        );
+    }
+    if let Some(feed) = &generated.feed {
+        println!(
+            "{} {}",
+            "✔ Atom feed updated:".green(),
+            feed.display().to_string().cyan()
+        );
+    }
+    for outcome in &generated.deliveries {
+        match &outcome.result {
+            Ok(None) => println!(
+                "{} {}",
+                "✔ Delivered to".green(),
+                outcome.sink.to_string().cyan()
+            ),
+            Ok(Some(url)) => println!(
+                "{} {}: {}",
+                "✔ Delivered to".green(),
+                outcome.sink.to_string().cyan(),
+                url
+            ),
+            Err(e) => println!(
+                "{} {}: {}",
+                "✖ Delivery failed:".red(),
+                outcome.sink.to_string().cyan(),
+                e
+            ),
+        }
+    }
+    if let Some(tokens) = &generated.usage {
+        println!(
+            "  {}",
+            format!("AI usage: {}", usage::format_usage(tokens, generated.cost)).dimmed()
+        );
+    }
+    if !generated.links.is_clean() {
+        println!(
+            "  {}",
+            format!(
+                "Links: {} fixed, {} hallucinated links removed",
+                generated.links.fixed, generated.links.stripped
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Digests a many-repo run combines its changelogs into
+#[derive(Clone, Copy)]
+enum DigestScope<'a> {
+    None,
+    /// One digest of the group's repos
+    Group(&'a str),
+    /// A digest per group, plus an index of them
+    EachGroup,
+}
+
+/// Generates changelogs for the repos in parallel, plus the digests of the scope
+/// Failed repos can be retried afterwards without redoing the ones that succeeded
+async fn generate_changelog_many(repos: Vec<Repo>, scope: DigestScope<'_>) -> Result<()> {
     let period = load_time_period()?;
 
     if repos.is_empty() {
@@ -130,39 +543,159 @@ async fn generate_changelog_all() -> Result<()> {
         repos.len().to_string().yellow()
     );
 
-    let service = Arc::new(ChangelogService::new()?);
+    let progress = RepoProgress::new();
+    let service = Arc::new(
+        changelog_service(&session_config()?)?
+            .with_progress(progress.reporter())
+            .with_collision_resolver(collision_prompt()),
+    );
+    let parallel = Config::load()?.get_max_parallel_repos();
 
-    // Create futures for all repos
-    let futures: Vec<_> = repos
-        .into_iter()
-        .map(|repo| {
-            let service = Arc::clone(&service);
-            async move {
-                let result = service.generate_for_repo(&repo, period).await;
-                (repo, result)
-            }
-        })
-        .collect();
+    // Contexts stored from here on were gathered by this run, so retries can reuse them
+    let started = Utc::now();
+    let mut pending = repos;
+    let mut retrying = false;
+    let mut run_cost = 0.0;
+    let mut run_tokens = 0;
+    let mut digest_parts = Vec::new();
 
-    // Execute all in parallel
-    let results = join_all(futures).await;
+    loop {
+        // Create futures for the pending repos
+        let futures: Vec<_> = pending
+            .into_iter()
+            .map(|repo| {
+                let service = Arc::clone(&service);
+                let progress = &progress;
+                let period = period.clone();
+                async move {
+                    let result = if retrying {
+                        service.retry_for_repo(&repo, period, started).await
+                    } else {
+                        service.generate_for_repo(&repo, period).await
+                    };
+                    progress.finish(&repo);
+                    (repo, result)
+                }
+            })
+            .collect();
 
-    // Print results
-    println!();
-    for (repo, result) in results {
-        match result {
-            Ok(path) => {
-                println!(
-                    "{} {} → {}",
-                    "✔".green(),
-                    repo.full_name().cyan(),
-                    path.display().to_string().dimmed()
-                );
+        // Execute in parallel, a few repos at a time
+        let results: Vec<_> = stream::iter(futures).buffered(parallel).collect().await;
+
+        // Print results
+        println!();
+        let mut failed = Vec::new();
+        for (repo, result) in results {
+            match result {
+                Ok(generated) => {
+                    println!(
+                        "{} {} → {}",
+                        "✔".green(),
+                        repo.key().cyan(),
+                        generated.path.display().to_string().dimmed()
+                    );
+                    for outcome in &generated.deliveries {
+                        if let Err(e) = &outcome.result {
+                            println!("    {} {}: {}", "✖".red(), outcome.sink, e);
+                        }
+                    }
+                    for warning in &generated.warnings {
+                        println!("    {} {}", "⚠".yellow(), warning);
+                    }
+                    if let Some(tokens) = &generated.usage {
+                        run_tokens += tokens.total();
+                        run_cost += generated.cost.unwrap_or(0.0);
+                    }
+                    digest_parts.push((repo, generated.content));
+                }
+                Err(e) => {
+                    println!("{} {} → {}", "✖".red(), repo.key().cyan(), e);
+                    // Nothing merged in the period won't change on a retry
+                    if !matches!(
+                        GazetteError::find(&e),
+                        Some(GazetteError::EmptyPeriod { .. })
+                    ) {
+                        failed.push(repo);
+                    }
+                }
             }
-            Err(e) => {
-                println!("{} {} → {}", "✖".red(), repo.full_name().cyan(), e);
+        }
+
+        if failed.is_empty() {
+            break;
+        }
+        let retry = Confirm::new(&format!("Retry the {} failed repo(s)?", failed.len()))
+            .with_default(true)
+            .with_help_message("PRs fetched before the failure are reused")
+            .prompt()?;
+        if !retry {
+            break;
+        }
+        pending = failed;
+        retrying = true;
+    }
+
+    let digests = match scope {
+        DigestScope::None => Vec::new(),
+        DigestScope::Group(group) => vec![(group.to_string(), digest_parts)],
+        DigestScope::EachGroup => digest::by_group(&digest_parts),
+    };
+    let executive_summary = Config::load()?.executive_summary;
+    let mut saved = Vec::new();
+    for (group, parts) in digests {
+        if parts.is_empty() {
+            continue;
+        }
+        let title = format!("{} digest — {}", group, period.description());
+        let summary = if executive_summary {
+            match service
+                .summarize_digest(&title, period.clone(), &parts)
+                .await
+            {
+                Ok(summary) => summary,
+                Err(e) => {
+                    println!("{} {}", "✖ Executive summary →".red(), e);
+                    None
+                }
             }
+        } else {
+            None
+        };
+        if let Some(summary) = &summary
+            && let Some(tokens) = &summary.usage
+        {
+            run_tokens += tokens.total();
+            run_cost += summary.cost.unwrap_or(0.0);
         }
+        let combined = digest::combine(&title, summary.as_ref().map(|s| s.text.as_str()), &parts);
+        let path = digest::save(&group, &combined)?;
+        println!(
+            "\n{} {}",
+            "✔ Group digest saved to:".green().bold(),
+            path.display().to_string().cyan()
+        );
+        saved.push(GroupDigest {
+            group,
+            path,
+            repos: parts.into_iter().map(|(repo, _)| repo).collect(),
+        });
+    }
+
+    if matches!(scope, DigestScope::EachGroup) && !saved.is_empty() {
+        let title = format!("Team digests — {}", period.description());
+        let path = digest::save_index(&digest::index(&title, &saved))?;
+        println!(
+            "{} {}",
+            "✔ Digest index saved to:".green().bold(),
+            path.display().to_string().cyan()
+        );
+    }
+
+    if run_tokens > 0 {
+        println!(
+            "\n{}",
+            format!("AI usage: {} tokens (~${:.4})", run_tokens, run_cost).dimmed()
+        );
     }
 
     Ok(())
@@ -1,16 +1,19 @@
-use std::env;
 use std::fmt;
-use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::Path;
-
-use anyhow::Result;
-use inquire::{Confirm, Select, Text};
-use owo_colors::OwoColorize;
-
-use crate::config::{AIProvider, Config, configure_ai_model};
-
-const ENV_FILE: &str = ".env";
+use std::io::{IsTerminal, stdout};
+
+use anyhow::{Result, bail};
+use inquire::{Confirm, Password, PasswordDisplayMode, Select, Text};
+use ratatui::crossterm::cursor::MoveTo;
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{Clear, ClearType};
+
+use crate::ai;
+use crate::config::{AIProvider, Config, DeliveryChannel, configure_ai_model};
+use crate::error::GazetteError;
+use crate::github::GitHubClient;
+use crate::jira::JiraClient;
+use crate::secrets;
+use crate::style::{self, Paint};
 
 #[derive(Debug, Clone, Copy)]
 pub enum CredentialsOption {
@@ -19,6 +22,11 @@ pub enum CredentialsOption {
     UpdateAIModel,
     UpdateAIApiKey,
     UpdateJiraCredentials,
+    UpdateLinearApiKey,
+    UpdateDeliveryCredentials,
+    TestGithubToken,
+    TestJiraConnection,
+    TestAIProvider,
     Back,
 }
 
@@ -30,6 +38,11 @@ impl fmt::Display for CredentialsOption {
             Self::UpdateAIModel => write!(f, "Change AI model"),
             Self::UpdateAIApiKey => write!(f, "Update AI API key"),
             Self::UpdateJiraCredentials => write!(f, "Update Jira credentials"),
+            Self::UpdateLinearApiKey => write!(f, "Update Linear API key"),
+            Self::UpdateDeliveryCredentials => write!(f, "Update delivery credentials"),
+            Self::TestGithubToken => write!(f, "Test GitHub token"),
+            Self::TestJiraConnection => write!(f, "Test Jira connection"),
+            Self::TestAIProvider => write!(f, "Test AI provider"),
             Self::Back => write!(f, "Back to main menu"),
         }
     }
@@ -43,13 +56,22 @@ impl CredentialsOption {
             Self::UpdateAIModel,
             Self::UpdateAIApiKey,
             Self::UpdateJiraCredentials,
+            Self::UpdateLinearApiKey,
+            Self::UpdateDeliveryCredentials,
+            Self::TestGithubToken,
+            Self::TestJiraConnection,
+            Self::TestAIProvider,
             Self::Back,
         ]
     }
 }
 
-pub fn menu_credentials() -> Result<()> {
-    let ans = Select::new("Select credential to update:", CredentialsOption::all()).prompt()?;
+pub async fn menu_credentials() -> Result<()> {
+    let ans = Select::new(
+        "Select credential to update or test:",
+        CredentialsOption::all(),
+    )
+    .prompt()?;
 
     match ans {
         CredentialsOption::UpdateGithubToken => {
@@ -73,11 +95,167 @@ pub fn menu_credentials() -> Result<()> {
             update_jira_credentials()?;
             println!("{}", "✔ Jira credentials updated successfully!".green());
         }
+        CredentialsOption::UpdateLinearApiKey => {
+            let key = prompt_secret("Linear personal API key:")?;
+            save_secret("LINEAR_API_KEY", key.trim())?;
+            println!("{}", "✔ Linear API key updated successfully!".green());
+        }
+        CredentialsOption::UpdateDeliveryCredentials => {
+            let channel =
+                Select::new("Select delivery channel:", DeliveryChannel::all()).prompt()?;
+            prompt_delivery_credentials(channel)?;
+            println!("{}", "✔ Delivery credentials updated successfully!".green());
+        }
+        CredentialsOption::TestGithubToken => test_github_token().await,
+        CredentialsOption::TestJiraConnection => test_jira_connection().await,
+        CredentialsOption::TestAIProvider => test_ai_provider().await?,
         CredentialsOption::Back => return Ok(()),
     }
     Ok(())
 }
 
+/// Checks the GitHub token with a call to /user, reporting its owner, scopes and quota
+async fn test_github_token() {
+    let github = match GitHubClient::new() {
+        Ok(github) => github,
+        Err(e) => return report_failure("GitHub token", &e),
+    };
+    let owner = match github.authenticated_user().await {
+        Ok(owner) => owner,
+        Err(e) => return report_failure("GitHub token", &e),
+    };
+
+    println!(
+        "{} {}",
+        "✔ GitHub token works, signed in as".green(),
+        format!("@{}", owner.login).cyan()
+    );
+    match owner.scopes {
+        Some(scopes) => {
+            let listed = if scopes.is_empty() {
+                "none".to_string()
+            } else {
+                scopes.join(", ")
+            };
+            println!("  Scopes: {}", listed);
+            if !scopes.iter().any(|scope| scope == "repo") {
+                println!(
+                    "  {}",
+                    "⚠ Without the repo scope, private repos can't be read".yellow()
+                );
+            }
+        }
+        None => println!(
+            "  {}",
+            "Fine-grained token: check it can read the subscribed repos' pull requests".dimmed()
+        ),
+    }
+    if let Ok(limits) = github.get_rate_limits().await {
+        println!(
+            "  Requests left: {} / {}",
+            limits.core.remaining, limits.core.limit
+        );
+    }
+}
+
+/// Checks the Jira credentials by fetching the account they belong to
+async fn test_jira_connection() {
+    if !secrets::is_set("JIRA_URL") {
+        println!(
+            "{}",
+            "Jira isn't configured; choose Update Jira credentials to set it up".yellow()
+        );
+        return;
+    }
+    let result = match JiraClient::new() {
+        Ok(jira) => jira.current_user().await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(name) => println!(
+            "{} {} {}",
+            "✔ Connected to Jira as".green(),
+            name.cyan(),
+            format!("({})", secrets::get("JIRA_URL").unwrap_or_default()).dimmed()
+        ),
+        Err(e) => report_failure("Jira connection", &e),
+    }
+}
+
+/// Checks the AI provider's key by listing its models, without spending tokens, and that the
+/// configured model is among them
+async fn test_ai_provider() -> Result<()> {
+    let config = Config::load()?;
+    let provider = config.ai_provider;
+    let model = config.get_ai_model();
+
+    let client = match ai::create_ai_client(provider, &model) {
+        Ok(client) => client,
+        Err(e) => {
+            report_failure(provider.short_name(), &e);
+            return Ok(());
+        }
+    };
+    let listed = match client.list_models().await {
+        Ok(Some(models)) => models,
+        // No models endpoint: a tiny generation is the cheapest real call left
+        Ok(None) => match client.generate("Reply with OK").await {
+            Ok(_) => {
+                println!(
+                    "{} {}",
+                    format!("✔ {} answered with", provider.short_name()).green(),
+                    model.cyan()
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                report_failure(provider.short_name(), &e);
+                return Ok(());
+            }
+        },
+        Err(e) => {
+            report_failure(provider.short_name(), &e);
+            return Ok(());
+        }
+    };
+
+    println!(
+        "{} {}",
+        format!("✔ {} accepted the credentials,", provider.short_name()).green(),
+        format!("{} models available", listed.len()).cyan()
+    );
+    let available = listed
+        .iter()
+        .any(|listed| listed == &model || listed.strip_suffix(":latest") == Some(&model));
+    if available {
+        println!("  Model {} is available", model.cyan());
+    } else {
+        println!(
+            "  {}",
+            format!(
+                "⚠ Model {} isn't offered to this account; generation will fail until you pick another (gazette models)",
+                model
+            )
+            .yellow()
+        );
+    }
+    if config.no_ai {
+        println!(
+            "  {}",
+            "AI-free formatting is on, so runs don't use the provider".dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Prints why a credential check failed, with the full cause chain and what to do about it
+fn report_failure(what: &str, error: &anyhow::Error) {
+    println!("{} {:#}", format!("✖ {} check failed:", what).red(), error);
+    if let Some(hint) = GazetteError::find(error).and_then(GazetteError::hint) {
+        println!("  {}", hint.yellow());
+    }
+}
+
 /// Loads all required credentials at startup
 pub fn load_all_credentials() -> Result<()> {
     // GitHub token (required)
@@ -99,7 +277,7 @@ fn load_ai_credentials() -> Result<()> {
     let env_var = provider.api_key_env_var();
 
     // Check if we need to select a provider (first run or missing API key)
-    let has_api_key = env::var(env_var).map(|v| !v.is_empty()).unwrap_or(false);
+    let has_api_key = secrets::is_set(env_var);
 
     if !has_api_key {
         println!(
@@ -142,10 +320,10 @@ fn prompt_ai_api_key(provider: AIProvider) -> Result<()> {
     let value = if let Some(default) = provider.default_value() {
         Text::new(prompt).with_default(default).prompt()?
     } else {
-        Text::new(prompt).prompt()?
+        prompt_secret(prompt)?
     };
 
-    save_env_var(env_var, &value)?;
+    save_secret(env_var, &value)?;
     Ok(())
 }
 
@@ -153,7 +331,7 @@ fn prompt_ai_api_key(provider: AIProvider) -> Result<()> {
 /// If not configured, prompts the user to enter it
 pub fn ensure_provider_api_key(provider: AIProvider) -> Result<()> {
     let env_var = provider.api_key_env_var();
-    let has_api_key = env::var(env_var).map(|v| !v.is_empty()).unwrap_or(false);
+    let has_api_key = secrets::is_set(env_var);
 
     if !has_api_key {
         println!("{}", format!("{} not configured.", env_var).yellow());
@@ -164,10 +342,32 @@ pub fn ensure_provider_api_key(provider: AIProvider) -> Result<()> {
     Ok(())
 }
 
+fn prompt_delivery_credentials(channel: DeliveryChannel) -> Result<()> {
+    for (env_var, prompt) in channel.credentials() {
+        let value = prompt_secret(prompt)?;
+        save_secret(env_var, &value)?;
+    }
+    Ok(())
+}
+
+/// Ensures the credentials for the given delivery channel are configured
+/// Prompts only for the missing ones
+pub fn ensure_delivery_credentials(channel: DeliveryChannel) -> Result<()> {
+    for (env_var, prompt) in channel.credentials() {
+        if !secrets::is_set(env_var) {
+            println!("{}", format!("{} not configured.", env_var).yellow());
+            let value = prompt_secret(prompt)?;
+            save_secret(env_var, &value)?;
+        }
+    }
+
+    Ok(())
+}
+
 fn load_jira_credentials() -> Result<()> {
-    let has_jira = env::var("JIRA_URL").is_ok()
-        && env::var("JIRA_EMAIL").is_ok()
-        && env::var("JIRA_API_TOKEN").is_ok();
+    let has_jira = secrets::is_set("JIRA_URL")
+        && secrets::is_set("JIRA_EMAIL")
+        && secrets::is_set("JIRA_API_TOKEN");
 
     if has_jira {
         println!("{}", "✔ Jira credentials loaded".green());
@@ -196,19 +396,70 @@ fn load_jira_credentials() -> Result<()> {
 fn prompt_jira_credentials() -> Result<()> {
     let url = Text::new("Jira URL (e.g., https://company.atlassian.net):").prompt()?;
     let email = Text::new("Jira email:").prompt()?;
-    let token = Text::new("Jira API token:").prompt()?;
+    let token = prompt_secret("Jira API token:")?;
+
+    save_secret("JIRA_URL", &url)?;
+    save_secret("JIRA_EMAIL", &email)?;
+    save_secret("JIRA_API_TOKEN", &token)?;
 
-    save_env_var("JIRA_URL", &url)?;
-    save_env_var("JIRA_EMAIL", &email)?;
-    save_env_var("JIRA_API_TOKEN", &token)?;
+    Ok(())
+}
 
+/// Saves a secret, telling the user when the keyring was unavailable and it went to .env
+fn save_secret(key: &str, value: &str) -> Result<()> {
+    if secrets::save(key, value)? != Config::load()?.secret_backend {
+        println!(
+            "{}",
+            format!("Keyring unavailable, saved {} to .env instead", key).yellow()
+        );
+    }
     Ok(())
 }
 
+/// Asks for the passphrase of secrets.age, twice when `new` as it protects a new file
+pub fn prompt_passphrase(new: bool) -> Result<String> {
+    if !std::io::stdin().is_terminal() {
+        bail!("GAZETTE_PASSPHRASE is not set and there is no terminal to ask for it");
+    }
+
+    let prompt = if new {
+        "Choose a passphrase for secrets.age:"
+    } else {
+        "Passphrase for secrets.age:"
+    };
+
+    let mut password = Password::new(prompt).with_display_mode(PasswordDisplayMode::Masked);
+    if !new {
+        password = password.without_confirmation();
+    }
+
+    Ok(password.prompt()?)
+}
+
+/// Asks for a token or key without echoing it, then clears the screen so the
+/// masked prompt doesn't stay in view
+fn prompt_secret(prompt: &str) -> Result<String> {
+    let value = Password::new(prompt)
+        .with_display_mode(PasswordDisplayMode::Masked)
+        .with_display_toggle_enabled()
+        .with_help_message("Ctrl+R shows what you typed")
+        .without_confirmation()
+        .prompt()?;
+
+    if stdout().is_terminal() && !style::is_plain() {
+        let _ = execute!(
+            stdout(),
+            Clear(ClearType::All),
+            Clear(ClearType::Purge),
+            MoveTo(0, 0)
+        );
+    }
+
+    Ok(value)
+}
+
 fn load_env_var(key: &str, prompt_msg: &str, required: bool) -> Result<Option<String>> {
-    if let Ok(value) = env::var(key)
-        && !value.is_empty()
-    {
+    if let Some(value) = secrets::get(key) {
         return Ok(Some(value));
     }
 
@@ -217,53 +468,45 @@ fn load_env_var(key: &str, prompt_msg: &str, required: bool) -> Result<Option<St
     }
 
     println!("{} not found.", key.yellow());
-    let value = Text::new(prompt_msg).prompt()?;
-    save_env_var(key, &value)?;
+    let value = prompt_secret(prompt_msg)?;
+    save_secret(key, &value)?;
 
     Ok(Some(value))
 }
 
-pub fn update_github_token() -> Result<()> {
-    let token = Text::new("Enter your new GitHub token:").prompt()?;
-    save_env_var("GITHUB_TOKEN", &token)?;
-    Ok(())
-}
-
-pub fn update_jira_credentials() -> Result<()> {
-    prompt_jira_credentials()
-}
+/// Offers to enter a credential again after a generation failed without it or had it rejected
+pub fn offer_credential_update(error: &GazetteError) -> Result<()> {
+    if !error.needs_credentials() {
+        return Ok(());
+    }
+    let update = Confirm::new("Update the credential now?")
+        .with_default(true)
+        .prompt()?;
+    if !update {
+        return Ok(());
+    }
 
-fn save_env_var(key: &str, value: &str) -> Result<()> {
-    let env_path = Path::new(ENV_FILE);
-
-    if env_path.exists() {
-        // Read existing content and update/add the key
-        let content = fs::read_to_string(env_path)?;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .open(env_path)?;
-
-        // Remove existing key if present
-        let new_content: String = content
-            .lines()
-            .filter(|line| !line.starts_with(&format!("{}=", key)))
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        if new_content.is_empty() {
-            writeln!(file, "{}={}", key, value)?;
-        } else {
-            writeln!(file, "{}\n{}={}", new_content, key, value)?;
+    match error {
+        GazetteError::MissingCredential(key) => {
+            let value = prompt_secret(&format!("{}:", key))?;
+            save_secret(key, &value)?;
         }
-    } else {
-        // Create new .env file
-        let mut file = fs::File::create(env_path)?;
-        writeln!(file, "{}={}", key, value)?;
+        GazetteError::Auth { service, .. } if service == "GitHub" => update_github_token()?,
+        GazetteError::Auth { service, .. } if service == "Jira" => update_jira_credentials()?,
+        // The remaining services that can fail a generation are AI providers
+        _ => prompt_ai_api_key(Config::load()?.ai_provider)?,
     }
+    println!("{}", "✔ Credential saved; generate again to use it".green());
 
-    // Reload .env to make new variable available in current process
-    dotenvy::dotenv().ok();
+    Ok(())
+}
 
+pub fn update_github_token() -> Result<()> {
+    let token = prompt_secret("Enter your new GitHub token:")?;
+    save_secret("GITHUB_TOKEN", &token)?;
     Ok(())
 }
+
+pub fn update_jira_credentials() -> Result<()> {
+    prompt_jira_credentials()
+}
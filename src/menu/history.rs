@@ -0,0 +1,132 @@
+use std::fmt;
+use std::fs;
+
+use anyhow::{Context, Result};
+use inquire::Select;
+
+use crate::config::{Config, DeliveryChannel, channel_sink};
+use crate::delivery::Delivery;
+use crate::fuzzy;
+use crate::history::{History, HistoryEntry};
+use crate::jira;
+use crate::menu::credentials::ensure_delivery_credentials;
+use crate::render;
+use crate::style::Paint;
+
+#[derive(Debug, Clone, Copy)]
+enum HistoryAction {
+    Preview,
+    Redeliver,
+    Back,
+}
+
+impl fmt::Display for HistoryAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Preview => write!(f, "Preview"),
+            Self::Redeliver => write!(f, "Re-deliver to a channel"),
+            Self::Back => write!(f, "Back"),
+        }
+    }
+}
+
+impl HistoryAction {
+    fn all() -> Vec<Self> {
+        vec![Self::Preview, Self::Redeliver, Self::Back]
+    }
+}
+
+/// Wrapper for history selection with a Back option
+#[derive(Debug, Clone)]
+enum HistorySelection {
+    Entry(Box<HistoryEntry>),
+    Back,
+}
+
+impl fmt::Display for HistorySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entry(entry) => write!(f, "{}", entry),
+            Self::Back => write!(f, "← Back"),
+        }
+    }
+}
+
+pub async fn menu_history() -> Result<()> {
+    let entries = History::load()?.available();
+
+    if entries.is_empty() {
+        println!("{}", "No past changelogs found.".yellow());
+        return Ok(());
+    }
+
+    let mut options: Vec<HistorySelection> = entries
+        .into_iter()
+        .map(|e| HistorySelection::Entry(Box::new(e)))
+        .collect();
+    options.push(HistorySelection::Back);
+
+    let HistorySelection::Entry(entry) = Select::new("Select a changelog:", options)
+        .with_filter(&fuzzy::filter)
+        .prompt()?
+    else {
+        return Ok(());
+    };
+
+    let content = fs::read_to_string(&entry.path)
+        .with_context(|| format!("Failed to read {}", entry.path.display()))?;
+
+    loop {
+        match Select::new("Action:", HistoryAction::all()).prompt()? {
+            HistoryAction::Preview => {
+                render::show_in_pager(&render::render_terminal(&content))?;
+            }
+            HistoryAction::Redeliver => {
+                let channel = Select::new("Deliver to:", DeliveryChannel::all()).prompt()?;
+                ensure_delivery_credentials(channel)?;
+
+                let delivery = Delivery {
+                    repo: &entry.repo,
+                    content: &content,
+                    period: entry.period.clone(),
+                    path: &entry.path,
+                    repo_path: entry.repo_path.clone(),
+                    jira_keys: jira::extract_jira_keys(&content).into_iter().fold(
+                        Vec::new(),
+                        |mut keys, key| {
+                            if !keys.contains(&key) {
+                                keys.push(key);
+                            }
+                            keys
+                        },
+                    ),
+                    version: None,
+                };
+                let result = match channel_sink(channel, &Config::load()?) {
+                    Ok(sink) => sink.deliver(&delivery).await,
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(None) => println!(
+                        "{} {}",
+                        "✔ Delivered to".green(),
+                        channel.to_string().cyan()
+                    ),
+                    Ok(Some(url)) => println!(
+                        "{} {}: {}",
+                        "✔ Delivered to".green(),
+                        channel.to_string().cyan(),
+                        url
+                    ),
+                    Err(e) => println!(
+                        "{} {}: {}",
+                        "✖ Delivery failed:".red(),
+                        channel.to_string().cyan(),
+                        e
+                    ),
+                }
+            }
+            HistoryAction::Back => return Ok(()),
+        }
+    }
+}
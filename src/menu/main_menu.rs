@@ -5,10 +5,15 @@ pub enum MainMenuOption {
     Subscribe,
     Unsubscribe,
     ListRepos,
+    RepoGroups,
+    RepoPaths,
     ConfigureTimePeriod,
     ChangeAIProvider,
     ChangeAIModel,
     GenerateChangelog,
+    BrowseHistory,
+    Limits,
+    Settings,
     UpdateCredentials,
     Exit,
 }
@@ -19,10 +24,15 @@ impl fmt::Display for MainMenuOption {
             Self::Subscribe => write!(f, "Subscribe to a repo"),
             Self::Unsubscribe => write!(f, "Unsubscribe from a repo"),
             Self::ListRepos => write!(f, "List subscribed repos"),
+            Self::RepoGroups => write!(f, "Manage repo groups"),
+            Self::RepoPaths => write!(f, "Scope repos to paths"),
             Self::ConfigureTimePeriod => write!(f, "Configure time period"),
             Self::ChangeAIProvider => write!(f, "Change AI provider"),
             Self::ChangeAIModel => write!(f, "Change AI model"),
             Self::GenerateChangelog => write!(f, "Generate changelog"),
+            Self::BrowseHistory => write!(f, "Browse past changelogs"),
+            Self::Limits => write!(f, "Check rate limits"),
+            Self::Settings => write!(f, "Settings"),
             Self::UpdateCredentials => write!(f, "Update credentials"),
             Self::Exit => write!(f, "Exit"),
         }
@@ -35,10 +45,15 @@ impl MainMenuOption {
             Self::Subscribe,
             Self::Unsubscribe,
             Self::ListRepos,
+            Self::RepoGroups,
+            Self::RepoPaths,
             Self::ConfigureTimePeriod,
             Self::ChangeAIProvider,
             Self::ChangeAIModel,
             Self::GenerateChangelog,
+            Self::BrowseHistory,
+            Self::Limits,
+            Self::Settings,
             Self::UpdateCredentials,
             Self::Exit,
         ]
@@ -1,7 +1,11 @@
 mod changelog;
 pub mod credentials;
+mod history;
 mod main_menu;
+mod settings;
 
 pub use changelog::*;
 pub use credentials::menu_credentials;
+pub use history::menu_history;
 pub use main_menu::*;
+pub use settings::menu_settings;
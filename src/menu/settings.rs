@@ -0,0 +1,234 @@
+use std::fmt;
+
+use anyhow::Result;
+use inquire::Select;
+
+use crate::config::{
+    configure_activity_chart, configure_ai_rate_limit, configure_atom_feed,
+    configure_changelog_format, configure_ci_status, configure_components,
+    configure_contributor_shoutouts, configure_daemon_interval, configure_daemon_schedules,
+    configure_delivery_channels, configure_dependency_grouping, configure_deployment_environment,
+    configure_discussions, configure_document_template, configure_duplicate_prs,
+    configure_executive_summary, configure_existing_changelog, configure_frontmatter,
+    configure_generation_mode, configure_group_by_epic, configure_hooks, configure_impact_scoring,
+    configure_integrations, configure_issue_concurrency, configure_jira_comments,
+    configure_jira_status_filter, configure_json_artifact, configure_keep_a_changelog,
+    configure_lead_time_stats, configure_markdown_flavor, configure_max_parallel_repos,
+    configure_no_ai, configure_pr_body_sections, configure_pr_comments,
+    configure_project_status_field, configure_reviewer_credits, configure_secret_backend,
+    configure_security_advisories, configure_select_prs, configure_stats_appendix,
+    configure_timezone, configure_title_exclusions, configure_tls, configure_two_pass_generation,
+    configure_version_naming,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum SettingsOption {
+    ChangelogFormat,
+    MarkdownFlavor,
+    Frontmatter,
+    VersionNaming,
+    GenerationMode,
+    NoAi,
+    Integrations,
+    JiraStatusFilter,
+    JiraComments,
+    EpicGrouping,
+    TwoPassGeneration,
+    JsonArtifact,
+    AtomFeed,
+    ContributorShoutouts,
+    DependencyGrouping,
+    ReviewerCredits,
+    LeadTimeStats,
+    StatsAppendix,
+    ActivityChart,
+    ImpactScoring,
+    DeploymentEnvironment,
+    CiStatus,
+    SecurityAdvisories,
+    Discussions,
+    PrComments,
+    PrBodySections,
+    ProjectStatus,
+    ExecutiveSummary,
+    DeliveryChannels,
+    KeepAChangelog,
+    Components,
+    DocumentTemplate,
+    Hooks,
+    Timezone,
+    Tls,
+    SecretBackend,
+    DaemonInterval,
+    MaxParallelRepos,
+    AiRateLimit,
+    IssueConcurrency,
+    DaemonSchedules,
+    DuplicatePrs,
+    ExistingChangelog,
+    TitleExclusions,
+    SelectPrs,
+    Back,
+}
+
+impl fmt::Display for SettingsOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChangelogFormat => write!(f, "Configure changelog format"),
+            Self::MarkdownFlavor => write!(f, "Configure markdown flavor"),
+            Self::Frontmatter => write!(f, "Configure YAML frontmatter"),
+            Self::VersionNaming => write!(f, "Configure version-anchored naming"),
+            Self::GenerationMode => {
+                write!(f, "Configure generation mode (changelog or release notes)")
+            }
+            Self::NoAi => write!(f, "Toggle AI-free formatting"),
+            Self::Integrations => write!(f, "Skip Jira or PR enrichment this session"),
+            Self::JiraStatusFilter => write!(f, "Configure Jira status filter"),
+            Self::JiraComments => write!(f, "Toggle Jira ticket comments"),
+            Self::EpicGrouping => write!(f, "Toggle grouping by Jira epic"),
+            Self::TwoPassGeneration => write!(f, "Toggle two-pass generation"),
+            Self::JsonArtifact => write!(f, "Toggle JSON artifact"),
+            Self::AtomFeed => write!(f, "Toggle Atom feed"),
+            Self::ContributorShoutouts => write!(f, "Toggle contributor shoutouts"),
+            Self::DependencyGrouping => write!(f, "Toggle dependency update grouping"),
+            Self::ReviewerCredits => write!(f, "Configure reviewer credits"),
+            Self::LeadTimeStats => write!(f, "Toggle lead-time stats"),
+            Self::StatsAppendix => write!(f, "Toggle stats section"),
+            Self::ActivityChart => write!(f, "Configure activity chart"),
+            Self::ImpactScoring => write!(f, "Toggle impact scoring"),
+            Self::DeploymentEnvironment => write!(f, "Configure deployment tracking"),
+            Self::CiStatus => write!(f, "Toggle CI status checks"),
+            Self::SecurityAdvisories => write!(f, "Toggle security section"),
+            Self::Discussions => write!(f, "Configure discussions"),
+            Self::PrComments => write!(f, "Configure PR comment excerpts"),
+            Self::PrBodySections => write!(f, "Configure PR description sections"),
+            Self::ProjectStatus => write!(f, "Configure project board status"),
+            Self::ExecutiveSummary => write!(f, "Toggle digest executive summary"),
+            Self::DeliveryChannels => write!(f, "Configure delivery channels"),
+            Self::KeepAChangelog => write!(f, "Configure CHANGELOG.md mode"),
+            Self::Components => write!(f, "Configure repo components"),
+            Self::DocumentTemplate => write!(f, "Configure document template"),
+            Self::Hooks => write!(f, "Configure generation hooks"),
+            Self::Timezone => write!(f, "Configure timezone"),
+            Self::Tls => write!(f, "Configure CA certificates"),
+            Self::SecretBackend => write!(f, "Configure credential storage"),
+            Self::DaemonInterval => write!(f, "Configure daemon interval"),
+            Self::MaxParallelRepos => write!(f, "Configure parallel repo limit"),
+            Self::AiRateLimit => write!(f, "Configure AI rate limit"),
+            Self::IssueConcurrency => write!(f, "Configure parallel issue lookups"),
+            Self::DaemonSchedules => write!(f, "Configure daemon schedules"),
+            Self::DuplicatePrs => write!(f, "Configure previously reported PRs"),
+            Self::ExistingChangelog => write!(f, "Configure existing changelog files"),
+            Self::TitleExclusions => write!(f, "Configure title exclusion rules"),
+            Self::SelectPrs => write!(f, "Toggle PR selection before drafting"),
+            Self::Back => write!(f, "Back to main menu"),
+        }
+    }
+}
+
+impl SettingsOption {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::ChangelogFormat,
+            Self::MarkdownFlavor,
+            Self::Frontmatter,
+            Self::VersionNaming,
+            Self::GenerationMode,
+            Self::NoAi,
+            Self::Integrations,
+            Self::JiraStatusFilter,
+            Self::JiraComments,
+            Self::EpicGrouping,
+            Self::TwoPassGeneration,
+            Self::JsonArtifact,
+            Self::AtomFeed,
+            Self::ContributorShoutouts,
+            Self::DependencyGrouping,
+            Self::ReviewerCredits,
+            Self::LeadTimeStats,
+            Self::StatsAppendix,
+            Self::ActivityChart,
+            Self::ImpactScoring,
+            Self::DeploymentEnvironment,
+            Self::CiStatus,
+            Self::SecurityAdvisories,
+            Self::Discussions,
+            Self::PrComments,
+            Self::PrBodySections,
+            Self::ProjectStatus,
+            Self::ExecutiveSummary,
+            Self::DeliveryChannels,
+            Self::KeepAChangelog,
+            Self::Components,
+            Self::DocumentTemplate,
+            Self::Hooks,
+            Self::Timezone,
+            Self::Tls,
+            Self::SecretBackend,
+            Self::DaemonInterval,
+            Self::MaxParallelRepos,
+            Self::AiRateLimit,
+            Self::IssueConcurrency,
+            Self::DaemonSchedules,
+            Self::DuplicatePrs,
+            Self::ExistingChangelog,
+            Self::TitleExclusions,
+            Self::SelectPrs,
+            Self::Back,
+        ]
+    }
+}
+
+pub fn menu_settings() -> Result<()> {
+    let ans = Select::new("Select setting to change:", SettingsOption::all()).prompt()?;
+
+    match ans {
+        SettingsOption::ChangelogFormat => configure_changelog_format()?,
+        SettingsOption::MarkdownFlavor => configure_markdown_flavor()?,
+        SettingsOption::Frontmatter => configure_frontmatter()?,
+        SettingsOption::VersionNaming => configure_version_naming()?,
+        SettingsOption::GenerationMode => configure_generation_mode()?,
+        SettingsOption::NoAi => configure_no_ai()?,
+        SettingsOption::Integrations => configure_integrations()?,
+        SettingsOption::JiraStatusFilter => configure_jira_status_filter()?,
+        SettingsOption::JiraComments => configure_jira_comments()?,
+        SettingsOption::EpicGrouping => configure_group_by_epic()?,
+        SettingsOption::TwoPassGeneration => configure_two_pass_generation()?,
+        SettingsOption::JsonArtifact => configure_json_artifact()?,
+        SettingsOption::AtomFeed => configure_atom_feed()?,
+        SettingsOption::ContributorShoutouts => configure_contributor_shoutouts()?,
+        SettingsOption::DependencyGrouping => configure_dependency_grouping()?,
+        SettingsOption::ReviewerCredits => configure_reviewer_credits()?,
+        SettingsOption::LeadTimeStats => configure_lead_time_stats()?,
+        SettingsOption::StatsAppendix => configure_stats_appendix()?,
+        SettingsOption::ActivityChart => configure_activity_chart()?,
+        SettingsOption::ImpactScoring => configure_impact_scoring()?,
+        SettingsOption::DeploymentEnvironment => configure_deployment_environment()?,
+        SettingsOption::CiStatus => configure_ci_status()?,
+        SettingsOption::SecurityAdvisories => configure_security_advisories()?,
+        SettingsOption::Discussions => configure_discussions()?,
+        SettingsOption::PrComments => configure_pr_comments()?,
+        SettingsOption::PrBodySections => configure_pr_body_sections()?,
+        SettingsOption::ProjectStatus => configure_project_status_field()?,
+        SettingsOption::ExecutiveSummary => configure_executive_summary()?,
+        SettingsOption::DeliveryChannels => configure_delivery_channels()?,
+        SettingsOption::KeepAChangelog => configure_keep_a_changelog()?,
+        SettingsOption::Components => configure_components()?,
+        SettingsOption::DocumentTemplate => configure_document_template()?,
+        SettingsOption::Hooks => configure_hooks()?,
+        SettingsOption::Timezone => configure_timezone()?,
+        SettingsOption::Tls => configure_tls()?,
+        SettingsOption::SecretBackend => configure_secret_backend()?,
+        SettingsOption::DaemonInterval => configure_daemon_interval()?,
+        SettingsOption::MaxParallelRepos => configure_max_parallel_repos()?,
+        SettingsOption::AiRateLimit => configure_ai_rate_limit()?,
+        SettingsOption::IssueConcurrency => configure_issue_concurrency()?,
+        SettingsOption::DaemonSchedules => configure_daemon_schedules()?,
+        SettingsOption::DuplicatePrs => configure_duplicate_prs()?,
+        SettingsOption::ExistingChangelog => configure_existing_changelog()?,
+        SettingsOption::TitleExclusions => configure_title_exclusions()?,
+        SettingsOption::SelectPrs => configure_select_prs()?,
+        SettingsOption::Back => return Ok(()),
+    }
+    Ok(())
+}
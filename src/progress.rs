@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+pub use gazette_core::progress::*;
+
+use crate::config::Repo;
+use crate::style;
+
+/// One spinner per repo, drawn on stderr and hidden when it isn't a terminal or output is plain
+pub struct RepoProgress {
+    multi: MultiProgress,
+    bars: Mutex<HashMap<String, ProgressBar>>,
+}
+
+impl RepoProgress {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            multi: if style::is_plain() {
+                MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+            } else {
+                MultiProgress::new()
+            },
+            bars: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// A callback that updates the repo's spinner, to hand to the changelog service
+    pub fn reporter(self: &Arc<Self>) -> ProgressFn {
+        let progress = Arc::clone(self);
+        Arc::new(move |repo, stage| progress.bar(repo).set_message(stage.to_string()))
+    }
+
+    /// Removes the repo's spinner once its generation is over
+    pub fn finish(&self, repo: &Repo) {
+        let bar = self
+            .bars
+            .lock()
+            .expect("progress bars lock poisoned")
+            .remove(&repo.key());
+        if let Some(bar) = bar {
+            bar.finish_and_clear();
+        }
+    }
+
+    fn bar(&self, repo: &Repo) -> ProgressBar {
+        let mut bars = self.bars.lock().expect("progress bars lock poisoned");
+        bars.entry(repo.key())
+            .or_insert_with(|| {
+                let bar = self.multi.add(ProgressBar::new_spinner());
+                bar.set_style(
+                    ProgressStyle::with_template("{spinner:.green} {prefix:.cyan} {msg:.dim}")
+                        .expect("valid progress template"),
+                );
+                bar.set_prefix(repo.key());
+                bar.enable_steady_tick(Duration::from_millis(100));
+                bar
+            })
+            .clone()
+    }
+}
@@ -0,0 +1,185 @@
+use std::env;
+use std::io::{IsTerminal, Write, stdout};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use owo_colors::Style;
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+
+use crate::style::{self, Paint};
+
+const DEFAULT_PAGER: &str = "less -R";
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Renders markdown as styled terminal text
+/// Links become OSC 8 hyperlinks, which supporting terminals make clickable
+/// Plain output keeps the markdown as it is
+pub fn render_terminal(markdown: &str) -> String {
+    if style::is_plain() {
+        return markdown.to_string();
+    }
+
+    let mut output = String::new();
+    let mut heading: Option<HeadingLevel> = None;
+    let mut strong = false;
+    let mut emphasis = false;
+    let mut link: Option<String> = None;
+    // One entry per nested list, holding the next item number for ordered lists
+    let mut lists: Vec<Option<u64>> = Vec::new();
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                if !output.is_empty() && !output.ends_with("\n\n") {
+                    output.push('\n');
+                }
+                heading = Some(level);
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                heading = None;
+                output.push_str("\n\n");
+            }
+            Event::Start(Tag::Strong) => strong = true,
+            Event::End(TagEnd::Strong) => strong = false,
+            Event::Start(Tag::Emphasis) => emphasis = true,
+            Event::End(TagEnd::Emphasis) => emphasis = false,
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                output.push_str(&format!("\x1b]8;;{}\x1b\\", dest_url));
+                link = Some(dest_url.to_string());
+            }
+            Event::End(TagEnd::Link) => {
+                output.push_str("\x1b]8;;\x1b\\");
+                link = None;
+            }
+            Event::Start(Tag::List(start)) => {
+                if !lists.is_empty() && !output.ends_with('\n') {
+                    output.push('\n');
+                }
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    output.push('\n');
+                }
+            }
+            Event::Start(Tag::Item) => {
+                let indent = "  ".repeat(lists.len().saturating_sub(1));
+                let marker = match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "•".to_string(),
+                };
+                output.push_str(&format!("  {}{} ", indent, marker.green()));
+            }
+            Event::End(TagEnd::Item) if !output.ends_with('\n') => output.push('\n'),
+            Event::End(TagEnd::Paragraph) => {
+                output.push('\n');
+                if lists.is_empty() {
+                    output.push('\n');
+                }
+            }
+            Event::Text(text) => {
+                let mut style = Style::new();
+                style = match heading {
+                    Some(HeadingLevel::H1) => style.bold().green().underline(),
+                    Some(HeadingLevel::H2) => style.bold().cyan(),
+                    Some(_) => style.bold(),
+                    None => style,
+                };
+                if strong {
+                    style = style.bold();
+                }
+                if emphasis {
+                    style = style.italic();
+                }
+                if link.is_some() {
+                    style = style.cyan().underline();
+                }
+                output.push_str(&text.paint(style).to_string());
+            }
+            Event::Code(code) => output.push_str(&code.yellow().to_string()),
+            Event::SoftBreak => output.push(' '),
+            Event::HardBreak => output.push('\n'),
+            Event::Rule => output.push_str(&format!("{}\n\n", "─".repeat(40).dimmed())),
+            _ => {}
+        }
+    }
+
+    output.trim_end().to_string() + "\n"
+}
+
+/// Shows text through $PAGER (default `less -R`), falling back to printing it directly, as
+/// plain output always does
+pub fn show_in_pager(text: &str) -> Result<()> {
+    if !stdout().is_terminal() || style::is_plain() {
+        print!("{}", text);
+        return Ok(());
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+
+    let spawned = parts.next().and_then(|program| {
+        Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .spawn()
+            .ok()
+    });
+
+    let Some(mut child) = spawned else {
+        print!("{}", text);
+        return Ok(());
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may exit before reading everything (e.g., user quits early)
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+
+    Ok(())
+}
+
+/// Opens text in $VISUAL or $EDITOR (default `vi`) and returns it as saved
+pub fn edit_in_editor(text: &str, name: &str) -> Result<String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| DEFAULT_EDITOR.to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR);
+
+    let path = env::temp_dir().join(format!("gazette-{}-{}.md", name, std::process::id()));
+    std::fs::write(&path, text)?;
+
+    let status = Command::new(program).args(parts).arg(&path).status();
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let status = status.with_context(|| format!("Failed to launch editor `{}`", program))?;
+    if !status.success() {
+        anyhow::bail!("Editor `{}` exited with {}", program, status);
+    }
+    Ok(edited?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_terminal_strips_markdown_syntax() {
+        let rendered = render_terminal(
+            "# Changelog\n\n## Features\n- Add OAuth2 ([#142](https://github.com/acme/backend/pull/142))\n",
+        );
+
+        assert!(!rendered.contains("## "));
+        assert!(!rendered.contains("]("));
+        assert!(rendered.contains("Features"));
+        assert!(rendered.contains("Add OAuth2"));
+        assert!(rendered.contains("\x1b]8;;https://github.com/acme/backend/pull/142\x1b\\"));
+    }
+}
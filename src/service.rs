@@ -0,0 +1,253 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use crate::feed::escape_xml;
+use crate::style::Paint;
+
+const SYSTEMD_UNIT: &str = "gazette.service";
+const LAUNCHD_LABEL: &str = "dev.gazette.daemon";
+
+/// Service managers gazette can install the daemon into
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServiceManager {
+    Systemd,
+    Launchd,
+}
+
+impl ServiceManager {
+    /// The service manager of the current platform
+    pub fn current() -> Result<Self> {
+        match env::consts::OS {
+            "linux" => Ok(Self::Systemd),
+            "macos" => Ok(Self::Launchd),
+            other => bail!("Service installation is not supported on {}", other),
+        }
+    }
+
+    /// Where the unit or plist is installed for the current user
+    pub fn definition_path(&self) -> Result<PathBuf> {
+        let home = env::var("HOME").context("HOME is not set")?;
+
+        Ok(match self {
+            Self::Systemd => Path::new(&home)
+                .join(".config/systemd/user")
+                .join(SYSTEMD_UNIT),
+            Self::Launchd => Path::new(&home)
+                .join("Library/LaunchAgents")
+                .join(format!("{}.plist", LAUNCHD_LABEL)),
+        })
+    }
+
+    /// Renders the service definition running `gazette daemon` from `working_dir`
+    pub fn render(&self, exe: &Path, working_dir: &Path) -> String {
+        match self {
+            Self::Systemd => render_systemd_unit(exe, working_dir),
+            Self::Launchd => render_launchd_plist(exe, working_dir),
+        }
+    }
+}
+
+/// A systemd user unit; config and state are read from the working directory, like an interactive run
+pub fn render_systemd_unit(exe: &Path, working_dir: &Path) -> String {
+    format!(
+        "[Unit]
+Description=Gazette changelog daemon
+After=network-online.target
+Wants=network-online.target
+
+[Service]
+Type=simple
+WorkingDirectory={dir}
+# Credentials for unattended runs, e.g. GAZETTE_PASSPHRASE; keep it readable only by you
+EnvironmentFile=-{env}
+ExecStart={exe} daemon
+Restart=on-failure
+RestartSec=30
+
+[Install]
+WantedBy=default.target
+",
+        dir = systemd_path(working_dir),
+        env = systemd_path(&working_dir.join(".env")),
+        exe = systemd_quoted(exe)
+    )
+}
+
+/// A path as a systemd setting taking the rest of the line, which doesn't unquote but
+/// expands `%` specifiers
+fn systemd_path(path: &Path) -> String {
+    path.display().to_string().replace('%', "%%")
+}
+
+/// A path as one word of an `ExecStart` command line, which systemd splits on spaces
+fn systemd_quoted(path: &Path) -> String {
+    let escaped = systemd_path(path)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "$$");
+    format!("\"{}\"", escaped)
+}
+
+/// A launchd agent plist, logging to gazette-daemon.log in the working directory
+pub fn render_launchd_plist(exe: &Path, working_dir: &Path) -> String {
+    let dir = escape_xml(&working_dir.display().to_string());
+    let log = escape_xml(&working_dir.join("gazette-daemon.log").display().to_string());
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{exe}</string>
+        <string>daemon</string>
+    </array>
+    <key>WorkingDirectory</key>
+    <string>{dir}</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>StandardOutPath</key>
+    <string>{log}</string>
+    <key>StandardErrorPath</key>
+    <string>{log}</string>
+</dict>
+</plist>
+"#,
+        label = LAUNCHD_LABEL,
+        exe = escape_xml(&exe.display().to_string()),
+        dir = dir,
+        log = log
+    )
+}
+
+/// Writes the service definition for the current platform and starts the daemon
+/// With `print_only`, the definition is printed instead of installed
+pub fn install(print_only: bool) -> Result<()> {
+    let manager = ServiceManager::current()?;
+    let exe = env::current_exe().context("Failed to locate the gazette executable")?;
+    let working_dir = env::current_dir().context("Failed to read the working directory")?;
+    let definition = manager.render(&exe, &working_dir);
+
+    if print_only {
+        print!("{}", definition);
+        return Ok(());
+    }
+
+    let path = manager.definition_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create service directory")?;
+    }
+    fs::write(&path, definition).context("Failed to write service definition")?;
+    println!(
+        "{} {}",
+        "✔ Service definition written to".green(),
+        path.display().to_string().cyan()
+    );
+
+    let path_arg = path.display().to_string();
+    let commands: Vec<Vec<&str>> = match manager {
+        ServiceManager::Systemd => vec![
+            vec!["systemctl", "--user", "daemon-reload"],
+            vec!["systemctl", "--user", "enable", "--now", SYSTEMD_UNIT],
+        ],
+        ServiceManager::Launchd => vec![vec!["launchctl", "load", "-w", &path_arg]],
+    };
+    run_all(&commands)?;
+
+    println!("{}", "✔ Gazette daemon installed and started".green());
+    if manager == ServiceManager::Systemd {
+        println!(
+            "{}",
+            "  Run `loginctl enable-linger` to keep it running while you're logged out.".dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Stops the daemon and removes the service definition
+pub fn uninstall() -> Result<()> {
+    let manager = ServiceManager::current()?;
+    let path = manager.definition_path()?;
+
+    if !path.exists() {
+        println!("{}", "Gazette service is not installed.".yellow());
+        return Ok(());
+    }
+
+    let path_arg = path.display().to_string();
+    let commands: Vec<Vec<&str>> = match manager {
+        ServiceManager::Systemd => vec![vec![
+            "systemctl",
+            "--user",
+            "disable",
+            "--now",
+            SYSTEMD_UNIT,
+        ]],
+        ServiceManager::Launchd => vec![vec!["launchctl", "unload", "-w", &path_arg]],
+    };
+    run_all(&commands)?;
+
+    fs::remove_file(&path).context("Failed to remove service definition")?;
+    if manager == ServiceManager::Systemd {
+        run_all(&[vec!["systemctl", "--user", "daemon-reload"]])?;
+    }
+
+    println!("{}", "✔ Gazette service removed".green());
+    Ok(())
+}
+
+fn run_all(commands: &[Vec<&str>]) -> Result<()> {
+    for command in commands {
+        let status = Command::new(command[0])
+            .args(&command[1..])
+            .status()
+            .with_context(|| format!("Failed to run {}", command[0]))?;
+
+        if !status.success() {
+            bail!("`{}` failed ({})", command.join(" "), status);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_systemd_unit_runs_daemon_from_working_dir() {
+        let unit = render_systemd_unit(
+            Path::new("/usr/local/bin/gazette"),
+            Path::new("/home/me/gazette"),
+        );
+
+        assert!(unit.contains("WorkingDirectory=/home/me/gazette\n"));
+        assert!(unit.contains("EnvironmentFile=-/home/me/gazette/.env\n"));
+        assert!(unit.contains("ExecStart=\"/usr/local/bin/gazette\" daemon\n"));
+    }
+
+    #[test]
+    fn test_systemd_unit_quotes_paths_with_spaces() {
+        let unit = render_systemd_unit(
+            Path::new("/home/me/My Tools/gazette"),
+            Path::new("/home/me/100% notes"),
+        );
+
+        assert!(unit.contains("WorkingDirectory=/home/me/100%% notes\n"));
+        assert!(unit.contains("EnvironmentFile=-/home/me/100%% notes/.env\n"));
+        assert!(unit.contains("ExecStart=\"/home/me/My Tools/gazette\" daemon\n"));
+    }
+}
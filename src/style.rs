@@ -0,0 +1,112 @@
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use owo_colors::Style;
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Turns terminal styling off for the rest of the process, e.g. for `--plain`
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether output is unstyled
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// True when the environment asks for unstyled output: NO_COLOR (https://no-color.org) or a
+/// dumb terminal
+pub fn plain_requested() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+        || env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// Colors and emphasis that are dropped in plain mode, in place of `owo_colors::OwoColorize`
+pub trait Paint: fmt::Display {
+    fn paint(&self, style: Style) -> Painted<'_, Self> {
+        Painted { value: self, style }
+    }
+
+    fn green(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().green())
+    }
+
+    fn red(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().red())
+    }
+
+    fn yellow(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().yellow())
+    }
+
+    fn cyan(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().cyan())
+    }
+
+    fn dimmed(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().dimmed())
+    }
+
+    fn bold(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().bold())
+    }
+
+    fn italic(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().italic())
+    }
+
+    fn underline(&self) -> Painted<'_, Self> {
+        self.paint(Style::new().underline())
+    }
+}
+
+impl<T: fmt::Display + ?Sized> Paint for T {}
+
+/// A value shown with a style unless output is plain
+pub struct Painted<'a, T: ?Sized> {
+    value: &'a T,
+    style: Style,
+}
+
+/// Chained styles combine into one, e.g. `"✖".red().bold()`
+macro_rules! chain {
+    ($($name:ident),*) => {
+        impl<T: ?Sized> Painted<'_, T> {
+            $(
+                pub fn $name(mut self) -> Self {
+                    self.style = self.style.$name();
+                    self
+                }
+            )*
+        }
+    };
+}
+
+chain!(bold);
+
+impl<T: fmt::Display + ?Sized> fmt::Display for Painted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_plain() {
+            write!(f, "{}", self.value)
+        } else {
+            write!(f, "{}", self.style.style(self.value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_output_drops_the_styles() {
+        assert_eq!("✖".red().bold().to_string(), "\u{1b}[31;1m✖\u{1b}[0m");
+
+        set_plain(true);
+        let plain = format!("{} {}", "✖".red().bold(), 42.dimmed());
+        set_plain(false);
+        assert_eq!(plain, "✖ 42");
+    }
+}
@@ -0,0 +1,239 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+
+use crate::config::{Config, Repo};
+
+/// Keeps the log pane from growing without bound over a long session
+const MAX_LOG_LINES: usize = 500;
+/// Finished jobs kept in the jobs pane once newer ones start
+const MAX_FINISHED_JOBS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub at: DateTime<Local>,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+impl LogLine {
+    pub fn new(level: LogLevel, message: impl Into<String>) -> Self {
+        Self {
+            at: Local::now(),
+            level,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Queued,
+    /// The generation stage the job is in, e.g. "waiting on AI"
+    Running(String),
+    Done,
+    /// Finished without a changelog, e.g. no PRs were merged in the window
+    Skipped(String),
+    Failed(String),
+}
+
+impl JobStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done | Self::Skipped(_) | Self::Failed(_))
+    }
+}
+
+/// One repo's generation, as shown in the jobs pane
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: usize,
+    pub repo: String,
+    pub status: JobStatus,
+    pub started_at: DateTime<Local>,
+    pub finished_at: Option<DateTime<Local>>,
+}
+
+/// Messages sent from background jobs to the interface
+pub enum JobEvent {
+    Log(LogLine),
+    Status { job: usize, status: JobStatus },
+    Finished,
+}
+
+/// State of the interactive interface
+pub struct App {
+    pub config: Config,
+    pub selected: usize,
+    pub log: Vec<LogLine>,
+    /// Per-repo generations, oldest first
+    pub jobs: Vec<Job>,
+    next_job: usize,
+    /// Background generation tasks still in flight
+    pub running: usize,
+    /// Set when quitting was requested while jobs were running, so a second press confirms
+    pub confirm_quit: bool,
+}
+
+impl App {
+    pub fn load() -> Result<Self> {
+        Ok(Self {
+            config: Config::load()?,
+            selected: 0,
+            log: Vec::new(),
+            jobs: Vec::new(),
+            next_job: 0,
+            running: 0,
+            confirm_quit: false,
+        })
+    }
+
+    /// Reloads the config after a flow that may have changed it, keeping the selection in range
+    pub fn reload(&mut self) -> Result<()> {
+        self.config = Config::load()?;
+        self.selected = self.selected.min(self.config.repos.len().saturating_sub(1));
+        Ok(())
+    }
+
+    pub fn repos(&self) -> &[Repo] {
+        &self.config.repos
+    }
+
+    pub fn selected_repo(&self) -> Option<&Repo> {
+        self.config.repos.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.config.repos.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = self.config.repos.len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    pub fn push_log(&mut self, line: LogLine) {
+        self.log.push(line);
+        if self.log.len() > MAX_LOG_LINES {
+            let excess = self.log.len() - MAX_LOG_LINES;
+            self.log.drain(..excess);
+        }
+    }
+
+    pub fn log(&mut self, level: LogLevel, message: impl Into<String>) {
+        self.push_log(LogLine::new(level, message));
+    }
+
+    /// Adds a queued job for the repo to the jobs pane and returns its id
+    pub fn add_job(&mut self, repo: &Repo) -> usize {
+        let id = self.next_job;
+        self.next_job += 1;
+        self.jobs.push(Job {
+            id,
+            repo: repo.key(),
+            status: JobStatus::Queued,
+            started_at: Local::now(),
+            finished_at: None,
+        });
+
+        let finished = self.jobs.iter().filter(|j| j.status.is_finished()).count();
+        if finished > MAX_FINISHED_JOBS {
+            let mut excess = finished - MAX_FINISHED_JOBS;
+            self.jobs.retain(|j| {
+                if excess > 0 && j.status.is_finished() {
+                    excess -= 1;
+                    return false;
+                }
+                true
+            });
+        }
+
+        id
+    }
+
+    pub fn handle_job_event(&mut self, event: JobEvent) {
+        match event {
+            JobEvent::Log(line) => self.push_log(line),
+            JobEvent::Status { job, status } => {
+                if let Some(job) = self.jobs.iter_mut().find(|j| j.id == job) {
+                    // A late stage update must not reopen a job that already finished
+                    if job.status.is_finished() {
+                        return;
+                    }
+                    if status.is_finished() {
+                        job.finished_at = Some(Local::now());
+                    }
+                    job.status = status;
+                }
+            }
+            JobEvent::Finished => self.running = self.running.saturating_sub(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_wraps_around() {
+        let mut app = App {
+            config: Config {
+                repos: vec![Repo::new("acme", "backend"), Repo::new("acme", "frontend")],
+                ..Config::default()
+            },
+            selected: 0,
+            log: Vec::new(),
+            jobs: Vec::new(),
+            next_job: 0,
+            running: 0,
+            confirm_quit: false,
+        };
+
+        app.select_previous();
+        assert_eq!(app.selected_repo().unwrap().name, "frontend");
+        app.select_next();
+        assert_eq!(app.selected_repo().unwrap().name, "backend");
+    }
+
+    #[test]
+    fn test_finished_jobs_ignore_late_stage_updates() {
+        let mut app = App {
+            config: Config::default(),
+            selected: 0,
+            log: Vec::new(),
+            jobs: Vec::new(),
+            next_job: 0,
+            running: 0,
+            confirm_quit: false,
+        };
+        let job = app.add_job(&Repo::new("acme", "backend"));
+
+        app.handle_job_event(JobEvent::Status {
+            job,
+            status: JobStatus::Running("waiting on AI".to_string()),
+        });
+        app.handle_job_event(JobEvent::Status {
+            job,
+            status: JobStatus::Done,
+        });
+        app.handle_job_event(JobEvent::Status {
+            job,
+            status: JobStatus::Running("delivering".to_string()),
+        });
+
+        assert_eq!(app.jobs[0].status, JobStatus::Done);
+        assert!(app.jobs[0].finished_at.is_some());
+    }
+}
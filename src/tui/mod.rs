@@ -0,0 +1,431 @@
+mod app;
+mod ui;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use inquire::{InquireError, Select};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+
+use crate::changelog::GeneratedChangelog;
+use crate::config::{
+    Repo, changelog_service, configure_ai_model, configure_ai_provider, configure_repo_groups,
+    configure_repo_paths, configure_time_period, list_repos, session_config, subscribe_repo,
+    unsubscribe_repo,
+};
+use crate::error::GazetteError;
+use crate::history::History;
+use crate::menu::{MainMenuOption, menu_changelog, menu_credentials, menu_history, menu_settings};
+use crate::progress::ProgressFn;
+use crate::render;
+use crate::style::{self, Paint};
+use crate::usage;
+
+use app::{App, JobEvent, JobStatus, LogLevel, LogLine};
+
+/// How often the interface redraws while waiting for input, so job progress shows up
+const TICK: Duration = Duration::from_millis(200);
+
+/// Runs the interactive interface until the user quits
+/// Generation runs in the background and reports to the log pane; prompts that need
+/// the full terminal (settings, credentials, ...) suspend the interface while they run
+/// Plain output uses the prompts alone, without the full-screen interface
+pub async fn run() -> Result<()> {
+    if style::is_plain() {
+        return run_plain().await;
+    }
+
+    let mut app = App::load()?;
+    let (events, mut receiver) = unbounded_channel();
+    app.log(
+        LogLevel::Info,
+        "Welcome to gazette. Press enter for all actions.",
+    );
+
+    let mut terminal = ratatui::try_init()?;
+
+    let result = loop {
+        while let Ok(event) = receiver.try_recv() {
+            app.handle_job_event(event);
+        }
+
+        if let Err(e) = terminal.draw(|frame| ui::draw(frame, &app)) {
+            break Err(e.into());
+        }
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if key.code != KeyCode::Char('q') {
+            app.confirm_quit = false;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if app.running == 0 || app.confirm_quit {
+                    break Ok(());
+                }
+                app.confirm_quit = true;
+            }
+            KeyCode::Down | KeyCode::Char('j') => app.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => app.select_previous(),
+            KeyCode::Char('g') => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    spawn_generate(&mut app, vec![repo], &events);
+                }
+            }
+            KeyCode::Char('a') => {
+                let repos = app.repos().to_vec();
+                spawn_generate(&mut app, repos, &events);
+            }
+            KeyCode::Char('r') => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    spawn_regenerate(&mut app, repo, &events);
+                }
+            }
+            KeyCode::Char('v') => {
+                if let Some(repo) = app.selected_repo().cloned() {
+                    terminal = suspend(terminal, &mut app, Flow::View(Box::new(repo))).await?;
+                }
+            }
+            KeyCode::Char(c) => {
+                let option = match c {
+                    's' => MainMenuOption::Subscribe,
+                    'x' => MainMenuOption::Unsubscribe,
+                    't' => MainMenuOption::ConfigureTimePeriod,
+                    'p' => MainMenuOption::ChangeAIProvider,
+                    'm' => MainMenuOption::ChangeAIModel,
+                    'h' => MainMenuOption::BrowseHistory,
+                    'c' => MainMenuOption::Settings,
+                    _ => continue,
+                };
+                terminal = suspend(terminal, &mut app, Flow::Menu(Some(option))).await?;
+            }
+            KeyCode::Enter => {
+                terminal = suspend(terminal, &mut app, Flow::Menu(None)).await?;
+            }
+            _ => {}
+        }
+    };
+
+    ratatui::try_restore()?;
+    result
+}
+
+/// The main menu as a prompt, repeated until Exit, for screen readers and dumb terminals
+async fn run_plain() -> Result<()> {
+    loop {
+        let option = match Select::new("Choose an option:", MainMenuOption::all()).prompt() {
+            Ok(MainMenuOption::Exit) => return Ok(()),
+            Ok(option) => option,
+            Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => {
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        match run_menu_option(Some(option)).await {
+            Ok(Some(name)) => println!("{} {}", "✔ Done:".green(), name),
+            Ok(None) => {}
+            Err(e) if is_cancelled(&e) => println!("Cancelled"),
+            Err(e) => println!("{} {}", "✖ Error:".red().bold(), e),
+        }
+    }
+}
+
+/// A prompt-driven flow that runs outside the interface
+enum Flow {
+    /// A main menu option, or the menu itself when None
+    Menu(Option<MainMenuOption>),
+    /// The repo's latest saved changelog in the pager
+    View(Box<Repo>),
+}
+
+/// Hands the terminal to a prompt-driven flow, then restores the interface
+/// Outcomes are logged rather than ending the session, including cancelled prompts
+async fn suspend(terminal: DefaultTerminal, app: &mut App, flow: Flow) -> Result<DefaultTerminal> {
+    drop(terminal);
+    ratatui::try_restore()?;
+
+    let (result, pause) = match flow {
+        Flow::Menu(option) => (run_menu_option(option).await, true),
+        Flow::View(repo) => (view_latest(&repo), false),
+    };
+
+    match result {
+        Ok(Some(name)) => app.log(LogLevel::Info, format!("Done: {}", name)),
+        Ok(None) => {}
+        Err(e) if is_cancelled(&e) => app.log(LogLevel::Info, "Cancelled"),
+        Err(e) => {
+            println!("{} {}", "✖ Error:".red().bold(), e);
+            app.log(LogLevel::Error, e.to_string());
+        }
+    }
+
+    if pause {
+        println!("\n{}", "Press Enter to return...".dimmed());
+        let _ = std::io::stdin().read_line(&mut String::new());
+    }
+
+    app.reload()?;
+    Ok(ratatui::try_init()?)
+}
+
+/// Runs a main menu option with the existing prompts, returning its name for the log
+/// None means nothing ran, e.g. the user backed out of the menu
+async fn run_menu_option(option: Option<MainMenuOption>) -> Result<Option<String>> {
+    let option = match option {
+        Some(option) => option,
+        None => {
+            let options: Vec<MainMenuOption> = MainMenuOption::all()
+                .into_iter()
+                .filter(|o| !matches!(o, MainMenuOption::Exit))
+                .collect();
+            Select::new("Choose an option:", options).prompt()?
+        }
+    };
+
+    match option {
+        MainMenuOption::Subscribe => subscribe_repo()?,
+        MainMenuOption::Unsubscribe => unsubscribe_repo()?,
+        MainMenuOption::ListRepos => list_repos()?,
+        MainMenuOption::RepoGroups => configure_repo_groups()?,
+        MainMenuOption::RepoPaths => configure_repo_paths()?,
+        MainMenuOption::ConfigureTimePeriod => configure_time_period()?,
+        MainMenuOption::ChangeAIProvider => {
+            configure_ai_provider()?;
+        }
+        MainMenuOption::ChangeAIModel => {
+            configure_ai_model()?;
+        }
+        MainMenuOption::GenerateChangelog => menu_changelog().await?,
+        MainMenuOption::BrowseHistory => menu_history().await?,
+        MainMenuOption::Limits => crate::commands::limits().await?,
+        MainMenuOption::Settings => menu_settings()?,
+        MainMenuOption::UpdateCredentials => menu_credentials().await?,
+        MainMenuOption::Exit => return Ok(None),
+    }
+
+    Ok(Some(option.to_string()))
+}
+
+fn view_latest(repo: &Repo) -> Result<Option<String>> {
+    let entry = History::load()?
+        .available()
+        .into_iter()
+        .find(|entry| entry.repo.key() == repo.key());
+
+    let Some(entry) = entry else {
+        anyhow::bail!("No saved changelog for {} yet", repo.key());
+    };
+
+    let content = std::fs::read_to_string(&entry.path)?;
+    render::show_in_pager(&render::render_terminal(&content))?;
+    Ok(None)
+}
+
+fn is_cancelled(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<InquireError>(),
+        Some(InquireError::OperationCanceled | InquireError::OperationInterrupted)
+    )
+}
+
+/// Sends a background job's progress to the log pane
+struct JobLog(UnboundedSender<JobEvent>);
+
+impl JobLog {
+    fn send(&self, level: LogLevel, message: String) {
+        // The interface may already be gone when the user quit with jobs running
+        let _ = self.0.send(JobEvent::Log(LogLine::new(level, message)));
+    }
+
+    fn status(&self, job: usize, status: JobStatus) {
+        let _ = self.0.send(JobEvent::Status { job, status });
+    }
+
+    fn finish(&self) {
+        let _ = self.0.send(JobEvent::Finished);
+    }
+
+    /// A progress callback marking each repo's job with its current stage
+    fn progress(&self, jobs: HashMap<String, usize>) -> ProgressFn {
+        let events = self.0.clone();
+        Arc::new(move |repo, stage| {
+            if let Some(&job) = jobs.get(&repo.key()) {
+                let status = JobStatus::Running(stage.to_string());
+                let _ = events.send(JobEvent::Status { job, status });
+            }
+        })
+    }
+}
+
+/// Generates and delivers changelogs for the repos in a background job, one jobs pane entry per repo
+fn spawn_generate(app: &mut App, repos: Vec<Repo>, events: &UnboundedSender<JobEvent>) {
+    if repos.is_empty() {
+        app.log(
+            LogLevel::Warning,
+            "No subscribed repos. Press s to subscribe.",
+        );
+        return;
+    }
+
+    let period = app.config.time_period.clone();
+    let parallel = app.config.get_max_parallel_repos();
+    let log = JobLog(events.clone());
+    let jobs: Vec<(Repo, usize)> = repos
+        .into_iter()
+        .map(|repo| {
+            let job = app.add_job(&repo);
+            (repo, job)
+        })
+        .collect();
+    app.running += 1;
+
+    tokio::spawn(async move {
+        let ids = jobs.iter().map(|(repo, job)| (repo.key(), *job)).collect();
+        match session_config().and_then(|config| changelog_service(&config)) {
+            Ok(service) => {
+                let service = Arc::new(service.with_progress(log.progress(ids)));
+                let futures = jobs.into_iter().map(|(repo, job)| {
+                    let service = Arc::clone(&service);
+                    let log = &log;
+                    let period = period.clone();
+                    async move {
+                        log.send(
+                            LogLevel::Info,
+                            format!("Generating {} from {}...", repo.key(), period.description()),
+                        );
+                        let result = service.generate_for_repo(&repo, period).await;
+                        report(log, job, &repo, result);
+                    }
+                });
+                // Jobs past the limit stay queued until a running one finishes
+                stream::iter(futures)
+                    .buffer_unordered(parallel)
+                    .collect::<Vec<()>>()
+                    .await;
+            }
+            Err(e) => {
+                for (_, job) in &jobs {
+                    log.status(*job, JobStatus::Failed(e.to_string()));
+                }
+                log.send(LogLevel::Error, e.to_string());
+            }
+        }
+
+        log.finish();
+    });
+}
+
+/// Regenerates a repo's changelog from its last run's context in a background job
+fn spawn_regenerate(app: &mut App, repo: Repo, events: &UnboundedSender<JobEvent>) {
+    let log = JobLog(events.clone());
+    let job = app.add_job(&repo);
+    app.running += 1;
+
+    tokio::spawn(async move {
+        log.send(
+            LogLevel::Info,
+            format!("Regenerating {} from the last run's context...", repo.key()),
+        );
+        let ids = HashMap::from([(repo.key(), job)]);
+        let result = match session_config().and_then(|config| changelog_service(&config)) {
+            Ok(service) => {
+                let service = service.with_progress(log.progress(ids));
+                match service.regenerate_for_repo(&repo).await {
+                    Ok(draft) => service.publish(draft).await,
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        };
+        report(&log, job, &repo, result);
+
+        log.finish();
+    });
+}
+
+/// Logs the outcome of a generation, including failed deliveries and repaired links
+fn report(log: &JobLog, job: usize, repo: &Repo, result: Result<GeneratedChangelog>) {
+    let name = repo.key();
+
+    let generated = match result {
+        Ok(generated) => generated,
+        Err(e)
+            if matches!(
+                GazetteError::find(&e),
+                Some(GazetteError::EmptyPeriod { .. })
+            ) =>
+        {
+            log.status(job, JobStatus::Skipped(e.to_string()));
+            log.send(LogLevel::Info, format!("{}: {}", name, e));
+            return;
+        }
+        Err(e) => {
+            log.status(job, JobStatus::Failed(e.to_string()));
+            log.send(LogLevel::Error, format!("✖ {}: {}", name, e));
+            if let Some(error) = GazetteError::find(&e) {
+                if let Some(hint) = error.hint() {
+                    log.send(LogLevel::Warning, format!("  {}", hint));
+                }
+                if error.needs_credentials() {
+                    log.send(
+                        LogLevel::Warning,
+                        "  Choose Update credentials to enter it again".to_string(),
+                    );
+                }
+            }
+            return;
+        }
+    };
+    log.status(job, JobStatus::Done);
+
+    let usage = generated
+        .usage
+        .as_ref()
+        .map(|tokens| format!(" ({})", usage::format_usage(tokens, generated.cost)))
+        .unwrap_or_default();
+    log.send(
+        LogLevel::Success,
+        format!("✔ {} → {}{}", name, generated.path.display(), usage),
+    );
+
+    for outcome in &generated.deliveries {
+        match &outcome.result {
+            Ok(_) => log.send(
+                LogLevel::Success,
+                format!("  delivered to {}", outcome.sink),
+            ),
+            Err(e) => log.send(
+                LogLevel::Warning,
+                format!("  delivery to {} failed: {}", outcome.sink, e),
+            ),
+        }
+    }
+
+    for warning in &generated.warnings {
+        log.send(LogLevel::Warning, format!("  {}", warning));
+    }
+    if !generated.links.is_clean() {
+        log.send(
+            LogLevel::Warning,
+            format!(
+                "  links: {} fixed, {} hallucinated links removed",
+                generated.links.fixed, generated.links.stripped
+            ),
+        );
+    }
+}
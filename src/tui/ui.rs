@@ -0,0 +1,243 @@
+use chrono::Local;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+
+use super::app::{App, JobStatus, LogLevel};
+
+/// Key bindings shown in the help bar
+const HELP: &[(&str, &str)] = &[
+    ("↑↓", "select"),
+    ("enter", "menu"),
+    ("q", "quit"),
+    ("g", "generate"),
+    ("a", "all"),
+    ("r", "regenerate"),
+    ("v", "view"),
+    ("s", "subscribe"),
+    ("x", "unsubscribe"),
+    ("t", "period"),
+    ("p", "provider"),
+    ("m", "model"),
+    ("h", "history"),
+    ("c", "settings"),
+];
+
+/// Rows the jobs pane grows to before older jobs scroll out of view
+const MAX_JOB_ROWS: usize = 8;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [sidebar, right] =
+        Layout::horizontal([Constraint::Length(34), Constraint::Min(0)]).areas(main);
+    let jobs_height = app.jobs.len().clamp(1, MAX_JOB_ROWS) as u16 + 2;
+    let [status, jobs, log] = Layout::vertical([
+        Constraint::Length(10),
+        Constraint::Length(jobs_height),
+        Constraint::Min(0),
+    ])
+    .areas(right);
+
+    draw_repos(frame, app, sidebar);
+    draw_status(frame, app, status);
+    draw_jobs(frame, app, jobs);
+    draw_log(frame, app, log);
+    draw_help(frame, app, help);
+}
+
+fn draw_repos(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(format!(" Repos ({}) ", app.repos().len()));
+
+    if app.repos().is_empty() {
+        let empty = Paragraph::new("No subscribed repos.\nPress s to subscribe.")
+            .style(Style::default().fg(Color::Yellow))
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .repos()
+        .iter()
+        .map(|repo| {
+            let mut spans = vec![Span::raw(repo.key())];
+            if !repo.groups.is_empty() {
+                spans.push(Span::styled(
+                    format!(" [{}]", repo.groups.join(", ")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+        .highlight_symbol("› ");
+
+    let mut state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let config = &app.config;
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<14}", label),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(value, Style::default().fg(Color::Cyan)),
+        ])
+    };
+
+    let channels = if config.delivery_channels.is_empty() {
+        "none".to_string()
+    } else {
+        config
+            .delivery_channels
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let active = app.jobs.iter().filter(|j| !j.status.is_finished()).count();
+    let jobs = match active {
+        0 => "idle".to_string(),
+        n => format!("{} running", n),
+    };
+
+    let lines = vec![
+        field("Period", config.time_period.to_string()),
+        field(
+            "AI",
+            format!(
+                "{} ({})",
+                config.ai_provider.short_name(),
+                config.get_ai_model()
+            ),
+        ),
+        field("Format", config.changelog_format.to_string()),
+        field("Delivery", channels),
+        field("Duplicate PRs", config.duplicate_prs.to_string()),
+        field("Credentials", config.secret_backend.to_string()),
+        field(
+            "Daemon",
+            format!("every {} min", config.get_daemon_interval_minutes()),
+        ),
+        field("Jobs", jobs),
+    ];
+
+    let status = Paragraph::new(lines)
+        .block(Block::bordered().title(" Gazette "))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(status, area);
+}
+
+fn draw_jobs(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Jobs ");
+
+    if app.jobs.is_empty() {
+        let empty = Paragraph::new("No jobs yet. Press g to generate.")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let now = Local::now();
+    let visible = area.height.saturating_sub(2) as usize;
+    let start = app.jobs.len().saturating_sub(visible);
+
+    let lines: Vec<Line> = app.jobs[start..]
+        .iter()
+        .map(|job| {
+            let (symbol, detail, color) = match &job.status {
+                JobStatus::Queued => ("…", "queued".to_string(), Color::DarkGray),
+                JobStatus::Running(stage) => ("▶", stage.clone(), Color::Cyan),
+                JobStatus::Done => ("✔", "done".to_string(), Color::Green),
+                JobStatus::Skipped(reason) => ("–", reason.clone(), Color::DarkGray),
+                JobStatus::Failed(error) => ("✖", error.clone(), Color::Red),
+            };
+            let elapsed = (job.finished_at.unwrap_or(now) - job.started_at).num_seconds();
+
+            Line::from(vec![
+                Span::styled(format!("{} ", symbol), Style::default().fg(color)),
+                Span::raw(format!("{:<28} ", job.repo)),
+                Span::styled(
+                    format!("{:>4}s ", elapsed),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(detail, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_log(frame: &mut Frame, app: &App, area: Rect) {
+    // Only the newest lines that fit inside the borders are shown
+    let visible = area.height.saturating_sub(2) as usize;
+    let start = app.log.len().saturating_sub(visible);
+
+    let lines: Vec<Line> = app.log[start..]
+        .iter()
+        .map(|line| {
+            let color = match line.level {
+                LogLevel::Info => Color::Reset,
+                LogLevel::Success => Color::Green,
+                LogLevel::Warning => Color::Yellow,
+                LogLevel::Error => Color::Red,
+            };
+            Line::from(vec![
+                Span::styled(
+                    line.at.format("%H:%M:%S ").to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(line.message.clone(), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let log = Paragraph::new(lines).block(Block::bordered().title(" Log "));
+    frame.render_widget(log, area);
+}
+
+fn draw_help(frame: &mut Frame, app: &App, area: Rect) {
+    if app.confirm_quit {
+        let warning = Paragraph::new(format!(
+            " {} job(s) still running — press q again to quit anyway",
+            app.running
+        ))
+        .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(warning, area);
+        return;
+    }
+
+    let spans: Vec<Span> = HELP
+        .iter()
+        .flat_map(|(key, action)| {
+            [
+                Span::styled(
+                    format!(" {}", key),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" {} ", action),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}